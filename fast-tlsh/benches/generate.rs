@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Benchmark: generating fuzzy hashes from a large input.
+//!
+//! The normal variants with the 1-byte and 3-byte checksums only differ in
+//! the checksum update, so comparing both measures the cost of the long
+//! checksum update.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use tlsh::hashes;
+
+/// The size of the random input.
+const INPUT_SIZE: usize = 64 * 1024 * 1024;
+
+fn generate(c: &mut Criterion) {
+    let mut data = vec![0u8; INPUT_SIZE];
+    Xoshiro256PlusPlus::seed_from_u64(0x6e1d_a5c8_34f0_b297).fill_bytes(&mut data);
+    let mut group = c.benchmark_group("generate");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    macro_rules! bench {
+        ($ty:ty, $name:literal) => {
+            group.bench_with_input(BenchmarkId::new($name, "64MiB"), &data, |b, data| {
+                b.iter(|| tlsh::hash_buf_for::<$ty>(black_box(data)).unwrap())
+            });
+        };
+    }
+    bench!(hashes::Normal, "normal");
+    bench!(hashes::NormalWithLongChecksum, "normal-long-checksum");
+    bench!(hashes::Long, "long");
+    bench!(hashes::LongWithLongChecksum, "long-long-checksum");
+    group.finish();
+}
+
+criterion_group!(benches, generate);
+criterion_main!(benches);
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Benchmark: [`PreparedQuery`] against repeated [`FuzzyHashType::compare()`].
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use tlsh::{hashes, FuzzyHashType, PreparedQuery};
+
+/// The number of candidates compared against the query on each iteration.
+const NUM_CANDIDATES: usize = 4096;
+
+/// Generates fuzzy hashes from `count` random byte sequences.
+fn random_hashes<T>(seed: u64, count: usize, hash: impl Fn(&[u8]) -> T) -> Vec<T> {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let mut data = [0u8; 512];
+    (0..count)
+        .map(|_| {
+            rng.fill_bytes(&mut data);
+            hash(&data)
+        })
+        .collect()
+}
+
+fn bench_variant<T: FuzzyHashType + Clone>(c: &mut Criterion, name: &str, hashes: &[T]) {
+    let (query, candidates) = hashes.split_first().unwrap();
+    let mut group = c.benchmark_group("prepared_query");
+    group.throughput(Throughput::Elements(candidates.len() as u64));
+    group.bench_with_input(
+        BenchmarkId::new("compare", name),
+        candidates,
+        |b, candidates| {
+            b.iter(|| {
+                candidates
+                    .iter()
+                    .map(|candidate| query.compare(black_box(candidate)))
+                    .fold(0u32, u32::wrapping_add)
+            })
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("distance", name),
+        candidates,
+        |b, candidates| {
+            let prepared = PreparedQuery::new(query);
+            b.iter(|| {
+                candidates
+                    .iter()
+                    .map(|candidate| prepared.distance(black_box(candidate)))
+                    .fold(0u32, u32::wrapping_add)
+            })
+        },
+    );
+    group.finish();
+}
+
+fn prepared_query(c: &mut Criterion) {
+    macro_rules! bench {
+        ($ty:ty, $name:literal, $seed:literal) => {
+            let hashes = random_hashes($seed, NUM_CANDIDATES + 1, |data| {
+                tlsh::hash_buf_for::<$ty>(data).unwrap()
+            });
+            bench_variant(c, $name, &hashes);
+        };
+    }
+    bench!(hashes::Short, "short", 0x2d9c_71e4_b05a_f836);
+    bench!(hashes::Normal, "normal", 0x8a43_f0d6_1c7e_b529);
+    bench!(hashes::Long, "long", 0xc516_2b8f_e94d_037a);
+}
+
+criterion_group!(benches, prepared_query);
+criterion_main!(benches);
@@ -22,6 +22,21 @@ fn main() {
         println!("cargo:rustc-cfg=fast_tlsh_error_in_core=\"stable\"");
     }
 
+    // Module: core::arch::{x86,x86_64} (AVX-512 intrinsics)
+    // unstable: -1.88 (not implemented)
+    //   stable: 1.89-
+    println!(
+        "cargo:rustc-check-cfg=cfg(\
+            fast_tlsh_x86_avx512, \
+            values(\
+                \"stable\"\
+            )\
+        )"
+    );
+    if rustc::is_min_version("1.89.0").unwrap_or(false) {
+        println!("cargo:rustc-cfg=fast_tlsh_x86_avx512=\"stable\"");
+    }
+
     // Other cfgs (rustc-check-cfg)
     println!("cargo:rustc-check-cfg=cfg(fast_tlsh_tests_without_debug_assertions)");
     println!("cargo:rustc-check-cfg=cfg(fast_tlsh_tests_reduce_on_miri)");
@@ -20,6 +20,40 @@ fn serde_json_example() {
     assert_eq!(hash, hash2);
 }
 
+#[test]
+fn serde_json_example_array() {
+    const HASH_STR: &str =
+        "T12AD5BE86FFE41D17CC268876A9AE472077B2B0032716DBAF1849A7647DDB7C0DF16488";
+    const HASH_ARRAY: &str = "[162,93,235,\
+        134,255,228,29,23,204,38,136,118,169,174,71,32,119,178,176,\
+        3,39,22,219,175,24,73,167,100,125,219,124,13,241,100,136]";
+    let hash = Tlsh::from_str(HASH_STR).unwrap();
+    let hash2 = serde_json::from_str::<Tlsh>(HASH_ARRAY).unwrap();
+    assert_eq!(hash, hash2);
+    // Both representations are accepted in a single dataset.
+    let hashes =
+        serde_json::from_str::<Vec<Tlsh>>(&format!(r#"["{HASH_STR}",{HASH_ARRAY}]"#)).unwrap();
+    assert_eq!(hashes, [hash, hash]);
+}
+
+#[test]
+fn serde_json_example_str_as_from_str() {
+    const HASH_STR: &str =
+        "T12AD5BE86FFE41D17CC268876A9AE472077B2B0032716DBAF1849A7647DDB7C0DF16488";
+    let hash = Tlsh::from_str(HASH_STR).unwrap();
+    // Surrounding whitespace and the lowercase prefix (as in FromStr).
+    let padded = format!(" {HASH_STR}\n");
+    assert_eq!(Tlsh::from_str(&padded), Ok(hash));
+    let hash2 = serde_json::from_str::<Tlsh>(&serde_json::to_string(&padded).unwrap()).unwrap();
+    assert_eq!(hash, hash2);
+    let hash2 = serde_json::from_str::<Tlsh>(&format!(r#""t{}""#, &HASH_STR[1..])).unwrap();
+    assert_eq!(hash, hash2);
+    // Embedded whitespace is still rejected.
+    let result =
+        serde_json::from_str::<Tlsh>(&format!(r#""{} {}""#, &HASH_STR[..8], &HASH_STR[8..]));
+    assert!(result.is_err());
+}
+
 #[test]
 fn serde_json_de_err_not_a_hash() {
     let result = serde_json::from_str::<Tlsh>("1");
@@ -35,6 +69,39 @@ fn serde_json_de_err_invalid_str() {
     assert!(result.is_err());
 }
 
+#[test]
+fn serde_json_de_err_invalid_array() {
+    // Too short
+    let result = serde_json::from_str::<Tlsh>("[162,93,235]");
+    assert!(result.is_err());
+    // Too long
+    let result = serde_json::from_str::<Tlsh>(
+        "[162,93,235,\
+        134,255,228,29,23,204,38,136,118,169,174,71,32,119,178,176,\
+        3,39,22,219,175,24,73,167,100,125,219,124,13,241,100,136,0]",
+    );
+    assert!(result.is_err());
+    // Out of range
+    let result = serde_json::from_str::<Tlsh>(
+        "[162,93,235,\
+        134,255,228,29,23,204,38,136,118,169,174,71,32,119,178,176,\
+        3,39,22,219,175,24,73,167,100,125,219,124,13,241,100,256]",
+    );
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "strict-parser")]
+#[test]
+fn serde_json_de_err_array_strict_parser() {
+    // The length encoding (0xff) is invalid.
+    let result = serde_json::from_str::<Tlsh>(
+        "[162,255,235,\
+        134,255,228,29,23,204,38,136,118,169,174,71,32,119,178,176,\
+        3,39,22,219,175,24,73,167,100,125,219,124,13,241,100,136]",
+    );
+    assert!(result.is_err());
+}
+
 #[test]
 fn postcard_example() {
     const HASH_STR: &str =
@@ -54,6 +121,46 @@ fn postcard_example() {
     assert_eq!(hash, hash2);
 }
 
+#[test]
+fn bincode_roundtrip_all_variants() {
+    macro_rules! test {
+        ($ty:ty, $hash_str:literal) => {{
+            let hash = <$ty>::from_str($hash_str).unwrap();
+            let mut bytes = [0u8; <$ty>::SIZE_IN_BYTES];
+            assert_eq!(hash.store_into_bytes(&mut bytes), Ok(<$ty>::SIZE_IN_BYTES));
+            // The raw bytes prefixed by its length (u64, little endian)
+            let data = bincode::serialize(&hash).unwrap();
+            assert_eq!(data.len(), 8 + <$ty>::SIZE_IN_BYTES);
+            assert_eq!(data[..8], (<$ty>::SIZE_IN_BYTES as u64).to_le_bytes());
+            assert_eq!(&data[8..], bytes.as_slice());
+            let hash2 = bincode::deserialize::<$ty>(data.as_slice()).unwrap();
+            assert_eq!(hash, hash2);
+            // Wrong length
+            let mut data = data;
+            data[0] -= 1;
+            data.pop();
+            assert!(bincode::deserialize::<$ty>(data.as_slice()).is_err());
+        }};
+    }
+    test!(tlsh::hashes::Short, "T1E1F029B2FCAA4D5FE04846105FA5E2");
+    test!(
+        tlsh::hashes::Normal,
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        tlsh::hashes::NormalWithLongChecksum,
+        "T1DC33D4F0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        tlsh::hashes::Long,
+        "T1DCF0DCA405C02AF1D4860CA5894A05301D60E9915198060A7044C608A1E89A11BD2B2836520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        tlsh::hashes::LongWithLongChecksum,
+        "T1DC33D4F0DCA405C02AF1D4860CA5894A05301D60E9915198060A7044C608A1E89A11BD2B2836520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+}
+
 #[test]
 fn postcard_de_err_not_a_hash() {
     const POSTCARD_DATA: &[u8] = b"\x00"; // empty byte string
@@ -61,6 +168,29 @@ fn postcard_de_err_not_a_hash() {
     assert!(result.is_err());
 }
 
+#[test]
+fn postcard_de_err_wrong_length() {
+    // 0x22 (data length; one byte shorter) is prefixed
+    const POSTCARD_DATA: &[u8] = b"\x22\
+        \xa2\x5d\xeb\
+        \x86\xff\xe4\x1d\x17\xcc\x26\x88\x76\xa9\xae\x47\x20\x77\xb2\xb0\
+        \x03\x27\x16\xdb\xaf\x18\x49\xa7\x64\x7d\xdb\x7c\x0d\xf1\x64";
+    let result = postcard::from_bytes::<Tlsh>(POSTCARD_DATA);
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "strict-parser")]
+#[test]
+fn postcard_de_err_strict_parser() {
+    // The length encoding (0xff) is invalid.
+    const POSTCARD_DATA: &[u8] = b"\x23\
+        \xa2\xff\xeb\
+        \x86\xff\xe4\x1d\x17\xcc\x26\x88\x76\xa9\xae\x47\x20\x77\xb2\xb0\
+        \x03\x27\x16\xdb\xaf\x18\x49\xa7\x64\x7d\xdb\x7c\x0d\xf1\x64\x88";
+    let result = postcard::from_bytes::<Tlsh>(POSTCARD_DATA);
+    assert!(result.is_err());
+}
+
 #[test]
 fn ciborium_example() {
     let mut data = vec![];
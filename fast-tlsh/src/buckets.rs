@@ -6,9 +6,27 @@
 
 use crate::buckets::constrained::{FuzzyHashBucketMapper, FuzzyHashBucketsInfo};
 use crate::generate::bucket_aggregation;
+#[cfg(feature = "opt-simd-generate")]
+use crate::generate::bucket_mapping::{self, BlockWindow, BucketIndices};
+#[cfg(feature = "experiment-variants")]
+use crate::hash::body::BODY_SIZE_EXTRA_SHORT;
 use crate::hash::body::{BODY_SIZE_LONG, BODY_SIZE_NORMAL, BODY_SIZE_SHORT};
+#[cfg(feature = "experiment-variants")]
+use crate::pearson::tlsh_b_mapping_24;
 use crate::pearson::{tlsh_b_mapping_256, tlsh_b_mapping_48};
 
+/// (fast-tlsh specific)
+/// The effective number of buckets on the experimental extra short variant
+/// (with 24 buckets).
+///
+/// On this variant, we have at least 25 physical buckets but the last one is
+/// used only to drain outliers (like [the short variant](NUM_BUCKETS_SHORT)).
+///
+/// This variant has no counterpart in the official TLSH implementation.
+#[cfg(feature = "experiment-variants")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "experiment-variants")))]
+pub const NUM_BUCKETS_EXTRA_SHORT: usize = 24;
+
 /// The effective number of buckets on the short variant (with 48 buckets).
 ///
 /// On this variant, we have at least 49 physical buckets but the last one is
@@ -32,10 +50,63 @@ pub const NUM_BUCKETS_NORMAL: usize = 128;
 pub const NUM_BUCKETS_LONG: usize = 256;
 
 // Those sizes must be divisible by 4.
+#[cfg(feature = "experiment-variants")]
+static_assertions::const_assert_eq!(NUM_BUCKETS_EXTRA_SHORT % 4, 0);
 static_assertions::const_assert_eq!(NUM_BUCKETS_SHORT % 4, 0);
 static_assertions::const_assert_eq!(NUM_BUCKETS_NORMAL % 4, 0);
 static_assertions::const_assert_eq!(NUM_BUCKETS_LONG % 4, 0);
 
+/// Computes three quartiles (Q1, Q2 and Q3) of the bucket counts
+/// in the TLSH way.
+///
+/// Like the official implementation, the quartiles are selected as follows
+/// (where `N` is the number of buckets and each value is selected from
+/// the sorted bucket counts, with 0-based indices):
+///
+/// *   Q1: the value at the index `N / 4 - 1`
+/// *   Q2: the value at the index `N / 2 - 1`
+/// *   Q3: the value at the index `N - N / 4 - 1`
+///
+/// This is the same algorithm used by generators (and the result is the same
+/// as [`BucketStats::quartiles`](crate::BucketStats::quartiles) if the same
+/// bucket counts are given).  It internally sorts (partially) a copy of
+/// `buckets` and the input itself is not modified.
+///
+/// # Panics
+///
+/// It panics if the number of buckets is zero, is not a multiple of 4 or
+/// exceeds [`NUM_BUCKETS_LONG`].
+///
+/// # Example
+///
+/// ```
+/// let buckets: Vec<u32> = (0..128).rev().collect();
+/// assert_eq!(tlsh::buckets::quartiles(&buckets), (31, 63, 95));
+/// ```
+pub fn quartiles(buckets: &[u32]) -> (u32, u32, u32) {
+    assert!(
+        !buckets.is_empty() && buckets.len() % 4 == 0 && buckets.len() <= NUM_BUCKETS_LONG,
+        "the number of buckets must be a non-zero multiple of 4 (up to 256)"
+    );
+    let mut copy_buckets = [0u32; NUM_BUCKETS_LONG];
+    let copy_buckets = &mut copy_buckets[..buckets.len()];
+    copy_buckets.copy_from_slice(buckets);
+    quartiles_in_place(copy_buckets)
+}
+
+/// Computes three quartiles (Q1, Q2 and Q3) of the bucket counts
+/// by partially sorting the given buckets.
+///
+/// See [`quartiles()`] for details.
+#[inline(always)]
+pub(crate) fn quartiles_in_place(buckets: &mut [u32]) -> (u32, u32, u32) {
+    let len = buckets.len();
+    let (l0, &mut q2, l1) = buckets.select_nth_unstable(len / 2 - 1);
+    let (_, &mut q1, _) = l0.select_nth_unstable(len / 4 - 1);
+    let (_, &mut q3, _) = l1.select_nth_unstable(len / 4 - 1);
+    (q1, q2, q3)
+}
+
 /// The module containing private traits (along with its implementations).
 pub(crate) mod constrained {
     use super::*;
@@ -67,6 +138,10 @@ pub(crate) mod constrained {
         /// buckets.  If not, some may be equal to or greater than that and will
         /// need to ignore such values by some means.
         const IS_B_MAPPING_CONSTRAINED_WITHIN_BUCKETS: bool;
+        /// TLSH's B (bucket) mapping over multiple windows at once
+        /// (see [`bucket_mapping`] for details).
+        #[cfg(feature = "opt-simd-generate")]
+        fn b_mapping_block(out: &mut BucketIndices, window: &BlockWindow);
         /// Bucket aggregation function.
         fn aggregate_buckets(
             out: &mut Self::RawBodyType,
@@ -81,6 +156,37 @@ pub(crate) mod constrained {
     /// by the number of buckets.
     pub struct FuzzyHashBucketsInfo<const SIZE_BUCKETS: usize>;
 
+    // Extra short (24 bucket; experimental) bucket mapping implementation
+    #[cfg(feature = "experiment-variants")]
+    impl private::Sealed for FuzzyHashBucketsInfo<NUM_BUCKETS_EXTRA_SHORT> {}
+    #[cfg(feature = "experiment-variants")]
+    impl FuzzyHashBucketMapper for FuzzyHashBucketsInfo<NUM_BUCKETS_EXTRA_SHORT> {
+        type RawBucketType = [u32; NUM_BUCKETS_EXTRA_SHORT];
+        type RawBodyType = [u8; BODY_SIZE_EXTRA_SHORT];
+        // Scaled from the short variant (18 out of 48 buckets).
+        const MIN_NONZERO_BUCKETS: usize = 9;
+        #[inline(always)]
+        fn b_mapping(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+            tlsh_b_mapping_24(b0, b1, b2, b3)
+        }
+        const IS_B_MAPPING_CONSTRAINED_WITHIN_BUCKETS: bool = false;
+        #[cfg(feature = "opt-simd-generate")]
+        #[inline(always)]
+        fn b_mapping_block(out: &mut BucketIndices, window: &BlockWindow) {
+            bucket_mapping::b_mapping_block_24(out, window);
+        }
+        #[inline(always)]
+        fn aggregate_buckets(
+            out: &mut Self::RawBodyType,
+            buckets: &Self::RawBucketType,
+            q1: u32,
+            q2: u32,
+            q3: u32,
+        ) {
+            bucket_aggregation::aggregate_24(out, buckets, q1, q2, q3);
+        }
+    }
+
     // Short (48 bucket) bucket mapping implementation
     impl private::Sealed for FuzzyHashBucketsInfo<NUM_BUCKETS_SHORT> {}
     impl FuzzyHashBucketMapper for FuzzyHashBucketsInfo<NUM_BUCKETS_SHORT> {
@@ -92,6 +198,11 @@ pub(crate) mod constrained {
             tlsh_b_mapping_48(b0, b1, b2, b3)
         }
         const IS_B_MAPPING_CONSTRAINED_WITHIN_BUCKETS: bool = false;
+        #[cfg(feature = "opt-simd-generate")]
+        #[inline(always)]
+        fn b_mapping_block(out: &mut BucketIndices, window: &BlockWindow) {
+            bucket_mapping::b_mapping_block_48(out, window);
+        }
         #[inline(always)]
         fn aggregate_buckets(
             out: &mut Self::RawBodyType,
@@ -116,6 +227,11 @@ pub(crate) mod constrained {
             tlsh_b_mapping_256(b0, b1, b2, b3)
         }
         const IS_B_MAPPING_CONSTRAINED_WITHIN_BUCKETS: bool = false;
+        #[cfg(feature = "opt-simd-generate")]
+        #[inline(always)]
+        fn b_mapping_block(out: &mut BucketIndices, window: &BlockWindow) {
+            bucket_mapping::b_mapping_block_256(out, window);
+        }
         #[inline(always)]
         fn aggregate_buckets(
             out: &mut Self::RawBodyType,
@@ -139,6 +255,11 @@ pub(crate) mod constrained {
             tlsh_b_mapping_256(b0, b1, b2, b3)
         }
         const IS_B_MAPPING_CONSTRAINED_WITHIN_BUCKETS: bool = true;
+        #[cfg(feature = "opt-simd-generate")]
+        #[inline(always)]
+        fn b_mapping_block(out: &mut BucketIndices, window: &BlockWindow) {
+            bucket_mapping::b_mapping_block_256(out, window);
+        }
         #[inline(always)]
         fn aggregate_buckets(
             out: &mut Self::RawBodyType,
@@ -197,12 +318,26 @@ where
         }
     }
 
+    /// Clears all buckets.
+    #[inline(always)]
+    pub(crate) fn clear(&mut self) {
+        self.buckets.fill(0);
+    }
+
     /// Returns the reference to the data (as a slice).
     #[inline(always)]
     pub(crate) fn data(&self) -> &[u32] {
         &self.buckets[..SIZE_BUCKETS]
     }
 
+    /// Adds all bucket counts of another buckets object to this one.
+    #[inline]
+    pub(crate) fn merge(&mut self, other: &Self) {
+        for (dst, &src) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            *dst = dst.wrapping_add(src);
+        }
+    }
+
     /// Increment a bucket specified by the index.
     ///
     /// By default, it increments the specified bucket no matter what.
@@ -224,3 +359,5 @@ where
         self.buckets[index] = self.buckets[index].wrapping_add(1);
     }
 }
+
+mod tests;
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::buckets`].
+
+#![cfg(test)]
+
+use super::{quartiles, NUM_BUCKETS_LONG, NUM_BUCKETS_NORMAL, NUM_BUCKETS_SHORT};
+
+#[test]
+fn quartiles_examples() {
+    // Sorted (and reverse-sorted) bucket counts.
+    for num_buckets in [4, NUM_BUCKETS_SHORT, NUM_BUCKETS_NORMAL, NUM_BUCKETS_LONG] {
+        let n = num_buckets as u32;
+        let expected = (n / 4 - 1, n / 2 - 1, n - n / 4 - 1);
+        let buckets: Vec<u32> = (0..n).collect();
+        assert_eq!(quartiles(&buckets), expected);
+        let buckets: Vec<u32> = (0..n).rev().collect();
+        assert_eq!(quartiles(&buckets), expected);
+    }
+    // The input is not modified.
+    let buckets = [3, 1, 4, 1, 5, 9, 2, 6];
+    assert_eq!(quartiles(&buckets), (1, 3, 5));
+    assert_eq!(buckets, [3, 1, 4, 1, 5, 9, 2, 6]);
+}
+
+#[test]
+#[should_panic]
+fn quartiles_panic_empty() {
+    quartiles(&[]);
+}
+
+#[test]
+#[should_panic]
+fn quartiles_panic_not_multiple_of_4() {
+    quartiles(&[0; 5]);
+}
+
+#[test]
+#[should_panic]
+fn quartiles_panic_too_many() {
+    quartiles(&[0; NUM_BUCKETS_LONG + 4]);
+}
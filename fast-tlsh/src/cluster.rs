@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Clustering of fuzzy hashes.
+//!
+//! This module provides a simple density-based clustering (DBSCAN) helper
+//! and a pairwise distance matrix helper using
+//! [the distance between two fuzzy hashes](FuzzyHashType::compare())
+//! with [the default configuration](crate::ComparisonConfiguration::Default).
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(feature = "unstable", doc(cfg(feature = "alloc")))]
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{FuzzyHashType, PreparedQuery};
+
+/// Returns indices of fuzzy hashes within the distance `eps` from
+/// `hashes[index]` (including `index` itself).
+fn neighbors<T: FuzzyHashType>(hashes: &[T], index: usize, eps: u32) -> Vec<usize> {
+    hashes[index]
+        .find_within(hashes, eps)
+        .map(|(index, _, _)| index)
+        .collect()
+}
+
+/// Clusters fuzzy hashes by DBSCAN (Density-Based Spatial Clustering of
+/// Applications with Noise).
+///
+/// A fuzzy hash is a *core point* if at least `min_pts` fuzzy hashes
+/// (including itself) are within the distance `eps` from it.
+/// A cluster consists of core points reachable from each other (through
+/// other core points within `eps`) and non-core points within `eps` from
+/// one of the core points in the cluster.
+///
+/// It returns a cluster label per input (in the same order as `hashes`).
+/// Cluster labels are assigned sequentially from `0` in the order
+/// of discovery and [`None`] means that the fuzzy hash is a noise
+/// (does not belong to any cluster).
+///
+/// The result is deterministic given the input order.  Note that, as in
+/// the original DBSCAN algorithm, a non-core point reachable from multiple
+/// clusters belongs to the first discovered one.
+///
+/// This function compares fuzzy hashes by brute force
+/// (using [`FuzzyHashType::find_within()`]) and requires quadratic time.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::prelude::*;
+///
+/// let hashes = [
+///     "T11632623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+///     "T11642623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+/// ]
+/// .map(|s| Tlsh::from_str(s).unwrap());
+/// let labels = tlsh::cluster::dbscan(&hashes, 30, 2);
+/// assert_eq!(labels, [Some(0), None, Some(0)]);
+/// ```
+pub fn dbscan<T: FuzzyHashType>(hashes: &[T], eps: u32, min_pts: usize) -> Vec<Option<usize>> {
+    let mut labels = vec![None; hashes.len()];
+    let mut visited = vec![false; hashes.len()];
+    let mut num_clusters = 0;
+    let mut queue = Vec::new();
+    for index in 0..hashes.len() {
+        if visited[index] {
+            continue;
+        }
+        visited[index] = true;
+        let seeds = neighbors(hashes, index, eps);
+        if seeds.len() < min_pts {
+            // Noise (unless it is later found to be a border point).
+            continue;
+        }
+        // Expand a new cluster from this core point.
+        let label = num_clusters;
+        num_clusters += 1;
+        labels[index] = Some(label);
+        queue.clear();
+        queue.extend(seeds);
+        let mut pos = 0;
+        while pos < queue.len() {
+            let other = queue[pos];
+            pos += 1;
+            if labels[other].is_none() {
+                labels[other] = Some(label);
+            }
+            if visited[other] {
+                continue;
+            }
+            visited[other] = true;
+            let seeds = neighbors(hashes, other, eps);
+            if seeds.len() >= min_pts {
+                queue.extend(seeds);
+            }
+        }
+    }
+    labels
+}
+
+/// Returns the index of the distance between `hashes[i]` and `hashes[j]`
+/// in the result of [`distance_matrix()`] (where `n` is the number of
+/// fuzzy hashes).
+///
+/// Because the distance is symmetric, `i` and `j` can be specified
+/// in either order.
+///
+/// # Panics
+///
+/// It panics if `i == j` or either `i` or `j` is not less than `n`.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::cluster::matrix_index;
+///
+/// // The upper triangle of 4x4 matrix in row-major order:
+/// // (0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)
+/// assert_eq!(matrix_index(0, 1, 4), 0);
+/// assert_eq!(matrix_index(0, 3, 4), 2);
+/// assert_eq!(matrix_index(1, 2, 4), 3);
+/// assert_eq!(matrix_index(3, 2, 4), 5);
+/// ```
+pub fn matrix_index(i: usize, j: usize, n: usize) -> usize {
+    let (i, j) = if i < j { (i, j) } else { (j, i) };
+    assert!(i != j, "the diagonal is not a part of the distance matrix");
+    assert!(j < n, "the index is out of range");
+    // Number of elements in rows before i: (n-1) + (n-2) + ... + (n-i)
+    i * (2 * n - i - 1) / 2 + (j - i - 1)
+}
+
+/// Computes the distances between all pairs of fuzzy hashes.
+///
+/// It returns the upper triangle of the distance matrix (excluding the
+/// diagonal) in the row-major order (of length `n * (n - 1) / 2` where `n`
+/// is the number of fuzzy hashes).  Use [`matrix_index()`] to retrieve
+/// the index of a pair.
+///
+/// Each row is computed using [`PreparedQuery`].  This function requires
+/// quadratic time and space.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::cluster::{distance_matrix, matrix_index};
+/// use tlsh::prelude::*;
+///
+/// let hashes = [
+///     "T11632623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+///     "T11642623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+/// ]
+/// .map(|s| Tlsh::from_str(s).unwrap());
+/// let matrix = distance_matrix(&hashes);
+/// assert_eq!(matrix.len(), 3);
+/// assert_eq!(matrix[matrix_index(0, 2, 3)], hashes[0].compare(&hashes[2]));
+/// ```
+pub fn distance_matrix<T: FuzzyHashType + Clone>(hashes: &[T]) -> Vec<u32> {
+    let n = hashes.len();
+    let mut matrix = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+    for (i, query) in hashes.iter().enumerate() {
+        let prepared = PreparedQuery::new(query);
+        matrix.extend(hashes[i + 1..].iter().map(|hash| prepared.distance(hash)));
+    }
+    matrix
+}
+
+/// Computes the distances between all pairs of fuzzy hashes in parallel.
+///
+/// The result is the same as [`distance_matrix()`] (including the layout)
+/// but rows of the upper triangle are distributed to the [`rayon`] thread
+/// pool, each task owning a [`PreparedQuery`] for the row being processed.
+///
+/// This function is only available with the `rayon` feature.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::cluster::{distance_matrix, distance_matrix_par};
+/// use tlsh::prelude::*;
+///
+/// let hashes = [
+///     "T11632623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+///     "T11642623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+/// ]
+/// .map(|s| Tlsh::from_str(s).unwrap());
+/// assert_eq!(distance_matrix_par(&hashes), distance_matrix(&hashes));
+/// ```
+#[cfg(feature = "rayon")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "rayon")))]
+pub fn distance_matrix_par<T: FuzzyHashType + Clone + Sync>(hashes: &[T]) -> Vec<u32> {
+    use rayon::prelude::*;
+    let n = hashes.len();
+    let mut matrix = vec![0u32; n * n.saturating_sub(1) / 2];
+    // Split the output into rows (of different lengths).
+    let mut rows = Vec::with_capacity(n);
+    let mut rest = matrix.as_mut_slice();
+    for i in 0..n {
+        let (row, next) = rest.split_at_mut(n - 1 - i);
+        rows.push((i, row));
+        rest = next;
+    }
+    rows.into_par_iter().for_each(|(i, row)| {
+        let prepared = PreparedQuery::new(&hashes[i]);
+        for (out, hash) in row.iter_mut().zip(hashes[i + 1..].iter()) {
+            *out = prepared.distance(hash);
+        }
+    });
+    matrix
+}
+
+mod tests;
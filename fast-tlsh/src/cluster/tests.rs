@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::cluster`].
+
+#![cfg(test)]
+
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::{dbscan, distance_matrix, matrix_index};
+
+use crate::{FuzzyHashType, GeneratorType, Tlsh, TlshGenerator};
+
+/// Generates fuzzy hashes of `count` slightly modified variants of
+/// random data (generated from `seed`).
+fn variants(seed: u64, count: usize) -> Vec<Tlsh> {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let mut data = [0u8; 4096];
+    rng.fill_bytes(&mut data);
+    (0..count)
+        .map(|i| {
+            let mut data = data;
+            data[i * 16] ^= 0xff;
+            let mut generator = TlshGenerator::new();
+            generator.update(&data);
+            generator.finalize().unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn empty() {
+    assert_eq!(dbscan::<Tlsh>(&[], 30, 2), []);
+}
+
+#[test]
+fn clusters_and_noise() {
+    let cluster_a = variants(0x2c5e8a1f4b7d9036, 4);
+    let cluster_b = variants(0x71f3b9d5a2c4e680, 3);
+    let noise = variants(0xd08b6e4a2f1c3957, 1);
+    // Interleave clusters and noise.
+    let hashes = [
+        cluster_a[0],
+        cluster_b[0],
+        noise[0],
+        cluster_a[1],
+        cluster_b[1],
+        cluster_a[2],
+        cluster_b[2],
+        cluster_a[3],
+    ];
+    let labels = dbscan(&hashes, 50, 2);
+    assert_eq!(
+        labels,
+        [
+            Some(0),
+            Some(1),
+            None,
+            Some(0),
+            Some(1),
+            Some(0),
+            Some(1),
+            Some(0)
+        ]
+    );
+    // If min_pts is too large, all points are noise.
+    assert_eq!(dbscan(&hashes, 50, 5), [None; 8]);
+    // If min_pts is 1 (or 0), all points belong to some cluster.
+    let labels = dbscan(&hashes, 50, 1);
+    assert_eq!(
+        labels,
+        [
+            Some(0),
+            Some(1),
+            Some(2),
+            Some(0),
+            Some(1),
+            Some(0),
+            Some(1),
+            Some(0)
+        ]
+    );
+    assert_eq!(dbscan(&hashes, 50, 0), labels);
+}
+
+#[test]
+fn border_points() {
+    // hashes[0] and hashes[2] are within the distance from hashes[1]
+    // but not each other (hashes[1] is the only core point).
+    let base = variants(0x4a9e2d7b1c5f3086, 1)[0];
+    let hashes: Vec<Tlsh> = [0u32, 1, 2]
+        .iter()
+        .map(|&i| {
+            let mut s = base.to_string().into_bytes();
+            // Modify the last i hex digits of the body.
+            let len = s.len();
+            for j in 0..i as usize {
+                s[len - 1 - j] = if s[len - 1 - j] == b'0' { b'3' } else { b'0' };
+            }
+            core::str::from_utf8(&s).unwrap().parse().unwrap()
+        })
+        .collect();
+    let eps = hashes[0]
+        .compare(&hashes[1])
+        .max(hashes[1].compare(&hashes[2]));
+    assert!(hashes[0].compare(&hashes[2]) > eps);
+    assert_eq!(dbscan(&hashes, eps, 3), [Some(0), Some(0), Some(0)]);
+    assert_eq!(dbscan(&hashes, eps, 4), [None, None, None]);
+}
+
+#[test]
+fn matrix_index_layout() {
+    for n in 2..=16 {
+        let mut expected = 0;
+        for i in 0..n {
+            for j in i + 1..n {
+                assert_eq!(matrix_index(i, j, n), expected);
+                assert_eq!(matrix_index(j, i, n), expected);
+                expected += 1;
+            }
+        }
+        assert_eq!(expected, n * (n - 1) / 2);
+    }
+}
+
+#[test]
+#[should_panic]
+fn matrix_index_diagonal() {
+    matrix_index(1, 1, 3);
+}
+
+#[test]
+#[should_panic]
+fn matrix_index_out_of_range() {
+    matrix_index(1, 3, 3);
+}
+
+#[test]
+fn distance_matrix_consistency() {
+    assert_eq!(distance_matrix::<Tlsh>(&[]), []);
+    let hashes = variants(0x4d8b_1e6f_03a9_c257, 1);
+    assert_eq!(distance_matrix(&hashes), []);
+    let mut hashes = variants(0x4d8b_1e6f_03a9_c257, 10);
+    hashes.extend(variants(0xb2e0_7c5a_9f14_d386, 10));
+    let n = hashes.len();
+    let matrix = distance_matrix(&hashes);
+    assert_eq!(matrix.len(), n * (n - 1) / 2);
+    for i in 0..n {
+        for j in 0..n {
+            if i != j {
+                assert_eq!(matrix[matrix_index(i, j, n)], hashes[i].compare(&hashes[j]));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn distance_matrix_par_consistency() {
+    use super::distance_matrix_par;
+
+    assert_eq!(distance_matrix_par::<Tlsh>(&[]), []);
+    let hashes = variants(0x7a3f_c219_05e8_b64d, 1);
+    assert_eq!(distance_matrix_par(&hashes), []);
+    for count in [2, 3, 10, 50] {
+        let mut hashes = variants(0x7a3f_c219_05e8_b64d, count);
+        hashes.extend(variants(0x1c94_e6b0_d827_3fa5, count));
+        assert_eq!(distance_matrix_par(&hashes), distance_matrix(&hashes));
+    }
+}
@@ -9,6 +9,12 @@ pub(crate) mod dist_length;
 pub(crate) mod dist_qratios;
 pub(crate) mod utils;
 
+mod prepared;
+pub use prepared::PreparedQuery;
+
+use crate::hash::body::{FuzzyHashBody, FuzzyHashBodyData};
+use crate::FuzzyHashType;
+
 /// Denotes the mode of comparison (between two fuzzy hashes).
 ///
 /// For description of the parts, see [`FuzzyHashType`](crate::FuzzyHashType).
@@ -29,6 +35,516 @@ pub enum ComparisonConfiguration {
     ///
     /// This is renamed from an erroneous name `NoDistance`.
     NoLength,
+    /// The weighted length distance mode.
+    ///
+    /// In this mode, all checksum, length, Q ratio pair and body are
+    /// compared to another but the distance of the length encoding is
+    /// multiplied by `length_multiplier` before summing.
+    ///
+    /// [`Weighted { length_multiplier: 1 }`](Self::Weighted) is equivalent to
+    /// [`Default`](Self::Default) and
+    /// [`Weighted { length_multiplier: 0 }`](Self::Weighted) is equivalent to
+    /// [`NoLength`](Self::NoLength).  Larger multipliers make the length
+    /// difference more significant.
+    ///
+    /// If the weighted length distance overflows, it saturates at
+    /// [`u32::MAX`].
+    Weighted {
+        /// The multiplier applied to the distance of the length encoding.
+        length_multiplier: u32,
+    },
+    /// The no-checksum distance mode.
+    ///
+    /// In this mode, all length, Q ratio pair and body (all *except* the
+    /// checksum) are compared to another.
+    ///
+    /// This is useful to compare fuzzy hashes with checksums cleared by
+    /// [`FuzzyHashType::clear_checksum()`](crate::FuzzyHashType::clear_checksum())
+    /// against regular ones without artificially inflated distances.
+    NoChecksum,
+    /// The body-only distance mode.
+    ///
+    /// In this mode, only the body is compared to another (checksum, length
+    /// and Q ratio pair are all ignored).
+    ///
+    /// This is stricter than both [`NoLength`](Self::NoLength) and
+    /// [`NoChecksum`](Self::NoChecksum) (which still compare other parts
+    /// including the Q ratio pair).  Because the comparison only involves
+    /// the body, this is the cheapest mode to compute.
+    BodyOnly,
+}
+
+impl ComparisonConfiguration {
+    /// Creates the configuration corresponding to the `len_diff` argument of
+    /// the official TLSH implementation.
+    ///
+    /// The official implementation (`Tlsh::totalDiff(other, len_diff)` in
+    /// C++ and `tlsh.diff` / `tlsh.diffxlen` in `py-tlsh`) only has
+    /// a boolean flag to include the length part in the distance.
+    /// Other parts (the checksum, the Q ratio pair and the body) are always
+    /// compared.  Using the configuration returned by this function, this
+    /// crate returns exactly the same distance as the official one.
+    ///
+    /// | `len_diff` | Official API                          | Configuration                 |
+    /// | ---------- | ------------------------------------- | ----------------------------- |
+    /// | `true`     | `totalDiff(other, true)`, `diff`      | [`Default`](Self::Default)    |
+    /// | `false`    | `totalDiff(other, false)`, `diffxlen` | [`NoLength`](Self::NoLength)  |
+    ///
+    /// Other configurations ([`Weighted`](Self::Weighted),
+    /// [`NoChecksum`](Self::NoChecksum) and [`BodyOnly`](Self::BodyOnly))
+    /// have no counterparts in the official implementation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tlsh::ComparisonConfiguration;
+    ///
+    /// assert_eq!(
+    ///     ComparisonConfiguration::from_tlsh_len_diff(true),
+    ///     ComparisonConfiguration::Default
+    /// );
+    /// assert_eq!(
+    ///     ComparisonConfiguration::from_tlsh_len_diff(false),
+    ///     ComparisonConfiguration::NoLength
+    /// );
+    /// ```
+    pub const fn from_tlsh_len_diff(len_diff: bool) -> Self {
+        if len_diff {
+            ComparisonConfiguration::Default
+        } else {
+            ComparisonConfiguration::NoLength
+        }
+    }
+
+    /// Applies this configuration to the raw distance of the length encoding.
+    #[inline(always)]
+    pub(crate) fn weight_length_distance(self, distance: u32) -> u32 {
+        match self {
+            ComparisonConfiguration::Default => distance,
+            ComparisonConfiguration::NoLength => 0,
+            ComparisonConfiguration::Weighted { length_multiplier } => {
+                distance.saturating_mul(length_multiplier)
+            }
+            ComparisonConfiguration::NoChecksum => distance,
+            ComparisonConfiguration::BodyOnly => 0,
+        }
+    }
+
+    /// Applies this configuration to the raw distance of the checksum.
+    #[inline(always)]
+    pub(crate) fn weight_checksum_distance(self, distance: u32) -> u32 {
+        match self {
+            ComparisonConfiguration::NoChecksum | ComparisonConfiguration::BodyOnly => 0,
+            _ => distance,
+        }
+    }
+
+    /// Applies this configuration to the raw distance of the Q ratio pair.
+    #[inline(always)]
+    pub(crate) fn weight_qratios_distance(self, distance: u32) -> u32 {
+        match self {
+            ComparisonConfiguration::BodyOnly => 0,
+            _ => distance,
+        }
+    }
+}
+
+/// The per-part breakdown of the distance between two fuzzy hashes.
+///
+/// The sum of all fields is equal to the distance returned by
+/// [`FuzzyHashType::compare_with_config()`](crate::FuzzyHashType::compare_with_config())
+/// with the same configuration (and can be retrieved by [`total()`](Self::total())).
+///
+/// For description of the parts, see [`FuzzyHashType`](crate::FuzzyHashType).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DistanceBreakdown {
+    /// The distance on the checksum part.
+    ///
+    /// This is always zero on [`ComparisonConfiguration::NoChecksum`] and
+    /// [`ComparisonConfiguration::BodyOnly`].
+    pub checksum: u32,
+    /// The distance on the length part.
+    ///
+    /// This is always zero on [`ComparisonConfiguration::NoLength`] and
+    /// [`ComparisonConfiguration::BodyOnly`] and weighted on
+    /// [`ComparisonConfiguration::Weighted`].
+    pub length: u32,
+    /// The distance on the Q ratio pair part.
+    ///
+    /// This is always zero on [`ComparisonConfiguration::BodyOnly`].
+    pub qratios: u32,
+    /// The distance on the body part.
+    pub body: u32,
+}
+
+impl DistanceBreakdown {
+    /// Returns the total distance (the sum of all parts).
+    ///
+    /// Like [`FuzzyHashType::compare_with_config()`](crate::FuzzyHashType::compare_with_config()),
+    /// it saturates at [`u32::MAX`].
+    #[inline]
+    pub fn total(&self) -> u32 {
+        (self.checksum + self.qratios + self.body).saturating_add(self.length)
+    }
+}
+
+/// (fast-tlsh specific)
+/// The distance between two fuzzy hashes.
+///
+/// Lower is more similar (`0` means that two fuzzy hashes are identical on
+/// the compared parts).  So, the ordering of this type directly denotes
+/// "closeness": the smaller one is closer.
+///
+/// This is a type-safe wrapper of the [`u32`] distance to prevent confusion
+/// with the [similarity](Similarity) (where higher is more similar).
+///
+/// This is returned by
+/// [`FuzzyHashType::compare_typed()`](crate::FuzzyHashType::compare_typed()).
+///
+/// # Example
+///
+/// ```
+/// use tlsh::Distance;
+///
+/// let closer = Distance(12);
+/// let farther = Distance(138);
+/// assert!(closer < farther);
+/// assert_eq!(closer.min(farther), closer);
+/// assert_eq!(u32::from(farther), 138);
+/// assert_eq!(farther.to_string(), "138");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Distance(pub u32);
+
+impl Distance {
+    /// Returns the raw distance value.
+    #[inline(always)]
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+
+    /// Checks whether this distance does not exceed `threshold`.
+    #[inline(always)]
+    pub const fn is_within(self, threshold: Distance) -> bool {
+        self.0 <= threshold.0
+    }
+}
+
+impl From<u32> for Distance {
+    #[inline(always)]
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Distance> for u32 {
+    #[inline(always)]
+    fn from(value: Distance) -> Self {
+        value.0
+    }
+}
+
+impl core::fmt::Display for Distance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// (fast-tlsh specific)
+/// The normalized similarity score (in the range of `0..=100`).
+///
+/// Higher is more similar (`100` means that two fuzzy hashes are identical
+/// and `0` means that they are maximally different).
+///
+/// This is a type-safe wrapper of the similarity score returned by
+/// [`FuzzyHashType::similarity()`](crate::FuzzyHashType::similarity())
+/// to prevent confusion with the [distance](Distance)
+/// (where lower is more similar).
+///
+/// # Example
+///
+/// ```
+/// use tlsh::{Distance, Similarity};
+/// use tlsh::Tlsh;
+///
+/// assert_eq!(Similarity::new(100).map(Similarity::value), Some(100));
+/// assert_eq!(Similarity::new(101), None);
+/// // 100 - floor(138 * 100 / 2473)
+/// let similarity = Similarity::from_distance::<Tlsh>(Distance(138));
+/// assert_eq!(similarity.value(), 95);
+/// assert_eq!(similarity.to_string(), "95%");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Similarity(u8);
+
+impl Similarity {
+    /// The maximum similarity score (identical).
+    pub const MAX: Self = Self(100);
+
+    /// The minimum similarity score (maximally different).
+    pub const MIN: Self = Self(0);
+
+    /// Creates the similarity score from the raw value.
+    ///
+    /// It returns [`None`] if `value` exceeds `100`.
+    #[inline]
+    pub const fn new(value: u8) -> Option<Self> {
+        if value <= 100 {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the raw similarity score (in the range of `0..=100`).
+    #[inline(always)]
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Converts the distance (on
+    /// [the default configuration](ComparisonConfiguration::Default))
+    /// to the similarity score for the fuzzy hash type `T`.
+    ///
+    /// This is the same mapping as
+    /// [`FuzzyHashType::similarity()`](crate::FuzzyHashType::similarity()).
+    #[inline]
+    pub fn from_distance<T: FuzzyHashType>(distance: Distance) -> Self {
+        let max_distance = T::max_distance(ComparisonConfiguration::Default);
+        let distance = distance.0.min(max_distance);
+        Self((100 - distance * 100 / max_distance) as u8)
+    }
+}
+
+impl From<Similarity> for u8 {
+    #[inline(always)]
+    fn from(value: Similarity) -> Self {
+        value.0
+    }
+}
+
+impl core::fmt::Display for Similarity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+/// Finds the closest fuzzy hash in a packed binary store
+/// (with a configuration).
+///
+/// `region` is interpreted as a concatenation of the binary representations
+/// (as stored by [`FuzzyHashType::store_into_bytes()`]) and each entry of
+/// [`SIZE_IN_BYTES`](FuzzyHashType::SIZE_IN_BYTES) bytes is compared with
+/// `query` by [`FuzzyHashType::compare_to_bytes()`].
+///
+/// It returns [`Some`] containing the index of the closest entry and its
+/// distance, or [`None`] if there's no valid entries.  If multiple entries
+/// have the same distance, the first one is returned.
+///
+/// Entries which cannot be parsed (e.g. invalid length encoding with the
+/// `strict-parser` feature) and the incomplete trailing bytes are ignored.
+///
+/// This function does not allocate and works on `no_std` environments.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::prelude::*;
+/// use tlsh::ComparisonConfiguration;
+///
+/// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+/// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+/// let hash1 = Tlsh::from_str(hash1).unwrap();
+/// let hash2 = Tlsh::from_str(hash2).unwrap();
+/// let mut region = [0u8; Tlsh::SIZE_IN_BYTES * 2];
+/// hash2.store_into_bytes(&mut region[..Tlsh::SIZE_IN_BYTES]).unwrap();
+/// hash1.store_into_bytes(&mut region[Tlsh::SIZE_IN_BYTES..]).unwrap();
+/// let config = ComparisonConfiguration::Default;
+/// assert_eq!(tlsh::min_distance_to_bytes(&hash1, &region, config), Some((1, 0)));
+/// assert_eq!(tlsh::min_distance_to_bytes(&hash2, &region, config), Some((0, 0)));
+/// assert_eq!(tlsh::min_distance_to_bytes(&hash1, &region[..Tlsh::SIZE_IN_BYTES], config), Some((0, 138)));
+/// assert_eq!(tlsh::min_distance_to_bytes(&hash1, &[], config), None);
+/// ```
+pub fn min_distance_to_bytes<T: FuzzyHashType>(
+    query: &T,
+    region: &[u8],
+    config: ComparisonConfiguration,
+) -> Option<(usize, u32)> {
+    let mut closest: Option<(usize, u32)> = None;
+    for (index, raw) in region.chunks_exact(T::SIZE_IN_BYTES).enumerate() {
+        if let Ok(distance) = query.compare_to_bytes(raw, config) {
+            if closest.map_or(true, |(_, min)| distance < min) {
+                closest = Some((index, distance));
+            }
+        }
+    }
+    closest
+}
+
+/// (fast-tlsh specific)
+/// Packs the body parts of `hashes` into a contiguous buffer.
+///
+/// Each entry occupies [`SIZE`](FuzzyHashBody::SIZE) bytes of the body type
+/// in the same order as `hashes` and the same layout as
+/// [`as_packed_dibits()`](FuzzyHashBody::as_packed_dibits()).
+/// The result is intended to be scanned by [`min_distance_packed()`].
+///
+/// This function requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::prelude::*;
+/// use tlsh::hash::body::FuzzyHashBody;
+///
+/// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+/// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+/// let hash1 = Tlsh::from_str(hash1).unwrap();
+/// let hash2 = Tlsh::from_str(hash2).unwrap();
+/// let packed = tlsh::pack_bodies(&[hash1, hash2]);
+/// assert_eq!(packed.len(), 32 * 2);
+/// assert_eq!(&packed[..32], hash1.body().as_packed_dibits());
+/// assert_eq!(&packed[32..], hash2.body().as_packed_dibits());
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "alloc")))]
+pub fn pack_bodies<T: FuzzyHashType>(hashes: &[T]) -> alloc::vec::Vec<u8> {
+    let mut packed = alloc::vec::Vec::with_capacity(hashes.len() * T::BodyType::SIZE);
+    for hash in hashes {
+        packed.extend_from_slice(hash.body().as_packed_dibits());
+    }
+    packed
+}
+
+/// (fast-tlsh specific)
+/// Searches the body closest to `query_body` in the packed body buffer.
+///
+/// `packed` is a concatenation of the body parts (as created by
+/// [`pack_bodies()`]) and each entry of [`SIZE`](FuzzyHashBody::SIZE) bytes
+/// is compared with `query_body` by
+/// [`compare_within()`](FuzzyHashBody::compare_within()) with the budget
+/// tightened by the closest entry so far (the scan stops early once an
+/// identical body is found).
+///
+/// It returns [`Some`] containing the index of the closest entry and its
+/// body distance, or [`None`] if `packed` has no complete entries.  If
+/// multiple entries have the same distance, the first one is returned.
+/// The incomplete trailing bytes are ignored.
+///
+/// Note that only the body parts are compared.  This is useful as a
+/// cache-friendly pre-filter for large collections where headers are
+/// compared separately (or not at all).
+///
+/// This function does not allocate and works on `no_std` environments.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::prelude::*;
+/// use tlsh::hash::body::FuzzyHashBody;
+///
+/// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+/// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+/// let hash1 = Tlsh::from_str(hash1).unwrap();
+/// let hash2 = Tlsh::from_str(hash2).unwrap();
+/// let mut packed = [0u8; 32 * 2];
+/// packed[..32].copy_from_slice(hash2.body().as_packed_dibits());
+/// packed[32..].copy_from_slice(hash1.body().as_packed_dibits());
+/// assert_eq!(tlsh::min_distance_packed(hash1.body(), &packed), Some((1, 0)));
+/// assert_eq!(tlsh::min_distance_packed(hash2.body(), &packed), Some((0, 0)));
+/// assert_eq!(tlsh::min_distance_packed(hash1.body(), &[]), None);
+/// ```
+pub fn min_distance_packed<const SIZE_BODY: usize>(
+    query_body: &FuzzyHashBodyData<SIZE_BODY>,
+    packed: &[u8],
+) -> Option<(usize, u32)>
+where
+    FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
+{
+    let mut chunks = packed.chunks_exact(SIZE_BODY).enumerate();
+    let (_, first) = chunks.next()?;
+    let first = FuzzyHashBodyData::from_raw(first.try_into().unwrap());
+    let mut closest = (0, query_body.compare(&first));
+    for (index, raw) in chunks {
+        // Only strictly closer entries replace the current one.
+        let Some(budget) = closest.1.checked_sub(1) else {
+            break;
+        };
+        let body = FuzzyHashBodyData::from_raw(raw.try_into().unwrap());
+        if let Some(distance) = query_body.compare_within(&body, budget) {
+            closest = (index, distance);
+        }
+    }
+    Some(closest)
+}
+
+/// (fast-tlsh specific)
+/// Returns `n` candidates closest to `query`
+/// (with [the default configuration](ComparisonConfiguration::Default)).
+///
+/// It returns a vector of the index in `candidates` and the distance,
+/// sorted by the distance (ties are broken by the index).  If `candidates`
+/// have less than `n` entries, all entries are returned.
+///
+/// This is more efficient than computing all distances and sorting them.
+/// While scanning, it maintains `n` closest candidates so far in a bounded
+/// max-heap and compares the next candidate by
+/// [`compare_within()`](FuzzyHashType::compare_within()) with the budget
+/// tightened by the current farthest one.
+///
+/// This function requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::prelude::*;
+///
+/// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+/// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+/// let hash1 = Tlsh::from_str(hash1).unwrap();
+/// let hash2 = Tlsh::from_str(hash2).unwrap();
+/// let candidates = [hash2, hash1, hash2];
+/// assert_eq!(tlsh::nearest_n(&hash1, &candidates, 2), [(1, 0), (0, 138)]);
+/// assert_eq!(tlsh::nearest_n(&hash2, &candidates, 5), [(0, 0), (2, 0), (1, 138)]);
+/// assert!(tlsh::nearest_n(&hash1, &candidates, 0).is_empty());
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "alloc")))]
+pub fn nearest_n<T: FuzzyHashType>(
+    query: &T,
+    candidates: &[T],
+    n: usize,
+) -> alloc::vec::Vec<(usize, u32)> {
+    use alloc::collections::BinaryHeap;
+    if n == 0 {
+        return alloc::vec::Vec::new();
+    }
+    // The max-heap of (distance, index) pairs (the top is the farthest).
+    let mut heap: BinaryHeap<(u32, usize)> = BinaryHeap::with_capacity(n.min(candidates.len()));
+    for (index, candidate) in candidates.iter().enumerate() {
+        if heap.len() < n {
+            heap.push((query.compare(candidate), index));
+            continue;
+        }
+        // Only strictly closer candidates replace the farthest one
+        // (which has a smaller index on a tie).
+        let Some(budget) = heap.peek().unwrap().0.checked_sub(1) else {
+            // All candidates so far have the distance zero.
+            break;
+        };
+        if let Some(distance) = query.compare_within(candidate, budget) {
+            heap.pop();
+            heap.push((distance, index));
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|(distance, index)| (index, distance))
+        .collect()
 }
 
 mod tests;
@@ -57,6 +57,8 @@ mod pseudo_simd_32;
 #[allow(dead_code)]
 mod pseudo_simd_64;
 mod x86_avx2;
+mod x86_avx512;
+#[allow(dead_code)]
 mod x86_sse2;
 mod x86_sse4_1;
 
@@ -66,6 +68,10 @@ mod fuzzer;
 pub const BODY_OUTLIER_VALUE: u32 = 6;
 static_assertions::const_assert!(BODY_OUTLIER_VALUE >= 0b11); // must be at least 3.
 
+/// The maximum distance between two 6-byte bodies.
+#[cfg(feature = "experiment-variants")]
+pub const MAX_DISTANCE_EXTRA_SHORT: u32 = 6 * 4 * BODY_OUTLIER_VALUE;
+
 /// The maximum distance between two 12-byte bodies.
 pub const MAX_DISTANCE_SHORT: u32 = 12 * 4 * BODY_OUTLIER_VALUE;
 
@@ -138,7 +144,8 @@ static DISPATCH_DISTANCE_64: OnceLock<&'static (dyn Fn(&[u8; 64], &[u8; 64]) ->
 /// Generates distance functions like [`distance_32()`].
 ///
 /// Note that is doesn't generate [`distance_12()`] (the shortest variant)
-/// because handling this variant using SIMD can be very inefficient.
+/// because handling this variant using SIMD can be very inefficient
+/// (an SSE2 implementation is only available as an option).
 macro_rules! distance_func_template {
     {$($name:ident = ($size:literal, $dispatch:path);)*} => {
         $(
@@ -160,7 +167,7 @@ macro_rules! distance_func_template {
                         $dispatch.get_or_init(|| {
                             #[cfg(all(target_arch = "arm"))]
                             {
-                                if is_arm_feature_detected!("neon") {
+                                if crate::simd::allows(crate::simd::Backend::Neon) && is_arm_feature_detected!("neon") {
                                     return &|body1, body2| {
                                         #[allow(unsafe_code)]
                                         unsafe {
@@ -169,9 +176,23 @@ macro_rules! distance_func_template {
                                     };
                                 }
                             }
+                            #[cfg(all(
+                                any(target_arch = "x86", target_arch = "x86_64"),
+                                fast_tlsh_x86_avx512 = "stable"
+                            ))]
+                            {
+                                if crate::simd::allows(crate::simd::Backend::Avx512) && is_x86_feature_detected!("avx512bw") {
+                                    return &|body1, body2| {
+                                        #[allow(unsafe_code)]
+                                        unsafe {
+                                            x86_avx512::$name(body1, body2)
+                                        }
+                                    };
+                                }
+                            }
                             #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
                             {
-                                if is_x86_feature_detected!("avx2") {
+                                if crate::simd::allows(crate::simd::Backend::Avx2) && is_x86_feature_detected!("avx2") {
                                     return &|body1, body2| {
                                         #[allow(unsafe_code)]
                                         unsafe {
@@ -179,7 +200,7 @@ macro_rules! distance_func_template {
                                         }
                                     };
                                 }
-                                if is_x86_feature_detected!("sse4.1") {
+                                if crate::simd::allows(crate::simd::Backend::Sse4_1) && is_x86_feature_detected!("sse4.1") {
                                     return &|body1, body2| {
                                         #[allow(unsafe_code)]
                                         unsafe {
@@ -187,7 +208,7 @@ macro_rules! distance_func_template {
                                         }
                                     };
                                 }
-                                if is_x86_feature_detected!("sse2") {
+                                if crate::simd::allows(crate::simd::Backend::Sse2) && is_x86_feature_detected!("sse2") {
                                     return &|body1, body2| {
                                         #[allow(unsafe_code)]
                                         unsafe {
@@ -227,6 +248,18 @@ macro_rules! distance_func_template {
                             arm_neon::$name(body1, body2)
                         }
                     }
+                    else if #[cfg(all(
+                        feature = "simd-per-arch",
+                        feature = "opt-simd-body-comparison",
+                        any(target_arch = "x86", target_arch = "x86_64"),
+                        fast_tlsh_x86_avx512 = "stable",
+                        target_feature = "avx512bw"
+                    ))] {
+                        #[allow(unsafe_code)]
+                        unsafe {
+                            x86_avx512::$name(body1, body2)
+                        }
+                    }
                     else if #[cfg(all(
                         feature = "simd-per-arch",
                         feature = "opt-simd-body-comparison",
@@ -284,16 +317,127 @@ distance_func_template! {
     distance_64 = (64, DISPATCH_DISTANCE_64);
 }
 
-/// Computes the distance between two 12-byte TLSH bodies.
+/// Computes the distance between two 6-byte TLSH bodies
+/// (on the experimental extra short variant).
+#[cfg(feature = "experiment-variants")]
 #[cfg_attr(feature = "unstable", coverage(off))]
-pub fn distance_12(body1: &[u8; 12], body2: &[u8; 12]) -> u32 {
+pub fn distance_6(body1: &[u8; 6], body2: &[u8; 6]) -> u32 {
     if usize::BITS >= 64 {
-        pseudo_simd_64::distance_12(body1, body2)
+        pseudo_simd_64::distance_6(body1, body2)
     } else {
-        pseudo_simd_32::distance_12(body1, body2)
+        pseudo_simd_32::distance_6(body1, body2)
+    }
+}
+
+/// Computes the distance between two 6-byte TLSH bodies
+/// unless it exceeds `max_distance`.
+///
+/// Because the body is short, this variant does not stop early.
+#[cfg(feature = "experiment-variants")]
+#[inline]
+pub fn distance_within_6(body1: &[u8; 6], body2: &[u8; 6], max_distance: u32) -> Option<u32> {
+    let distance = distance_6(body1, body2);
+    (distance <= max_distance).then_some(distance)
+}
+
+/// Computes the distance between two 12-byte TLSH bodies.
+///
+/// By default, it uses a pseudo-SIMD implementation.  With the
+/// `opt-simd-body-comparison-short` feature, it uses the SSE2 implementation
+/// on `x86` / `x86_64` (only if SSE2 is enabled on compile time, which is
+/// the baseline on `x86_64`).
+#[cfg_attr(feature = "unstable", coverage(off))]
+pub fn distance_12(body1: &[u8; 12], body2: &[u8; 12]) -> u32 {
+    cfg_if::cfg_if! {
+        if #[cfg(all(
+            feature = "simd-per-arch",
+            feature = "opt-simd-body-comparison",
+            feature = "opt-simd-body-comparison-short",
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "sse2"
+        ))] {
+            #[allow(unsafe_code)]
+            unsafe {
+                x86_sse2::distance_12(body1, body2)
+            }
+        }
+        else {
+            if usize::BITS >= 64 {
+                pseudo_simd_64::distance_12(body1, body2)
+            } else {
+                pseudo_simd_32::distance_12(body1, body2)
+            }
+        }
+    }
+}
+
+/// Computes the distance between two 12-byte TLSH bodies
+/// unless it exceeds `max_distance`.
+///
+/// Because the body is short, this variant does not stop early.
+#[inline]
+pub fn distance_within_12(body1: &[u8; 12], body2: &[u8; 12], max_distance: u32) -> Option<u32> {
+    let distance = distance_12(body1, body2);
+    (distance <= max_distance).then_some(distance)
+}
+
+/// Generates distance functions like [`distance_within_32()`].
+macro_rules! distance_within_func_template {
+    {$($name:ident = $size:literal;)*} => {
+        $(
+            #[doc = concat!(
+                "Computes the distance between two ",
+                stringify!($size),
+                "-byte TLSH bodies unless it exceeds `max_distance`.\n",
+                "\n",
+                "It returns [`None`] as soon as the running distance exceeds ",
+                "`max_distance`.  The granularity of this early exit depends on ",
+                "the implementation: on SIMD implementations, it is checked ",
+                "per 32-byte chunk and on pseudo-SIMD implementations, ",
+                "per machine word."
+            )]
+            #[inline]
+            pub fn $name(
+                body1: &[u8; $size],
+                body2: &[u8; $size],
+                max_distance: u32,
+            ) -> Option<u32> {
+                cfg_if::cfg_if! {
+                    if #[cfg(all(
+                        feature = "opt-simd-body-comparison",
+                        any(feature = "simd-per-arch", feature = "simd-portable")
+                    ))] {
+                        let mut total = 0;
+                        for (x, y) in body1
+                            .as_slice()
+                            .chunks_exact(32)
+                            .zip(body2.as_slice().chunks_exact(32))
+                        {
+                            total += distance_32(x.try_into().unwrap(), y.try_into().unwrap());
+                            if total > max_distance {
+                                return None;
+                            }
+                        }
+                        Some(total)
+                    }
+                    else {
+                        if usize::BITS >= 64 {
+                            pseudo_simd_64::distance_within(body1, body2, max_distance)
+                        } else {
+                            pseudo_simd_32::distance_within(body1, body2, max_distance)
+                        }
+                    }
+                }
+            }
+        )*
     }
 }
 
+distance_within_func_template! {
+    distance_within_32 = 32;
+    distance_within_64 = 64;
+}
+
 /// The naïve implementation.
 #[cfg(any(doc, test))]
 #[cfg_attr(feature = "unstable", doc(cfg(all())))]
@@ -55,6 +55,20 @@ pub(crate) fn sub_distance(x: u32, y: u32) -> u32 {
     ((s * Wrapping(0x01010101)) >> 24).0 // SUM (0..=96)
 }
 
+/// Computes the distance between two 6-byte TLSH bodies.
+#[cfg(feature = "experiment-variants")]
+#[inline]
+pub fn distance_6(body1: &[u8; 6], body2: &[u8; 6]) -> u32 {
+    let x = u32::from_ne_bytes(body1[0..4].try_into().unwrap());
+    let y = u32::from_ne_bytes(body2[0..4].try_into().unwrap());
+    let mut total = sub_distance(x, y);
+    // Zero padding does not affect the distance.
+    let x = u32::from_ne_bytes([body1[4], body1[5], 0, 0]);
+    let y = u32::from_ne_bytes([body2[4], body2[5], 0, 0]);
+    total += sub_distance(x, y);
+    total
+}
+
 /// Generates distance functions like [`distance_32()`].
 macro_rules! distance_func_template {
     {$($name:ident = $size:literal;)*} => {
@@ -83,3 +97,27 @@ distance_func_template! {
     distance_32 = 32;
     distance_64 = 64;
 }
+
+/// Computes the distance between two TLSH bodies unless it exceeds
+/// `max_distance` (stops early on each 4-byte chunk).
+#[inline]
+pub fn distance_within<const N: usize>(
+    body1: &[u8; N],
+    body2: &[u8; N],
+    max_distance: u32,
+) -> Option<u32> {
+    let mut total = 0;
+    for (x, y) in body1
+        .as_slice()
+        .chunks_exact(4)
+        .zip(body2.as_slice().chunks_exact(4))
+    {
+        let x = u32::from_ne_bytes(x.try_into().unwrap());
+        let y = u32::from_ne_bytes(y.try_into().unwrap());
+        total += sub_distance(x, y);
+        if total > max_distance {
+            return None;
+        }
+    }
+    Some(total)
+}
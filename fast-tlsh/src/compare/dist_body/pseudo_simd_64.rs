@@ -67,6 +67,18 @@ pub fn distance_12(body1: &[u8; 12], body2: &[u8; 12]) -> u32 {
     total
 }
 
+/// Computes the distance between two 6-byte TLSH bodies.
+#[cfg(feature = "experiment-variants")]
+#[inline]
+pub fn distance_6(body1: &[u8; 6], body2: &[u8; 6]) -> u32 {
+    // Zero padding does not affect the distance.
+    let mut x = [0u8; 8];
+    let mut y = [0u8; 8];
+    x[..6].copy_from_slice(body1);
+    y[..6].copy_from_slice(body2);
+    sub_distance(u64::from_ne_bytes(x), u64::from_ne_bytes(y))
+}
+
 /// Generates distance functions like [`distance_32()`].
 macro_rules! distance_func_template {
     {$($name:ident = $size:literal;)*} => {
@@ -94,3 +106,27 @@ distance_func_template! {
     distance_32 = 32;
     distance_64 = 64;
 }
+
+/// Computes the distance between two TLSH bodies unless it exceeds
+/// `max_distance` (stops early on each 8-byte chunk).
+#[inline]
+pub fn distance_within<const N: usize>(
+    body1: &[u8; N],
+    body2: &[u8; N],
+    max_distance: u32,
+) -> Option<u32> {
+    let mut total = 0;
+    for (x, y) in body1
+        .as_slice()
+        .chunks_exact(8)
+        .zip(body2.as_slice().chunks_exact(8))
+    {
+        let x = u64::from_ne_bytes(x.try_into().unwrap());
+        let y = u64::from_ne_bytes(y.try_into().unwrap());
+        total += sub_distance(x, y);
+        if total > max_distance {
+            return None;
+        }
+    }
+    Some(total)
+}
@@ -139,3 +139,207 @@ fn equivalence_optimized_impl() {
     test::<BODY_SIZE_NORMAL>();
     test::<BODY_SIZE_LONG>();
 }
+
+#[cfg(all(
+    feature = "simd-per-arch",
+    feature = "opt-simd-body-comparison",
+    feature = "detect-features",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    fast_tlsh_x86_avx512 = "stable"
+))]
+#[test]
+fn equivalence_x86_avx512() {
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use super::x86_avx512;
+
+    if !std::arch::is_x86_feature_detected!("avx512bw") {
+        return;
+    }
+    macro_rules! test {
+        ($name:ident, $size:literal, $seed:literal) => {
+            #[allow(unsafe_code)]
+            let check = |body1: &[u8; $size], body2: &[u8; $size]| {
+                let expected = naive::distance(body1, body2);
+                let actual = unsafe { x86_avx512::$name(body1, body2) };
+                assert_eq!(
+                    actual, expected,
+                    "failed on body1={body1:?}, body2={body2:?}"
+                );
+            };
+            // Single dibit difference
+            for index in 0..$size * 4 {
+                for a in 0..4 {
+                    let mut body_a = [0u8; $size];
+                    body_a[$size - 1 - index / 4] |= a << (2 * (index % 4));
+                    for b in 0..4 {
+                        let mut body_b = [0u8; $size];
+                        body_b[$size - 1 - index / 4] |= b << (2 * (index % 4));
+                        check(&body_a, &body_b);
+                    }
+                }
+            }
+            // All dibit difference
+            for a in 0..4 {
+                let body_a = [(0..4).fold(0u8, |x, _| (x << 2) | a); $size];
+                for b in 0..4 {
+                    let body_b = [(0..4).fold(0u8, |x, _| (x << 2) | b); $size];
+                    check(&body_a, &body_b);
+                }
+            }
+            // Random bodies
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut body1 = [0u8; $size];
+            let mut body2 = [0u8; $size];
+            for _ in 0..10000 {
+                rng.fill_bytes(body1.as_mut_slice());
+                rng.fill_bytes(body2.as_mut_slice());
+                check(&body1, &body2);
+            }
+        };
+    }
+    test!(distance_32, 32, 0x2b4f0a6c8e1d3957);
+    test!(distance_64, 64, 0x91c3e5a7b8d2f046);
+}
+
+#[cfg(all(
+    feature = "simd-per-arch",
+    feature = "opt-simd-body-comparison",
+    feature = "opt-simd-body-comparison-short",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+#[test]
+fn equivalence_x86_sse2_distance_12() {
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use super::x86_sse2;
+
+    #[allow(unsafe_code)]
+    let check = |body1: &[u8; 12], body2: &[u8; 12]| {
+        let expected = naive::distance(body1, body2);
+        let actual = unsafe { x86_sse2::distance_12(body1, body2) };
+        assert_eq!(
+            actual, expected,
+            "failed on body1={body1:?}, body2={body2:?}"
+        );
+        assert_eq!(super::distance_12(body1, body2), expected);
+    };
+    // Single dibit difference
+    for index in 0..12 * 4 {
+        for a in 0..4 {
+            let mut body_a = [0u8; 12];
+            body_a[12 - 1 - index / 4] |= a << (2 * (index % 4));
+            for b in 0..4 {
+                let mut body_b = [0u8; 12];
+                body_b[12 - 1 - index / 4] |= b << (2 * (index % 4));
+                check(&body_a, &body_b);
+            }
+        }
+    }
+    // All dibit difference (including the maximum distance)
+    for a in 0..4 {
+        let body_a = [(0..4).fold(0u8, |x, _| (x << 2) | a); 12];
+        for b in 0..4 {
+            let body_b = [(0..4).fold(0u8, |x, _| (x << 2) | b); 12];
+            check(&body_a, &body_b);
+        }
+    }
+    // Random bodies
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0xc41e_7a09_5fd2_b368);
+    let mut body1 = [0u8; 12];
+    let mut body2 = [0u8; 12];
+    for _ in 0..10000 {
+        rng.fill_bytes(body1.as_mut_slice());
+        rng.fill_bytes(body2.as_mut_slice());
+        check(&body1, &body2);
+    }
+}
+
+#[test]
+fn distance_within_consistency() {
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    macro_rules! test {
+        ($name:ident, $size:literal, $seed:literal) => {
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut body1 = [0u8; $size];
+            let mut body2 = [0u8; $size];
+            for _ in 0..1000 {
+                rng.fill_bytes(body1.as_mut_slice());
+                rng.fill_bytes(body2.as_mut_slice());
+                let distance = naive::distance(&body1, &body2);
+                for max_distance in [
+                    0,
+                    distance / 2,
+                    distance - 1,
+                    distance,
+                    distance + 1,
+                    u32::MAX,
+                ] {
+                    let expected = (distance <= max_distance).then_some(distance);
+                    assert_eq!(super::$name(&body1, &body2, max_distance), expected);
+                    if $size != 12 {
+                        assert_eq!(
+                            pseudo_simd_32::distance_within(&body1, &body2, max_distance),
+                            expected
+                        );
+                        assert_eq!(
+                            pseudo_simd_64::distance_within(&body1, &body2, max_distance),
+                            expected
+                        );
+                    }
+                }
+            }
+        };
+    }
+    test!(distance_within_12, 12, 0x3d6f1a2b9c4e8057);
+    test!(distance_within_32, 32, 0xa4c8e2f6b1d3957a);
+    test!(distance_within_64, 64, 0x6e1b3d5f7a9c2048);
+}
+
+#[cfg(feature = "experiment-variants")]
+#[test]
+fn equivalence_distance_6() {
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    // Single dibit difference
+    for index in 0..6 * 4 {
+        for a in 0..4 {
+            let mut body_a = [0u8; 6];
+            body_a[6 - 1 - index / 4] |= a << (2 * (index % 4));
+            for b in 0..4 {
+                let mut body_b = [0u8; 6];
+                body_b[6 - 1 - index / 4] |= b << (2 * (index % 4));
+                let expected = distance_dibits(a, b);
+                assert_eq!(naive::distance(&body_a, &body_b), expected);
+                assert_eq!(super::distance_6(&body_a, &body_b), expected);
+                assert_eq!(pseudo_simd_32::distance_6(&body_a, &body_b), expected);
+                assert_eq!(pseudo_simd_64::distance_6(&body_a, &body_b), expected);
+            }
+        }
+    }
+    // Random bodies
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x5a17_c3e9_02bd_846f);
+    let mut body1 = [0u8; 6];
+    let mut body2 = [0u8; 6];
+    for _ in 0..1000 {
+        rng.fill_bytes(body1.as_mut_slice());
+        rng.fill_bytes(body2.as_mut_slice());
+        let expected = naive::distance(&body1, &body2);
+        assert!(expected <= super::MAX_DISTANCE_EXTRA_SHORT);
+        assert_eq!(super::distance_6(&body1, &body2), expected);
+        assert_eq!(pseudo_simd_32::distance_6(&body1, &body2), expected);
+        assert_eq!(pseudo_simd_64::distance_6(&body1, &body2), expected);
+        for max_distance in [0, expected / 2, expected, expected + 1] {
+            assert_eq!(
+                super::distance_within_6(&body1, &body2, max_distance),
+                (expected <= max_distance).then_some(expected)
+            );
+        }
+    }
+}
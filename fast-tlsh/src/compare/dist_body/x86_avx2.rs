@@ -9,7 +9,13 @@
     feature = "simd-per-arch",
     feature = "opt-simd-body-comparison",
     any(target_arch = "x86", target_arch = "x86_64"),
-    any(feature = "detect-features", target_feature = "avx2")
+    any(
+        feature = "detect-features",
+        all(
+            not(all(fast_tlsh_x86_avx512 = "stable", target_feature = "avx512bw")),
+            target_feature = "avx2"
+        )
+    )
 ))]
 
 #[cfg(target_arch = "x86")]
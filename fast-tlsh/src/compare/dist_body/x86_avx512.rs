@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! AVX-512 implementation (x86) of TLSH body comparison.
+//!
+//! This implementation handles a 512-bit integer as 256 2-bit integers.
+
+#![cfg(all(
+    feature = "simd-per-arch",
+    feature = "opt-simd-body-comparison",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    fast_tlsh_x86_avx512 = "stable",
+    any(feature = "detect-features", target_feature = "avx512bw")
+))]
+// AVX-512 intrinsics are only enabled on Rust 1.89 or later (see build.rs).
+#![allow(clippy::incompatible_msrv)]
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+static_assertions::const_assert_eq!(super::BODY_OUTLIER_VALUE, 6);
+
+/// Computes the distance between two 512-bit vectors and return as
+/// a packed `u64` array (8 elements).
+#[allow(unsafe_code)]
+#[cfg_attr(not(feature = "detect-features"), inline(always))]
+#[cfg_attr(
+    feature = "detect-features",
+    target_feature(enable = "avx512bw"),
+    inline
+)]
+unsafe fn packed_distance_as_u64x8(x: __m512i, y: __m512i) -> __m512i {
+    // Constants
+    let mask_dibit_01 = _mm512_set1_epi8(0b01_01_01_01i8);
+    let mask_dibit_10 = _mm512_set1_epi8(0b10_10_10_10u8 as i8);
+    let mask_nibble_0011 = _mm512_set1_epi8(0b0011_0011);
+    let mask_byte_00001111 = _mm512_set1_epi8(0b00001111);
+
+    let z = _mm512_xor_si512(x, y);
+
+    // Step by Step evaluation (independent A and B are interleaved)
+    let ta = _mm512_and_si512(y, mask_dibit_01);
+    let tb = _mm512_and_si512(x, mask_dibit_01);
+    let ta = _mm512_or_si512(ta, _mm512_slli_epi32::<1>(ta)); // * 3
+    let tb = _mm512_sub_epi32(mask_dibit_10, tb);
+    let ta = _mm512_xor_si512(ta, x);
+    let tb = _mm512_xor_si512(tb, x);
+    let sa = _mm512_and_si512(ta, z); // SUM 1 (2-bit sliced; 0..=3)
+    let tb = _mm512_and_si512(tb, z);
+    let ta = _mm512_srli_epi32::<2>(sa);
+    let sa = _mm512_and_si512(sa, mask_nibble_0011);
+    let tb = _mm512_srli_epi32::<1>(tb);
+    let ta = _mm512_and_si512(ta, mask_nibble_0011);
+    let tb = _mm512_or_si512(tb, _mm512_slli_epi32::<1>(tb)); // * 3
+    let sa = _mm512_add_epi32(sa, ta); // SUM 1 (4-bit sliced; 0..=6)
+    let sb = _mm512_and_si512(tb, z); // SUM 2 (2-bit sliced; 0..=3)
+    let tb = _mm512_srli_epi32::<2>(sb);
+    let sb = _mm512_and_si512(sb, mask_nibble_0011);
+    let tb = _mm512_and_si512(tb, mask_nibble_0011);
+    let sb = _mm512_add_epi32(sb, tb); // SUM 2 (4-bit sliced; 0..=6)
+
+    // Aggregation
+    let s = _mm512_add_epi32(sb, sa); // SUM (4-bit sliced; 0..=12)
+    let t = _mm512_srli_epi32::<4>(s);
+    let s = _mm512_and_si512(s, mask_byte_00001111);
+    let t = _mm512_and_si512(t, mask_byte_00001111);
+    let s = _mm512_add_epi32(s, t); // SUM (8-bit sliced; 0..=24)
+    _mm512_sad_epu8(s, _mm512_setzero_si512()) // SUM (64-bit sliced; 0..=192)
+}
+
+/// Computes the distance between two 32-byte TLSH bodies.
+#[allow(unsafe_code)]
+#[cfg_attr(not(feature = "detect-features"), inline(always))]
+#[cfg_attr(
+    feature = "detect-features",
+    target_feature(enable = "avx512bw"),
+    inline
+)]
+pub unsafe fn distance_32(body1: &[u8; 32], body2: &[u8; 32]) -> u32 {
+    // Upper 256 bits are zero and do not contribute to the distance.
+    let x = _mm512_zextsi256_si512(_mm256_loadu_si256(body1 as *const u8 as *const __m256i));
+    let y = _mm512_zextsi256_si512(_mm256_loadu_si256(body2 as *const u8 as *const __m256i));
+    let s = packed_distance_as_u64x8(x, y);
+    _mm512_reduce_add_epi64(s) as u32
+}
+
+/// Computes the distance between two 64-byte TLSH bodies.
+#[allow(unsafe_code)]
+#[cfg_attr(not(feature = "detect-features"), inline(always))]
+#[cfg_attr(
+    feature = "detect-features",
+    target_feature(enable = "avx512bw"),
+    inline
+)]
+pub unsafe fn distance_64(body1: &[u8; 64], body2: &[u8; 64]) -> u32 {
+    let x = _mm512_loadu_si512(body1 as *const u8 as *const _);
+    let y = _mm512_loadu_si512(body2 as *const u8 as *const _);
+    let s = packed_distance_as_u64x8(x, y);
+    _mm512_reduce_add_epi64(s) as u32
+}
@@ -15,7 +15,8 @@
             not(target_feature = "avx2"),
             not(target_feature = "sse4.1"),
             target_feature = "sse2"
-        )
+        ),
+        all(feature = "opt-simd-body-comparison-short", target_feature = "sse2")
     )
 ))]
 
@@ -73,6 +74,35 @@ unsafe fn packed_distance_as_u16x8(x: __m128i, y: __m128i) -> __m128i {
     _mm_add_epi16(s, t) // SUM (16-bit sliced; 0..=48)
 }
 
+/// Computes the distance between two 12-byte TLSH bodies.
+///
+/// Each body is loaded as a 128-bit vector with upper 4 bytes zero-padded
+/// (reading 16 bytes directly would read beyond the references).
+/// The zero padding does not affect the distance because the padding of
+/// both bodies has the same values (distance `0` on those lanes).
+#[allow(unsafe_code)]
+#[cfg(feature = "opt-simd-body-comparison-short")]
+#[cfg_attr(not(feature = "detect-features"), inline(always))]
+#[cfg_attr(feature = "detect-features", target_feature(enable = "sse2"), inline)]
+pub unsafe fn distance_12(body1: &[u8; 12], body2: &[u8; 12]) -> u32 {
+    let load = |body: &[u8; 12]| {
+        let lo = u64::from_le_bytes(body[0..8].try_into().unwrap());
+        let hi = u32::from_le_bytes(body[8..12].try_into().unwrap());
+        _mm_set_epi64x(hi as i64, lo as i64)
+    };
+    let x = load(body1);
+    let y = load(body2);
+    let s = packed_distance_as_u16x8(x, y); // SUM (16-bit sliced; 0..=48)
+
+    // Horizontal sum
+    let t = _mm_shuffle_epi32::<0b11_10_11_10>(s);
+    let s = _mm_add_epi16(s, t); // SUM (16-bit sliced; 0..=96 on lanes 0-3)
+    let t = _mm_shuffle_epi32::<0b01_01_01_01>(s);
+    let s = _mm_add_epi16(s, t); // SUM (16-bit sliced; 0..=192 on lanes 0-1)
+    let t = _mm_cvtsi128_si32(s) as u32;
+    (t & 0xffff).wrapping_add(t.wrapping_shr(16))
+}
+
 /// Computes the distance between two 32-byte TLSH bodies.
 #[allow(unsafe_code)]
 #[cfg_attr(not(feature = "detect-features"), inline(always))]
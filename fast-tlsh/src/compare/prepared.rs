@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>
+
+//! The prepared query for repeated comparisons.
+
+use crate::compare::ComparisonConfiguration;
+use crate::hash::body::FuzzyHashBody;
+use crate::hash::checksum::FuzzyHashChecksum;
+use crate::hash::qratios::FuzzyHashQRatios;
+use crate::length::FuzzyHashLengthEncoding;
+use crate::FuzzyHashType;
+
+/// The fuzzy hash prepared to be compared against many candidates.
+///
+/// In a scanning loop, the query fuzzy hash is usually fixed while
+/// the candidates change.  This type precomputes the distances of the
+/// length and Q ratio pair parts between the query and all possible values
+/// (with the length distance already weighted by the configuration) so that
+/// those parts of each comparison are just table lookups.
+///
+/// Since the body comparison dominates, whether this is faster than
+/// [`FuzzyHashType::compare_with_config()`] depends on the variant and
+/// the target.  The `prepared_query` benchmark (`cargo bench`) compares both.
+///
+/// The result is always the same as
+/// [`FuzzyHashType::compare_with_config()`] (or
+/// [`FuzzyHashType::compare_within_with_config()`]) with the same
+/// configuration.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::PreparedQuery;
+/// use tlsh::prelude::*;
+///
+/// let query: Tlsh = Tlsh::from_str(
+///     "T12AD5BE86FFE41D17CC268876A9AE472077B2B0032716DBAF1849A7647DDB7C0DF16488"
+/// ).unwrap();
+/// let candidate: Tlsh = Tlsh::from_str(
+///     "T1EDD5BE96FFE41D1BCC268C7699AE4720B7B2A0032716DBAF1848A7647DD77C0DF16488"
+/// ).unwrap();
+/// let prepared = PreparedQuery::new(&query);
+/// assert_eq!(prepared.distance(&candidate), 9);
+/// assert_eq!(prepared.distance(&candidate), query.compare(&candidate));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PreparedQuery<T: FuzzyHashType + Clone> {
+    /// The query fuzzy hash.
+    query: T,
+    /// The comparison configuration.
+    config: ComparisonConfiguration,
+    /// The (weighted) distances of the length part,
+    /// indexed by the raw length encoding of the candidate.
+    length_distances: [u32; 256],
+    /// The distances of the Q ratio pair part,
+    /// indexed by the raw Q ratio pair value of the candidate.
+    qratios_distances: [u32; 256],
+}
+
+impl<T: FuzzyHashType + Clone> PreparedQuery<T> {
+    /// Prepares the query with the default comparison configuration.
+    #[inline]
+    pub fn new(query: &T) -> Self {
+        Self::with_config(query, ComparisonConfiguration::Default)
+    }
+
+    /// Prepares the query with specified comparison configuration.
+    pub fn with_config(query: &T, config: ComparisonConfiguration) -> Self {
+        let mut length_distances = [0u32; 256];
+        let mut qratios_distances = [0u32; 256];
+        for (value, (length_distance, qratios_distance)) in length_distances
+            .iter_mut()
+            .zip(qratios_distances.iter_mut())
+            .enumerate()
+        {
+            let value = value as u8;
+            *length_distance = config.weight_length_distance(
+                query
+                    .length()
+                    .compare(&FuzzyHashLengthEncoding::from_raw(value)),
+            );
+            *qratios_distance = config.weight_qratios_distance(
+                query.qratios().compare(&FuzzyHashQRatios::from_raw(value)),
+            );
+        }
+        Self {
+            query: query.clone(),
+            config,
+            length_distances,
+            qratios_distances,
+        }
+    }
+
+    /// Returns the query fuzzy hash.
+    #[inline(always)]
+    pub fn query(&self) -> &T {
+        &self.query
+    }
+
+    /// Returns the comparison configuration.
+    #[inline(always)]
+    pub fn config(&self) -> ComparisonConfiguration {
+        self.config
+    }
+
+    /// Returns the distance of the parts except the body.
+    #[inline(always)]
+    fn distance_header(&self, candidate: &T) -> u32 {
+        (self
+            .config
+            .weight_checksum_distance(self.query.checksum().compare(candidate.checksum()))
+            + self.qratios_distances[candidate.qratios().value() as usize])
+            .saturating_add(self.length_distances[candidate.length().value() as usize])
+    }
+
+    /// Compare the query against a candidate and return the distance
+    /// between them.
+    ///
+    /// The result is the same as
+    /// [`FuzzyHashType::compare_with_config()`] with the configuration
+    /// of this object.
+    #[inline]
+    pub fn distance(&self, candidate: &T) -> u32 {
+        self.query
+            .body()
+            .compare(candidate.body())
+            .saturating_add(self.distance_header(candidate))
+    }
+
+    /// Compare the query against a candidate and return the distance
+    /// between them only if it does not exceed `max_distance`.
+    ///
+    /// The result is the same as
+    /// [`FuzzyHashType::compare_within_with_config()`] with the
+    /// configuration of this object.
+    #[inline]
+    pub fn distance_within(&self, candidate: &T, max_distance: u32) -> Option<u32> {
+        let distance = self.distance_header(candidate);
+        if distance > max_distance {
+            return None;
+        }
+        let distance_body = self
+            .query
+            .body()
+            .compare_within(candidate.body(), max_distance - distance)?;
+        Some(distance + distance_body)
+    }
+}
+
+mod tests;
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::compare::prepared`].
+
+#![cfg(test)]
+
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::PreparedQuery;
+
+use crate::compare::ComparisonConfiguration;
+use crate::hashes;
+use crate::FuzzyHashType;
+
+#[test]
+fn consistency() {
+    macro_rules! test {
+        ($ty:ty) => {
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x6e1f0c93a7d5b248);
+            let mut random_hash = || loop {
+                // Retry if the strict parser rejects the data.
+                let mut bytes = [0u8; <$ty>::SIZE_IN_BYTES];
+                rng.fill_bytes(&mut bytes);
+                if let Ok(hash) = <$ty>::try_from(bytes.as_slice()) {
+                    break hash;
+                }
+            };
+            for config in [
+                ComparisonConfiguration::Default,
+                ComparisonConfiguration::NoLength,
+                ComparisonConfiguration::NoChecksum,
+                ComparisonConfiguration::BodyOnly,
+                ComparisonConfiguration::Weighted {
+                    length_multiplier: 3,
+                },
+                ComparisonConfiguration::Weighted {
+                    length_multiplier: u32::MAX,
+                },
+            ] {
+                for _ in 0..10 {
+                    let query = random_hash();
+                    let prepared = PreparedQuery::with_config(&query, config);
+                    assert_eq!(prepared.query(), &query);
+                    assert_eq!(prepared.config(), config);
+                    for _ in 0..100 {
+                        let candidate = random_hash();
+                        let expected = query.compare_with_config(&candidate, config);
+                        assert_eq!(prepared.distance(&candidate), expected);
+                        for max_distance in [0, expected / 2, expected, expected.saturating_add(1)]
+                        {
+                            assert_eq!(
+                                prepared.distance_within(&candidate, max_distance),
+                                query.compare_within_with_config(&candidate, config, max_distance)
+                            );
+                        }
+                    }
+                    assert_eq!(
+                        prepared.distance(&query),
+                        query.compare_with_config(&query, config)
+                    );
+                }
+            }
+            let query = random_hash();
+            let candidate = random_hash();
+            assert_eq!(
+                PreparedQuery::new(&query).distance(&candidate),
+                query.compare(&candidate)
+            );
+        };
+    }
+    test!(hashes::Short);
+    test!(hashes::Normal);
+    test!(hashes::NormalWithLongChecksum);
+    test!(hashes::Long);
+    test!(hashes::LongWithLongChecksum);
+}
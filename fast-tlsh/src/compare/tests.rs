@@ -7,6 +7,12 @@
 
 use core::str::FromStr;
 
+use crate::compare::{ComparisonConfiguration, Distance, DistanceBreakdown, Similarity};
+use crate::generate::tests::LOREM_IPSUM_HASH_NORMAL;
+use crate::hash::body::FuzzyHashBody;
+use crate::hash::checksum::FuzzyHashChecksum;
+use crate::hash::qratios::FuzzyHashQRatios;
+use crate::length::FuzzyHashLengthEncoding;
 use crate::{FuzzyHashType, Tlsh};
 
 #[test]
@@ -20,3 +26,487 @@ fn tlsh_timing_unittest_vectors() {
     assert_eq!(hash1.compare(&hash2), expected);
     assert_eq!(hash2.compare(&hash1), expected);
 }
+
+#[test]
+fn distance_breakdown() {
+    let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+    let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+    let hash1 = Tlsh::from_str(hash1).unwrap();
+    let hash2 = Tlsh::from_str(hash2).unwrap();
+    for config in [
+        ComparisonConfiguration::Default,
+        ComparisonConfiguration::NoLength,
+        ComparisonConfiguration::NoChecksum,
+        ComparisonConfiguration::BodyOnly,
+    ] {
+        let parts = hash1.compare_parts(&hash2, config);
+        assert_eq!(parts, hash2.compare_parts(&hash1, config));
+        assert_eq!(parts.total(), hash1.compare_with_config(&hash2, config));
+        match config {
+            ComparisonConfiguration::NoChecksum | ComparisonConfiguration::BodyOnly => {
+                assert_eq!(parts.checksum, 0)
+            }
+            _ => assert_eq!(parts.checksum, hash1.checksum().compare(hash2.checksum())),
+        }
+        match config {
+            ComparisonConfiguration::BodyOnly => assert_eq!(parts.qratios, 0),
+            _ => assert_eq!(parts.qratios, hash1.qratios().compare(hash2.qratios())),
+        }
+        assert_eq!(parts.body, hash1.body().compare(hash2.body()));
+        match config {
+            ComparisonConfiguration::NoLength | ComparisonConfiguration::BodyOnly => {
+                assert_eq!(parts.length, 0)
+            }
+            _ => assert_eq!(parts.length, hash1.length().compare(hash2.length())),
+        }
+    }
+    // Identical hashes
+    assert_eq!(
+        hash1.compare_parts(&hash1, ComparisonConfiguration::Default),
+        DistanceBreakdown::default()
+    );
+}
+
+#[test]
+fn similarity_score() {
+    let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+    let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+    let hash1 = Tlsh::from_str(hash1).unwrap();
+    let hash2 = Tlsh::from_str(hash2).unwrap();
+    assert_eq!(hash1.similarity(&hash1), 100);
+    assert_eq!(hash2.similarity(&hash2), 100);
+    // 100 - floor(138 * 100 / 2473)
+    assert_eq!(Tlsh::max_distance(ComparisonConfiguration::Default), 2473);
+    assert_eq!(hash1.similarity(&hash2), 95);
+    assert_eq!(hash2.similarity(&hash1), 95);
+}
+
+#[test]
+fn similarity_score_f64() {
+    let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+    let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+    let hash1 = Tlsh::from_str(hash1).unwrap();
+    let hash2 = Tlsh::from_str(hash2).unwrap();
+    assert_eq!(hash1.similarity_f64(&hash1), 1.0);
+    assert_eq!(hash2.similarity_f64(&hash2), 1.0);
+    // 1 - 138 / 2473
+    assert_eq!(Tlsh::max_distance(ComparisonConfiguration::Default), 2473);
+    assert_eq!(hash1.similarity_f64(&hash2), 1.0 - 138.0 / 2473.0);
+    assert_eq!(hash2.similarity_f64(&hash1), 1.0 - 138.0 / 2473.0);
+    // Consistency with the integer similarity score.
+    assert_eq!(
+        hash1.similarity(&hash2),
+        100 - ((1.0 - hash1.similarity_f64(&hash2)) * 100.0) as u8
+    );
+}
+
+#[test]
+fn typed_distance_and_similarity() {
+    let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+    let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+    let hash1 = Tlsh::from_str(hash1).unwrap();
+    let hash2 = Tlsh::from_str(hash2).unwrap();
+    assert_eq!(hash1.compare_typed(&hash1), Distance(0));
+    assert_eq!(hash1.compare_typed(&hash2), Distance(138));
+    assert_eq!(hash1.compare_typed(&hash2).value(), hash1.compare(&hash2));
+    // Ordering: smaller is closer.
+    assert!(hash1.compare_typed(&hash1) < hash1.compare_typed(&hash2));
+    assert!(Distance(138).is_within(Distance(138)));
+    assert!(!Distance(139).is_within(Distance(138)));
+    assert_eq!(Distance::from(5u32), Distance(5));
+    assert_eq!(u32::from(Distance(5)), 5);
+    assert_eq!(Distance::default(), Distance(0));
+    // Similarity
+    for value in 0..=100 {
+        let similarity = Similarity::new(value).unwrap();
+        assert_eq!(similarity.value(), value);
+        assert_eq!(u8::from(similarity), value);
+    }
+    for value in 101..=u8::MAX {
+        assert_eq!(Similarity::new(value), None);
+    }
+    assert!(Similarity::MIN < Similarity::MAX);
+    assert_eq!(Similarity::MAX.value(), 100);
+    assert_eq!(Similarity::MIN.value(), 0);
+    // Consistency with the untyped similarity score.
+    for (a, b) in [(&hash1, &hash1), (&hash1, &hash2), (&hash2, &hash1)] {
+        assert_eq!(
+            Similarity::from_distance::<Tlsh>(a.compare_typed(b)).value(),
+            a.similarity(b)
+        );
+    }
+    let max_distance = Tlsh::max_distance(ComparisonConfiguration::Default);
+    assert_eq!(
+        Similarity::from_distance::<Tlsh>(Distance(max_distance)),
+        Similarity::MIN
+    );
+    assert_eq!(
+        Similarity::from_distance::<Tlsh>(Distance(u32::MAX)),
+        Similarity::MIN
+    );
+    assert_eq!(
+        Similarity::from_distance::<Tlsh>(Distance(0)),
+        Similarity::MAX
+    );
+    // Display
+    assert_eq!(format!("{}", Distance(138)), "138");
+    assert_eq!(format!("{}", Similarity::new(95).unwrap()), "95%");
+}
+
+#[test]
+fn weighted_length_distance() {
+    let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+    let hash1 = Tlsh::from_str(hash1).unwrap();
+    let hash2 = Tlsh::from_str(LOREM_IPSUM_HASH_NORMAL).unwrap();
+    let dist_length = hash1.length().compare(hash2.length());
+    assert_ne!(dist_length, 0);
+    let dist_default = hash1.compare_with_config(&hash2, ComparisonConfiguration::Default);
+    let dist_no_length = hash1.compare_with_config(&hash2, ComparisonConfiguration::NoLength);
+    assert_eq!(dist_default - dist_no_length, dist_length);
+    // Equivalent configurations
+    for (weighted, equivalent) in [
+        (0, ComparisonConfiguration::NoLength),
+        (1, ComparisonConfiguration::Default),
+    ] {
+        let weighted = ComparisonConfiguration::Weighted {
+            length_multiplier: weighted,
+        };
+        assert_eq!(
+            hash1.compare_with_config(&hash2, weighted),
+            hash1.compare_with_config(&hash2, equivalent)
+        );
+        assert_eq!(Tlsh::max_distance(weighted), Tlsh::max_distance(equivalent));
+    }
+    // Custom multipliers
+    for length_multiplier in [2, 3, 10] {
+        let config = ComparisonConfiguration::Weighted { length_multiplier };
+        let parts = hash1.compare_parts(&hash2, config);
+        assert_eq!(parts.length, dist_length * length_multiplier);
+        assert_eq!(
+            hash1.compare_with_config(&hash2, config),
+            dist_no_length + dist_length * length_multiplier
+        );
+        assert_eq!(
+            Tlsh::max_distance(config),
+            Tlsh::max_distance(ComparisonConfiguration::NoLength)
+                + FuzzyHashLengthEncoding::MAX_DISTANCE * length_multiplier
+        );
+    }
+    // Saturation
+    let config = ComparisonConfiguration::Weighted {
+        length_multiplier: u32::MAX,
+    };
+    assert_eq!(Tlsh::max_distance(config), u32::MAX);
+    assert_eq!(hash1.compare_with_config(&hash2, config), u32::MAX);
+    assert_eq!(hash1.compare_with_config(&hash1, config), 0);
+}
+
+#[test]
+fn no_checksum_distance() {
+    let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+    let hash1 = Tlsh::from_str(hash1).unwrap();
+    let hash2 = Tlsh::from_str(LOREM_IPSUM_HASH_NORMAL).unwrap();
+    let dist_checksum = hash1.checksum().compare(hash2.checksum());
+    assert_ne!(dist_checksum, 0);
+    let dist_default = hash1.compare_with_config(&hash2, ComparisonConfiguration::Default);
+    let dist_no_checksum = hash1.compare_with_config(&hash2, ComparisonConfiguration::NoChecksum);
+    assert_eq!(dist_default - dist_no_checksum, dist_checksum);
+    assert_eq!(
+        hash1.compare_within_with_config(&hash2, ComparisonConfiguration::NoChecksum, u32::MAX),
+        Some(dist_no_checksum)
+    );
+    // Comparing against a checksum-cleared fuzzy hash.
+    let mut hash2_cleared = hash2;
+    hash2_cleared.clear_checksum();
+    assert_eq!(
+        hash1.compare_with_config(&hash2_cleared, ComparisonConfiguration::NoChecksum),
+        dist_no_checksum
+    );
+    assert_eq!(
+        hash2.compare_with_config(&hash2_cleared, ComparisonConfiguration::NoChecksum),
+        0
+    );
+    // Max distance
+    assert_eq!(
+        Tlsh::max_distance(ComparisonConfiguration::Default)
+            - Tlsh::max_distance(ComparisonConfiguration::NoChecksum),
+        <Tlsh as FuzzyHashType>::ChecksumType::MAX_DISTANCE
+    );
+}
+
+#[test]
+fn body_only_distance() {
+    let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+    let hash1 = Tlsh::from_str(hash1).unwrap();
+    let hash2 = Tlsh::from_str(LOREM_IPSUM_HASH_NORMAL).unwrap();
+    let dist_body = hash1.body().compare(hash2.body());
+    let config = ComparisonConfiguration::BodyOnly;
+    assert_eq!(hash1.compare_with_config(&hash2, config), dist_body);
+    assert_eq!(hash2.compare_with_config(&hash1, config), dist_body);
+    assert!(dist_body < hash1.compare_with_config(&hash2, ComparisonConfiguration::NoLength));
+    assert!(dist_body < hash1.compare_with_config(&hash2, ComparisonConfiguration::NoChecksum));
+    assert_eq!(
+        hash1.compare_within_with_config(&hash2, config, u32::MAX),
+        Some(dist_body)
+    );
+    assert_eq!(
+        hash1.compare_within_with_config(&hash2, config, dist_body),
+        Some(dist_body)
+    );
+    assert_eq!(
+        hash1.compare_within_with_config(&hash2, config, dist_body - 1),
+        None
+    );
+    // Max distance
+    assert_eq!(
+        Tlsh::max_distance(config),
+        <Tlsh as FuzzyHashType>::BodyType::MAX_DISTANCE
+    );
+}
+
+#[test]
+fn max_distance_parts() {
+    for config in [
+        ComparisonConfiguration::Default,
+        ComparisonConfiguration::NoLength,
+        ComparisonConfiguration::NoChecksum,
+        ComparisonConfiguration::BodyOnly,
+        ComparisonConfiguration::Weighted {
+            length_multiplier: 3,
+        },
+        ComparisonConfiguration::Weighted {
+            length_multiplier: u32::MAX,
+        },
+    ] {
+        let parts = Tlsh::max_distance_parts(config);
+        assert_eq!(parts.total(), Tlsh::max_distance(config));
+        assert_eq!(
+            parts.length,
+            config.weight_length_distance(FuzzyHashLengthEncoding::MAX_DISTANCE)
+        );
+        assert_eq!(
+            parts.checksum,
+            config.weight_checksum_distance(<Tlsh as FuzzyHashType>::ChecksumType::MAX_DISTANCE)
+        );
+        assert_eq!(
+            parts.qratios,
+            config.weight_qratios_distance(FuzzyHashQRatios::MAX_DISTANCE)
+        );
+        assert_eq!(parts.body, <Tlsh as FuzzyHashType>::BodyType::MAX_DISTANCE);
+    }
+}
+
+#[test]
+fn min_distance_to_bytes_random() {
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use crate::compare::min_distance_to_bytes;
+    use crate::hash::tests::ALL_CONFIGS;
+    use crate::hashes;
+
+    macro_rules! test {
+        ($ty: ty, $seed: literal) => {{
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut query = [0u8; <$ty>::SIZE_IN_BYTES];
+            let mut region = [0u8; <$ty>::SIZE_IN_BYTES * 16 + 5];
+            for _ in 0..100 {
+                rng.fill_bytes(&mut query);
+                rng.fill_bytes(&mut region);
+                let query = match <$ty>::try_from(&query[..]) {
+                    Ok(query) => query,
+                    Err(_) => continue, // may happen with the strict parser.
+                };
+                for config in ALL_CONFIGS {
+                    // Naïve implementation.
+                    let mut expected: Option<(usize, u32)> = None;
+                    for (index, raw) in region.chunks_exact(<$ty>::SIZE_IN_BYTES).enumerate() {
+                        if let Ok(distance) = query.compare_to_bytes(raw, config) {
+                            if expected.map_or(true, |(_, min)| distance < min) {
+                                expected = Some((index, distance));
+                            }
+                        }
+                    }
+                    assert_eq!(min_distance_to_bytes(&query, &region, config), expected);
+                    // The closest entry must be found on its own.
+                    if let Some((index, distance)) = expected {
+                        let raw = &region[index * <$ty>::SIZE_IN_BYTES..];
+                        assert_eq!(
+                            min_distance_to_bytes(&query, &raw[..<$ty>::SIZE_IN_BYTES], config),
+                            Some((0, distance))
+                        );
+                    }
+                    // Incomplete entries are ignored.
+                    assert_eq!(
+                        min_distance_to_bytes(&query, &region[..<$ty>::SIZE_IN_BYTES - 1], config),
+                        None
+                    );
+                }
+            }
+        }};
+    }
+    test!(hashes::Short, 0x3c5e9a07b41f6d28);
+    test!(hashes::Normal, 0x9e16d4a3c7b0f582);
+    test!(hashes::NormalWithLongChecksum, 0x54f0b2e98a1c7d36);
+    test!(hashes::Long, 0xd7a2c51e06b94f83);
+    test!(hashes::LongWithLongChecksum, 0x0b8e7f3d2a6c9154);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn nearest_n_random() {
+    use alloc::vec::Vec;
+
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use crate::compare::nearest_n;
+    use crate::hashes;
+
+    macro_rules! test {
+        ($ty: ty, $seed: literal) => {{
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut raw = [0u8; <$ty>::SIZE_IN_BYTES];
+            let mut hashes: Vec<$ty> = Vec::new();
+            while hashes.len() < 64 {
+                rng.fill_bytes(&mut raw);
+                if let Ok(hash) = <$ty>::try_from(&raw[..]) {
+                    hashes.push(hash);
+                    // Make some duplicates to test ties.
+                    if rng.gen_range(0..4) == 0 {
+                        hashes.push(hash);
+                    }
+                }
+            }
+            for query in &hashes[..8] {
+                // Naïve implementation (stable sort by the distance).
+                let mut expected: Vec<(usize, u32)> = hashes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, hash)| (index, query.compare(hash)))
+                    .collect();
+                expected.sort_by_key(|&(_, distance)| distance);
+                for n in [0, 1, 2, 3, 10, hashes.len(), hashes.len() + 1] {
+                    let n_expected = n.min(hashes.len());
+                    assert_eq!(nearest_n(query, &hashes, n), &expected[..n_expected]);
+                }
+            }
+        }};
+    }
+    test!(hashes::Short, 0x6f1d_82a3_c04b_e759);
+    test!(hashes::Normal, 0xb3e7_5c90_1a6d_48f2);
+    test!(hashes::NormalWithLongChecksum, 0x2a94_f0c6_7e35_b1d8);
+    test!(hashes::Long, 0xd85b_3e17_a92c_064f);
+    test!(hashes::LongWithLongChecksum, 0x47c0_e9b2_5d18_f36a);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn min_distance_packed_random() {
+    use alloc::vec::Vec;
+
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use crate::compare::{min_distance_packed, pack_bodies};
+    use crate::hashes;
+
+    macro_rules! test {
+        ($ty: ty, $seed: literal) => {{
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut raw = [0u8; <$ty>::SIZE_IN_BYTES];
+            let mut hashes: Vec<$ty> = Vec::new();
+            while hashes.len() < 64 {
+                rng.fill_bytes(&mut raw);
+                if let Ok(hash) = <$ty>::try_from(&raw[..]) {
+                    hashes.push(hash);
+                    // Make some duplicates to test ties.
+                    if rng.gen_range(0..4) == 0 {
+                        hashes.push(hash);
+                    }
+                }
+            }
+            let packed = pack_bodies(&hashes);
+            let size = <$ty as FuzzyHashType>::BodyType::SIZE;
+            assert_eq!(packed.len(), hashes.len() * size);
+            for (hash, raw) in hashes.iter().zip(packed.chunks_exact(size)) {
+                assert_eq!(hash.body().as_packed_dibits(), raw);
+            }
+            for query in &hashes {
+                // Naïve implementation.
+                let mut expected: Option<(usize, u32)> = None;
+                for (index, hash) in hashes.iter().enumerate() {
+                    let distance = query.body().compare(hash.body());
+                    if expected.map_or(true, |(_, min)| distance < min) {
+                        expected = Some((index, distance));
+                    }
+                }
+                assert_eq!(min_distance_packed(query.body(), &packed), expected);
+                // Queries not in the buffer.
+                let index = hashes.iter().position(|hash| hash == query).unwrap();
+                let tail = &packed[(index + 1) * size..];
+                let expected = hashes[index + 1..]
+                    .iter()
+                    .enumerate()
+                    .map(|(index, hash)| (index, query.body().compare(hash.body())))
+                    .min_by_key(|&(_, distance)| distance);
+                assert_eq!(min_distance_packed(query.body(), tail), expected);
+                // Incomplete trailing bytes are ignored.
+                assert_eq!(min_distance_packed(query.body(), &packed[..size - 1]), None);
+            }
+        }};
+    }
+    test!(hashes::Short, 0x81f3_4c2d_e6a0_975b);
+    test!(hashes::Normal, 0x5d0a_b7e4_392f_c168);
+    test!(hashes::NormalWithLongChecksum, 0xe2c6_1f58_a4d7_0b93);
+    test!(hashes::Long, 0x39b4_d0e7_6c82_5fa1);
+    test!(hashes::LongWithLongChecksum, 0xa7e1_5b3c_08f9_d246);
+}
+
+#[test]
+fn from_tlsh_len_diff_reference() {
+    // Reference values follow `Tlsh::totalDiff()` in the official
+    // implementation: the length distance is the circular difference of
+    // the length encoding (1 stays as is and larger ones are multiplied
+    // by 12) and it is only added if `len_diff` is true.  Other parts are
+    // unchanged by modifying the length encoding.
+    // (hash1, hash2, totalDiff(len_diff=true), totalDiff(len_diff=false))
+    const PAIRS: [(&str, &str, u32, u32); 4] = [
+        (
+            "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008",
+            "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C",
+            138,
+            138,
+        ),
+        (
+            // Length: 0x52 vs. 0x53
+            "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008",
+            "T129351210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C",
+            138 + 1,
+            138,
+        ),
+        (
+            // Length: 0x52 vs. 0x55
+            "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008",
+            "T129551210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C",
+            138 + 3 * 12,
+            138,
+        ),
+        (
+            // Length: 0x52 vs. 0x50
+            "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008",
+            "T129051210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C",
+            138 + 2 * 12,
+            138,
+        ),
+    ];
+    for (hash1, hash2, dist_len_diff, dist_no_len_diff) in PAIRS {
+        let hash1 = Tlsh::from_str(hash1).unwrap();
+        let hash2 = Tlsh::from_str(hash2).unwrap();
+        for (len_diff, expected) in [(true, dist_len_diff), (false, dist_no_len_diff)] {
+            let config = ComparisonConfiguration::from_tlsh_len_diff(len_diff);
+            assert_eq!(hash1.compare_with_config(&hash2, config), expected);
+            assert_eq!(hash2.compare_with_config(&hash1, config), expected);
+        }
+    }
+}
@@ -5,7 +5,7 @@
 
 #![cfg(feature = "easy-functions")]
 
-use crate::errors::{ParseErrorEither, ParseErrorSide};
+use crate::errors::{ParseError, ParseErrorEither, ParseErrorSide};
 use crate::params::ConstrainedFuzzyHashType;
 use crate::Tlsh;
 
@@ -41,7 +41,7 @@ use crate::Tlsh;
 /// );
 /// let err = result.unwrap_err();
 /// assert_eq!(err.side(), ParseErrorSide::Right);
-/// assert_eq!(err.inner_err(), ParseError::InvalidStringLength);
+/// assert_eq!(err.inner_err(), ParseError::NullHash);
 /// ```
 pub fn compare_with<T: ConstrainedFuzzyHashType>(
     lhs: &str,
@@ -89,11 +89,100 @@ pub fn compare_with<T: ConstrainedFuzzyHashType>(
 /// );
 /// let err = result.unwrap_err();
 /// assert_eq!(err.side(), ParseErrorSide::Right);
-/// assert_eq!(err.inner_err(), ParseError::InvalidStringLength);
+/// assert_eq!(err.inner_err(), ParseError::NullHash);
 /// ```
 #[inline(always)]
 pub fn compare(lhs: &str, rhs: &str) -> Result<u32, ParseErrorEither> {
     compare_with::<Tlsh>(lhs, rhs)
 }
 
+/// Compare two fuzzy hashes (either parsed or not) with specified
+/// intermediate fuzzy hash type.
+///
+/// Each side can be anything convertible to the fuzzy hash type `T`
+/// (with an error convertible to [`ParseError`]) including:
+///
+/// *   A string slice (`&str`; parsed as a TLSH string),
+/// *   A byte slice (`&[u8]`; parsed as a binary representation) and
+/// *   A parsed fuzzy hash of type `T` itself.
+///
+/// If a conversion error occurs, [`Err`] containing
+/// [a parse error](ParseErrorEither) is returned.  Otherwise, [`Ok`]
+/// containing the distance-based score is returned.
+///
+/// # Examples
+///
+/// ```
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// let lhs: CustomTlsh = str::parse("T140D5F17F44F8AB007AE2AC46E515DC").unwrap();
+/// let result = tlsh::compare_any_with::<CustomTlsh, _, _>(
+///     lhs,
+///     "T140D5F17F44FCAB007AE2A846E515DC"
+/// );
+/// assert_eq!(result, Ok(2));
+/// ```
+pub fn compare_any_with<T, L, R>(lhs: L, rhs: R) -> Result<u32, ParseErrorEither>
+where
+    T: ConstrainedFuzzyHashType,
+    L: TryInto<T>,
+    L::Error: Into<ParseError>,
+    R: TryInto<T>,
+    R::Error: Into<ParseError>,
+{
+    let lhs: T = match lhs.try_into() {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(ParseErrorEither(ParseErrorSide::Left, err.into()));
+        }
+    };
+    let rhs: T = match rhs.try_into() {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(ParseErrorEither(ParseErrorSide::Right, err.into()));
+        }
+    };
+    Ok(lhs.compare(&rhs))
+}
+
+/// Compare two fuzzy hashes (either parsed or not).
+///
+/// Each side can be a string slice, a byte slice or a parsed [`Tlsh`]
+/// object (see [`compare_any_with()`] for details).
+///
+/// If a conversion error occurs, [`Err`] containing
+/// [a parse error](ParseErrorEither) is returned.  Otherwise, [`Ok`]
+/// containing the distance-based score (`0..=2473`) is returned.
+///
+/// # Examples
+///
+/// ```
+/// use tlsh::{ParseError, ParseErrorSide, Tlsh};
+///
+/// let lhs: Tlsh = str::parse(
+///     "T12AD5BE86FFE41D17CC268876A9AE472077B2B0032716DBAF1849A7647DDB7C0DF16488"
+/// ).unwrap();
+/// // Distance between rustc 1.66.1–1.67.1 (Linux, x86_64) is 9.
+/// let result = tlsh::compare_any(
+///     lhs,
+///     "T1EDD5BE96FFE41D1BCC268C7699AE4720B7B2A0032716DBAF1848A7647DD77C0DF16488"
+/// );
+/// assert_eq!(result, Ok(9));
+///
+/// // The parser fails on the right.
+/// let err = tlsh::compare_any(lhs, "TNULL").unwrap_err();
+/// assert_eq!(err.side(), ParseErrorSide::Right);
+/// assert_eq!(err.inner_err(), ParseError::NullHash);
+/// ```
+#[inline(always)]
+pub fn compare_any<L, R>(lhs: L, rhs: R) -> Result<u32, ParseErrorEither>
+where
+    L: TryInto<Tlsh>,
+    L::Error: Into<ParseError>,
+    R: TryInto<Tlsh>,
+    R::Error: Into<ParseError>,
+{
+    compare_any_with::<Tlsh, L, R>(lhs, rhs)
+}
+
 mod tests;
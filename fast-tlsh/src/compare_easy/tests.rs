@@ -5,10 +5,11 @@
 
 #![cfg(test)]
 
-use super::{compare, compare_with};
+use super::{compare, compare_any, compare_any_with, compare_with};
 
 use crate::errors::{ParseError, ParseErrorSide};
 use crate::hashes;
+use crate::Tlsh;
 
 #[test]
 fn test_compare_with() {
@@ -25,12 +26,12 @@ fn test_compare_with() {
     let result = compare_with::<hashes::Short>(HASH_ERR, HASH_OK);
     let err = result.unwrap_err();
     assert_eq!(err.side(), ParseErrorSide::Left);
-    assert_eq!(err.inner_err(), ParseError::InvalidStringLength);
+    assert_eq!(err.inner_err(), ParseError::NullHash);
     // Right side fails.
     let result = compare_with::<hashes::Short>(HASH_OK, HASH_ERR);
     let err = result.unwrap_err();
     assert_eq!(err.side(), ParseErrorSide::Right);
-    assert_eq!(err.inner_err(), ParseError::InvalidStringLength);
+    assert_eq!(err.inner_err(), ParseError::NullHash);
 }
 
 #[test]
@@ -49,10 +50,79 @@ fn test_compare() {
     let result = compare(HASH_ERR, HASH_OK);
     let err = result.unwrap_err();
     assert_eq!(err.side(), ParseErrorSide::Left);
-    assert_eq!(err.inner_err(), ParseError::InvalidStringLength);
+    assert_eq!(err.inner_err(), ParseError::NullHash);
     // Right side fails.
     let result = compare(HASH_OK, HASH_ERR);
     let err = result.unwrap_err();
     assert_eq!(err.side(), ParseErrorSide::Right);
+    assert_eq!(err.inner_err(), ParseError::NullHash);
+}
+
+#[test]
+fn test_compare_any_with() {
+    const HASH_L: &str = "T140D5F17F44F8AB007AE2AC46E515DC";
+    const HASH_R: &str = "T140D5F17F44FCAB007AE2A846E515DC";
+    const HASH_ERR: &str = "TNULL";
+    let hash_l: hashes::Short = str::parse(HASH_L).unwrap();
+    let hash_r: hashes::Short = str::parse(HASH_R).unwrap();
+    let bytes_l = hash_l.to_bytes();
+    let bytes_r = hash_r.to_bytes();
+    // Comparison succeeds (all combinations of representations).
+    assert_eq!(
+        compare_any_with::<hashes::Short, _, _>(HASH_L, HASH_R),
+        Ok(2)
+    );
+    assert_eq!(
+        compare_any_with::<hashes::Short, _, _>(hash_l, HASH_R),
+        Ok(2)
+    );
+    assert_eq!(
+        compare_any_with::<hashes::Short, _, _>(HASH_L, hash_r),
+        Ok(2)
+    );
+    assert_eq!(
+        compare_any_with::<hashes::Short, _, _>(hash_l, hash_r),
+        Ok(2)
+    );
+    assert_eq!(
+        compare_any_with::<hashes::Short, _, _>(bytes_l.as_slice(), HASH_R),
+        Ok(2)
+    );
+    assert_eq!(
+        compare_any_with::<hashes::Short, _, _>(hash_l, bytes_r.as_slice()),
+        Ok(2)
+    );
+    // Left side fails.
+    let err = compare_any_with::<hashes::Short, _, _>(HASH_ERR, hash_r).unwrap_err();
+    assert_eq!(err.side(), ParseErrorSide::Left);
+    assert_eq!(err.inner_err(), ParseError::NullHash);
+    let err = compare_any_with::<hashes::Short, _, _>(&bytes_l[1..], HASH_R).unwrap_err();
+    assert_eq!(err.side(), ParseErrorSide::Left);
     assert_eq!(err.inner_err(), ParseError::InvalidStringLength);
+    // Right side fails.
+    let err = compare_any_with::<hashes::Short, _, _>(hash_l, HASH_ERR).unwrap_err();
+    assert_eq!(err.side(), ParseErrorSide::Right);
+    assert_eq!(err.inner_err(), ParseError::NullHash);
+}
+
+#[test]
+fn test_compare_any() {
+    const HASH_L: &str = "T12AD5BE86FFE41D17CC268876A9AE472077B2B0032716DBAF1849A7647DDB7C0DF16488";
+    const HASH_R: &str = "T1EDD5BE96FFE41D1BCC268C7699AE4720B7B2A0032716DBAF1848A7647DD77C0DF16488";
+    const HASH_ERR: &str = "TNULL";
+    let hash_l: Tlsh = str::parse(HASH_L).unwrap();
+    let hash_r: Tlsh = str::parse(HASH_R).unwrap();
+    // Consistent with compare().
+    assert_eq!(compare_any(HASH_L, HASH_R), compare(HASH_L, HASH_R));
+    assert_eq!(compare_any(hash_l, HASH_R), Ok(9));
+    assert_eq!(compare_any(HASH_L, hash_r), Ok(9));
+    assert_eq!(compare_any(hash_l, hash_r), Ok(9));
+    // Left side fails.
+    let err = compare_any(HASH_ERR, hash_r).unwrap_err();
+    assert_eq!(err.side(), ParseErrorSide::Left);
+    assert_eq!(err.inner_err(), ParseError::NullHash);
+    // Right side fails.
+    let err = compare_any(hash_l, HASH_ERR).unwrap_err();
+    assert_eq!(err.side(), ParseErrorSide::Right);
+    assert_eq!(err.inner_err(), ParseError::NullHash);
 }
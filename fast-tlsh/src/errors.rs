@@ -25,6 +25,13 @@ pub enum ParseError {
     InvalidStringLength,
     /// The checksum part contained an invalid value.
     InvalidChecksum,
+    /// The string is the null hash (`"TNULL"` or `"NULL"`).
+    ///
+    /// The official implementation emits `"TNULL"` instead of the fuzzy hash
+    /// if it cannot generate one from the input.  Use
+    /// [`FuzzyHashType::from_str_opt()`](crate::FuzzyHashType::from_str_opt())
+    /// to handle this sentinel as [`None`].
+    NullHash,
 }
 impl Display for ParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -34,9 +41,20 @@ impl Display for ParseError {
             ParseError::InvalidCharacter => "encountered an invalid character",
             ParseError::InvalidStringLength => "string length is invalid",
             ParseError::InvalidChecksum => "has an invalid checksum field",
+            ParseError::NullHash => "is the null hash",
         })
     }
 }
+impl From<core::convert::Infallible> for ParseError {
+    /// Converts an infallible error (this is never called).
+    ///
+    /// This is for generic parsing functions that also accept already
+    /// parsed fuzzy hashes (the identity conversion).
+    #[inline(always)]
+    fn from(value: core::convert::Infallible) -> Self {
+        match value {}
+    }
+}
 #[cfg(feature = "std")]
 #[cfg_attr(feature = "unstable", doc(cfg(all())))]
 impl std::error::Error for ParseError {}
@@ -35,6 +35,10 @@ fn parse_error_impls() {
         format!("{err}", err = ParseError::InvalidChecksum),
         "has an invalid checksum field"
     );
+    assert_eq!(
+        format!("{err}", err = ParseError::NullHash),
+        "is the null hash"
+    );
 }
 
 #[test]
@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The C foreign function interface.
+//!
+//! This module exposes a small subset of this crate as C functions, similar to
+//! the surface of the official `libtlsh`.  All functions operate on the
+//! default fuzzy hash type ([`Tlsh`]).
+//!
+//! To use them from C, build this crate as a C-compatible library
+//! with the `ffi` feature (e.g. `cargo rustc --release --features ffi
+//! --crate-type cdylib`) and declare functions as follows:
+//!
+//! ```c
+//! #include <stddef.h>
+//! #include <stdint.h>
+//!
+//! typedef struct tlsh_generator tlsh_generator;
+//!
+//! tlsh_generator *tlsh_new(void);
+//! void tlsh_update(tlsh_generator *ptr, const uint8_t *buf, size_t len);
+//! int32_t tlsh_final(const tlsh_generator *ptr, char *out_str, size_t out_len);
+//! int32_t tlsh_compare(const char *a_str, const char *b_str);
+//! void tlsh_free(tlsh_generator *ptr);
+//!
+//! #define TLSH_LEN_IN_STR 72
+//! ```
+//!
+//! # Error Codes
+//!
+//! Functions returning [`i32`] return a non-negative value on success and
+//! one of the negative error codes below on failure.
+//!
+//! | Constant                                   | Value | Error                                          |
+//! | ------------------------------------------ | -----:| ---------------------------------------------- |
+//! | [`TLSH_ERR_INVALID_ARGUMENT`]              |  `-1` | A null pointer is specified.                   |
+//! | [`TLSH_ERR_BUFFER_TOO_SMALL`]              |  `-2` | [`OperationError::BufferIsTooSmall`]           |
+//! | [`TLSH_ERR_TOO_LARGE_INPUT`]               | `-16` | [`GeneratorError::TooLargeInput`]              |
+//! | [`TLSH_ERR_TOO_SMALL_INPUT`]               | `-17` | [`GeneratorError::TooSmallInput`]              |
+//! | [`TLSH_ERR_BUCKETS_HALF_EMPTY`]            | `-18` | [`GeneratorError::BucketsAreHalfEmpty`]        |
+//! | [`TLSH_ERR_BUCKETS_THREE_QUARTER_EMPTY`]   | `-19` | [`GeneratorError::BucketsAreThreeQuarterEmpty`] |
+//! | [`TLSH_ERR_LENGTH_TOO_LARGE`]              | `-32` | [`ParseError::LengthIsTooLarge`]               |
+//! | [`TLSH_ERR_INVALID_PREFIX`]                | `-33` | [`ParseError::InvalidPrefix`]                  |
+//! | [`TLSH_ERR_INVALID_CHARACTER`]             | `-34` | [`ParseError::InvalidCharacter`]               |
+//! | [`TLSH_ERR_INVALID_STRING_LENGTH`]         | `-35` | [`ParseError::InvalidStringLength`]            |
+//! | [`TLSH_ERR_INVALID_CHECKSUM`]              | `-36` | [`ParseError::InvalidChecksum`]                |
+//! | [`TLSH_ERR_NULL_HASH`]                     | `-37` | [`ParseError::NullHash`]                       |
+
+#![cfg(feature = "ffi")]
+
+use std::ffi::{c_char, CStr};
+
+use crate::errors::{GeneratorError, OperationError, ParseError};
+use crate::hash::HexStringPrefix;
+use crate::{FuzzyHashType, GeneratorType, Tlsh, TlshGenerator};
+
+/// The length of the TLSH's hexadecimal representation of [`Tlsh`]
+/// (not including the terminating NUL character).
+pub const TLSH_LEN_IN_STR: usize = Tlsh::LEN_IN_STR;
+
+/// Error code: a null pointer is specified.
+pub const TLSH_ERR_INVALID_ARGUMENT: i32 = -1;
+/// Error code: [`OperationError::BufferIsTooSmall`].
+pub const TLSH_ERR_BUFFER_TOO_SMALL: i32 = -2;
+/// Error code: [`GeneratorError::TooLargeInput`].
+pub const TLSH_ERR_TOO_LARGE_INPUT: i32 = -16;
+/// Error code: [`GeneratorError::TooSmallInput`].
+pub const TLSH_ERR_TOO_SMALL_INPUT: i32 = -17;
+/// Error code: [`GeneratorError::BucketsAreHalfEmpty`].
+pub const TLSH_ERR_BUCKETS_HALF_EMPTY: i32 = -18;
+/// Error code: [`GeneratorError::BucketsAreThreeQuarterEmpty`].
+pub const TLSH_ERR_BUCKETS_THREE_QUARTER_EMPTY: i32 = -19;
+/// Error code: [`ParseError::LengthIsTooLarge`].
+pub const TLSH_ERR_LENGTH_TOO_LARGE: i32 = -32;
+/// Error code: [`ParseError::InvalidPrefix`].
+pub const TLSH_ERR_INVALID_PREFIX: i32 = -33;
+/// Error code: [`ParseError::InvalidCharacter`].
+pub const TLSH_ERR_INVALID_CHARACTER: i32 = -34;
+/// Error code: [`ParseError::InvalidStringLength`].
+pub const TLSH_ERR_INVALID_STRING_LENGTH: i32 = -35;
+/// Error code: [`ParseError::InvalidChecksum`].
+pub const TLSH_ERR_INVALID_CHECKSUM: i32 = -36;
+/// Error code: [`ParseError::NullHash`].
+pub const TLSH_ERR_NULL_HASH: i32 = -37;
+
+/// Converts a generator error to the corresponding error code.
+fn generator_error_code(err: GeneratorError) -> i32 {
+    match err {
+        GeneratorError::TooLargeInput => TLSH_ERR_TOO_LARGE_INPUT,
+        GeneratorError::TooSmallInput => TLSH_ERR_TOO_SMALL_INPUT,
+        GeneratorError::BucketsAreHalfEmpty => TLSH_ERR_BUCKETS_HALF_EMPTY,
+        GeneratorError::BucketsAreThreeQuarterEmpty => TLSH_ERR_BUCKETS_THREE_QUARTER_EMPTY,
+    }
+}
+
+/// Converts a parse error to the corresponding error code.
+fn parse_error_code(err: ParseError) -> i32 {
+    match err {
+        ParseError::LengthIsTooLarge => TLSH_ERR_LENGTH_TOO_LARGE,
+        ParseError::InvalidPrefix => TLSH_ERR_INVALID_PREFIX,
+        ParseError::InvalidCharacter => TLSH_ERR_INVALID_CHARACTER,
+        ParseError::InvalidStringLength => TLSH_ERR_INVALID_STRING_LENGTH,
+        ParseError::InvalidChecksum => TLSH_ERR_INVALID_CHECKSUM,
+        ParseError::NullHash => TLSH_ERR_NULL_HASH,
+    }
+}
+
+/// Converts an operation error to the corresponding error code.
+fn operation_error_code(err: OperationError) -> i32 {
+    match err {
+        OperationError::BufferIsTooSmall => TLSH_ERR_BUFFER_TOO_SMALL,
+    }
+}
+
+/// Creates a new generator.
+///
+/// The returned pointer must be freed by [`tlsh_free()`].
+#[no_mangle]
+pub extern "C" fn tlsh_new() -> *mut TlshGenerator {
+    Box::into_raw(Box::new(TlshGenerator::new()))
+}
+
+/// Updates the generator by feeding `len` bytes from `buf`.
+///
+/// It does nothing if either `ptr` or `buf` is a null pointer.
+///
+/// # Safety
+///
+/// *   `ptr` must be a pointer returned by [`tlsh_new()`] (or null).
+/// *   `buf` must be valid for reads of `len` bytes (or null).
+#[no_mangle]
+pub unsafe extern "C" fn tlsh_update(ptr: *mut TlshGenerator, buf: *const u8, len: usize) {
+    if ptr.is_null() || buf.is_null() {
+        return;
+    }
+    (*ptr).update(core::slice::from_raw_parts(buf, len));
+}
+
+/// Finalizes the generator and stores the TLSH's hexadecimal representation
+/// (with the `"T1"` prefix and a terminating NUL character) to `out_str`.
+///
+/// `out_len` is the size of the `out_str` buffer and must be at least
+/// [`TLSH_LEN_IN_STR`] plus 1.  The generator is not consumed and can
+/// continue to be updated after this call.
+///
+/// It returns the length of the string (not including the terminating NUL
+/// character) on success and a negative error code on failure.
+///
+/// # Safety
+///
+/// *   `ptr` must be a pointer returned by [`tlsh_new()`] (or null).
+/// *   `out_str` must be valid for writes of `out_len` bytes (or null).
+#[no_mangle]
+pub unsafe extern "C" fn tlsh_final(
+    ptr: *const TlshGenerator,
+    out_str: *mut c_char,
+    out_len: usize,
+) -> i32 {
+    if ptr.is_null() || out_str.is_null() {
+        return TLSH_ERR_INVALID_ARGUMENT;
+    }
+    let hash = match (*ptr).finalize() {
+        Ok(hash) => hash,
+        Err(err) => return generator_error_code(err),
+    };
+    let out = core::slice::from_raw_parts_mut(out_str as *mut u8, out_len);
+    if out.len() <= TLSH_LEN_IN_STR {
+        return TLSH_ERR_BUFFER_TOO_SMALL;
+    }
+    match hash.store_into_str_bytes(out, HexStringPrefix::WithVersion) {
+        Ok(len) => {
+            out[len] = 0;
+            len as i32
+        }
+        Err(err) => operation_error_code(err),
+    }
+}
+
+/// Compares two NUL-terminated TLSH's hexadecimal representations.
+///
+/// It returns the distance between two fuzzy hashes on success and
+/// a negative error code on failure.
+///
+/// # Safety
+///
+/// `a_str` and `b_str` must be valid NUL-terminated strings (or null).
+#[no_mangle]
+pub unsafe extern "C" fn tlsh_compare(a_str: *const c_char, b_str: *const c_char) -> i32 {
+    if a_str.is_null() || b_str.is_null() {
+        return TLSH_ERR_INVALID_ARGUMENT;
+    }
+    let parse = |s: *const c_char| Tlsh::from_str_bytes(CStr::from_ptr(s).to_bytes(), None);
+    let a = match parse(a_str) {
+        Ok(hash) => hash,
+        Err(err) => return parse_error_code(err),
+    };
+    let b = match parse(b_str) {
+        Ok(hash) => hash,
+        Err(err) => return parse_error_code(err),
+    };
+    a.compare(&b) as i32
+}
+
+/// Frees the generator created by [`tlsh_new()`].
+///
+/// It does nothing if `ptr` is a null pointer.
+///
+/// # Safety
+///
+/// `ptr` must be a pointer returned by [`tlsh_new()`] (or null) and
+/// must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn tlsh_free(ptr: *mut TlshGenerator) {
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr));
+    }
+}
+
+mod tests;
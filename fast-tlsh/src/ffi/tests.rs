@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::ffi`].
+
+#![cfg(test)]
+
+use core::ptr;
+
+use super::*;
+
+use crate::generate::tests::{LOREM_IPSUM, LOREM_IPSUM_HASH_NORMAL};
+
+#[test]
+fn generate_and_compare() {
+    let mut out = [0 as c_char; TLSH_LEN_IN_STR + 1];
+    unsafe {
+        let generator = tlsh_new();
+        // Not enough data
+        tlsh_update(generator, b"Hello".as_ptr(), 5);
+        assert_eq!(
+            tlsh_final(generator, out.as_mut_ptr(), out.len()),
+            TLSH_ERR_TOO_SMALL_INPUT
+        );
+        // Continue updating
+        tlsh_update(generator, LOREM_IPSUM.as_ptr(), LOREM_IPSUM.len());
+        tlsh_free(generator);
+        let generator = tlsh_new();
+        tlsh_update(generator, LOREM_IPSUM.as_ptr(), LOREM_IPSUM.len());
+        assert_eq!(
+            tlsh_final(generator, out.as_mut_ptr(), out.len() - 1),
+            TLSH_ERR_BUFFER_TOO_SMALL
+        );
+        assert_eq!(
+            tlsh_final(generator, out.as_mut_ptr(), out.len()),
+            TLSH_LEN_IN_STR as i32
+        );
+        tlsh_free(generator);
+        let hash_str = CStr::from_ptr(out.as_ptr()).to_str().unwrap();
+        assert_eq!(hash_str, LOREM_IPSUM_HASH_NORMAL);
+        assert_eq!(tlsh_compare(out.as_ptr(), out.as_ptr()), 0);
+    }
+}
+
+#[test]
+fn compare_errors() {
+    const VALID: &[u8] =
+        b"T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2\0";
+    const INVALID_PREFIX: &[u8] =
+        b"T0DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2\0";
+    const INVALID_LENGTH: &[u8] =
+        b"T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A\0";
+    const INVALID_CHARACTER: &[u8] =
+        b"T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AAG\0";
+    let as_ptr = |s: &[u8]| s.as_ptr() as *const c_char;
+    unsafe {
+        assert_eq!(tlsh_compare(as_ptr(VALID), as_ptr(VALID)), 0);
+        assert_eq!(
+            tlsh_compare(as_ptr(VALID), ptr::null()),
+            TLSH_ERR_INVALID_ARGUMENT
+        );
+        assert_eq!(
+            tlsh_compare(ptr::null(), as_ptr(VALID)),
+            TLSH_ERR_INVALID_ARGUMENT
+        );
+        assert_eq!(
+            tlsh_compare(as_ptr(VALID), as_ptr(INVALID_PREFIX)),
+            TLSH_ERR_INVALID_PREFIX
+        );
+        assert_eq!(
+            tlsh_compare(as_ptr(INVALID_LENGTH), as_ptr(VALID)),
+            TLSH_ERR_INVALID_STRING_LENGTH
+        );
+        assert_eq!(
+            tlsh_compare(as_ptr(VALID), as_ptr(INVALID_CHARACTER)),
+            TLSH_ERR_INVALID_CHARACTER
+        );
+    }
+}
+
+#[test]
+fn null_pointers() {
+    let mut out = [0 as c_char; TLSH_LEN_IN_STR + 1];
+    unsafe {
+        tlsh_update(ptr::null_mut(), b"Hello".as_ptr(), 5);
+        assert_eq!(
+            tlsh_final(ptr::null(), out.as_mut_ptr(), out.len()),
+            TLSH_ERR_INVALID_ARGUMENT
+        );
+        let generator = tlsh_new();
+        tlsh_update(generator, ptr::null(), 5);
+        assert_eq!(
+            tlsh_final(generator, ptr::null_mut(), 0),
+            TLSH_ERR_INVALID_ARGUMENT
+        );
+        tlsh_free(generator);
+        tlsh_free(ptr::null_mut());
+    }
+}
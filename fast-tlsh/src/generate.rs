@@ -5,8 +5,12 @@
 //! The fuzzy hash generator.
 
 use crate::buckets::constrained::{FuzzyHashBucketMapper, FuzzyHashBucketsInfo};
-use crate::buckets::FuzzyHashBucketsData;
+use crate::buckets::{
+    quartiles_in_place, FuzzyHashBucketsData, NUM_BUCKETS_LONG, NUM_BUCKETS_SHORT,
+};
 use crate::errors::GeneratorError;
+#[cfg(feature = "opt-simd-generate")]
+use crate::generate::bucket_mapping::{BlockWindow, BucketIndices, BLOCK_SIZE, NUM_MAPPINGS};
 use crate::hash::body::{FuzzyHashBody, FuzzyHashBodyData};
 use crate::hash::checksum::inner::InnerChecksum;
 use crate::hash::checksum::{FuzzyHashChecksum, FuzzyHashChecksumData};
@@ -24,6 +28,7 @@ use crate::params::{
 use crate::{FuzzyHashType, GeneratorType};
 
 pub(crate) mod bucket_aggregation;
+pub(crate) mod bucket_mapping;
 
 /// Window size to obtain local features.
 ///
@@ -34,9 +39,15 @@ pub(crate) mod bucket_aggregation;
 ///
 /// This constant is not designed to be easily configurable.  In the original
 /// implementation, it was configurable between 4–8 but we rarely use a
-/// non-default constant.
+/// non-default constant.  If you need other window sizes (for research
+/// purposes), use [`GeneratorWithWindow`] (the output is TLSH-incompatible
+/// unless the window size is equal to this value).
 pub const WINDOW_SIZE: usize = 5;
 
+/// The size of the chunk buffer used by
+/// [`GeneratorType::update_iter()`].
+const UPDATE_ITER_CHUNK_SIZE: usize = 256;
+
 bitflags::bitflags! {
     /// TLSH-compatible generator option flags.
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -61,6 +72,10 @@ bitflags::bitflags! {
         /// If set, it allows statistically weak buckets
         /// (approximately 3/4 or more are empty).
         const ALLOW_STATISTICALLY_WEAK_BUCKETS_QUARTER = 0x04;
+        /// If set, it emits the null (all-zero) fuzzy hash instead of
+        /// returning an error on too small inputs or statistically weak
+        /// buckets.
+        const EMIT_NULL_ON_FAILURE                     = 0x08;
     }
 }
 
@@ -73,6 +88,9 @@ pub struct GeneratorOptions {
     compat_flags: TLSHCompatibleGeneratorFlags,
     /// Flags indicating TLSH-incompatible flags.
     incompat_flags: TLSHIncompatibleGeneratorFlags,
+    /// The maximum input size accepted by the local policy
+    /// ([`None`] means no limits other than the TLSH's).
+    max_input_size: Option<u32>,
 }
 
 impl GeneratorOptions {
@@ -82,9 +100,18 @@ impl GeneratorOptions {
             length_mode: Default::default(),
             compat_flags: TLSHCompatibleGeneratorFlags::empty(),
             incompat_flags: TLSHIncompatibleGeneratorFlags::empty(),
+            max_input_size: None,
         }
     }
 
+    /// Returns the maximum input size (in bytes) accepted by these options.
+    ///
+    /// This is the smaller one of 4GiB (the limit of the generator) and
+    /// the value set by [`max_input_size()`](Self::max_input_size()).
+    pub(crate) fn input_size_limit(&self) -> u64 {
+        self.max_input_size.map_or(1 << 32, u64::from)
+    }
+
     /// Query whether this generator options are compatible to the official
     /// implementation of TLSH.
     ///
@@ -110,6 +137,60 @@ impl GeneratorOptions {
         self.incompat_flags.is_empty()
     }
 
+    /// (fast-tlsh specific)
+    /// Lists the reasons why this generator options are incompatible with
+    /// the official implementation of TLSH.
+    ///
+    /// Each element is a human-readable reason corresponding to an enabled
+    /// TLSH-incompatible option.  If the options are compatible with the
+    /// official implementation (see
+    /// [`is_tlsh_compatible()`](Self::is_tlsh_compatible())),
+    /// the returned vector is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tlsh::generate::GeneratorOptions;
+    ///
+    /// let options = GeneratorOptions::new();
+    /// assert!(options.incompatibility_report().is_empty());
+    /// let options = GeneratorOptions::new()
+    ///     .with_allow_small_size_files(true)
+    ///     .with_allow_statistically_weak_buckets_half(true);
+    /// assert_eq!(options.incompatibility_report().len(), 2);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "unstable", doc(cfg(feature = "alloc")))]
+    pub fn incompatibility_report(&self) -> alloc::vec::Vec<&'static str> {
+        const REASONS: [(TLSHIncompatibleGeneratorFlags, &str); 4] = [
+            (
+                TLSHIncompatibleGeneratorFlags::ALLOW_SMALL_SIZE_FILES,
+                "small-size-files: inputs smaller than the minimum length \
+                 are accepted (the result may be statistically weak)",
+            ),
+            (
+                TLSHIncompatibleGeneratorFlags::ALLOW_STATISTICALLY_WEAK_BUCKETS_HALF,
+                "weak-buckets-half: fuzzy hashes with approximately half or \
+                 more of the buckets empty are accepted",
+            ),
+            (
+                TLSHIncompatibleGeneratorFlags::ALLOW_STATISTICALLY_WEAK_BUCKETS_QUARTER,
+                "weak-buckets-quarter: fuzzy hashes with approximately 3/4 or \
+                 more of the buckets empty are accepted",
+            ),
+            (
+                TLSHIncompatibleGeneratorFlags::EMIT_NULL_ON_FAILURE,
+                "emit-null-on-failure: the null fuzzy hash is emitted \
+                 instead of returning an error",
+            ),
+        ];
+        REASONS
+            .iter()
+            .filter(|(flag, _)| self.incompat_flags.contains(flag.clone()))
+            .map(|&(_, reason)| reason)
+            .collect()
+    }
+
     /// Set the data length processing mode.
     ///
     /// For more information, see [`DataLengthProcessingMode`].
@@ -307,6 +388,200 @@ impl GeneratorOptions {
         );
         self
     }
+
+    /// (fast-tlsh specific)
+    /// Set whether we emit the null (all-zero) fuzzy hash instead of
+    /// returning an error if the input is too small or the buckets are
+    /// statistically weak.
+    ///
+    /// On those cases, [`GeneratorType::finalize_with_options()`] returns
+    /// the fuzzy hash with all parts zero (as a placeholder like `"TNULL"`
+    /// on the official implementation), which can be detected by
+    /// [`FuzzyHashType::is_null()`].
+    /// Note that too large inputs are still rejected.
+    ///
+    /// **Warning**: This is a TLSH-incompatible option.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tlsh::prelude::*;
+    /// use tlsh::GeneratorOptions;
+    ///
+    /// let mut generator = TlshGenerator::new();
+    /// generator.update(b"ABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDE");
+    /// assert!(generator.finalize().is_err());
+    ///
+    /// // Emit the null hash instead of returning an error.
+    /// let hash = generator.finalize_with_options(
+    ///     GeneratorOptions::new().emit_null_on_failure(true)
+    /// ).unwrap();
+    /// assert!(hash.is_null());
+    /// ```
+    pub fn emit_null_on_failure(&mut self, value: bool) -> &mut Self {
+        self.incompat_flags
+            .set(TLSHIncompatibleGeneratorFlags::EMIT_NULL_ON_FAILURE, value);
+        self
+    }
+
+    /// (fast-tlsh specific)
+    /// Set the maximum input size (in bytes) to accept.
+    ///
+    /// This is a local policy to limit resources (e.g. to reject
+    /// adversarially large inputs on a service), not a TLSH-compatibility
+    /// option.  It does not change the fuzzy hash of accepted inputs and
+    /// [`is_tlsh_compatible()`](Self::is_tlsh_compatible()) is not affected.
+    ///
+    /// If `value` is [`Some`] and the generator has processed more than
+    /// the contained number of bytes,
+    /// finalization with these options fails with
+    /// [`GeneratorError::TooLargeInput`] (like the inputs too large
+    /// for TLSH).  To bail out before processing the exceeding bytes,
+    /// create a generator with these options by
+    /// [`Generator::with_options()`] and feed the data with
+    /// [`try_update()`](GeneratorType::try_update()).
+    ///
+    /// The default value is [`None`], meaning that no limits are
+    /// imposed other than the TLSH's (up to 4GiB can be processed and
+    /// a fuzzy hash can be generated if the input is slightly smaller
+    /// than 4GiB).  Note that `Some(u32::MAX)` is a real limit
+    /// (one byte smaller than 4GiB).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tlsh::prelude::*;
+    /// use tlsh::{GeneratorError, GeneratorOptions};
+    ///
+    /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+    ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+    /// let options = GeneratorOptions::new().with_max_input_size(Some(200));
+    /// assert!(options.is_tlsh_compatible());
+    ///
+    /// let mut generator = TlshGenerator::with_options(options.clone());
+    /// assert_eq!(generator.try_update(data), Ok(()));
+    /// assert!(generator.finalize().is_ok());
+    /// // The second update would exceed the limit (not processed).
+    /// assert_eq!(generator.try_update(data), Err(GeneratorError::TooLargeInput));
+    /// assert_eq!(generator.processed_len(), Some(data.len() as u32));
+    ///
+    /// // Finalization fails if the limit is exceeded by update().
+    /// generator.update(data);
+    /// assert_eq!(generator.finalize(), Err(GeneratorError::TooLargeInput));
+    /// assert!(generator.finalize_with_options(&GeneratorOptions::new()).is_ok());
+    /// ```
+    pub fn max_input_size(&mut self, value: Option<u32>) -> &mut Self {
+        self.max_input_size = value;
+        self
+    }
+
+    /// Set the data length processing mode (consuming and returning `self`).
+    ///
+    /// This is the owned builder variant of
+    /// [`length_processing_mode()`](Self::length_processing_mode()),
+    /// useful to construct options which are stored and reused later.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tlsh::prelude::*;
+    /// use tlsh::GeneratorOptions;
+    /// use tlsh::length::DataLengthProcessingMode;
+    ///
+    /// struct Config {
+    ///     options: GeneratorOptions,
+    /// }
+    ///
+    /// let config = Config {
+    ///     options: GeneratorOptions::new()
+    ///         .with_length_processing_mode(DataLengthProcessingMode::Conservative)
+    ///         .with_allow_small_size_files(true),
+    /// };
+    /// let mut generator = TlshGenerator::new();
+    /// generator.update(b"The quick brown fox jumps over the lazy dog.");
+    /// assert!(generator.finalize_with_options(&config.options).is_ok());
+    /// ```
+    pub fn with_length_processing_mode(mut self, value: DataLengthProcessingMode) -> Self {
+        self.length_processing_mode(value);
+        self
+    }
+
+    /// Set whether we compute Q ratio values by pure integers
+    /// (consuming and returning `self`).
+    ///
+    /// This is the owned builder variant of
+    /// [`pure_integer_qratio_computation()`](Self::pure_integer_qratio_computation()).
+    pub fn with_pure_integer_qratio_computation(mut self, value: bool) -> Self {
+        self.pure_integer_qratio_computation(value);
+        self
+    }
+
+    /// (fast-tlsh specific)
+    /// Set whether we allow generating fuzzy hashes from very small inputs
+    /// (consuming and returning `self`).
+    ///
+    /// This is the owned builder variant of
+    /// [`allow_small_size_files()`](Self::allow_small_size_files()).
+    ///
+    /// **Warning**: This is a TLSH-incompatible option.
+    pub fn with_allow_small_size_files(mut self, value: bool) -> Self {
+        self.allow_small_size_files(value);
+        self
+    }
+
+    /// (fast-tlsh specific)
+    /// Set whether we allow generating fuzzy hashes from
+    /// statistically weak buckets
+    /// (when approximately half or more of them are empty;
+    /// consuming and returning `self`).
+    ///
+    /// This is the owned builder variant of
+    /// [`allow_statistically_weak_buckets_half()`](Self::allow_statistically_weak_buckets_half()).
+    ///
+    /// **Warning**: This is a TLSH-incompatible option.
+    pub fn with_allow_statistically_weak_buckets_half(mut self, value: bool) -> Self {
+        self.allow_statistically_weak_buckets_half(value);
+        self
+    }
+
+    /// (fast-tlsh specific)
+    /// Set whether we allow generating fuzzy hashes from
+    /// statistically weak buckets
+    /// (when approximately 3/4 or more of them are empty;
+    /// consuming and returning `self`).
+    ///
+    /// This is the owned builder variant of
+    /// [`allow_statistically_weak_buckets_quarter()`](Self::allow_statistically_weak_buckets_quarter()).
+    ///
+    /// **Warning**: This is a TLSH-incompatible option.
+    pub fn with_allow_statistically_weak_buckets_quarter(mut self, value: bool) -> Self {
+        self.allow_statistically_weak_buckets_quarter(value);
+        self
+    }
+
+    /// (fast-tlsh specific)
+    /// Set whether we emit the null (all-zero) fuzzy hash instead of
+    /// returning an error (consuming and returning `self`).
+    ///
+    /// This is the owned builder variant of
+    /// [`emit_null_on_failure()`](Self::emit_null_on_failure()).
+    ///
+    /// **Warning**: This is a TLSH-incompatible option.
+    pub fn with_emit_null_on_failure(mut self, value: bool) -> Self {
+        self.emit_null_on_failure(value);
+        self
+    }
+
+    /// (fast-tlsh specific)
+    /// Set the maximum input size (in bytes) to accept
+    /// (consuming and returning `self`).
+    ///
+    /// This is the owned builder variant of
+    /// [`max_input_size()`](Self::max_input_size()).
+    pub fn with_max_input_size(mut self, value: Option<u32>) -> Self {
+        self.max_input_size(value);
+        self
+    }
 }
 impl Default for GeneratorOptions {
     fn default() -> Self {
@@ -314,6 +589,93 @@ impl Default for GeneratorOptions {
     }
 }
 
+/// Statistics of the buckets in a generator.
+///
+/// This is returned by [`GeneratorType::bucket_stats()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BucketStats {
+    /// The number of non-zero buckets.
+    pub nonzero: usize,
+    /// The number of effective buckets.
+    pub total: usize,
+    /// The quartile values (Q1, Q2 and Q3) of the bucket counts.
+    pub quartiles: [u32; 3],
+}
+
+impl BucketStats {
+    /// (fast-tlsh specific)
+    /// Returns the diagnostic information of the Q ratio computation
+    /// based on these statistics.
+    ///
+    /// See [`QRatioDiagnostics`] for details.
+    #[inline]
+    pub fn qratio_diagnostics(&self) -> QRatioDiagnostics {
+        QRatioDiagnostics::new(self)
+    }
+}
+
+/// (fast-tlsh specific)
+/// The statistical quality of a generated fuzzy hash.
+///
+/// This is returned by [`GeneratorType::finalize_with_quality()`] and
+/// derived from the same thresholds used to reject statistically weak
+/// buckets on finalization (see
+/// [`GeneratorOptions::allow_statistically_weak_buckets_half()`] and
+/// [`GeneratorOptions::allow_statistically_weak_buckets_quarter()`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Quality {
+    /// The fuzzy hash is accepted without allowing statistically weak
+    /// buckets (as in the official implementation).
+    Strong,
+    /// The fuzzy hash is accepted only because statistically weak buckets
+    /// (approximately half or more are empty) are allowed.
+    WeakHalf,
+    /// The fuzzy hash is accepted only because statistically weak buckets
+    /// (approximately 3/4 or more are empty) are allowed.
+    ///
+    /// This is also the case when the null fuzzy hash is emitted on failure
+    /// (see [`GeneratorOptions::emit_null_on_failure()`]).
+    WeakQuarter,
+}
+
+impl Quality {
+    /// Checks whether the fuzzy hash is accepted without allowing
+    /// statistically weak buckets.
+    #[inline(always)]
+    pub fn is_strong(&self) -> bool {
+        matches!(self, Self::Strong)
+    }
+}
+
+/// (fast-tlsh specific)
+/// The state of the checksum after merging two generators.
+///
+/// This is returned by [`GeneratorType::merge()`].
+#[must_use = "the checksum may be cleared by merging generators"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergedChecksum {
+    /// The checksum is exactly the same as the one computed by processing
+    /// both segments sequentially.
+    ///
+    /// This is the case when either segment is empty or the generator does
+    /// not compute the checksum in the first place
+    /// (see [`GeneratorType::IS_CHECKSUM_EFFECTIVE`]).
+    Exact,
+    /// The checksum cannot be combined and is cleared (all zeroes).
+    ///
+    /// The resulting fuzzy hash differs from the sequential one only in
+    /// the checksum part (unless the total length exceeds the maximum).
+    Cleared,
+}
+
+impl MergedChecksum {
+    /// Checks whether the checksum is the same as the sequential one.
+    #[inline(always)]
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Self::Exact)
+    }
+}
+
 /// The public part for later `pub use` at crate root.
 pub(crate) mod public {
     use super::*;
@@ -333,6 +695,9 @@ pub(crate) mod public {
         /// In the official TLSH implementation, it is always [`true`]
         /// except multi-threaded and private modes.  This crate currently
         /// does not support those modes but will be implemented in the future.
+        ///
+        /// In this crate, it is [`false`] on
+        /// [`GeneratorWithoutChecksum`](crate::generate::GeneratorWithoutChecksum).
         const IS_CHECKSUM_EFFECTIVE: bool;
 
         /// The minimum data length
@@ -353,9 +718,227 @@ pub(crate) mod public {
         /// returned by [`Some`].
         fn processed_len(&self) -> Option<u32>;
 
+        /// Returns the number of additional bytes required until the processed
+        /// data length becomes valid on the specified mode.
+        ///
+        /// It returns [`Some`] containing the remaining length to reach
+        /// [`MIN`](Self::MIN) (on [the optimistic mode](DataLengthProcessingMode::Optimistic))
+        /// or [`MIN_CONSERVATIVE`](Self::MIN_CONSERVATIVE)
+        /// (on [the conservative mode](DataLengthProcessingMode::Conservative)).
+        /// If the processed data length is already valid or exceeds
+        /// [`MAX`](Self::MAX), it returns [`None`].
+        ///
+        /// Note that a valid data length alone does not guarantee that
+        /// the generator succeeds (e.g. the buckets may be statistically
+        /// unbalanced).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::length::DataLengthProcessingMode;
+        ///
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(b"Hello, World!");
+        /// let mode = DataLengthProcessingMode::Optimistic;
+        /// assert_eq!(generator.bytes_until_valid(mode), Some(TlshGenerator::MIN - 13));
+        /// generator.update(&[0; 50]);
+        /// assert_eq!(generator.bytes_until_valid(mode), None);
+        /// ```
+        fn bytes_until_valid(&self, mode: DataLengthProcessingMode) -> Option<u32> {
+            let len = self.processed_len()?;
+            let min = match mode {
+                DataLengthProcessingMode::Optimistic => Self::MIN,
+                DataLengthProcessingMode::Conservative => Self::MIN_CONSERVATIVE,
+            };
+            if len < min {
+                Some(min - len)
+            } else {
+                None
+            }
+        }
+
         /// Update the generator by feeding data to it.
+        ///
+        /// # Overflow Behavior
+        ///
+        /// A generator processes only the first 4GiB (2<sup>32</sup> bytes)
+        /// of the input.  Bytes beyond that are silently ignored and
+        /// [`processed_len()`](Self::processed_len()) returns [`None`] after
+        /// processing 4GiB.  Since such a length is also too large to encode,
+        /// finalization will fail with [`GeneratorError::TooLargeInput`].
+        ///
+        /// To detect the truncation on each update, use
+        /// [`try_update()`](Self::try_update()) instead.
         fn update(&mut self, data: &[u8]);
 
+        /// Update the generator by feeding data to it, unless a part of
+        /// `data` would be ignored.
+        ///
+        /// Unlike [`update()`](Self::update()) which silently ignores
+        /// bytes beyond the first 4GiB, this method returns
+        /// [`GeneratorError::TooLargeInput`] (without updating the generator)
+        /// if the total input would exceed 4GiB.  Otherwise, it is the same
+        /// as [`update()`](Self::update()).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        ///
+        /// let mut generator = TlshGenerator::new();
+        /// assert_eq!(generator.try_update(b"Hello, World!"), Ok(()));
+        /// assert_eq!(generator.processed_len(), Some(13));
+        /// ```
+        fn try_update(&mut self, data: &[u8]) -> Result<(), GeneratorError> {
+            // The maximum input length is 4GiB (processed_len() returns None
+            // only if exactly 4GiB is processed).
+            const MAX_INPUT_LEN: u64 = 1 << 32;
+            let len = self.processed_len().map_or(MAX_INPUT_LEN, u64::from);
+            if data.len() as u64 > MAX_INPUT_LEN - len {
+                return Err(GeneratorError::TooLargeInput);
+            }
+            self.update(data);
+            Ok(())
+        }
+
+        /// Update the generator by feeding bytes from an iterator.
+        ///
+        /// The result is the same as calling [`update()`](Self::update())
+        /// with the equivalent byte slice.  Bytes are buffered into
+        /// a small chunk on the stack before they are fed so that
+        /// the generator can process multiple windows at once.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        ///
+        /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        /// let mut generator1 = TlshGenerator::new();
+        /// let mut generator2 = TlshGenerator::new();
+        /// generator1.update(data);
+        /// generator2.update_iter(data.iter().copied());
+        /// assert_eq!(generator1.finalize(), generator2.finalize());
+        /// ```
+        fn update_iter<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+            let mut buffer = [0u8; UPDATE_ITER_CHUNK_SIZE];
+            let mut len = 0;
+            for b in iter {
+                buffer[len] = b;
+                len += 1;
+                if len == buffer.len() {
+                    self.update(&buffer);
+                    len = 0;
+                }
+            }
+            self.update(&buffer[..len]);
+        }
+
+        /// Update the generator by feeding multiple buffers to it (in order).
+        ///
+        /// The result is the same as calling [`update()`](Self::update())
+        /// with the concatenation of all buffers (the sliding window continues
+        /// across buffer boundaries).  This is useful when the data is
+        /// scattered in multiple non-contiguous buffers
+        /// (like an I/O vector) to avoid an intermediate copy.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        ///
+        /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        /// let mut generator1 = TlshGenerator::new();
+        /// let mut generator2 = TlshGenerator::new();
+        /// generator1.update(data);
+        /// generator2.update_vectored(&[&data[..10], &data[10..11], &data[11..]]);
+        /// assert_eq!(generator1.finalize(), generator2.finalize());
+        /// ```
+        fn update_vectored(&mut self, bufs: &[&[u8]]) {
+            for buf in bufs {
+                self.update(buf);
+            }
+        }
+
+        /// Resets the generator to the initial state.
+        ///
+        /// After calling this method, the generator behaves exactly like
+        /// a newly created one.  This is useful to reuse the same generator
+        /// object to process multiple inputs.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        ///
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit.");
+        /// generator.reset();
+        /// assert_eq!(generator.processed_len(), Some(0));
+        /// ```
+        fn reset(&mut self);
+
+        /// Merges another generator which processed the data immediately
+        /// following the data processed by this generator.
+        ///
+        /// This is useful to process a large input as multiple segments
+        /// separately (e.g. in parallel) and combine the results.
+        /// `other` must be a generator which started from the initial state
+        /// and processed only the second segment.
+        ///
+        /// Because the sliding windows straddling two segments are processed
+        /// by neither generator, `overlap` must be the first
+        /// [`WINDOW_SIZE`] `- 1` bytes of the second segment
+        /// (or the whole second segment if it is shorter than that)
+        /// so that those windows are recomputed.
+        ///
+        /// After merging, this generator behaves as if it processed both
+        /// segments sequentially except that the checksum may not be
+        /// recovered.  Because the checksum depends on the whole data
+        /// sequentially, the checksum is cleared if both segments are
+        /// non-empty (as in the multi-threaded mode of the official
+        /// TLSH implementation) and [`MergedChecksum::Cleared`] is returned.
+        /// Otherwise, [`MergedChecksum::Exact`] is returned.
+        /// Also, if the total length exceeds [`MAX`](Self::MAX),
+        /// the result will not be the same as the sequential one.
+        ///
+        /// # Panics
+        ///
+        /// It panics if the length of `overlap` is not equal to the expected
+        /// one.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::generate::MergedChecksum;
+        /// use tlsh::prelude::*;
+        ///
+        /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        /// let (first, second) = data.split_at(40);
+        ///
+        /// let mut generator1 = TlshGenerator::new();
+        /// let mut generator2 = TlshGenerator::new();
+        /// generator1.update(first);
+        /// generator2.update(second);
+        /// let checksum = generator1.merge(&generator2, &second[..4]);
+        /// assert_eq!(checksum, MergedChecksum::Cleared);
+        ///
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(data);
+        /// let mut expected = generator.finalize().unwrap();
+        /// assert_eq!(generator1.processed_len(), Some(data.len() as u32));
+        /// assert_ne!(generator1.finalize().unwrap(), expected);
+        ///
+        /// // The only difference is the checksum.
+        /// expected.clear_checksum();
+        /// assert_eq!(generator1.finalize().unwrap(), expected);
+        /// ```
+        fn merge(&mut self, other: &Self, overlap: &[u8]) -> MergedChecksum;
+
         /// Finalize the fuzzy hash with specified options.
         ///
         /// You will likely use the default options and use
@@ -365,6 +948,40 @@ pub(crate) mod public {
             options: &GeneratorOptions,
         ) -> Result<Self::Output, GeneratorError>;
 
+        /// Checks whether finalizing the current state with specified options
+        /// would succeed.
+        ///
+        /// This performs the same checks as
+        /// [`finalize_with_options()`](Self::finalize_with_options())
+        /// (the data length depending on the
+        /// [processing mode](GeneratorOptions::length_processing_mode()),
+        /// the number of non-zero buckets and the third quartile) without
+        /// computing the fuzzy hash itself.  So, on a streaming input,
+        /// you can use this method to decide whether to continue reading.
+        ///
+        /// It returns [`true`] if and only if
+        /// [`finalize_with_options()`](Self::finalize_with_options())
+        /// returns [`Ok`] with the same options
+        /// (including when the null hash is emitted on failure).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::GeneratorOptions;
+        ///
+        /// let options = GeneratorOptions::new();
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(b"ABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDE");
+        /// assert!(!generator.is_currently_hashable(&options));
+        /// assert!(generator.finalize_with_options(&options).is_err());
+        /// generator.update(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.");
+        /// assert!(generator.is_currently_hashable(&options));
+        /// assert!(generator.finalize_with_options(&options).is_ok());
+        /// ```
+        fn is_currently_hashable(&self, options: &GeneratorOptions) -> bool;
+
         /// Finalize the fuzzy hash with the default options.
         ///
         /// If you want to use [a custom generator options](GeneratorError),
@@ -375,9 +992,148 @@ pub(crate) mod public {
             self.finalize_with_options(&Default::default())
         }
 
-        /// Tests: count non-zero buckets.
-        #[cfg(test)]
-        fn count_nonzero_buckets(&self) -> usize;
+        /// (fast-tlsh specific)
+        /// Finalize the fuzzy hash with specified options and also return
+        /// its [statistical quality](Quality).
+        ///
+        /// The fuzzy hash is the same as the one returned by
+        /// [`finalize_with_options()`](Self::finalize_with_options()).
+        /// The quality is determined by the same checks (the number of
+        /// non-zero buckets and the third quartile) but without the options
+        /// to allow statistically weak buckets.  So, you can accept weak
+        /// fuzzy hashes while knowing that they are weak.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::generate::{GeneratorOptions, Quality};
+        ///
+        /// let options = GeneratorOptions::new()
+        ///     .with_allow_statistically_weak_buckets_quarter(true);
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.");
+        /// let (_, quality) = generator.finalize_with_quality(&options).unwrap();
+        /// assert_eq!(quality, Quality::Strong);
+        ///
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(b"ABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDE");
+        /// assert!(generator.finalize().is_err());
+        /// let (_, quality) = generator.finalize_with_quality(&options).unwrap();
+        /// assert_eq!(quality, Quality::WeakQuarter);
+        /// ```
+        fn finalize_with_quality(
+            &self,
+            options: &GeneratorOptions,
+        ) -> Result<(Self::Output, Quality), GeneratorError> {
+            let hash = self.finalize_with_options(options)?;
+            let mut probe = options
+                .clone()
+                .with_allow_statistically_weak_buckets_half(false)
+                .with_allow_statistically_weak_buckets_quarter(false)
+                .with_emit_null_on_failure(false);
+            let quality = if self.is_currently_hashable(&probe) {
+                Quality::Strong
+            } else if self.is_currently_hashable(probe.allow_statistically_weak_buckets_half(true))
+            {
+                Quality::WeakHalf
+            } else {
+                Quality::WeakQuarter
+            };
+            Ok((hash, quality))
+        }
+
+        /// Finalize the fuzzy hash with specified options, reusing the
+        /// statistics computed on the last call if the generator is not
+        /// updated since then.
+        ///
+        /// This returns the same result as
+        /// [`finalize_with_options()`](Self::finalize_with_options()) but
+        /// memoizes the statistics of the buckets (including quartiles) which
+        /// are costly to compute.  This is useful if you finalize the
+        /// generator repeatedly (e.g. while monitoring a growing file).
+        fn finalize_cached_with_options(
+            &mut self,
+            options: &GeneratorOptions,
+        ) -> Result<Self::Output, GeneratorError>;
+
+        /// Finalize the fuzzy hash with the default options, reusing the
+        /// statistics computed on the last call if the generator is not
+        /// updated since then.
+        ///
+        /// This returns the same result as [`finalize()`](Self::finalize())
+        /// but is faster if you finalize the same generator repeatedly.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        ///
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.");
+        /// let hash1 = generator.finalize_cached().unwrap();
+        /// // The second call reuses the cached statistics.
+        /// let hash2 = generator.finalize_cached().unwrap();
+        /// assert_eq!(hash1, hash2);
+        /// assert_eq!(hash1, generator.finalize().unwrap());
+        /// ```
+        #[inline(always)]
+        fn finalize_cached(&mut self) -> Result<Self::Output, GeneratorError> {
+            self.finalize_cached_with_options(&Default::default())
+        }
+
+        /// Returns the statistics of the buckets used to finalize
+        /// the fuzzy hash.
+        ///
+        /// This exposes the internal statistics computed on
+        /// [finalization](Self::finalize_with_options()) so that you can
+        /// inspect the data distribution (and choose generator options)
+        /// before finalizing.  It does not change the generator state.
+        ///
+        /// For instance, the default options reject the data if
+        /// [`nonzero`](BucketStats::nonzero) is not greater than the half of
+        /// [`total`](BucketStats::total) or the third quartile is zero.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        ///
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(b"ABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDE");
+        /// let stats = generator.bucket_stats();
+        /// assert_eq!(stats.total, 128);
+        /// assert!(stats.nonzero <= stats.total / 2);
+        /// assert!(generator.finalize().is_err());
+        /// ```
+        fn bucket_stats(&self) -> BucketStats;
+
+        /// (fast-tlsh specific)
+        /// Returns the raw bucket counts (the histogram of local features).
+        ///
+        /// Unlike the resulting fuzzy hash (which only contains aggregated
+        /// 2-bit values per bucket), this exposes the internal bucket counts
+        /// for further analysis (e.g. entropy and sparsity).
+        /// It does not change the generator state.
+        ///
+        /// Only the first [`NUMBER_OF_BUCKETS`](FuzzyHashType::NUMBER_OF_BUCKETS)
+        /// entries of the output type are meaningful (the rest is zero).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        ///
+        /// let mut generator = TlshGenerator::new();
+        /// generator.update(b"ABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDE");
+        /// let histogram = generator.histogram();
+        /// let nonzero = histogram[..Tlsh::NUMBER_OF_BUCKETS].iter().filter(|&&x| x != 0).count();
+        /// assert_eq!(nonzero, generator.bucket_stats().nonzero);
+        /// assert!(histogram[Tlsh::NUMBER_OF_BUCKETS..].iter().all(|&x| x == 0));
+        /// ```
+        fn histogram(&self) -> [u32; NUM_BUCKETS_LONG];
     }
 }
 
@@ -385,8 +1141,51 @@ pub(crate) mod public {
 pub(crate) mod inner {
     use super::*;
 
+    /// The tail type of generators with a configurable window size.
+    ///
+    /// Only the first `W - 1` bytes are used (where `W` is the window size).
+    pub(crate) type WindowTail = [u8; window::MAX_WINDOW_SIZE - 1];
+
+    /// The internal operations of the inner generator, used by
+    /// [`GeneratorWithWindow`](super::GeneratorWithWindow).
+    ///
+    /// On those operations, the `tail` field of the inner generator is unused
+    /// and the separate tail (with the effective length of the `tail_len`
+    /// field) is used instead.
+    pub trait WindowedGenerator {
+        /// Update the generator by feeding data to it
+        /// (using the `W`-byte window and the bucket mapping `M`).
+        fn update_with_window<const W: usize, M: BucketMapping>(
+            &mut self,
+            tail: &mut WindowTail,
+            data: &[u8],
+        );
+
+        /// Merges another generator which processed the data immediately
+        /// following the data processed by this generator
+        /// (using the `W`-byte window and the bucket mapping `M`).
+        fn merge_with_window<const W: usize, M: BucketMapping>(
+            &mut self,
+            tail: &mut WindowTail,
+            other: &Self,
+            other_tail: &WindowTail,
+            overlap: &[u8],
+        ) -> MergedChecksum;
+    }
+
+    /// The internal operation of the inner generator, used by
+    /// [`GeneratorWithoutChecksum`](super::GeneratorWithoutChecksum).
+    pub trait ChecksumlessGenerator {
+        /// Update the generator by feeding data to it
+        /// (without updating the checksum).
+        fn update_without_checksum(&mut self, data: &[u8]);
+    }
+
     /// The fuzzy hash generator corresponding specified parameters.
-    #[derive(Debug, Clone, PartialEq, Eq)]
+    ///
+    /// Note that the cached bucket statistics are not a part of
+    /// the state compared by [`PartialEq`].
+    #[derive(Debug, Clone)]
     pub struct Generator<
         const SIZE_CKSUM: usize,
         const SIZE_BODY: usize,
@@ -441,6 +1240,13 @@ pub(crate) mod inner {
         /// than the length of [`tail`](Self::tail) and we have to wait more
         /// data to be fed.
         pub(super) tail_len: u32,
+
+        /// The cached statistics of the buckets.
+        ///
+        /// This is set by
+        /// [`finalize_cached_with_options()`](crate::GeneratorType::finalize_cached_with_options())
+        /// and invalidated when the buckets may be changed.
+        pub(super) cached_stats: Option<BucketStats>,
     }
 
     impl<
@@ -449,13 +1255,11 @@ pub(crate) mod inner {
             const SIZE_BUCKETS: usize,
             const SIZE_IN_BYTES: usize,
             const SIZE_IN_STR_BYTES: usize,
-        > Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+        > PartialEq
+        for Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
     where
         FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
-        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper<
-            RawBodyType = [u8; SIZE_BODY],
-            RawBucketType = [u32; SIZE_BUCKETS],
-        >,
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper,
         FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>: FuzzyHashChecksum,
         VerboseFuzzyHashParams<
             SIZE_CKSUM,
@@ -466,38 +1270,27 @@ pub(crate) mod inner {
         >: ConstrainedVerboseFuzzyHashParams,
         LengthProcessingInfo<SIZE_BUCKETS>: ConstrainedLengthProcessingInfo,
     {
-        /// The maximum length of [`tail`](Self::tail) which is equal to one
-        /// less than [`WINDOW_SIZE`].
-        ///
-        /// If [`tail_len`](Self::tail_len) gets to this value and we have more
-        /// bytes to process, we start processing the file using
-        /// [`WINDOW_SIZE`]-byte sliding window.
-        const TAIL_SIZE: u32 = (WINDOW_SIZE - 1) as u32;
-
-        /// The maximum [`len`](Self::len), which is equal to the value first
-        /// overflows [`u32`] if we calculate `len + tail_len`.
-        const MAX_LEN: u32 = u32::MAX - (Self::TAIL_SIZE - 1);
-
-        /// TLSH's B (bucket) mapping suitable for this generator.
-        #[inline(always)]
-        fn b_mapping(v0: u8, v1: u8, v2: u8, v3: u8) -> u8 {
-            FuzzyHashBucketsInfo::<SIZE_BUCKETS>::b_mapping(v0, v1, v2, v3)
+        fn eq(&self, other: &Self) -> bool {
+            // The cached statistics are derived from the buckets
+            // (and only memoized by finalization).
+            self.buckets == other.buckets
+                && self.len == other.len
+                && self.checksum == other.checksum
+                && self.tail == other.tail
+                && self.tail_len == other.tail_len
         }
     }
+
     impl<
             const SIZE_CKSUM: usize,
             const SIZE_BODY: usize,
             const SIZE_BUCKETS: usize,
             const SIZE_IN_BYTES: usize,
             const SIZE_IN_STR_BYTES: usize,
-        > Default
-        for Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+        > Eq for Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
     where
         FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
-        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper<
-            RawBodyType = [u8; SIZE_BODY],
-            RawBucketType = [u32; SIZE_BUCKETS],
-        >,
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper,
         FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>: FuzzyHashChecksum,
         VerboseFuzzyHashParams<
             SIZE_CKSUM,
@@ -508,24 +1301,15 @@ pub(crate) mod inner {
         >: ConstrainedVerboseFuzzyHashParams,
         LengthProcessingInfo<SIZE_BUCKETS>: ConstrainedLengthProcessingInfo,
     {
-        fn default() -> Self {
-            Self {
-                buckets: FuzzyHashBucketsData::new(),
-                len: 0,
-                checksum: FuzzyHashChecksumData::new(),
-                tail: [0; WINDOW_SIZE - 1],
-                tail_len: 0,
-            }
-        }
     }
+
     impl<
             const SIZE_CKSUM: usize,
             const SIZE_BODY: usize,
             const SIZE_BUCKETS: usize,
             const SIZE_IN_BYTES: usize,
             const SIZE_IN_STR_BYTES: usize,
-        > crate::GeneratorType
-        for Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+        > Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
     where
         FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
         FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper<
@@ -542,24 +1326,60 @@ pub(crate) mod inner {
         >: ConstrainedVerboseFuzzyHashParams,
         LengthProcessingInfo<SIZE_BUCKETS>: ConstrainedLengthProcessingInfo,
     {
-        type Output = crate::hash::inner::FuzzyHash<
-            SIZE_CKSUM,
-            SIZE_BODY,
-            SIZE_BUCKETS,
-            SIZE_IN_BYTES,
-            SIZE_IN_STR_BYTES,
-        >;
+        /// The maximum length of [`tail`](Self::tail) which is equal to one
+        /// less than [`WINDOW_SIZE`].
+        ///
+        /// If [`tail_len`](Self::tail_len) gets to this value and we have more
+        /// bytes to process, we start processing the file using
+        /// [`WINDOW_SIZE`]-byte sliding window.
+        pub(super) const TAIL_SIZE: u32 = (WINDOW_SIZE - 1) as u32;
 
-        const IS_CHECKSUM_EFFECTIVE: bool = true;
-        const MIN: u32 = LengthProcessingInfo::<SIZE_BUCKETS>::MIN;
-        const MIN_CONSERVATIVE: u32 = LengthProcessingInfo::<SIZE_BUCKETS>::MIN_CONSERVATIVE;
-        const MAX: u32 = LengthProcessingInfo::<SIZE_BUCKETS>::MIN;
+        /// The maximum [`len`](Self::len), which is equal to the value first
+        /// overflows [`u32`] if we calculate `len + tail_len`.
+        pub(super) const MAX_LEN: u32 = u32::MAX - (Self::TAIL_SIZE - 1);
 
-        fn processed_len(&self) -> Option<u32> {
-            self.len.checked_add(self.tail_len)
+        /// TLSH's B (bucket) mapping suitable for this generator.
+        #[inline(always)]
+        fn b_mapping(v0: u8, v1: u8, v2: u8, v3: u8) -> u8 {
+            FuzzyHashBucketsInfo::<SIZE_BUCKETS>::b_mapping(v0, v1, v2, v3)
         }
 
-        fn update(&mut self, data: &[u8]) {
+        /// The bucket mapping `M` suitable for this generator.
+        #[inline(always)]
+        fn map_with<M: BucketMapping>(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+            #[cfg(feature = "experiment-variants")]
+            if SIZE_BUCKETS == crate::buckets::NUM_BUCKETS_EXTRA_SHORT {
+                return crate::pearson::fold_48_to_24(M::map_48(b0, b1, b2, b3));
+            }
+            if SIZE_BUCKETS == NUM_BUCKETS_SHORT {
+                M::map_48(b0, b1, b2, b3)
+            } else {
+                M::map(b0, b1, b2, b3)
+            }
+        }
+
+        /// Updates the checksum (if `CHECKSUM` is [`true`]) and the buckets
+        /// using a 5-byte window (`b4` is the latest byte).
+        #[inline(always)]
+        fn update_window<const CHECKSUM: bool>(&mut self, b0: u8, b1: u8, b2: u8, b3: u8, b4: u8) {
+            if CHECKSUM {
+                self.checksum.update(b4, b3);
+            }
+            self.buckets.increment(Self::b_mapping(0x2, b4, b3, b2));
+            self.buckets.increment(Self::b_mapping(0x3, b4, b3, b1));
+            self.buckets.increment(Self::b_mapping(0x5, b4, b2, b1));
+            self.buckets.increment(Self::b_mapping(0x7, b4, b2, b0));
+            self.buckets.increment(Self::b_mapping(0xb, b4, b3, b0));
+            self.buckets.increment(Self::b_mapping(0xd, b4, b1, b0));
+        }
+
+        /// Update the generator by feeding data to it
+        /// (the checksum is updated only if `CHECKSUM` is [`true`]).
+        fn update_impl<const CHECKSUM: bool>(&mut self, data: &[u8]) {
+            if data.is_empty() {
+                return;
+            }
+            self.cached_stats = None;
             // Fill self.tail (before we start updating).
             let mut data = data;
             if self.tail_len < Self::TAIL_SIZE {
@@ -595,15 +1415,41 @@ pub(crate) mod inner {
             // Update the buckets based on the 5-byte window.
             let (mut b0, mut b1, mut b2, mut b3) =
                 (self.tail[0], self.tail[1], self.tail[2], self.tail[3]);
-            for &b4 in data {
-                // Update the checksum and buckets
-                self.checksum.update(b4, b3);
-                self.buckets.increment(Self::b_mapping(0x2, b4, b3, b2));
-                self.buckets.increment(Self::b_mapping(0x3, b4, b3, b1));
-                self.buckets.increment(Self::b_mapping(0x5, b4, b2, b1));
-                self.buckets.increment(Self::b_mapping(0x7, b4, b2, b0));
-                self.buckets.increment(Self::b_mapping(0xb, b4, b3, b0));
-                self.buckets.increment(Self::b_mapping(0xd, b4, b1, b0));
+            #[cfg(not(feature = "opt-simd-generate"))]
+            let rest = data;
+            #[cfg(feature = "opt-simd-generate")]
+            let rest = {
+                // Process first bytes (depending on self.tail) one by one.
+                let head_len = data.len().min(Self::TAIL_SIZE as usize);
+                for &b4 in &data[..head_len] {
+                    self.update_window::<CHECKSUM>(b0, b1, b2, b3, b4);
+                    (b0, b1, b2, b3) = (b1, b2, b3, b4);
+                }
+                // Then process BLOCK_SIZE windows at once.
+                let mut pos = head_len;
+                let mut indices: BucketIndices = [[0; BLOCK_SIZE]; NUM_MAPPINGS];
+                while data.len() - pos >= BLOCK_SIZE {
+                    let window: &BlockWindow = data[pos - (WINDOW_SIZE - 1)..pos + BLOCK_SIZE]
+                        .try_into()
+                        .unwrap();
+                    FuzzyHashBucketsInfo::<SIZE_BUCKETS>::b_mapping_block(&mut indices, window);
+                    if CHECKSUM {
+                        for w in window[WINDOW_SIZE - 2..].windows(2) {
+                            self.checksum.update(w[1], w[0]);
+                        }
+                    }
+                    for indices in indices.iter() {
+                        for &index in indices {
+                            self.buckets.increment(index as u8);
+                        }
+                    }
+                    pos += BLOCK_SIZE;
+                    (b0, b1, b2, b3) = (data[pos - 4], data[pos - 3], data[pos - 2], data[pos - 1]);
+                }
+                &data[pos..]
+            };
+            for &b4 in rest {
+                self.update_window::<CHECKSUM>(b0, b1, b2, b3, b4);
                 // Shift
                 (b0, b1, b2, b3) = (b1, b2, b3, b4);
             }
@@ -619,10 +1465,75 @@ pub(crate) mod inner {
             }
         }
 
-        fn finalize_with_options(
+        /// Computes the statistics of the buckets.
+        fn bucket_statistics(&self) -> BucketStats {
+            let buckets: [u32; SIZE_BUCKETS] = self.buckets.data().try_into().unwrap();
+            let nonzero = buckets.iter().filter(|&&x| x != 0).count();
+            let mut copy_buckets = buckets;
+            let (q1, q2, q3) = quartiles_in_place(&mut copy_buckets);
+            BucketStats {
+                nonzero,
+                total: SIZE_BUCKETS,
+                quartiles: [q1, q2, q3],
+            }
+        }
+
+        /// Finalize the fuzzy hash with specified options and
+        /// the function to retrieve the statistics of the buckets.
+        ///
+        /// If the option to emit the null hash is set, it replaces
+        /// the errors (except [`GeneratorError::TooLargeInput`]) with
+        /// the null hash.
+        fn finalize_with_statistics(
             &self,
             options: &GeneratorOptions,
-        ) -> Result<Self::Output, GeneratorError> {
+            statistics: impl FnOnce() -> BucketStats,
+        ) -> Result<
+            crate::hash::inner::FuzzyHash<
+                SIZE_CKSUM,
+                SIZE_BODY,
+                SIZE_BUCKETS,
+                SIZE_IN_BYTES,
+                SIZE_IN_STR_BYTES,
+            >,
+            GeneratorError,
+        > {
+            match self.finalize_with_statistics_strict(options, statistics) {
+                Err(
+                    GeneratorError::TooSmallInput
+                    | GeneratorError::BucketsAreHalfEmpty
+                    | GeneratorError::BucketsAreThreeQuarterEmpty,
+                ) if options
+                    .incompat_flags
+                    .contains(TLSHIncompatibleGeneratorFlags::EMIT_NULL_ON_FAILURE) =>
+                {
+                    Ok(crate::hash::inner::FuzzyHash::from_raw(
+                        FuzzyHashBodyData::from_raw([0; SIZE_BODY]),
+                        FuzzyHashChecksumData::from_raw(&[0; SIZE_CKSUM]),
+                        FuzzyHashLengthEncoding::from_raw(0),
+                        FuzzyHashQRatios::from_raw(0),
+                    ))
+                }
+                result => result,
+            }
+        }
+
+        /// Checks whether the current state is accepted on finalization
+        /// with specified options and the function to retrieve
+        /// the statistics of the buckets (without emitting the null hash).
+        ///
+        /// `statistics` is called only if the data length is valid.
+        /// On success, it returns the statistics of the buckets.
+        fn check_acceptance_strict(
+            &self,
+            options: &GeneratorOptions,
+            statistics: impl FnOnce() -> BucketStats,
+        ) -> Result<BucketStats, GeneratorError> {
+            // Reject if the input exceeds the limit by the local policy.
+            let limit = options.input_size_limit();
+            if self.processed_len().map_or(1 << 32, u64::from) > limit {
+                return Err(GeneratorError::TooLargeInput);
+            }
             let len = self.processed_len().unwrap_or(u32::MAX); // assume u32::MAX is an invalid value.
             let validity = DataLengthValidity::new::<SIZE_BUCKETS>(len);
             if validity.is_err_on(options.length_mode) {
@@ -640,31 +1551,20 @@ pub(crate) mod inner {
                     }
                 }
             }
-            // Get encoded length part.
-            let lvalue = FuzzyHashLengthEncoding::new(len).unwrap();
-            // Get quartile values and number of non-zero buckets.
-            let buckets: [u32; SIZE_BUCKETS] = self.buckets.data().try_into().unwrap();
-            let nonzero_count = buckets.iter().filter(|&&x| x != 0).count();
-            let mut copy_buckets = buckets;
-            let (l0, &mut mut q2, l1) = copy_buckets.select_nth_unstable(SIZE_BUCKETS / 2 - 1);
-            let (_, &mut mut q1, _) = l0.select_nth_unstable(SIZE_BUCKETS / 4 - 1);
-            let (_, &mut mut q3, _) = l1.select_nth_unstable(SIZE_BUCKETS / 4 - 1);
+            let stats = statistics();
             // Reject if the data distribution is too statistically unbalanced
             // (so that an attempt to calculate Q ratios will cause an issue)
-            // unless an option is specified
-            // (in this case, dummy quartile values are set).
-            if q3 == 0 {
-                if !options.incompat_flags.contains(
+            // unless an option is specified.
+            if stats.quartiles[2] == 0
+                && !options.incompat_flags.contains(
                     TLSHIncompatibleGeneratorFlags::ALLOW_STATISTICALLY_WEAK_BUCKETS_QUARTER,
-                ) {
-                    return Err(GeneratorError::BucketsAreThreeQuarterEmpty);
-                }
-                // Set a value to force outputting a fuzzy hash.
-                (q1, q2, q3) = (1, 1, 1);
+                )
+            {
+                return Err(GeneratorError::BucketsAreThreeQuarterEmpty);
             }
             // Reject if the data distribution is statistically unbalanced
             // unless an option is specified.
-            if nonzero_count < FuzzyHashBucketsInfo::<SIZE_BUCKETS>::MIN_NONZERO_BUCKETS
+            if stats.nonzero < FuzzyHashBucketsInfo::<SIZE_BUCKETS>::MIN_NONZERO_BUCKETS
                 && !options.incompat_flags.intersects(
                     TLSHIncompatibleGeneratorFlags::ALLOW_STATISTICALLY_WEAK_BUCKETS_HALF
                         | TLSHIncompatibleGeneratorFlags::ALLOW_STATISTICALLY_WEAK_BUCKETS_QUARTER,
@@ -672,20 +1572,48 @@ pub(crate) mod inner {
             {
                 return Err(GeneratorError::BucketsAreHalfEmpty);
             }
+            Ok(stats)
+        }
+
+        /// Finalize the fuzzy hash with specified options and
+        /// the function to retrieve the statistics of the buckets
+        /// (without emitting the null hash).
+        ///
+        /// `statistics` is called only if the data length is valid.
+        fn finalize_with_statistics_strict(
+            &self,
+            options: &GeneratorOptions,
+            statistics: impl FnOnce() -> BucketStats,
+        ) -> Result<
+            crate::hash::inner::FuzzyHash<
+                SIZE_CKSUM,
+                SIZE_BODY,
+                SIZE_BUCKETS,
+                SIZE_IN_BYTES,
+                SIZE_IN_STR_BYTES,
+            >,
+            GeneratorError,
+        > {
+            let BucketStats {
+                quartiles: [mut q1, mut q2, mut q3],
+                ..
+            } = self.check_acceptance_strict(options, statistics)?;
+            if q3 == 0 {
+                // Set a value to force outputting a fuzzy hash
+                // (only if an option is specified).
+                (q1, q2, q3) = (1, 1, 1);
+            }
+            // Get encoded length part.
+            let lvalue = FuzzyHashLengthEncoding::new(self.processed_len().unwrap()).unwrap();
+            let buckets: [u32; SIZE_BUCKETS] = self.buckets.data().try_into().unwrap();
             // Get the Q ratios.
             let (q1ratio, q2ratio) = if options
                 .compat_flags
                 .contains(TLSHCompatibleGeneratorFlags::PURE_INTEGER_QRATIO_COMPUTATION)
             {
-                (
-                    (((q1 as u64 * 100) / q3 as u64) % 16) as u8,
-                    (((q2 as u64 * 100) / q3 as u64) % 16) as u8,
-                )
+                qratio::qratios_integer(q1, q2, q3)
             } else {
-                (
-                    (((q1.wrapping_mul(100) as f32) / q3 as f32) as u32 % 16) as u8,
-                    (((q2.wrapping_mul(100) as f32) / q3 as f32) as u32 % 16) as u8,
-                )
+                qratio::qratios_float(q1, q2, q3)
             };
             let qratios = FuzzyHashQRatios::new(q1ratio, q2ratio);
             // Compute the body part.
@@ -694,19 +1622,324 @@ pub(crate) mod inner {
                 &mut body, &buckets, q1, q2, q3,
             );
             // Return the new fuzzy hash object.
-            Ok(Self::Output::from_raw(
+            Ok(crate::hash::inner::FuzzyHash::from_raw(
                 FuzzyHashBodyData::from_raw(body),
                 self.checksum,
                 lvalue,
                 qratios,
             ))
         }
+    }
+    impl<
+            const SIZE_CKSUM: usize,
+            const SIZE_BODY: usize,
+            const SIZE_BUCKETS: usize,
+            const SIZE_IN_BYTES: usize,
+            const SIZE_IN_STR_BYTES: usize,
+        > Default
+        for Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+    where
+        FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper<
+            RawBodyType = [u8; SIZE_BODY],
+            RawBucketType = [u32; SIZE_BUCKETS],
+        >,
+        FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>: FuzzyHashChecksum,
+        VerboseFuzzyHashParams<
+            SIZE_CKSUM,
+            SIZE_BODY,
+            SIZE_BUCKETS,
+            SIZE_IN_BYTES,
+            SIZE_IN_STR_BYTES,
+        >: ConstrainedVerboseFuzzyHashParams,
+        LengthProcessingInfo<SIZE_BUCKETS>: ConstrainedLengthProcessingInfo,
+    {
+        fn default() -> Self {
+            Self {
+                buckets: FuzzyHashBucketsData::new(),
+                len: 0,
+                checksum: FuzzyHashChecksumData::new(),
+                tail: [0; WINDOW_SIZE - 1],
+                tail_len: 0,
+                cached_stats: None,
+            }
+        }
+    }
+    impl<
+            const SIZE_CKSUM: usize,
+            const SIZE_BODY: usize,
+            const SIZE_BUCKETS: usize,
+            const SIZE_IN_BYTES: usize,
+            const SIZE_IN_STR_BYTES: usize,
+        > crate::GeneratorType
+        for Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+    where
+        FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper<
+            RawBodyType = [u8; SIZE_BODY],
+            RawBucketType = [u32; SIZE_BUCKETS],
+        >,
+        FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>: FuzzyHashChecksum,
+        VerboseFuzzyHashParams<
+            SIZE_CKSUM,
+            SIZE_BODY,
+            SIZE_BUCKETS,
+            SIZE_IN_BYTES,
+            SIZE_IN_STR_BYTES,
+        >: ConstrainedVerboseFuzzyHashParams,
+        LengthProcessingInfo<SIZE_BUCKETS>: ConstrainedLengthProcessingInfo,
+    {
+        type Output = crate::hash::inner::FuzzyHash<
+            SIZE_CKSUM,
+            SIZE_BODY,
+            SIZE_BUCKETS,
+            SIZE_IN_BYTES,
+            SIZE_IN_STR_BYTES,
+        >;
 
-        #[cfg(test)]
-        fn count_nonzero_buckets(&self) -> usize {
-            // Excerpt from finalize_with_options above.
-            let buckets: [u32; SIZE_BUCKETS] = self.buckets.data().try_into().unwrap();
-            buckets.iter().filter(|&&x| x != 0).count()
+        const IS_CHECKSUM_EFFECTIVE: bool = true;
+        const MIN: u32 = LengthProcessingInfo::<SIZE_BUCKETS>::MIN;
+        const MIN_CONSERVATIVE: u32 = LengthProcessingInfo::<SIZE_BUCKETS>::MIN_CONSERVATIVE;
+        const MAX: u32 = LengthProcessingInfo::<SIZE_BUCKETS>::MIN;
+
+        fn processed_len(&self) -> Option<u32> {
+            self.len.checked_add(self.tail_len)
+        }
+
+        #[inline(always)]
+        fn update(&mut self, data: &[u8]) {
+            self.update_impl::<true>(data);
+        }
+
+        fn reset(&mut self) {
+            self.buckets.clear();
+            self.len = 0;
+            self.checksum.clear();
+            self.tail = [0; WINDOW_SIZE - 1];
+            self.tail_len = 0;
+            self.cached_stats = None;
+        }
+
+        fn merge(&mut self, other: &Self, overlap: &[u8]) -> MergedChecksum {
+            assert_eq!(
+                overlap.len(),
+                other.tail_len as usize,
+                "overlap must be the first bytes of the data processed by `other`"
+            );
+            if other.tail_len == 0 {
+                // other is empty.
+                return MergedChecksum::Exact;
+            }
+            if self.tail_len == 0 {
+                // self is empty (the result is exactly the same as other).
+                *self = other.clone();
+                return MergedChecksum::Exact;
+            }
+            // Process windows straddling the boundary (and fill self.tail
+            // if this generator has processed less than TAIL_SIZE bytes).
+            self.update(overlap);
+            if other.len != 0 {
+                self.buckets.merge(&other.buckets);
+                self.len = self.len.saturating_add(other.len).min(Self::MAX_LEN);
+                self.tail = other.tail;
+            }
+            // The checksum cannot be combined.
+            self.checksum.clear();
+            self.cached_stats = None;
+            MergedChecksum::Cleared
+        }
+
+        fn finalize_with_options(
+            &self,
+            options: &GeneratorOptions,
+        ) -> Result<Self::Output, GeneratorError> {
+            self.finalize_with_statistics(options, || self.bucket_statistics())
+        }
+
+        fn is_currently_hashable(&self, options: &GeneratorOptions) -> bool {
+            match self.check_acceptance_strict(options, || self.bucket_stats()) {
+                Ok(_) => true,
+                Err(
+                    GeneratorError::TooSmallInput
+                    | GeneratorError::BucketsAreHalfEmpty
+                    | GeneratorError::BucketsAreThreeQuarterEmpty,
+                ) => options
+                    .incompat_flags
+                    .contains(TLSHIncompatibleGeneratorFlags::EMIT_NULL_ON_FAILURE),
+                Err(_) => false,
+            }
+        }
+
+        fn finalize_cached_with_options(
+            &mut self,
+            options: &GeneratorOptions,
+        ) -> Result<Self::Output, GeneratorError> {
+            let statistics = match self.cached_stats {
+                Some(statistics) => statistics,
+                None => {
+                    let statistics = self.bucket_statistics();
+                    self.cached_stats = Some(statistics);
+                    statistics
+                }
+            };
+            self.finalize_with_statistics(options, || statistics)
+        }
+
+        fn bucket_stats(&self) -> BucketStats {
+            self.cached_stats
+                .unwrap_or_else(|| self.bucket_statistics())
+        }
+
+        fn histogram(&self) -> [u32; NUM_BUCKETS_LONG] {
+            let mut histogram = [0u32; NUM_BUCKETS_LONG];
+            histogram[..SIZE_BUCKETS].copy_from_slice(self.buckets.data());
+            histogram
+        }
+    }
+    impl<
+            const SIZE_CKSUM: usize,
+            const SIZE_BODY: usize,
+            const SIZE_BUCKETS: usize,
+            const SIZE_IN_BYTES: usize,
+            const SIZE_IN_STR_BYTES: usize,
+        > ChecksumlessGenerator
+        for Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+    where
+        FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper<
+            RawBodyType = [u8; SIZE_BODY],
+            RawBucketType = [u32; SIZE_BUCKETS],
+        >,
+        FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>: FuzzyHashChecksum,
+        VerboseFuzzyHashParams<
+            SIZE_CKSUM,
+            SIZE_BODY,
+            SIZE_BUCKETS,
+            SIZE_IN_BYTES,
+            SIZE_IN_STR_BYTES,
+        >: ConstrainedVerboseFuzzyHashParams,
+        LengthProcessingInfo<SIZE_BUCKETS>: ConstrainedLengthProcessingInfo,
+    {
+        #[inline(always)]
+        fn update_without_checksum(&mut self, data: &[u8]) {
+            self.update_impl::<false>(data);
+        }
+    }
+    impl<
+            const SIZE_CKSUM: usize,
+            const SIZE_BODY: usize,
+            const SIZE_BUCKETS: usize,
+            const SIZE_IN_BYTES: usize,
+            const SIZE_IN_STR_BYTES: usize,
+        > WindowedGenerator
+        for Generator<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+    where
+        FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper<
+            RawBodyType = [u8; SIZE_BODY],
+            RawBucketType = [u32; SIZE_BUCKETS],
+        >,
+        FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>: FuzzyHashChecksum,
+        VerboseFuzzyHashParams<
+            SIZE_CKSUM,
+            SIZE_BODY,
+            SIZE_BUCKETS,
+            SIZE_IN_BYTES,
+            SIZE_IN_STR_BYTES,
+        >: ConstrainedVerboseFuzzyHashParams,
+        LengthProcessingInfo<SIZE_BUCKETS>: ConstrainedLengthProcessingInfo,
+    {
+        fn update_with_window<const W: usize, M: BucketMapping>(
+            &mut self,
+            tail: &mut WindowTail,
+            data: &[u8],
+        ) {
+            if data.is_empty() {
+                return;
+            }
+            self.cached_stats = None;
+            let tail_size = (W - 1) as u32;
+            let max_len = u32::MAX - (tail_size - 1);
+            // Fill tail (before we start updating).
+            let mut data = data;
+            if self.tail_len < tail_size {
+                let tail_len = self.tail_len as usize;
+                let remaining = tail_size as usize - tail_len;
+                if data.len() <= remaining {
+                    tail[tail_len..tail_len + data.len()].copy_from_slice(data);
+                    self.tail_len += data.len() as u32;
+                    return;
+                }
+                tail[tail_len..W - 1].copy_from_slice(&data[..remaining]);
+                self.tail_len += remaining as u32;
+                data = &data[remaining..];
+            }
+            // If we have processed 4GiB already, ignore the rest.
+            if unlikely(self.len >= max_len) {
+                return;
+            }
+            // Update the processed data length
+            let mut data_len = u32::try_from(data.len()).unwrap_or(u32::MAX);
+            if unlikely(data_len > max_len - self.len) {
+                data_len = max_len - self.len;
+                data = &data[..data_len as usize];
+            }
+            self.len += data_len;
+            // Update the buckets based on the W-byte window
+            // (the last byte is the latest one).
+            let mut window = [0u8; window::MAX_WINDOW_SIZE];
+            window[..W - 1].copy_from_slice(&tail[..W - 1]);
+            for &b in data {
+                window[W - 1] = b;
+                self.checksum.update(b, window[W - 2]);
+                for &(salt, i, j) in window::WINDOW_TRIPLETS.iter() {
+                    if j < W {
+                        let (b2, b3) = (window[W - 1 - i], window[W - 1 - j]);
+                        self.buckets.increment(Self::map_with::<M>(salt, b, b2, b3));
+                    }
+                }
+                // Shift
+                window.copy_within(1..W, 0);
+            }
+            // Update tail.
+            tail[..W - 1].copy_from_slice(&window[..W - 1]);
+        }
+
+        fn merge_with_window<const W: usize, M: BucketMapping>(
+            &mut self,
+            tail: &mut WindowTail,
+            other: &Self,
+            other_tail: &WindowTail,
+            overlap: &[u8],
+        ) -> MergedChecksum {
+            assert_eq!(
+                overlap.len(),
+                other.tail_len as usize,
+                "overlap must be the first bytes of the data processed by `other`"
+            );
+            if other.tail_len == 0 {
+                // other is empty.
+                return MergedChecksum::Exact;
+            }
+            if self.tail_len == 0 {
+                // self is empty (the result is exactly the same as other).
+                *self = other.clone();
+                *tail = *other_tail;
+                return MergedChecksum::Exact;
+            }
+            // Process windows straddling the boundary (and fill tail
+            // if this generator has processed less than W - 1 bytes).
+            self.update_with_window::<W, M>(tail, overlap);
+            if other.len != 0 {
+                let max_len = u32::MAX - (W as u32 - 2);
+                self.buckets.merge(&other.buckets);
+                self.len = self.len.saturating_add(other.len).min(max_len);
+                *tail = *other_tail;
+            }
+            // The checksum cannot be combined.
+            self.checksum.clear();
+            self.cached_stats = None;
+            MergedChecksum::Cleared
         }
     }
 }
@@ -718,6 +1951,30 @@ macro_rules! inner_type {
     };
 }
 
+mod any;
+mod checkpoint;
+mod filter;
+mod mapping;
+mod multi;
+mod nochecksum;
+mod qratio;
+mod regions;
+mod tee;
+mod window;
+pub use any::{generator_for_variant, AnyGenerator};
+pub use checkpoint::Checkpoint;
+pub use filter::GeneratorWithByteFilter;
+pub use mapping::{BucketMapping, TlshBucketMapping};
+pub use multi::MultiGenerator;
+pub use nochecksum::GeneratorWithoutChecksum;
+pub use qratio::{
+    GeneratorWithQRatio, QRatioComputation, QRatioDiagnostics, QRatioFloat, QRatioInteger,
+};
+pub use regions::{windowed_hashes, windowed_hashes_for, WindowedHashes};
+#[cfg(feature = "digest")]
+pub use tee::TeeGenerator;
+pub use window::GeneratorWithWindow;
+
 /// The fuzzy hash generator corresponding specified fuzzy hash type.
 ///
 /// For the main functionalities, see [`GeneratorType`] documentation.
@@ -726,15 +1983,128 @@ pub struct Generator<T: ConstrainedFuzzyHashType> {
     /// The inner object representing actual contents of the generator.
     pub(crate) inner:
         <<T as ConstrainedFuzzyHashType>::Params as ConstrainedFuzzyHashParams>::InnerGeneratorType,
+    /// The options used by [`finalize()`](GeneratorType::finalize()) and
+    /// [`finalize_cached()`](GeneratorType::finalize_cached()).
+    pub(crate) options: GeneratorOptions,
 }
 impl<T: ConstrainedFuzzyHashType> Generator<T> {
     /// Creates the new generator.
     #[inline(always)]
     pub fn new() -> Self {
+        Self::with_options(GeneratorOptions::new())
+    }
+
+    /// (fast-tlsh specific)
+    /// Creates the new generator with the options to be used on
+    /// finalization by default.
+    ///
+    /// The specified options are used by
+    /// [`finalize()`](GeneratorType::finalize()) and
+    /// [`finalize_cached()`](GeneratorType::finalize_cached()) instead of
+    /// the default options so that you don't have to pass the same options
+    /// on every finalization.  Methods taking options explicitly (e.g.
+    /// [`finalize_with_options()`](GeneratorType::finalize_with_options()))
+    /// are not affected.
+    ///
+    /// The options are kept on [`reset()`](GeneratorType::reset()).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tlsh::prelude::*;
+    /// use tlsh::GeneratorOptions;
+    ///
+    /// let options = GeneratorOptions::new().with_emit_null_on_failure(true);
+    /// let mut generator = TlshGenerator::with_options(options);
+    /// generator.update(b"Hello!");
+    /// assert!(generator.finalize().unwrap().is_null());
+    /// // Explicitly specified options take precedence.
+    /// assert!(generator.finalize_with_options(&GeneratorOptions::new()).is_err());
+    /// ```
+    #[inline(always)]
+    pub fn with_options(options: GeneratorOptions) -> Self {
         Self {
             inner: Default::default(),
+            options,
         }
     }
+
+    /// (fast-tlsh specific)
+    /// Creates the new generator which uses
+    /// [the conservative data length processing mode](DataLengthProcessingMode::Conservative)
+    /// by default.
+    ///
+    /// This is the same as [`with_options()`](Self::with_options()) with
+    /// the default options except the data length processing mode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tlsh::prelude::*;
+    /// use tlsh::GeneratorError;
+    ///
+    /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+    ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+    /// let mut generator = TlshGenerator::new();
+    /// generator.update(data);
+    /// assert!(generator.finalize().is_ok());
+    /// // Conservative mode requires at least 128 bytes.
+    /// let mut generator = TlshGenerator::new_conservative();
+    /// generator.update(data);
+    /// assert_eq!(generator.finalize(), Err(GeneratorError::TooSmallInput));
+    /// ```
+    #[inline(always)]
+    pub fn new_conservative() -> Self {
+        Self::with_options(
+            GeneratorOptions::new()
+                .with_length_processing_mode(DataLengthProcessingMode::Conservative),
+        )
+    }
+
+    /// (fast-tlsh specific)
+    /// Returns the options used on finalization by default.
+    ///
+    /// See [`with_options()`](Self::with_options()) for details.
+    #[inline(always)]
+    pub fn options(&self) -> &GeneratorOptions {
+        &self.options
+    }
+
+    /// (fast-tlsh specific)
+    /// Creates the new generator with a custom bucket mapping `M`.
+    ///
+    /// The resulting generator uses the default window size
+    /// ([`WINDOW_SIZE`]) and is the same as
+    /// [`GeneratorWithWindow::<T, WINDOW_SIZE, M>::new()`](GeneratorWithWindow::new()).
+    ///
+    /// **Warning**: This is TLSH-incompatible unless `M` is
+    /// [`TlshBucketMapping`] (see [`BucketMapping`] for details).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tlsh::generate::{BucketMapping, Generator};
+    /// use tlsh::hashes;
+    /// use tlsh::prelude::*;
+    ///
+    /// // Use the sum of bytes as the bucket index (only for illustration).
+    /// struct SumMapping;
+    /// impl BucketMapping for SumMapping {
+    ///     fn map(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+    ///         b0.wrapping_add(b1).wrapping_add(b2).wrapping_add(b3)
+    ///     }
+    /// }
+    ///
+    /// let mut generator = Generator::<hashes::Normal>::with_mapping::<SumMapping>();
+    /// generator.update(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+    ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.");
+    /// assert!(!generator.is_tlsh_compatible(&tlsh::GeneratorOptions::new()));
+    /// let _hash = generator.finalize().unwrap();
+    /// ```
+    #[inline(always)]
+    pub fn with_mapping<M: BucketMapping>() -> GeneratorWithWindow<T, WINDOW_SIZE, M> {
+        GeneratorWithWindow::new()
+    }
 }
 impl<T: ConstrainedFuzzyHashType> Default for Generator<T> {
     fn default() -> Self {
@@ -759,6 +2129,33 @@ impl<T: ConstrainedFuzzyHashType> GeneratorType for Generator<T> {
         self.inner.update(data);
     }
 
+    /// Update the generator by feeding data to it, unless a part of
+    /// `data` would be ignored or the total input would exceed
+    /// [the limit](GeneratorOptions::max_input_size()) set on
+    /// [the options of this generator](Self::options()).
+    ///
+    /// On failure, it returns [`GeneratorError::TooLargeInput`]
+    /// without updating the generator.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), GeneratorError> {
+        let len = self.processed_len().map_or(1 << 32, u64::from);
+        let limit = self.options.input_size_limit();
+        if data.len() as u64 > limit.saturating_sub(len) {
+            return Err(GeneratorError::TooLargeInput);
+        }
+        self.update(data);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    #[inline(always)]
+    fn merge(&mut self, other: &Self, overlap: &[u8]) -> MergedChecksum {
+        self.inner.merge(&other.inner, overlap)
+    }
+
     #[inline(always)]
     fn finalize_with_options(
         &self,
@@ -767,9 +2164,141 @@ impl<T: ConstrainedFuzzyHashType> GeneratorType for Generator<T> {
         self.inner.finalize_with_options(options).map(T::new)
     }
 
-    #[cfg(test)]
-    fn count_nonzero_buckets(&self) -> usize {
-        self.inner.count_nonzero_buckets()
+    #[inline(always)]
+    fn is_currently_hashable(&self, options: &GeneratorOptions) -> bool {
+        self.inner.is_currently_hashable(options)
+    }
+
+    #[inline(always)]
+    fn finalize(&self) -> Result<Self::Output, GeneratorError> {
+        self.finalize_with_options(&self.options)
+    }
+
+    #[inline(always)]
+    fn finalize_cached_with_options(
+        &mut self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner.finalize_cached_with_options(options).map(T::new)
+    }
+
+    #[inline(always)]
+    fn finalize_cached(&mut self) -> Result<Self::Output, GeneratorError> {
+        self.inner
+            .finalize_cached_with_options(&self.options)
+            .map(T::new)
+    }
+
+    #[inline(always)]
+    fn bucket_stats(&self) -> BucketStats {
+        self.inner.bucket_stats()
+    }
+
+    #[inline(always)]
+    fn histogram(&self) -> [u32; NUM_BUCKETS_LONG] {
+        self.inner.histogram()
+    }
+}
+
+/// Feeding data through [`std::io::Write`].
+///
+/// This allows using the generator as a sink, such as the destination of
+/// [`std::io::copy()`].  Writing to the generator never fails and
+/// [`flush()`](std::io::Write::flush()) does nothing.
+///
+/// # Example
+///
+/// ```
+/// use std::fs::File;
+/// use tlsh::prelude::*;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut stream = File::open("data/examples/smallexe.exe")?;
+///     let mut generator = TlshGenerator::new();
+///     std::io::copy(&mut stream, &mut generator)?;
+///     let fuzzy_hash = generator.finalize().unwrap();
+///     let fuzzy_hash_str = fuzzy_hash.to_string();
+///     assert_eq!(fuzzy_hash_str, "T1FFE04C037F895471D42E5530499E47473757E5E456D28B13ED1944654C8534C7CE9E01");
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "std")))]
+impl<T: ConstrainedFuzzyHashType> std::io::Write for Generator<T> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Feeding data through [`tokio::io::AsyncWrite`].
+///
+/// This is the asynchronous counterpart of the [`std::io::Write`]
+/// implementation.  Hashing itself is synchronous (each buffer is processed
+/// immediately by [`update()`](GeneratorType::update())) and writing to
+/// the generator is always ready and never fails.
+/// [`poll_flush()`](tokio::io::AsyncWrite::poll_flush()) and
+/// [`poll_shutdown()`](tokio::io::AsyncWrite::poll_shutdown()) do nothing.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::prelude::*;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
+///     eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad \
+///     minim veniam, quis nostrud exercitation ullamco laboris nisi ut \
+///     aliquip ex ea commodo consequat. Duis aute irure dolor in \
+///     reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla \
+///     pariatur. Excepteur sint occaecat cupidatat non proident, sunt in \
+///     culpa qui officia deserunt mollit anim id est laborum.";
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let generator = runtime.block_on(async {
+///     let mut reader = data;
+///     let mut generator = TlshGenerator::new();
+///     tokio::io::copy(&mut reader, &mut generator).await.unwrap();
+///     generator
+/// });
+/// let fuzzy_hash = generator.finalize().unwrap();
+/// let fuzzy_hash_str = fuzzy_hash.to_string();
+/// assert_eq!(fuzzy_hash_str, "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2");
+/// ```
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "tokio")))]
+impl<T: ConstrainedFuzzyHashType> tokio::io::AsyncWrite for Generator<T>
+where
+    Self: Unpin,
+{
+    #[inline]
+    fn poll_write(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+        buf: &[u8],
+    ) -> core::task::Poll<std::io::Result<usize>> {
+        self.get_mut().update(buf);
+        core::task::Poll::Ready(Ok(buf.len()))
+    }
+
+    #[inline(always)]
+    fn poll_flush(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<std::io::Result<()>> {
+        core::task::Poll::Ready(Ok(()))
+    }
+
+    #[inline(always)]
+    fn poll_shutdown(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<std::io::Result<()>> {
+        core::task::Poll::Ready(Ok(()))
     }
 }
 
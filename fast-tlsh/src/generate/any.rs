@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The generator with the variant selected at runtime.
+
+use crate::errors::GeneratorError;
+use crate::generate::{BucketStats, Generator, GeneratorOptions};
+use crate::hashes::{
+    AnyTlsh, Long, LongWithLongChecksum, Normal, NormalWithLongChecksum, Short, TlshVariant,
+};
+use crate::GeneratorType;
+
+/// The fuzzy hash generator with the variant selected at runtime.
+///
+/// [`GeneratorType`] is not usable as a trait object (it has associated
+/// constants and generic methods).  Instead, this type wraps generators of
+/// all variants in [`TlshVariant`] and produces [`AnyTlsh`] so that the
+/// variant can be selected at runtime (e.g. by a command line option).
+///
+/// If the variant is known at compile time, use [`Generator`] instead
+/// (it does not need to dispatch on each operation).
+///
+/// # Example
+///
+/// ```
+/// use tlsh::generate::AnyGenerator;
+/// use tlsh::hashes::TlshVariant;
+///
+/// let mut generator = tlsh::generator_for_variant(TlshVariant::Short);
+/// generator.update(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.");
+/// let hash = generator.finalize().unwrap();
+/// assert_eq!(hash.variant(), TlshVariant::Short);
+/// assert_eq!(hash.to_string(), "T182B0B1B2F964985ECA0D03302FB1E0");
+/// ```
+#[derive(Debug, Clone)]
+pub enum AnyGenerator {
+    /// The generator of [`Short`] fuzzy hashes.
+    Short(Generator<Short>),
+    /// The generator of [`Normal`] fuzzy hashes.
+    Normal(Generator<Normal>),
+    /// The generator of [`NormalWithLongChecksum`] fuzzy hashes.
+    NormalWithLongChecksum(Generator<NormalWithLongChecksum>),
+    /// The generator of [`Long`] fuzzy hashes.
+    Long(Generator<Long>),
+    /// The generator of [`LongWithLongChecksum`] fuzzy hashes.
+    LongWithLongChecksum(Generator<LongWithLongChecksum>),
+}
+
+/// Dispatches an expression to the generator of the current variant.
+macro_rules! dispatch {
+    ($self:expr, $generator:ident => $expr:expr) => {
+        match $self {
+            AnyGenerator::Short($generator) => $expr,
+            AnyGenerator::Normal($generator) => $expr,
+            AnyGenerator::NormalWithLongChecksum($generator) => $expr,
+            AnyGenerator::Long($generator) => $expr,
+            AnyGenerator::LongWithLongChecksum($generator) => $expr,
+        }
+    };
+}
+
+impl AnyGenerator {
+    /// Creates the new generator of the specified variant.
+    pub fn new(variant: TlshVariant) -> Self {
+        match variant {
+            TlshVariant::Short => Self::Short(Generator::new()),
+            TlshVariant::Normal => Self::Normal(Generator::new()),
+            TlshVariant::NormalWithLongChecksum => Self::NormalWithLongChecksum(Generator::new()),
+            TlshVariant::Long => Self::Long(Generator::new()),
+            TlshVariant::LongWithLongChecksum => Self::LongWithLongChecksum(Generator::new()),
+        }
+    }
+
+    /// Returns the variant of this generator.
+    pub const fn variant(&self) -> TlshVariant {
+        match self {
+            Self::Short(_) => TlshVariant::Short,
+            Self::Normal(_) => TlshVariant::Normal,
+            Self::NormalWithLongChecksum(_) => TlshVariant::NormalWithLongChecksum,
+            Self::Long(_) => TlshVariant::Long,
+            Self::LongWithLongChecksum(_) => TlshVariant::LongWithLongChecksum,
+        }
+    }
+
+    /// Returns the length of data processed so far
+    /// (see [`GeneratorType::processed_len()`]).
+    #[inline]
+    pub fn processed_len(&self) -> Option<u32> {
+        dispatch!(self, generator => generator.processed_len())
+    }
+
+    /// Process data, updating the internal state
+    /// (see [`GeneratorType::update()`]).
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        dispatch!(self, generator => generator.update(data))
+    }
+
+    /// Resets the generator to the initial state
+    /// (see [`GeneratorType::reset()`]).
+    #[inline]
+    pub fn reset(&mut self) {
+        dispatch!(self, generator => generator.reset())
+    }
+
+    /// Checks whether finalizing the current state with specified options
+    /// would succeed (see [`GeneratorType::is_currently_hashable()`]).
+    #[inline]
+    pub fn is_currently_hashable(&self, options: &GeneratorOptions) -> bool {
+        dispatch!(self, generator => generator.is_currently_hashable(options))
+    }
+
+    /// Returns the statistics of the buckets
+    /// (see [`GeneratorType::bucket_stats()`]).
+    #[inline]
+    pub fn bucket_stats(&self) -> BucketStats {
+        dispatch!(self, generator => generator.bucket_stats())
+    }
+
+    /// Finalize the fuzzy hash with specified options
+    /// (see [`GeneratorType::finalize_with_options()`]).
+    #[inline]
+    pub fn finalize_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<AnyTlsh, GeneratorError> {
+        dispatch!(self, generator => generator.finalize_with_options(options).map(AnyTlsh::from))
+    }
+
+    /// Finalize the fuzzy hash with the options of the inner generator
+    /// (see [`GeneratorType::finalize()`]).
+    ///
+    /// Unless the inner generator is constructed with custom options
+    /// (e.g. [`Generator::with_options()`] or
+    /// [`Generator::new_conservative()`]), the default options are used.
+    #[inline]
+    pub fn finalize(&self) -> Result<AnyTlsh, GeneratorError> {
+        dispatch!(self, generator => generator.finalize().map(AnyTlsh::from))
+    }
+}
+
+impl From<TlshVariant> for AnyGenerator {
+    #[inline(always)]
+    fn from(value: TlshVariant) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "std")))]
+impl std::io::Write for AnyGenerator {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Creates the new generator of the variant selected at runtime.
+///
+/// This is a shorthand of [`AnyGenerator::new()`].
+///
+/// # Example
+///
+/// ```
+/// use tlsh::hashes::TlshVariant;
+///
+/// let variant: &str = "long"; // e.g. from a command line option
+/// let variant = match variant {
+///     "short" => TlshVariant::Short,
+///     "long" => TlshVariant::Long,
+///     _ => TlshVariant::Normal,
+/// };
+/// let mut generator = tlsh::generator_for_variant(variant);
+/// generator.update(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.");
+/// assert_eq!(generator.finalize().unwrap().variant(), TlshVariant::Long);
+/// ```
+#[inline]
+pub fn generator_for_variant(variant: TlshVariant) -> AnyGenerator {
+    AnyGenerator::new(variant)
+}
+
+mod tests;
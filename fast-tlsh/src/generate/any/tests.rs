@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::any`].
+
+#![cfg(test)]
+
+use super::{generator_for_variant, AnyGenerator};
+
+use crate::errors::GeneratorError;
+use crate::generate::tests::LOREM_IPSUM;
+use crate::generate::{Generator, GeneratorOptions};
+use crate::hashes::{self, AnyTlsh, TlshVariant};
+use crate::GeneratorType;
+
+#[test]
+fn matches_static_generators() {
+    macro_rules! test {
+        ($ty:ident) => {
+            let mut generator = generator_for_variant(TlshVariant::$ty);
+            let mut expected_generator = Generator::<hashes::$ty>::new();
+            assert_eq!(generator.variant(), TlshVariant::$ty);
+            assert!(matches!(generator, AnyGenerator::$ty(_)));
+            assert_eq!(generator.processed_len(), Some(0));
+            generator.update(LOREM_IPSUM);
+            expected_generator.update(LOREM_IPSUM);
+            assert_eq!(
+                generator.processed_len(),
+                expected_generator.processed_len()
+            );
+            assert_eq!(generator.bucket_stats(), expected_generator.bucket_stats());
+            let options = GeneratorOptions::new();
+            assert_eq!(
+                generator.is_currently_hashable(&options),
+                expected_generator.is_currently_hashable(&options)
+            );
+            assert_eq!(
+                generator.finalize(),
+                expected_generator.finalize().map(AnyTlsh::$ty)
+            );
+            // Reset
+            generator.reset();
+            assert_eq!(generator.variant(), TlshVariant::$ty);
+            assert_eq!(generator.processed_len(), Some(0));
+            assert_eq!(generator.finalize(), Err(GeneratorError::TooSmallInput));
+            let options = GeneratorOptions::new().with_emit_null_on_failure(true);
+            assert_eq!(
+                generator.finalize_with_options(&options),
+                Generator::<hashes::$ty>::new()
+                    .finalize_with_options(&options)
+                    .map(AnyTlsh::$ty)
+            );
+        };
+    }
+    test!(Short);
+    test!(Normal);
+    test!(NormalWithLongChecksum);
+    test!(Long);
+    test!(LongWithLongChecksum);
+}
+
+#[test]
+fn all_variants() {
+    for variant in TlshVariant::ALL {
+        let mut generator = AnyGenerator::from(variant);
+        assert_eq!(generator.variant(), variant);
+        generator.update(LOREM_IPSUM);
+        let hash = generator.finalize().unwrap();
+        assert_eq!(hash.variant(), variant);
+        assert_eq!(hash.compare(&hash), Some(0));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_write() {
+    use std::io::Write;
+    for variant in TlshVariant::ALL {
+        let mut generator1 = AnyGenerator::new(variant);
+        let mut generator2 = AnyGenerator::new(variant);
+        generator1.write_all(LOREM_IPSUM).unwrap();
+        generator1.flush().unwrap();
+        generator2.update(LOREM_IPSUM);
+        assert_eq!(generator1.finalize(), generator2.finalize());
+    }
+}
+
+#[test]
+fn finalize_with_inner_options() {
+    macro_rules! test {
+        ($ty:ident) => {{
+            // The conservative mode rejects inputs in this range.
+            type G = Generator<hashes::$ty>;
+            if G::MIN < G::MIN_CONSERVATIVE {
+                let data = &LOREM_IPSUM[..G::MIN_CONSERVATIVE as usize - 1];
+                let mut expected_generator = G::new_conservative();
+                expected_generator.update(data);
+                let generator = AnyGenerator::$ty(expected_generator.clone());
+                assert_eq!(generator.finalize(), Err(GeneratorError::TooSmallInput));
+                assert_eq!(
+                    generator.finalize(),
+                    expected_generator.finalize().map(AnyTlsh::$ty)
+                );
+                // Explicit options override the stored ones.
+                assert!(generator
+                    .finalize_with_options(&GeneratorOptions::new())
+                    .is_ok());
+            }
+            // Options other than the length processing mode.
+            let options = GeneratorOptions::new().with_emit_null_on_failure(true);
+            let mut expected_generator = G::with_options(options);
+            expected_generator.update(b"ABC");
+            let generator = AnyGenerator::$ty(expected_generator.clone());
+            assert!(generator.finalize().is_ok());
+            assert_eq!(
+                generator.finalize(),
+                expected_generator.finalize().map(AnyTlsh::$ty)
+            );
+        }};
+    }
+    test!(Short);
+    test!(Normal);
+    test!(NormalWithLongChecksum);
+    test!(Long);
+    test!(LongWithLongChecksum);
+}
@@ -9,6 +9,8 @@
 //! values.
 //!
 //! Normally, the 128 bucket variant [`aggregate_128()`] is used.
+//! The `aggregate_24()` function is also available for the experimental
+//! 24 bucket variant (with the `experiment-variants` feature).
 //!
 //! # Algorithm
 //!
@@ -84,6 +86,7 @@ use std::arch::is_x86_feature_detected;
 ))]
 use std::sync::OnceLock;
 
+mod arm_neon;
 #[allow(dead_code)]
 mod portable_simd;
 mod wasm32_simd128;
@@ -156,6 +159,7 @@ pub(crate) mod naive {
     }
 
     aggregation_func_template! {
+        aggregate_24  = ( 6,  24);
         aggregate_48  = (12,  48);
         aggregate_128 = (32, 128);
         aggregate_256 = (64, 256);
@@ -223,7 +227,7 @@ macro_rules! aggregation_func_template {
                         $dispatch.get_or_init(|| {
                             #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
                             {
-                                if is_x86_feature_detected!("avx2") {
+                                if crate::simd::allows(crate::simd::Backend::Avx2) && is_x86_feature_detected!("avx2") {
                                     return &|out, buckets, q1, q2, q3| {
                                         #[allow(unsafe_code)]
                                         unsafe {
@@ -231,7 +235,7 @@ macro_rules! aggregation_func_template {
                                         }
                                     };
                                 }
-                                if is_x86_feature_detected!("ssse3") {
+                                if crate::simd::allows(crate::simd::Backend::Ssse3) && is_x86_feature_detected!("ssse3") {
                                     return &|out, buckets, q1, q2, q3| {
                                         #[allow(unsafe_code)]
                                         unsafe {
@@ -239,7 +243,7 @@ macro_rules! aggregation_func_template {
                                         }
                                     };
                                 }
-                                if is_x86_feature_detected!("sse2") {
+                                if crate::simd::allows(crate::simd::Backend::Sse2) && is_x86_feature_detected!("sse2") {
                                     return &|out, buckets, q1, q2, q3| {
                                         #[allow(unsafe_code)]
                                         unsafe {
@@ -287,6 +291,17 @@ macro_rules! aggregation_func_template {
                             x86_sse2::$name(out, buckets, q1, q2, q3)
                         }
                     }
+                    else if #[cfg(all(
+                        feature = "simd-per-arch",
+                        feature = "opt-simd-bucket-aggregation",
+                        target_arch = "aarch64",
+                        target_feature = "neon"
+                    ))] {
+                        #[allow(unsafe_code)]
+                        unsafe {
+                            arm_neon::$name(out, buckets, q1, q2, q3)
+                        }
+                    }
                     else if #[cfg(all(
                         feature = "simd-per-arch",
                         feature = "opt-simd-bucket-aggregation",
@@ -319,4 +334,20 @@ aggregation_func_template! {
     aggregate_256 = (64, 256, DISPATCH_AGGREGATE_256);
 }
 
+/// Aggregate 24 buckets into the 6-byte digest based on three quartiles
+/// (on the experimental extra short variant).
+///
+/// Because the body is short, this always uses [`naive::aggregate_24()`].
+///
+/// This function requires that:
+/// *   `q1 <= q2`
+/// *   `q2 <= q3`
+#[cfg(feature = "experiment-variants")]
+#[inline]
+pub fn aggregate_24(out: &mut [u8; 6], buckets: &[u32; 24], q1: u32, q2: u32, q3: u32) {
+    debug_assert!(q1 <= q2);
+    debug_assert!(q2 <= q3);
+    naive::aggregate_24(out, buckets, q1, q2, q3);
+}
+
 mod tests;
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! NEON/ASIMD implementation (AArch64) of TLSH bucket aggregation.
+//!
+//! This implementation handles 4 buckets at once.
+//!
+//! On packing dibits, an AArch64-specific horizontal addition instruction
+//! (`ADDV`) is utilized.
+
+#![cfg(all(
+    feature = "simd-per-arch",
+    feature = "opt-simd-bucket-aggregation",
+    target_arch = "aarch64",
+    any(doc, target_feature = "neon")
+))]
+
+#[cfg(target_arch = "aarch64")]
+use core::arch::aarch64::*;
+
+/// Aggregate 4 buckets into the 1-byte sub-digest based on three quartiles.
+///
+/// It is assumed to be:
+/// *   `q1 <= q2`
+/// *   `q2 <= q3`
+#[allow(unsafe_code)]
+#[inline(always)]
+unsafe fn sub_aggregation(buckets: &[u32], q1: u32, q2: u32, q3: u32) -> u8 {
+    assert!(buckets.len() >= 4);
+    let qv1 = vdupq_n_u32(q1);
+    let qv2 = vdupq_n_u32(q2);
+    let qv3 = vdupq_n_u32(q3);
+    // Shift amounts to place the dibit of bucket i at bits (2*i)..=(2*i+1).
+    let shifts = vld1q_s32([0i32, 2, 4, 6].as_ptr());
+    let data = vld1q_u32(buckets.as_ptr());
+    let qc1 = vcgtq_u32(data, qv1);
+    let qc2 = vcgtq_u32(data, qv2);
+    let qc3 = vcgtq_u32(data, qv3);
+    let qb1 = vandq_u32(qc2, vdupq_n_u32(0b10));
+    let qb0 = veorq_u32(veorq_u32(qc1, qc2), qc3);
+    let qb0 = vandq_u32(qb0, vdupq_n_u32(0b01));
+    let q = vorrq_u32(qb0, qb1);
+    vaddvq_u32(vshlq_u32(q, shifts)) as u8
+}
+
+/// Generates aggregation functions like [`aggregate_128()`].
+macro_rules! aggregation_func_template {
+    {$($name:ident = ($size_small:literal, $size_large:literal);)*} => {
+        $(
+            #[doc = concat!(
+                "Aggregate ",
+                stringify!($size_large),
+                " buckets into the ",
+                stringify!($size_small),
+                "-byte digest based on three quartiles.\n",
+                "\n",
+                "This function requires that:\n",
+                "*   `q1 <= q2`\n",
+                "*   `q2 <= q3`"
+            )]
+            #[allow(unsafe_code)]
+            #[inline]
+            pub(super) unsafe fn $name(
+                out: &mut [u8; $size_small],
+                buckets: &[u32; $size_large],
+                q1: u32,
+                q2: u32,
+                q3: u32
+            ) {
+                for (out, subbuckets) in out.iter_mut().rev().zip(buckets.as_slice().chunks_exact(4)) {
+                    *out = sub_aggregation(subbuckets, q1, q2, q3);
+                }
+            }
+        )*
+    }
+}
+
+aggregation_func_template! {
+    aggregate_48  = (12,  48);
+    aggregate_128 = (32, 128);
+    aggregate_256 = (64, 256);
+}
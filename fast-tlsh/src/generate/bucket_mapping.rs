@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Block-based TLSH's B (bucket) mapping.
+//!
+//! The [`b_mapping_block_48()`] and [`b_mapping_block_256()`] functions
+//! compute all bucket indices for [`BLOCK_SIZE`] consecutive sliding windows
+//! at once.
+//!
+//! # Algorithm
+//!
+//! The scalar generator computes six B (bucket) mappings per byte and
+//! increments corresponding buckets immediately.  Because each mapping
+//! consists of three dependent table lookups (plus an initialization which
+//! is constant-folded), this is bottlenecked by the latency of table lookups.
+//!
+//! Instead, the functions in this module separate the computation of bucket
+//! indices from incrementing the buckets so that the computation of multiple
+//! windows can be performed in parallel (e.g. by using gather instructions
+//! on x86 or table lookup instructions on AArch64).
+//! The caller is responsible for incrementing the buckets.
+//!
+//! Window `i` in a block consists of the bytes `window[i..i+5]` and
+//! the latest byte is `window[i+4]`.  For each window, following six
+//! B mappings are computed (in this order) as in the scalar generator:
+//!
+//! Index | Prime | Bytes (latest first)
+//! ----: | ----: | ---------------------
+//!     0 | `0x2` | `b4`, `b3`, `b2`
+//!     1 | `0x3` | `b4`, `b3`, `b1`
+//!     2 | `0x5` | `b4`, `b2`, `b1`
+//!     3 | `0x7` | `b4`, `b2`, `b0`
+//!     4 | `0xb` | `b4`, `b3`, `b0`
+//!     5 | `0xd` | `b4`, `b1`, `b0`
+//!
+//! # Testing
+//!
+//! All implementations are tested against the naïve implementation
+//! (which depends on the scalar B mapping functions).
+
+#![cfg(feature = "opt-simd-generate")]
+
+#[cfg(all(
+    feature = "simd-per-arch",
+    feature = "detect-features",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+use std::arch::is_x86_feature_detected;
+#[cfg(all(
+    feature = "simd-per-arch",
+    feature = "detect-features",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+use std::sync::OnceLock;
+
+use crate::generate::WINDOW_SIZE;
+
+mod arm_neon;
+mod x86_avx2;
+
+#[cfg(all(test, feature = "tests-slow"))]
+mod fuzzer;
+
+static_assertions::const_assert_eq!(WINDOW_SIZE, 5);
+
+/// The number of sliding windows processed at once.
+pub(crate) const BLOCK_SIZE: usize = 32;
+
+/// The number of B mappings per window.
+pub(crate) const NUM_MAPPINGS: usize = 6;
+
+/// The type to store computed bucket indices.
+///
+/// The first index denotes the kind of the B mapping (see the
+/// [module documentation](self)) and the second one denotes the window.
+pub(crate) type BucketIndices = [[u32; BLOCK_SIZE]; NUM_MAPPINGS];
+
+/// The type of the input (containing [`BLOCK_SIZE`] sliding windows).
+pub(crate) type BlockWindow = [u8; BLOCK_SIZE + WINDOW_SIZE - 1];
+
+/// The naïve implementation.
+pub(crate) mod naive {
+    use super::*;
+
+    use crate::pearson::{tlsh_b_mapping_256, tlsh_b_mapping_48};
+
+    /// Generates naïve block B mapping functions.
+    macro_rules! b_mapping_block_template {
+        {$($name:ident = $b_mapping:ident;)*} => {
+            $(
+                #[doc = concat!(
+                    "Computes bucket indices for [`BLOCK_SIZE`] windows using [`",
+                    stringify!($b_mapping),
+                    "()`]."
+                )]
+                #[inline]
+                pub fn $name(out: &mut BucketIndices, window: &BlockWindow) {
+                    for (i, w) in window.windows(WINDOW_SIZE).enumerate() {
+                        let (b0, b1, b2, b3, b4) = (w[0], w[1], w[2], w[3], w[4]);
+                        out[0][i] = $b_mapping(0x2, b4, b3, b2) as u32;
+                        out[1][i] = $b_mapping(0x3, b4, b3, b1) as u32;
+                        out[2][i] = $b_mapping(0x5, b4, b2, b1) as u32;
+                        out[3][i] = $b_mapping(0x7, b4, b2, b0) as u32;
+                        out[4][i] = $b_mapping(0xb, b4, b3, b0) as u32;
+                        out[5][i] = $b_mapping(0xd, b4, b1, b0) as u32;
+                    }
+                }
+            )*
+        }
+    }
+
+    b_mapping_block_template! {
+        b_mapping_block_48  = tlsh_b_mapping_48;
+        b_mapping_block_256 = tlsh_b_mapping_256;
+    }
+}
+
+/// Generates block B mapping functions like [`b_mapping_block_256()`].
+macro_rules! b_mapping_block_template {
+    {$($name:ident = ($buckets:literal, $dispatch:ident);)*} => {
+        $(
+            #[doc = concat!(
+                "Block B mapping function for the ",
+                stringify!($buckets),
+                "-bucket variant (to be dynamically dispatched).\n",
+                "\n",
+                "By default, this is a reference to [`naive::",
+                stringify!($name),
+                "()`].\n",
+                "\n",
+                "If the platform is detected to have specific features ",
+                "(e.g. SIMD instructions), this is overridden with a reference to the ",
+                "suitable function (or its wrapper)."
+            )]
+            #[allow(clippy::type_complexity)]
+            #[cfg(all(
+                feature = "simd-per-arch",
+                feature = "detect-features",
+                any(target_arch = "x86", target_arch = "x86_64")
+            ))]
+            static $dispatch: OnceLock<
+                &'static (dyn Fn(&mut BucketIndices, &BlockWindow) + Sync),
+            > = OnceLock::new();
+
+            #[doc = concat!(
+                "Computes bucket indices for [`BLOCK_SIZE`] windows ",
+                "on the ",
+                stringify!($buckets),
+                "-bucket variant.\n",
+                "\n",
+                "See the [module documentation](self) for details."
+            )]
+            #[inline]
+            pub(crate) fn $name(out: &mut BucketIndices, window: &BlockWindow) {
+                cfg_if::cfg_if! {
+                    if #[cfg(all(
+                        feature = "simd-per-arch",
+                        feature = "detect-features",
+                        any(target_arch = "x86", target_arch = "x86_64")
+                    ))] {
+                        // Detect runtime CPU features, cache and call
+                        $dispatch.get_or_init(|| {
+                            if crate::simd::allows(crate::simd::Backend::Avx2) && is_x86_feature_detected!("avx2") {
+                                return &|out, window| {
+                                    #[allow(unsafe_code)]
+                                    unsafe {
+                                        x86_avx2::$name(out, window)
+                                    }
+                                };
+                            }
+                            &naive::$name
+                        })(out, window)
+                    }
+                    else if #[cfg(all(
+                        feature = "simd-per-arch",
+                        not(feature = "detect-features"),
+                        any(target_arch = "x86", target_arch = "x86_64"),
+                        target_feature = "avx2"
+                    ))] {
+                        #[allow(unsafe_code)]
+                        unsafe {
+                            x86_avx2::$name(out, window)
+                        }
+                    }
+                    else if #[cfg(all(
+                        feature = "simd-per-arch",
+                        target_arch = "aarch64",
+                        target_feature = "neon"
+                    ))] {
+                        #[allow(unsafe_code)]
+                        unsafe {
+                            arm_neon::$name(out, window)
+                        }
+                    }
+                    else {
+                        naive::$name(out, window)
+                    }
+                }
+            }
+        )*
+    }
+}
+
+b_mapping_block_template! {
+    b_mapping_block_48  = ( 48, DISPATCH_B_MAPPING_BLOCK_48);
+    b_mapping_block_256 = (256, DISPATCH_B_MAPPING_BLOCK_256);
+}
+
+/// Computes bucket indices for [`BLOCK_SIZE`] windows
+/// on the experimental 24-bucket variant.
+///
+/// This is computed by folding the result of [`b_mapping_block_48()`]
+/// (see [`fold_48_to_24()`](crate::pearson::fold_48_to_24())).
+#[cfg(feature = "experiment-variants")]
+#[inline]
+pub(crate) fn b_mapping_block_24(out: &mut BucketIndices, window: &BlockWindow) {
+    b_mapping_block_48(out, window);
+    for index in out.iter_mut().flatten() {
+        *index = crate::pearson::fold_48_to_24(*index as u8) as u32;
+    }
+}
+
+mod tests;
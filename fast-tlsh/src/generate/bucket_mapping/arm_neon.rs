@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! NEON/ASIMD implementation (AArch64) of block-based TLSH's B (bucket)
+//! mapping.
+//!
+//! This implementation handles 16 windows at once.
+//!
+//! On Pearson hashing table lookups, AArch64-specific table lookup
+//! instructions (`TBL` and `TBX`) with four 128-bit registers (64 bytes) are
+//! utilized.  A 256-byte table is looked up by one `TBL` and three `TBX`
+//! instructions (each `TBX` leaves lanes with out-of-range indices
+//! unchanged).
+
+#![cfg(all(
+    feature = "simd-per-arch",
+    target_arch = "aarch64",
+    any(doc, target_feature = "neon")
+))]
+
+#[cfg(target_arch = "aarch64")]
+use core::arch::aarch64::*;
+
+use super::{BlockWindow, BucketIndices, BLOCK_SIZE};
+use crate::pearson::{init, SUBST_TABLE, SUBST_TABLE_48};
+
+static_assertions::const_assert_eq!(BLOCK_SIZE % 16, 0);
+
+/// The 256-byte table split into four 64-byte parts.
+type Table = [uint8x16x4_t; 4];
+
+/// Loads a 256-byte Pearson hashing substitution table into registers.
+#[allow(unsafe_code)]
+#[inline(always)]
+unsafe fn load_table(table: &[u8; 256]) -> Table {
+    let p = table.as_ptr();
+    let load = |offset: usize| {
+        uint8x16x4_t(
+            vld1q_u8(p.add(offset)),
+            vld1q_u8(p.add(offset + 16)),
+            vld1q_u8(p.add(offset + 32)),
+            vld1q_u8(p.add(offset + 48)),
+        )
+    };
+    [load(0), load(64), load(128), load(192)]
+}
+
+/// Looks up the 256-byte table for each lane.
+#[allow(unsafe_code)]
+#[inline(always)]
+unsafe fn lookup(table: &Table, index: uint8x16_t) -> uint8x16_t {
+    let r = vqtbl4q_u8(table[0], index);
+    let r = vqtbx4q_u8(r, table[1], vsubq_u8(index, vdupq_n_u8(64)));
+    let r = vqtbx4q_u8(r, table[2], vsubq_u8(index, vdupq_n_u8(128)));
+    vqtbx4q_u8(r, table[3], vsubq_u8(index, vdupq_n_u8(192)))
+}
+
+/// Stores 16 bucket indices (widening to [`u32`]).
+#[allow(unsafe_code)]
+#[inline(always)]
+unsafe fn store_u32x16(out: &mut [u32], value: uint8x16_t) {
+    assert!(out.len() >= 16);
+    let p = out.as_mut_ptr();
+    let lo = vmovl_u8(vget_low_u8(value));
+    let hi = vmovl_high_u8(value);
+    vst1q_u32(p, vmovl_u16(vget_low_u16(lo)));
+    vst1q_u32(p.add(4), vmovl_high_u16(lo));
+    vst1q_u32(p.add(8), vmovl_u16(vget_low_u16(hi)));
+    vst1q_u32(p.add(12), vmovl_high_u16(hi));
+}
+
+/// Computes bucket indices for [`BLOCK_SIZE`] windows using the specified
+/// table for the final Pearson hashing update.
+#[allow(unsafe_code)]
+#[inline(always)]
+unsafe fn b_mapping_block(out: &mut BucketIndices, window: &BlockWindow, final_table: &[u8; 256]) {
+    let table = load_table(&SUBST_TABLE);
+    let final_table = load_table(final_table);
+    for i in (0..BLOCK_SIZE).step_by(16) {
+        let p = window[i..].as_ptr();
+        let b0 = vld1q_u8(p);
+        let b1 = vld1q_u8(p.add(1));
+        let b2 = vld1q_u8(p.add(2));
+        let b3 = vld1q_u8(p.add(3));
+        let b4 = vld1q_u8(p.add(4));
+        macro_rules! b_mapping {
+            ($index:literal, $prime:literal, $x:ident, $y:ident) => {
+                let s = vdupq_n_u8(init($prime));
+                let s = lookup(&table, veorq_u8(s, b4));
+                let s = lookup(&table, veorq_u8(s, $x));
+                let s = lookup(&final_table, veorq_u8(s, $y));
+                store_u32x16(&mut out[$index][i..], s);
+            };
+        }
+        b_mapping!(0, 0x2, b3, b2);
+        b_mapping!(1, 0x3, b3, b1);
+        b_mapping!(2, 0x5, b2, b1);
+        b_mapping!(3, 0x7, b2, b0);
+        b_mapping!(4, 0xb, b3, b0);
+        b_mapping!(5, 0xd, b1, b0);
+    }
+}
+
+/// Computes bucket indices for [`BLOCK_SIZE`] windows
+/// on the 48-bucket variant.
+#[allow(unsafe_code)]
+#[inline(always)]
+pub(super) unsafe fn b_mapping_block_48(out: &mut BucketIndices, window: &BlockWindow) {
+    b_mapping_block(out, window, &SUBST_TABLE_48);
+}
+
+/// Computes bucket indices for [`BLOCK_SIZE`] windows
+/// on the 256-bucket variant.
+#[allow(unsafe_code)]
+#[inline(always)]
+pub(super) unsafe fn b_mapping_block_256(out: &mut BucketIndices, window: &BlockWindow) {
+    b_mapping_block(out, window, &SUBST_TABLE);
+}
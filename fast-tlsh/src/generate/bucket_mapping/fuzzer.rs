@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::{BlockWindow, BucketIndices, BLOCK_SIZE};
+
+use crate::{hashes, GeneratorType, TlshGeneratorFor};
+
+#[cfg(all(miri, fast_tlsh_tests_reduce_on_miri))]
+const ITER: usize = 1_000;
+#[cfg(not(all(miri, fast_tlsh_tests_reduce_on_miri)))]
+const ITER: usize = 1_000_000;
+
+#[cfg(all(miri, fast_tlsh_tests_reduce_on_miri))]
+const ITER_STREAMS: usize = 10;
+#[cfg(not(all(miri, fast_tlsh_tests_reduce_on_miri)))]
+const ITER_STREAMS: usize = 10_000;
+
+macro_rules! fuzz_b_mapping_block_template {
+    {$($name:ident = ($method_to_test:ident, $seed:literal, $iter:expr);)*} => {
+        $(
+            #[test]
+            fn $name() {
+                let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+                let mut window: BlockWindow = [0; BLOCK_SIZE + 4];
+                for _ in 0..$iter {
+                    rng.fill_bytes(window.as_mut_slice());
+                    let mut expected: BucketIndices = Default::default();
+                    super::naive::$method_to_test(&mut expected, &window);
+                    let mut actual: BucketIndices = Default::default();
+                    super::$method_to_test(&mut actual, &window);
+                    assert_eq!(actual, expected, "failed on window={window:?}");
+                }
+            }
+        )*
+    }
+}
+
+fuzz_b_mapping_block_template! {
+    fuzz_b_mapping_block_48  = (b_mapping_block_48,  0x6a0f3e1d27c48b95, ITER);
+    fuzz_b_mapping_block_256 = (b_mapping_block_256, 0xd3815b72e940c6af, ITER);
+}
+
+macro_rules! fuzz_generator_template {
+    {$($name:ident = ($hash_type:ty, $seed:literal, $iter:expr);)*} => {
+        $(
+            #[test]
+            fn $name() {
+                let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+                let mut data = [0u8; 4 * BLOCK_SIZE * 8];
+                for _ in 0..$iter {
+                    let len = rng.gen_range(0..=data.len());
+                    let data = &mut data[..len];
+                    rng.fill_bytes(data);
+                    // Feeding byte by byte always uses the scalar loop.
+                    let mut expected = TlshGeneratorFor::<$hash_type>::new();
+                    data.iter().for_each(|&b| expected.update(&[b]));
+                    // Feeding at once (block-based except tail-filling).
+                    let mut generator = TlshGeneratorFor::<$hash_type>::new();
+                    generator.update(data);
+                    assert_eq!(generator.inner, expected.inner, "failed on data={data:?}");
+                    // Feeding in random chunks.
+                    let mut generator = TlshGeneratorFor::<$hash_type>::new();
+                    let mut rest = &*data;
+                    while !rest.is_empty() {
+                        let chunk_len = rng.gen_range(0..=rest.len());
+                        generator.update(&rest[..chunk_len]);
+                        rest = &rest[chunk_len..];
+                    }
+                    assert_eq!(generator.inner, expected.inner, "failed on data={data:?}");
+                }
+            }
+        )*
+    }
+}
+
+fuzz_generator_template! {
+    fuzz_generator_short = (hashes::Short, 0x2c9e47a1f05b83d6, ITER_STREAMS);
+    fuzz_generator_normal = (hashes::Normal, 0x81f4d2c0b3a6e597, ITER_STREAMS);
+    fuzz_generator_long = (hashes::Long, 0x4b7a0e93d1c82f65, ITER_STREAMS);
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::bucket_mapping`].
+
+#![cfg(test)]
+
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::naive;
+use super::{b_mapping_block_256, b_mapping_block_48, BlockWindow, BucketIndices, BLOCK_SIZE};
+
+use crate::pearson::{tlsh_b_mapping_256, tlsh_b_mapping_48};
+
+#[test]
+fn naive_example() {
+    let window: BlockWindow = core::array::from_fn(|i| (i * 7 + 3) as u8);
+    let mut out: BucketIndices = Default::default();
+    naive::b_mapping_block_256(&mut out, &window);
+    for i in 0..BLOCK_SIZE {
+        let (b0, b1, b2, b3, b4) = (
+            window[i],
+            window[i + 1],
+            window[i + 2],
+            window[i + 3],
+            window[i + 4],
+        );
+        assert_eq!(out[0][i], tlsh_b_mapping_256(0x2, b4, b3, b2) as u32);
+        assert_eq!(out[1][i], tlsh_b_mapping_256(0x3, b4, b3, b1) as u32);
+        assert_eq!(out[2][i], tlsh_b_mapping_256(0x5, b4, b2, b1) as u32);
+        assert_eq!(out[3][i], tlsh_b_mapping_256(0x7, b4, b2, b0) as u32);
+        assert_eq!(out[4][i], tlsh_b_mapping_256(0xb, b4, b3, b0) as u32);
+        assert_eq!(out[5][i], tlsh_b_mapping_256(0xd, b4, b1, b0) as u32);
+    }
+    naive::b_mapping_block_48(&mut out, &window);
+    for i in 0..BLOCK_SIZE {
+        let (b0, b1, b2, b3, b4) = (
+            window[i],
+            window[i + 1],
+            window[i + 2],
+            window[i + 3],
+            window[i + 4],
+        );
+        assert_eq!(out[0][i], tlsh_b_mapping_48(0x2, b4, b3, b2) as u32);
+        assert_eq!(out[1][i], tlsh_b_mapping_48(0x3, b4, b3, b1) as u32);
+        assert_eq!(out[2][i], tlsh_b_mapping_48(0x5, b4, b2, b1) as u32);
+        assert_eq!(out[3][i], tlsh_b_mapping_48(0x7, b4, b2, b0) as u32);
+        assert_eq!(out[4][i], tlsh_b_mapping_48(0xb, b4, b3, b0) as u32);
+        assert_eq!(out[5][i], tlsh_b_mapping_48(0xd, b4, b1, b0) as u32);
+    }
+}
+
+#[test]
+fn equivalence_dispatched() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x5e0c7a1b93f2d846);
+    let mut window: BlockWindow = [0; BLOCK_SIZE + 4];
+    for _ in 0..1000 {
+        rng.fill_bytes(window.as_mut_slice());
+        let mut expected: BucketIndices = Default::default();
+        let mut actual: BucketIndices = Default::default();
+        naive::b_mapping_block_48(&mut expected, &window);
+        b_mapping_block_48(&mut actual, &window);
+        assert_eq!(actual, expected, "failed on window={window:?}");
+        naive::b_mapping_block_256(&mut expected, &window);
+        b_mapping_block_256(&mut actual, &window);
+        assert_eq!(actual, expected, "failed on window={window:?}");
+    }
+}
+
+#[cfg(all(
+    feature = "simd-per-arch",
+    feature = "detect-features",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+#[test]
+fn equivalence_x86_avx2() {
+    use super::x86_avx2;
+
+    if !std::arch::is_x86_feature_detected!("avx2") {
+        return;
+    }
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x9b2d4c6e1f3a5870);
+    let mut window: BlockWindow = [0; BLOCK_SIZE + 4];
+    for _ in 0..1000 {
+        rng.fill_bytes(window.as_mut_slice());
+        let mut expected: BucketIndices = Default::default();
+        let mut actual: BucketIndices = Default::default();
+        naive::b_mapping_block_48(&mut expected, &window);
+        #[allow(unsafe_code)]
+        unsafe {
+            x86_avx2::b_mapping_block_48(&mut actual, &window);
+        }
+        assert_eq!(actual, expected, "failed on window={window:?}");
+        naive::b_mapping_block_256(&mut expected, &window);
+        #[allow(unsafe_code)]
+        unsafe {
+            x86_avx2::b_mapping_block_256(&mut actual, &window);
+        }
+        assert_eq!(actual, expected, "failed on window={window:?}");
+    }
+}
+
+#[cfg(all(
+    feature = "simd-per-arch",
+    target_arch = "aarch64",
+    target_feature = "neon"
+))]
+#[test]
+fn equivalence_arm_neon() {
+    use super::arm_neon;
+
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x2c7f_91e3_4a06_d5b8);
+    let mut window: BlockWindow = [0; BLOCK_SIZE + 4];
+    for _ in 0..1000 {
+        rng.fill_bytes(window.as_mut_slice());
+        let mut expected: BucketIndices = Default::default();
+        let mut actual: BucketIndices = Default::default();
+        naive::b_mapping_block_48(&mut expected, &window);
+        #[allow(unsafe_code)]
+        unsafe {
+            arm_neon::b_mapping_block_48(&mut actual, &window);
+        }
+        assert_eq!(actual, expected, "failed on window={window:?}");
+        naive::b_mapping_block_256(&mut expected, &window);
+        #[allow(unsafe_code)]
+        unsafe {
+            arm_neon::b_mapping_block_256(&mut actual, &window);
+        }
+        assert_eq!(actual, expected, "failed on window={window:?}");
+    }
+}
+
+#[cfg(feature = "experiment-variants")]
+#[test]
+fn b_mapping_block_24_folded() {
+    use super::b_mapping_block_24;
+    use crate::pearson::tlsh_b_mapping_24;
+
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x3d8e_14b7_a6c0_59f2);
+    let mut window: BlockWindow = [0; BLOCK_SIZE + 4];
+    let mut out: BucketIndices = Default::default();
+    for _ in 0..1000 {
+        rng.fill_bytes(&mut window);
+        b_mapping_block_24(&mut out, &window);
+        for (i, w) in window.windows(5).enumerate() {
+            let (b0, b1, b2, b3, b4) = (w[0], w[1], w[2], w[3], w[4]);
+            assert_eq!(out[0][i], tlsh_b_mapping_24(0x2, b4, b3, b2) as u32);
+            assert_eq!(out[1][i], tlsh_b_mapping_24(0x3, b4, b3, b1) as u32);
+            assert_eq!(out[2][i], tlsh_b_mapping_24(0x5, b4, b2, b1) as u32);
+            assert_eq!(out[3][i], tlsh_b_mapping_24(0x7, b4, b2, b0) as u32);
+            assert_eq!(out[4][i], tlsh_b_mapping_24(0xb, b4, b3, b0) as u32);
+            assert_eq!(out[5][i], tlsh_b_mapping_24(0xd, b4, b1, b0) as u32);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! AVX2 implementation (x86) of block-based TLSH's B (bucket) mapping.
+//!
+//! This implementation handles 8 windows at once (using gather instructions
+//! for Pearson hashing table lookups).
+
+#![cfg(all(
+    feature = "simd-per-arch",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "detect-features", target_feature = "avx2")
+))]
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::{BlockWindow, BucketIndices, BLOCK_SIZE};
+use crate::pearson::{init, SUBST_TABLE, SUBST_TABLE_48};
+
+static_assertions::const_assert_eq!(BLOCK_SIZE % 8, 0);
+
+/// Expands a Pearson hashing substitution table to [`u32`] elements
+/// (for gather instructions).
+const fn expand_table(table: &[u8; 256]) -> [u32; 256] {
+    let mut array = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        array[i] = table[i] as u32;
+        i += 1;
+    }
+    array
+}
+
+/// Expanded [`SUBST_TABLE`].
+static SUBST_TABLE_U32: [u32; 256] = expand_table(&SUBST_TABLE);
+
+/// Expanded [`SUBST_TABLE_48`].
+static SUBST_TABLE_48_U32: [u32; 256] = expand_table(&SUBST_TABLE_48);
+
+/// Computes bucket indices for [`BLOCK_SIZE`] windows using the specified
+/// table for the final Pearson hashing update.
+#[allow(unsafe_code)]
+#[cfg_attr(not(feature = "detect-features"), inline(always))]
+#[cfg_attr(feature = "detect-features", target_feature(enable = "avx2"), inline)]
+unsafe fn b_mapping_block(out: &mut BucketIndices, window: &BlockWindow, final_table: &[u32; 256]) {
+    let table = SUBST_TABLE_U32.as_ptr() as *const i32;
+    let final_table = final_table.as_ptr() as *const i32;
+    for i in (0..BLOCK_SIZE).step_by(8) {
+        let p = window.as_ptr().add(i);
+        let b0 = _mm256_cvtepu8_epi32(_mm_loadl_epi64(p as *const __m128i));
+        let b1 = _mm256_cvtepu8_epi32(_mm_loadl_epi64(p.add(1) as *const __m128i));
+        let b2 = _mm256_cvtepu8_epi32(_mm_loadl_epi64(p.add(2) as *const __m128i));
+        let b3 = _mm256_cvtepu8_epi32(_mm_loadl_epi64(p.add(3) as *const __m128i));
+        let b4 = _mm256_cvtepu8_epi32(_mm_loadl_epi64(p.add(4) as *const __m128i));
+        macro_rules! b_mapping {
+            ($index:literal, $prime:literal, $x:ident, $y:ident) => {
+                let s = _mm256_set1_epi32(init($prime) as i32);
+                let s = _mm256_i32gather_epi32::<4>(table, _mm256_xor_si256(s, b4));
+                let s = _mm256_i32gather_epi32::<4>(table, _mm256_xor_si256(s, $x));
+                let s = _mm256_i32gather_epi32::<4>(final_table, _mm256_xor_si256(s, $y));
+                _mm256_storeu_si256(out[$index][i..].as_mut_ptr() as *mut __m256i, s);
+            };
+        }
+        b_mapping!(0, 0x2, b3, b2);
+        b_mapping!(1, 0x3, b3, b1);
+        b_mapping!(2, 0x5, b2, b1);
+        b_mapping!(3, 0x7, b2, b0);
+        b_mapping!(4, 0xb, b3, b0);
+        b_mapping!(5, 0xd, b1, b0);
+    }
+}
+
+/// Computes bucket indices for [`BLOCK_SIZE`] windows
+/// on the 48-bucket variant.
+#[allow(unsafe_code)]
+#[cfg_attr(not(feature = "detect-features"), inline(always))]
+#[cfg_attr(feature = "detect-features", target_feature(enable = "avx2"), inline)]
+pub(super) unsafe fn b_mapping_block_48(out: &mut BucketIndices, window: &BlockWindow) {
+    b_mapping_block(out, window, &SUBST_TABLE_48_U32);
+}
+
+/// Computes bucket indices for [`BLOCK_SIZE`] windows
+/// on the 256-bucket variant.
+#[allow(unsafe_code)]
+#[cfg_attr(not(feature = "detect-features"), inline(always))]
+#[cfg_attr(feature = "detect-features", target_feature(enable = "avx2"), inline)]
+pub(super) unsafe fn b_mapping_block_256(out: &mut BucketIndices, window: &BlockWindow) {
+    b_mapping_block(out, window, &SUBST_TABLE_U32);
+}
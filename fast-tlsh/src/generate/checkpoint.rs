@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The checkpoint of the generator.
+
+use crate::errors::GeneratorError;
+use crate::generate::{Generator, GeneratorOptions};
+use crate::params::{ConstrainedFuzzyHashParams, ConstrainedFuzzyHashType};
+use crate::GeneratorType;
+
+/// (fast-tlsh specific)
+/// The checkpoint of [`Generator`], captured by
+/// [`Generator::checkpoint()`].
+///
+/// A checkpoint holds the generator state at the point of capture and can be
+/// finalized later (with [`finalize()`](Self::finalize()) or
+/// [`finalize_with_options()`](Self::finalize_with_options())) or restored
+/// into a generator (with [`Generator::restore()`]).
+///
+/// The state of the generator has a fixed size (it consists of the buckets,
+/// the checksum, the processed length and a few bytes of the last window).
+/// So, neither capturing nor restoring a checkpoint allocates memory and
+/// both are simple copies.
+/// Calling [`clone()`](Clone::clone()) on the generator and finalizing it
+/// is equivalent and also supported but a checkpoint cannot be updated
+/// by mistake.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::prelude::*;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+///
+/// let mut generator = TlshGenerator::new();
+/// generator.update(data);
+/// let checkpoint = generator.checkpoint();
+/// generator.update(b"Ut enim ad minim veniam, quis nostrud exercitation ullamco \
+///     laboris nisi ut aliquip ex ea commodo consequat.");
+///
+/// // The checkpoint can be finalized later.
+/// let mut expected = TlshGenerator::new();
+/// expected.update(data);
+/// assert_eq!(checkpoint.finalize(), expected.finalize());
+///
+/// // The generator can be rewound to the checkpoint.
+/// generator.restore(&checkpoint);
+/// assert_eq!(generator.processed_len(), Some(data.len() as u32));
+/// assert_eq!(generator.finalize(), expected.finalize());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Checkpoint<T: ConstrainedFuzzyHashType> {
+    /// The copy of the inner generator.
+    pub(super) inner:
+        <<T as ConstrainedFuzzyHashType>::Params as ConstrainedFuzzyHashParams>::InnerGeneratorType,
+}
+
+impl<T: ConstrainedFuzzyHashType> Checkpoint<T> {
+    /// Returns the processed data length at the checkpoint.
+    ///
+    /// See [`GeneratorType::processed_len()`] for details.
+    #[inline(always)]
+    pub fn processed_len(&self) -> Option<u32> {
+        self.inner.processed_len()
+    }
+
+    /// Finalize the fuzzy hash at the checkpoint with specified options.
+    ///
+    /// See [`GeneratorType::finalize_with_options()`] for details.
+    #[inline(always)]
+    pub fn finalize_with_options(&self, options: &GeneratorOptions) -> Result<T, GeneratorError> {
+        self.inner.finalize_with_options(options).map(T::new)
+    }
+
+    /// Finalize the fuzzy hash at the checkpoint with the default options.
+    ///
+    /// See [`GeneratorType::finalize()`] for details.
+    #[inline(always)]
+    pub fn finalize(&self) -> Result<T, GeneratorError> {
+        self.finalize_with_options(&Default::default())
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType> Generator<T> {
+    /// (fast-tlsh specific)
+    /// Captures the current state as a [`Checkpoint`].
+    ///
+    /// See [`Checkpoint`] for details.
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint<T> {
+        Checkpoint {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// (fast-tlsh specific)
+    /// Restores the state captured as a [`Checkpoint`].
+    ///
+    /// After restoring, this generator behaves exactly as the generator
+    /// at the point the checkpoint is captured.
+    ///
+    /// See [`Checkpoint`] for details.
+    #[inline]
+    pub fn restore(&mut self, checkpoint: &Checkpoint<T>) {
+        self.inner.clone_from(&checkpoint.inner);
+    }
+}
+
+mod tests;
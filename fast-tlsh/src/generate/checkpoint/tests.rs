@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::checkpoint`].
+
+#![cfg(test)]
+
+use core::fmt::Debug;
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use crate::generate::GeneratorOptions;
+use crate::hashes;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::{GeneratorType, TlshGeneratorFor};
+
+#[test]
+fn checkpoints_on_stream() {
+    fn test<T: ConstrainedFuzzyHashType + Debug>(seed: u64) {
+        let options = GeneratorOptions::new()
+            .with_allow_small_size_files(true)
+            .with_allow_statistically_weak_buckets_quarter(true);
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let mut data = [0u8; 1024];
+        rng.fill_bytes(&mut data);
+        let mut generator = TlshGeneratorFor::<T>::new();
+        let mut pos = 0;
+        while pos < data.len() {
+            let next = rng.gen_range(pos..=data.len().min(pos + 100));
+            generator.update(&data[pos..next]);
+            pos = next;
+            let checkpoint = generator.checkpoint();
+            // Compare with the generator fed with the same prefix at once.
+            let mut expected = TlshGeneratorFor::<T>::new();
+            expected.update(&data[..pos]);
+            assert_eq!(checkpoint.processed_len(), expected.processed_len());
+            assert_eq!(checkpoint.finalize(), expected.finalize());
+            assert_eq!(
+                checkpoint.finalize_with_options(&options),
+                expected.finalize_with_options(&options)
+            );
+        }
+    }
+    test::<hashes::Short>(0x2d7a_c591_e0b3_468f);
+    test::<hashes::Normal>(0x91c4_6e0f_37a8_d25b);
+    test::<hashes::NormalWithLongChecksum>(0x5b08_f3d6_a41e_97c2);
+    test::<hashes::Long>(0xe6f3_1a7c_9052_b8d4);
+    test::<hashes::LongWithLongChecksum>(0x0c95_b24e_d8f7_613a);
+}
+
+#[test]
+fn restore_and_continue() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x7e41_d0a9_2c6b_f583);
+    let mut data = [0u8; 1024];
+    rng.fill_bytes(&mut data);
+    let (first, second) = data.split_at(500);
+    let mut generator = TlshGeneratorFor::<hashes::Normal>::new();
+    generator.update(first);
+    let checkpoint = generator.checkpoint();
+    // Diverge and then rewind (including the tail of the last window).
+    generator.update(b"ABCDEFGH");
+    generator.restore(&checkpoint);
+    assert_eq!(generator.processed_len(), Some(first.len() as u32));
+    generator.update(second);
+    let mut expected = TlshGeneratorFor::<hashes::Normal>::new();
+    expected.update(&data);
+    assert_eq!(generator.finalize(), expected.finalize());
+    // Restoring a checkpoint after reset.
+    generator.reset();
+    generator.restore(&checkpoint);
+    generator.update(second);
+    assert_eq!(generator.finalize(), expected.finalize());
+}
@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The generator skipping specified byte values.
+
+use crate::buckets::NUM_BUCKETS_LONG;
+use crate::errors::GeneratorError;
+use crate::generate::{BucketStats, Generator, GeneratorOptions, MergedChecksum, WINDOW_SIZE};
+use crate::params::ConstrainedFuzzyHashType;
+use crate::GeneratorType;
+
+/// Checks whether the bitmap `set` contains `value`.
+#[inline(always)]
+fn contains(set: &[u64; 4], value: u8) -> bool {
+    set[(value >> 6) as usize] & (1 << (value & 0x3f)) != 0
+}
+
+/// (fast-tlsh specific)
+/// The fuzzy hash generator which skips specified byte values.
+///
+/// Before processing, all bytes in the set specified by
+/// [`skip_bytes()`](Self::skip_bytes()) are removed from the input.
+/// This is useful to ignore padding (e.g. long runs of `0x00`) which
+/// normally makes the buckets statistically weak.
+///
+/// # Window Continuity
+///
+/// Skipped bytes are *dropped*, not replaced.  The sliding windows are
+/// computed over the remaining bytes as if they are adjacent, even if
+/// they are split into multiple [`update()`](GeneratorType::update()) calls.
+/// In other words, the result is always the same as the result of
+/// [`Generator`] fed with the filtered data (concatenated).
+///
+/// As a consequence, the [processed length](GeneratorType::processed_len())
+/// and the length part of the resulting fuzzy hash only count the bytes
+/// not skipped.
+///
+/// **Warning**: This is TLSH-incompatible unless no bytes are skipped.
+/// Use [`is_tlsh_compatible()`](Self::is_tlsh_compatible()) to query
+/// whether the result is compatible with the official TLSH implementation.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::generate::GeneratorWithByteFilter;
+/// use tlsh::prelude::*;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+/// let options = tlsh::GeneratorOptions::new();
+///
+/// // Skipping NUL bytes ignores the padding.
+/// let mut generator = GeneratorWithByteFilter::<Tlsh>::new().with_skip_bytes(&[0x00]);
+/// generator.update(&data[..40]);
+/// generator.update(&[0; 4096]);
+/// generator.update(&data[40..]);
+/// assert!(!generator.is_tlsh_compatible(&options));
+///
+/// let mut expected = TlshGenerator::new();
+/// expected.update(data);
+/// assert_eq!(generator.finalize(), expected.finalize());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GeneratorWithByteFilter<T: ConstrainedFuzzyHashType> {
+    /// The inner generator.
+    inner: Generator<T>,
+
+    /// The set of byte values to skip (as a bitmap).
+    skip: [u64; 4],
+}
+
+impl<T: ConstrainedFuzzyHashType> GeneratorWithByteFilter<T> {
+    /// Creates the new generator (which does not skip any bytes).
+    pub fn new() -> Self {
+        Self {
+            inner: Generator::new(),
+            skip: [0; 4],
+        }
+    }
+
+    /// Adds byte values to skip.
+    ///
+    /// The filter applies to the subsequent updates only (it does not
+    /// affect the data already processed).
+    pub fn skip_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        for &b in bytes {
+            self.skip[(b >> 6) as usize] |= 1 << (b & 0x3f);
+        }
+        self
+    }
+
+    /// Adds byte values to skip (owned builder).
+    ///
+    /// See [`skip_bytes()`](Self::skip_bytes()) for details.
+    pub fn with_skip_bytes(mut self, bytes: &[u8]) -> Self {
+        self.skip_bytes(bytes);
+        self
+    }
+
+    /// Checks whether the specified byte value is skipped.
+    #[inline(always)]
+    pub fn is_skipped(&self, value: u8) -> bool {
+        contains(&self.skip, value)
+    }
+
+    /// Query whether the result of this generator with specified options is
+    /// compatible to the official implementation of TLSH.
+    ///
+    /// It returns [`true`] only if no bytes are skipped and the options are
+    /// TLSH-compatible (see [`GeneratorOptions::is_tlsh_compatible()`]).
+    pub fn is_tlsh_compatible(&self, options: &GeneratorOptions) -> bool {
+        self.skip == [0; 4] && options.is_tlsh_compatible()
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType> Default for GeneratorWithByteFilter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType> GeneratorType for GeneratorWithByteFilter<T> {
+    type Output = T;
+
+    const IS_CHECKSUM_EFFECTIVE: bool = Generator::<T>::IS_CHECKSUM_EFFECTIVE;
+    const MIN: u32 = Generator::<T>::MIN;
+    const MIN_CONSERVATIVE: u32 = Generator::<T>::MIN_CONSERVATIVE;
+    const MAX: u32 = Generator::<T>::MAX;
+
+    #[inline(always)]
+    fn processed_len(&self) -> Option<u32> {
+        self.inner.processed_len()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        if self.skip == [0; 4] {
+            self.inner.update(data);
+            return;
+        }
+        let skip = self.skip;
+        for run in data.split(|&b| contains(&skip, b)) {
+            if !run.is_empty() {
+                self.inner.update(run);
+            }
+        }
+    }
+
+    /// Update the generator by feeding data to it, unless a part of
+    /// `data` would be ignored.
+    ///
+    /// Only the bytes not skipped count toward the 4GiB limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), GeneratorError> {
+        const MAX_INPUT_LEN: u64 = 1 << 32;
+        let len = self.processed_len().map_or(MAX_INPUT_LEN, u64::from);
+        let skip = self.skip;
+        let count = data.iter().filter(|&&b| !contains(&skip, b)).count();
+        if count as u64 > MAX_INPUT_LEN - len {
+            return Err(GeneratorError::TooLargeInput);
+        }
+        self.update(data);
+        Ok(())
+    }
+
+    /// Resets the generator to the initial state
+    /// (the set of bytes to skip is preserved).
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Merges the generator processing the second segment.
+    ///
+    /// Unlike other generators, `overlap` must contain the first
+    /// [`WINDOW_SIZE`] `- 1` bytes *not skipped* in the second segment
+    /// (or the whole second segment if it has less bytes not skipped).
+    /// Skipped bytes in `overlap` are ignored, so that passing a long enough
+    /// prefix of the second segment is sufficient.
+    ///
+    /// Both generators must skip the same set of bytes.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `overlap` does not contain enough bytes not skipped.
+    fn merge(&mut self, other: &Self, overlap: &[u8]) -> MergedChecksum {
+        debug_assert_eq!(self.skip, other.skip);
+        let mut filtered = [0u8; WINDOW_SIZE - 1];
+        let mut len = 0;
+        for &b in overlap.iter().filter(|&&b| !self.is_skipped(b)) {
+            if len == filtered.len() {
+                break;
+            }
+            filtered[len] = b;
+            len += 1;
+        }
+        self.inner.merge(&other.inner, &filtered[..len])
+    }
+
+    #[inline(always)]
+    fn finalize_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner.finalize_with_options(options)
+    }
+
+    #[inline(always)]
+    fn is_currently_hashable(&self, options: &GeneratorOptions) -> bool {
+        self.inner.is_currently_hashable(options)
+    }
+
+    #[inline(always)]
+    fn finalize_cached_with_options(
+        &mut self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner.finalize_cached_with_options(options)
+    }
+
+    #[inline(always)]
+    fn bucket_stats(&self) -> BucketStats {
+        self.inner.bucket_stats()
+    }
+
+    #[inline(always)]
+    fn histogram(&self) -> [u32; NUM_BUCKETS_LONG] {
+        self.inner.histogram()
+    }
+}
+
+mod tests;
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::filter`].
+
+#![cfg(test)]
+
+use core::fmt::Debug;
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::GeneratorWithByteFilter;
+
+use crate::errors::GeneratorError;
+use crate::generate::tests::LOREM_IPSUM;
+use crate::generate::{GeneratorOptions, MergedChecksum, WINDOW_SIZE};
+use crate::hashes;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::{FuzzyHashType, GeneratorType, TlshGeneratorFor};
+
+#[test]
+fn skip_bytes_set() {
+    let mut generator = GeneratorWithByteFilter::<hashes::Normal>::new();
+    let options = GeneratorOptions::new();
+    assert!(generator.is_tlsh_compatible(&options));
+    assert!(!generator.is_tlsh_compatible(&options.clone().with_allow_small_size_files(true)));
+    assert!((0..=u8::MAX).all(|b| !generator.is_skipped(b)));
+    generator.skip_bytes(&[0x00, 0x3f, 0x40, 0xff]);
+    generator.skip_bytes(&[0x80]);
+    for b in 0..=u8::MAX {
+        assert_eq!(
+            generator.is_skipped(b),
+            matches!(b, 0x00 | 0x3f | 0x40 | 0x80 | 0xff),
+            "failed on b={b}"
+        );
+    }
+    assert!(!generator.is_tlsh_compatible(&options));
+    // Resetting preserves the filter.
+    generator.reset();
+    assert!(generator.is_skipped(0x00));
+}
+
+#[test]
+fn no_skip_is_transparent() {
+    fn test<T: ConstrainedFuzzyHashType + Debug>() {
+        let mut generator = GeneratorWithByteFilter::<T>::new();
+        let mut expected = TlshGeneratorFor::<T>::new();
+        generator.update(LOREM_IPSUM);
+        expected.update(LOREM_IPSUM);
+        assert_eq!(generator.processed_len(), expected.processed_len());
+        assert_eq!(generator.finalize(), expected.finalize());
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn equivalent_to_filtered_data() {
+    fn test<T: ConstrainedFuzzyHashType + Debug>(seed: u64) {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let mut data = [0u8; 1024];
+        for _ in 0..50 {
+            rng.fill_bytes(&mut data);
+            // Make some runs of skipped bytes (including ones straddling
+            // the update boundaries).
+            for _ in 0..8 {
+                let start = rng.gen_range(0..data.len());
+                let end = rng.gen_range(start..=data.len().min(start + 64));
+                data[start..end].fill(0);
+            }
+            let skip = [0x00, rng.gen::<u8>(), rng.gen::<u8>()];
+            let filtered: Vec<u8> = data.iter().copied().filter(|b| !skip.contains(b)).collect();
+            let mut expected = TlshGeneratorFor::<T>::new();
+            expected.update(&filtered);
+            // Feed by random chunks.
+            let mut generator = GeneratorWithByteFilter::<T>::new().with_skip_bytes(&skip);
+            let mut rest = &data[..];
+            while !rest.is_empty() {
+                let (chunk, next) = rest.split_at(rng.gen_range(0..=rest.len().min(16)));
+                generator.update(chunk);
+                rest = next;
+            }
+            assert_eq!(generator.processed_len(), Some(filtered.len() as u32));
+            assert_eq!(generator.histogram(), expected.histogram());
+            assert_eq!(generator.finalize(), expected.finalize());
+            let options = GeneratorOptions::new()
+                .with_allow_small_size_files(true)
+                .with_allow_statistically_weak_buckets_quarter(true);
+            assert_eq!(
+                generator.finalize_with_options(&options),
+                expected.finalize_with_options(&options)
+            );
+        }
+    }
+    test::<hashes::Short>(0x8b2e_41d7_0c9f_a365);
+    test::<hashes::Normal>(0x1f6c_93a0_e54b_d728);
+    test::<hashes::NormalWithLongChecksum>(0xc4a7_0e2d_b951_36f8);
+    test::<hashes::Long>(0x5e03_b8f1_7a26_4dc9);
+    test::<hashes::LongWithLongChecksum>(0x92d5_6c1b_f04e_8a37);
+}
+
+#[test]
+fn merge_with_filter() {
+    let skip = [0x00];
+    let mut data = LOREM_IPSUM.to_vec();
+    // Insert skipped bytes around the boundary.
+    let boundary = 100;
+    data.splice(boundary..boundary, [0u8; 3]);
+    data.splice(boundary + 4..boundary + 4, [0u8; 5]);
+    let (first, second) = data.split_at(boundary);
+    let filtered: Vec<u8> = data.iter().copied().filter(|&b| b != 0).collect();
+    let mut expected = TlshGeneratorFor::<hashes::Normal>::new();
+    expected.update(&filtered);
+    let mut expected = expected.finalize().unwrap();
+    expected.clear_checksum();
+    let mut generator1 = GeneratorWithByteFilter::<hashes::Normal>::new().with_skip_bytes(&skip);
+    let mut generator2 = generator1.clone();
+    generator1.update(first);
+    generator2.update(second);
+    // The prefix of the second segment with enough non-skipped bytes.
+    let overlap = &second[..(WINDOW_SIZE - 1) + 3 + 5];
+    assert_eq!(
+        generator1.merge(&generator2, overlap),
+        MergedChecksum::Cleared
+    );
+    assert_eq!(generator1.processed_len(), Some(filtered.len() as u32));
+    assert_eq!(generator1.finalize(), Ok(expected));
+}
+
+#[test]
+#[should_panic]
+fn merge_with_filter_short_overlap() {
+    let skip = [0x00];
+    let mut generator1 = GeneratorWithByteFilter::<hashes::Normal>::new().with_skip_bytes(&skip);
+    let mut generator2 = generator1.clone();
+    generator1.update(LOREM_IPSUM);
+    generator2.update(b"\0\0\0\0ABCDEFGH");
+    // Only skipped bytes: not enough.
+    let _ = generator1.merge(&generator2, b"\0\0\0\0");
+}
+
+#[test]
+fn try_update_counts_bytes_not_skipped() {
+    let mut generator = GeneratorWithByteFilter::<hashes::Normal>::new().with_skip_bytes(&[0x00]);
+    generator.update(b"ABCD");
+    // Pretend that the generator has processed 4GiB - 4 bytes.
+    generator.inner.inner.len = u32::MAX - 3 - 4;
+    assert_eq!(generator.processed_len(), Some(u32::MAX - 3));
+    // Skipped bytes are not counted.
+    assert_eq!(generator.try_update(b"\0\0\0\0\0\0\0\0EF\0GH\0"), Ok(()));
+    assert_eq!(generator.processed_len(), None);
+    assert_eq!(generator.try_update(b"\0\0"), Ok(()));
+    assert_eq!(
+        generator.try_update(b"\0I"),
+        Err(GeneratorError::TooLargeInput)
+    );
+}
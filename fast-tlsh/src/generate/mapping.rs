@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Pluggable B (bucket) mappings for research purposes.
+
+use crate::pearson::{tlsh_b_mapping_256, tlsh_b_mapping_48};
+
+/// (fast-tlsh specific)
+/// The B (bucket) mapping to select the bucket to update from a local feature.
+///
+/// On each position of the sliding window, TLSH selects buckets to update
+/// by mapping a salt and three bytes in the window into a bucket index.
+/// By implementing this trait, you can plug a custom bucket mapping into
+/// a generator (see [`Generator::with_mapping()`](crate::generate::Generator::with_mapping())).
+///
+/// The bucket index returned by [`map()`](Self::map()) is used for all
+/// variants except the short one (which uses [`map_48()`](Self::map_48())).
+/// Bucket indices equal to or greater than the number of effective buckets
+/// are ignored (those will not be counted).
+///
+/// Note that this trait only affects the buckets.  The checksum is always
+/// computed by the TLSH's B mapping.
+///
+/// **Warning**: Fuzzy hashes generated with a custom bucket mapping are
+/// TLSH-incompatible.  This is only intended for research purposes.
+pub trait BucketMapping {
+    /// Whether this is the TLSH's B mapping.
+    ///
+    /// This is [`false`] by default and only [`TlshBucketMapping`] sets
+    /// this to [`true`].
+    const IS_TLSH_COMPATIBLE: bool = false;
+
+    /// Maps a salt (`b0`) and three bytes (`b1` is the latest byte and
+    /// others are older bytes in the window) into a bucket index.
+    fn map(b0: u8, b1: u8, b2: u8, b3: u8) -> u8;
+
+    /// Maps a salt (`b0`) and three bytes into a bucket index
+    /// for the short variant (with 48 effective buckets).
+    ///
+    /// The experimental extra short variant (with 24 effective buckets)
+    /// also uses this method and folds the result into 24 buckets.
+    ///
+    /// By default, this is the same as [`map()`](Self::map()).
+    #[inline(always)]
+    fn map_48(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+        Self::map(b0, b1, b2, b3)
+    }
+}
+
+/// (fast-tlsh specific)
+/// The TLSH's B (bucket) mapping based on Pearson hashing.
+///
+/// This is the default [`BucketMapping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TlshBucketMapping;
+
+impl BucketMapping for TlshBucketMapping {
+    const IS_TLSH_COMPATIBLE: bool = true;
+
+    #[inline(always)]
+    fn map(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+        tlsh_b_mapping_256(b0, b1, b2, b3)
+    }
+
+    #[inline(always)]
+    fn map_48(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+        tlsh_b_mapping_48(b0, b1, b2, b3)
+    }
+}
+
+mod tests;
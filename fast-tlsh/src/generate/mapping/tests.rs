@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::mapping`].
+
+#![cfg(test)]
+
+use core::fmt::Debug;
+
+use super::{BucketMapping, TlshBucketMapping};
+
+use crate::generate::tests::LOREM_IPSUM;
+use crate::generate::{Generator, GeneratorOptions};
+use crate::hashes;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::pearson::{tlsh_b_mapping_256, tlsh_b_mapping_48};
+use crate::{GeneratorType, TlshGeneratorFor};
+
+/// A custom bucket mapping (TLSH's B mapping with modified salts).
+struct XorSaltMapping;
+impl BucketMapping for XorSaltMapping {
+    fn map(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+        tlsh_b_mapping_256(b0 ^ 0x5a, b1, b2, b3)
+    }
+    fn map_48(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+        tlsh_b_mapping_48(b0 ^ 0x5a, b1, b2, b3)
+    }
+}
+
+/// A custom bucket mapping only with [`BucketMapping::map()`].
+struct SumMapping;
+impl BucketMapping for SumMapping {
+    fn map(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+        b0.wrapping_add(b1).wrapping_add(b2).wrapping_add(b3)
+    }
+}
+
+#[test]
+fn tlsh_mapping_equivalence() {
+    fn test<F: ConstrainedFuzzyHashType + Debug>() {
+        let mut generator = Generator::<F>::with_mapping::<TlshBucketMapping>();
+        let mut expected = TlshGeneratorFor::<F>::new();
+        generator.update(LOREM_IPSUM);
+        expected.update(LOREM_IPSUM);
+        assert!(generator.is_tlsh_compatible(&GeneratorOptions::new()));
+        assert_eq!(generator.bucket_stats(), expected.bucket_stats());
+        assert_eq!(generator.finalize(), expected.finalize());
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn custom_mappings() {
+    fn test<F: ConstrainedFuzzyHashType + Debug + PartialEq, M: BucketMapping>() {
+        let mut generator = Generator::<F>::with_mapping::<M>();
+        let mut expected = TlshGeneratorFor::<F>::new();
+        generator.update(LOREM_IPSUM);
+        expected.update(LOREM_IPSUM);
+        assert!(!generator.is_tlsh_compatible(&GeneratorOptions::new()));
+        let options = GeneratorOptions::new()
+            .with_allow_small_size_files(true)
+            .with_allow_statistically_weak_buckets_quarter(true);
+        let hash = generator.finalize_with_options(&options).unwrap();
+        let expected = expected.finalize_with_options(&options).unwrap();
+        // The checksum and the length are not affected by the bucket mapping.
+        let size_body = F::NUMBER_OF_BUCKETS / 4;
+        let size_cksum = F::SIZE_IN_BYTES - 2 - size_body;
+        let mut bytes = [0u8; 256];
+        let mut bytes_expected = [0u8; 256];
+        hash.store_into_bytes(&mut bytes).unwrap();
+        expected.store_into_bytes(&mut bytes_expected).unwrap();
+        assert_eq!(bytes[..size_cksum], bytes_expected[..size_cksum]);
+        assert_eq!(hash.length(), expected.length());
+        assert_ne!(
+            bytes[F::SIZE_IN_BYTES - size_body..F::SIZE_IN_BYTES],
+            bytes_expected[F::SIZE_IN_BYTES - size_body..F::SIZE_IN_BYTES]
+        );
+    }
+    test::<hashes::Short, XorSaltMapping>();
+    test::<hashes::Normal, XorSaltMapping>();
+    test::<hashes::Long, XorSaltMapping>();
+    test::<hashes::Short, SumMapping>();
+    test::<hashes::Normal, SumMapping>();
+    test::<hashes::Long, SumMapping>();
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The generator producing multiple variants of fuzzy hashes at once.
+
+use crate::buckets::{NUM_BUCKETS_NORMAL, NUM_BUCKETS_SHORT};
+use crate::errors::GeneratorError;
+use crate::generate::{Generator, GeneratorOptions};
+use crate::hash::checksum::inner::InnerChecksum;
+use crate::hash::checksum::{FuzzyHashChecksumData, CHECKSUM_SIZE_NORMAL};
+use crate::hashes;
+use crate::params::{ConstrainedFuzzyHashParams, ConstrainedFuzzyHashType};
+use crate::GeneratorType;
+
+/// The number of 256-bucket mapping values folded into 48 buckets.
+///
+/// See [`crate::pearson::tlsh_b_mapping_48()`] for details.
+const NUM_BUCKETS_FOLDED_48: usize = 256 / NUM_BUCKETS_SHORT * NUM_BUCKETS_SHORT;
+
+/// The fuzzy hash generator which produces the short, normal and long
+/// variants (all with 1-byte checksum) of the same data at once.
+///
+/// Three variants share the most of the internal state:
+///
+/// *   The 128-bucket (normal) variant uses the first 128 buckets of the
+///     256-bucket (long) variant.
+/// *   The bucket mapping of the 48-bucket (short) variant can be derived from
+///     the 256-bucket mapping (`x % 48` if the 256-bucket mapping value `x`
+///     is less than `240`; otherwise ignored).
+///     So, its buckets are computed from 256 buckets on finalization.
+/// *   The checksum of the normal variant is the same as the long one.
+///
+/// As a result, updating this generator costs about the same as updating
+/// a single long generator (plus a cheap checksum update for the short
+/// variant) while three separate generators compute bucket mappings three
+/// times.  It also requires less memory than three separate generators
+/// because it keeps only one bucket array.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::generate::MultiGenerator;
+/// use tlsh::prelude::*;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+/// let mut generator = MultiGenerator::new();
+/// generator.update(data);
+/// let hash_normal = generator.finalize_normal().unwrap();
+///
+/// let mut generator = TlshGenerator::new();
+/// generator.update(data);
+/// assert_eq!(hash_normal, generator.finalize().unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiGenerator {
+    /// The generator for the long variant.
+    ///
+    /// Its buckets and checksum are shared with other variants.
+    long: inner_type!(hashes::Long),
+
+    /// The checksum for the short variant.
+    checksum_short: FuzzyHashChecksumData<CHECKSUM_SIZE_NORMAL, NUM_BUCKETS_SHORT>,
+}
+
+impl MultiGenerator {
+    /// Creates the new generator.
+    pub fn new() -> Self {
+        Self {
+            long: Default::default(),
+            checksum_short: FuzzyHashChecksumData::new(),
+        }
+    }
+
+    /// Returns the data length it processed.
+    ///
+    /// See [`GeneratorType::processed_len()`] for details.
+    #[inline(always)]
+    pub fn processed_len(&self) -> Option<u32> {
+        self.long.processed_len()
+    }
+
+    /// Update the generator by feeding data to it.
+    pub fn update(&mut self, data: &[u8]) {
+        // Update the checksum for the short variant using the same bytes
+        // processed as the latest byte of a window by the inner generator.
+        let long = &self.long;
+        let skip = (<inner_type!(hashes::Long)>::TAIL_SIZE - long.tail_len) as usize;
+        if data.len() > skip {
+            let remaining = <inner_type!(hashes::Long)>::MAX_LEN.saturating_sub(long.len);
+            let count = (data.len() - skip).min(remaining as usize);
+            let mut prev = if skip == 0 {
+                long.tail[long.tail.len() - 1]
+            } else {
+                data[skip - 1]
+            };
+            for &curr in &data[skip..skip + count] {
+                self.checksum_short.update(curr, prev);
+                prev = curr;
+            }
+        }
+        self.long.update(data);
+    }
+
+    /// Resets the generator to the initial state.
+    pub fn reset(&mut self) {
+        self.long.reset();
+        self.checksum_short = FuzzyHashChecksumData::new();
+    }
+
+    /// Creates the generator for the normal variant from the shared state.
+    fn generator_normal(&self) -> Generator<hashes::Normal> {
+        let mut inner = <inner_type!(hashes::Normal)>::default();
+        inner.buckets.buckets[..NUM_BUCKETS_NORMAL]
+            .copy_from_slice(&self.long.buckets.data()[..NUM_BUCKETS_NORMAL]);
+        inner.len = self.long.len;
+        inner.checksum = FuzzyHashChecksumData::from_raw(self.long.checksum.data());
+        inner.tail = self.long.tail;
+        inner.tail_len = self.long.tail_len;
+        Generator {
+            inner,
+            options: GeneratorOptions::new(),
+        }
+    }
+
+    /// Creates the generator for the short variant from the shared state.
+    fn generator_short(&self) -> Generator<hashes::Short> {
+        let mut inner = <inner_type!(hashes::Short)>::default();
+        // Fold 256-bucket mapping values into 48 buckets
+        // (values 240 or greater are ignored).
+        for (i, &count) in self.long.buckets.data()[..NUM_BUCKETS_FOLDED_48]
+            .iter()
+            .enumerate()
+        {
+            let bucket = &mut inner.buckets.buckets[i % NUM_BUCKETS_SHORT];
+            *bucket = bucket.wrapping_add(count);
+        }
+        inner.len = self.long.len;
+        inner.checksum = self.checksum_short;
+        inner.tail = self.long.tail;
+        inner.tail_len = self.long.tail_len;
+        Generator {
+            inner,
+            options: GeneratorOptions::new(),
+        }
+    }
+
+    /// Finalize the short variant of the fuzzy hash with specified options.
+    pub fn finalize_short_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<hashes::Short, GeneratorError> {
+        self.generator_short().finalize_with_options(options)
+    }
+
+    /// Finalize the normal variant of the fuzzy hash with specified options.
+    pub fn finalize_normal_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<hashes::Normal, GeneratorError> {
+        self.generator_normal().finalize_with_options(options)
+    }
+
+    /// Finalize the long variant of the fuzzy hash with specified options.
+    pub fn finalize_long_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<hashes::Long, GeneratorError> {
+        self.long
+            .finalize_with_options(options)
+            .map(hashes::Long::new)
+    }
+
+    /// Finalize the short variant of the fuzzy hash with the default options.
+    #[inline(always)]
+    pub fn finalize_short(&self) -> Result<hashes::Short, GeneratorError> {
+        self.finalize_short_with_options(&Default::default())
+    }
+
+    /// Finalize the normal variant of the fuzzy hash with the default options.
+    #[inline(always)]
+    pub fn finalize_normal(&self) -> Result<hashes::Normal, GeneratorError> {
+        self.finalize_normal_with_options(&Default::default())
+    }
+
+    /// Finalize the long variant of the fuzzy hash with the default options.
+    #[inline(always)]
+    pub fn finalize_long(&self) -> Result<hashes::Long, GeneratorError> {
+        self.finalize_long_with_options(&Default::default())
+    }
+}
+
+impl Default for MultiGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod tests;
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::multi`].
+
+#![cfg(test)]
+
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::MultiGenerator;
+
+use crate::generate::tests::LOREM_IPSUM;
+use crate::generate::GeneratorOptions;
+use crate::hashes;
+use crate::{GeneratorType, TlshGeneratorFor};
+
+/// Checks that all variants match the corresponding single-variant generators.
+fn check(chunks: &[&[u8]]) {
+    let mut generator = MultiGenerator::new();
+    let mut generator_short = TlshGeneratorFor::<hashes::Short>::new();
+    let mut generator_normal = TlshGeneratorFor::<hashes::Normal>::new();
+    let mut generator_long = TlshGeneratorFor::<hashes::Long>::new();
+    for chunk in chunks {
+        generator.update(chunk);
+        generator_short.update(chunk);
+        generator_normal.update(chunk);
+        generator_long.update(chunk);
+    }
+    assert_eq!(generator.processed_len(), generator_long.processed_len());
+    let mut options_weak = GeneratorOptions::new();
+    options_weak
+        .allow_small_size_files(true)
+        .allow_statistically_weak_buckets_quarter(true);
+    for options in [&GeneratorOptions::default(), &options_weak] {
+        assert_eq!(
+            generator.finalize_short_with_options(options),
+            generator_short.finalize_with_options(options)
+        );
+        assert_eq!(
+            generator.finalize_normal_with_options(options),
+            generator_normal.finalize_with_options(options)
+        );
+        assert_eq!(
+            generator.finalize_long_with_options(options),
+            generator_long.finalize_with_options(options)
+        );
+    }
+    assert_eq!(generator.finalize_short(), generator_short.finalize());
+    assert_eq!(generator.finalize_normal(), generator_normal.finalize());
+    assert_eq!(generator.finalize_long(), generator_long.finalize());
+}
+
+#[test]
+fn equivalence_lorem_ipsum() {
+    check(&[LOREM_IPSUM]);
+    check(&LOREM_IPSUM.chunks(1).collect::<Vec<_>>());
+    check(&LOREM_IPSUM.chunks(3).collect::<Vec<_>>());
+    check(&[&LOREM_IPSUM[..2], &LOREM_IPSUM[2..]]);
+    // Small inputs
+    for len in 0..=60 {
+        check(&[&LOREM_IPSUM[..len]]);
+    }
+}
+
+#[test]
+fn equivalence_random() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x58a3c1e7f2b4d960);
+    let mut data = [0u8; 4096];
+    for _ in 0..20 {
+        rng.fill_bytes(&mut data);
+        let len = (rng.next_u32() as usize) % data.len();
+        let data = &data[..len];
+        let split = (rng.next_u32() as usize) % (len + 1);
+        check(&[&data[..split], &data[split..]]);
+    }
+}
+
+#[test]
+fn reset() {
+    let mut generator = MultiGenerator::new();
+    generator.update(b"Hello, World!");
+    generator.reset();
+    assert_eq!(generator.processed_len(), Some(0));
+    generator.update(LOREM_IPSUM);
+    let mut expected = MultiGenerator::new();
+    expected.update(LOREM_IPSUM);
+    assert_eq!(generator.finalize_short(), expected.finalize_short());
+    assert_eq!(generator.finalize_normal(), expected.finalize_normal());
+    assert_eq!(generator.finalize_long(), expected.finalize_long());
+}
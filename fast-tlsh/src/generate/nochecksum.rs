@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The generator which does not compute the checksum.
+
+use crate::buckets::NUM_BUCKETS_LONG;
+use crate::errors::GeneratorError;
+use crate::generate::inner::ChecksumlessGenerator;
+use crate::generate::{BucketStats, GeneratorOptions, MergedChecksum};
+use crate::params::{ConstrainedFuzzyHashParams, ConstrainedFuzzyHashType};
+use crate::GeneratorType;
+
+/// (fast-tlsh specific)
+/// The fuzzy hash generator which does not compute the checksum.
+///
+/// This is the same as [`Generator`](crate::generate::Generator) except that
+/// updating the checksum is skipped in the main loop.  The checksum part of
+/// the resulting fuzzy hash is always zero.
+///
+/// This is useful if the checksum is not used anyway (e.g. comparing with
+/// [`ComparisonConfiguration::NoChecksum`](crate::ComparisonConfiguration::NoChecksum)
+/// or [`ComparisonConfiguration::BodyOnly`](crate::ComparisonConfiguration::BodyOnly)).
+/// On such configurations, the comparison results are the same as
+/// the fuzzy hashes generated by [`Generator`](crate::generate::Generator).
+///
+/// Consumers can detect this generator by
+/// [`IS_CHECKSUM_EFFECTIVE`](GeneratorType::IS_CHECKSUM_EFFECTIVE)
+/// (which is [`false`] on this type).
+///
+/// **Warning**: This is TLSH-incompatible because the checksum is not
+/// computed.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::generate::GeneratorWithoutChecksum;
+/// use tlsh::hashes;
+/// use tlsh::prelude::*;
+/// use tlsh::ComparisonConfiguration;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+///
+/// let mut generator = GeneratorWithoutChecksum::<hashes::Normal>::new();
+/// generator.update(data);
+/// let hash = generator.finalize().unwrap();
+///
+/// let mut generator = TlshGenerator::new();
+/// generator.update(data);
+/// let mut expected = generator.finalize().unwrap();
+/// assert_ne!(hash, expected);
+/// assert_eq!(hash.compare_with_config(&expected, ComparisonConfiguration::NoChecksum), 0);
+///
+/// // The only difference is the checksum.
+/// expected.clear_checksum();
+/// assert_eq!(hash, expected);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GeneratorWithoutChecksum<T: ConstrainedFuzzyHashType> {
+    /// The inner generator (its checksum is never updated).
+    inner:
+        <<T as ConstrainedFuzzyHashType>::Params as ConstrainedFuzzyHashParams>::InnerGeneratorType,
+}
+
+impl<T: ConstrainedFuzzyHashType> GeneratorWithoutChecksum<T> {
+    /// Creates the new generator.
+    pub fn new() -> Self {
+        Self {
+            inner: Default::default(),
+        }
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType> Default for GeneratorWithoutChecksum<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType> GeneratorType for GeneratorWithoutChecksum<T> {
+    type Output = T;
+
+    const IS_CHECKSUM_EFFECTIVE: bool = false;
+    const MIN: u32 = <inner_type!(T)>::MIN;
+    const MIN_CONSERVATIVE: u32 = <inner_type!(T)>::MIN_CONSERVATIVE;
+    const MAX: u32 = <inner_type!(T)>::MAX;
+
+    #[inline(always)]
+    fn processed_len(&self) -> Option<u32> {
+        self.inner.processed_len()
+    }
+
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update_without_checksum(data);
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    #[inline(always)]
+    fn merge(&mut self, other: &Self, overlap: &[u8]) -> MergedChecksum {
+        // The inner generator clears the checksum on merging but
+        // the checksum is always zero on this generator anyway.
+        let _ = self.inner.merge(&other.inner, overlap);
+        MergedChecksum::Exact
+    }
+
+    #[inline(always)]
+    fn finalize_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner.finalize_with_options(options).map(T::new)
+    }
+
+    #[inline(always)]
+    fn is_currently_hashable(&self, options: &GeneratorOptions) -> bool {
+        self.inner.is_currently_hashable(options)
+    }
+
+    #[inline(always)]
+    fn finalize_cached_with_options(
+        &mut self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner.finalize_cached_with_options(options).map(T::new)
+    }
+
+    #[inline(always)]
+    fn bucket_stats(&self) -> BucketStats {
+        self.inner.bucket_stats()
+    }
+
+    #[inline(always)]
+    fn histogram(&self) -> [u32; NUM_BUCKETS_LONG] {
+        self.inner.histogram()
+    }
+}
+
+mod tests;
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::nochecksum`].
+
+#![cfg(test)]
+
+use core::fmt::Debug;
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::GeneratorWithoutChecksum;
+
+use crate::compare::ComparisonConfiguration;
+use crate::generate::tests::LOREM_IPSUM;
+use crate::generate::{GeneratorOptions, MergedChecksum, WINDOW_SIZE};
+use crate::hashes;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::{GeneratorType, TlshGeneratorFor};
+
+#[test]
+fn checksum_is_not_effective() {
+    fn test<T: ConstrainedFuzzyHashType>() {
+        assert!(TlshGeneratorFor::<T>::IS_CHECKSUM_EFFECTIVE);
+        assert!(!GeneratorWithoutChecksum::<T>::IS_CHECKSUM_EFFECTIVE);
+        assert_eq!(
+            GeneratorWithoutChecksum::<T>::MIN,
+            TlshGeneratorFor::<T>::MIN
+        );
+        assert_eq!(
+            GeneratorWithoutChecksum::<T>::MIN_CONSERVATIVE,
+            TlshGeneratorFor::<T>::MIN_CONSERVATIVE
+        );
+        assert_eq!(
+            GeneratorWithoutChecksum::<T>::MAX,
+            TlshGeneratorFor::<T>::MAX
+        );
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn equivalent_except_checksum() {
+    fn test<T: ConstrainedFuzzyHashType + Debug>(seed: u64) {
+        let options = GeneratorOptions::new()
+            .with_allow_small_size_files(true)
+            .with_allow_statistically_weak_buckets_quarter(true);
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let mut data = [0u8; 1024];
+        for _ in 0..50 {
+            rng.fill_bytes(&mut data);
+            let mut expected = TlshGeneratorFor::<T>::new();
+            expected.update(&data);
+            // Feed by random chunks.
+            let mut generator = GeneratorWithoutChecksum::<T>::new();
+            let mut rest = &data[..];
+            while !rest.is_empty() {
+                let (chunk, next) = rest.split_at(rng.gen_range(0..=rest.len().min(100)));
+                generator.update(chunk);
+                rest = next;
+            }
+            assert_eq!(generator.processed_len(), expected.processed_len());
+            assert_eq!(generator.histogram(), expected.histogram());
+            assert_eq!(generator.bucket_stats(), expected.bucket_stats());
+            let hash = generator.finalize_with_options(&options).unwrap();
+            let mut expected = expected.finalize_with_options(&options).unwrap();
+            // Comparisons ignoring the checksum are consistent.
+            for config in [
+                ComparisonConfiguration::NoChecksum,
+                ComparisonConfiguration::BodyOnly,
+            ] {
+                assert_eq!(hash.compare_with_config(&expected, config), 0);
+            }
+            // The checksum is always zero.
+            expected.clear_checksum();
+            assert_eq!(hash, expected);
+        }
+    }
+    test::<hashes::Short>(0x3b91_c7e2_54a0_6df8);
+    test::<hashes::Normal>(0xe06d_2a45_b8f3_1c97);
+    test::<hashes::NormalWithLongChecksum>(0x7c52_f9b0_13e8_a46d);
+    test::<hashes::Long>(0xa4f8_0d63_2e7b_95c1);
+    test::<hashes::LongWithLongChecksum>(0x158e_b3d9_c640_7f2a);
+}
+
+#[test]
+fn merge_without_checksum() {
+    let boundary = 100;
+    let (first, second) = LOREM_IPSUM.split_at(boundary);
+    let mut expected = GeneratorWithoutChecksum::<hashes::Normal>::new();
+    expected.update(LOREM_IPSUM);
+    let mut generator1 = GeneratorWithoutChecksum::<hashes::Normal>::new();
+    let mut generator2 = generator1.clone();
+    generator1.update(first);
+    generator2.update(second);
+    // The checksum is always zero (so that the result is exact).
+    assert_eq!(
+        generator1.merge(&generator2, &second[..WINDOW_SIZE - 1]),
+        MergedChecksum::Exact
+    );
+    assert_eq!(generator1.processed_len(), expected.processed_len());
+    assert_eq!(generator1.finalize(), expected.finalize());
+}
+
+#[test]
+fn reset_without_checksum() {
+    let mut generator = GeneratorWithoutChecksum::<hashes::Normal>::new();
+    generator.update(b"ABCDEFGH");
+    generator.reset();
+    generator.update(LOREM_IPSUM);
+    let mut expected = GeneratorWithoutChecksum::<hashes::Normal>::new();
+    expected.update(LOREM_IPSUM);
+    assert_eq!(generator.finalize(), expected.finalize());
+}
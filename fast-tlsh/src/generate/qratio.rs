@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The generator with the Q ratio computation fixed at compile time.
+
+use core::marker::PhantomData;
+
+use crate::buckets::NUM_BUCKETS_LONG;
+use crate::errors::GeneratorError;
+use crate::generate::{BucketStats, Generator, GeneratorOptions, MergedChecksum};
+use crate::params::ConstrainedFuzzyHashType;
+use crate::GeneratorType;
+
+/// The private part.
+mod private {
+    /// The sealed trait.
+    pub trait Sealed {}
+}
+
+/// The marker trait to fix the algorithm to compute Q ratio values.
+///
+/// See [`GeneratorWithQRatio`] for details.
+pub trait QRatioComputation: private::Sealed {
+    /// Whether Q ratio values are computed by pure integers.
+    ///
+    /// See [`GeneratorOptions::pure_integer_qratio_computation()`]
+    /// for details.
+    const PURE_INTEGER: bool;
+}
+
+/// The marker type to compute Q ratio values by pure integers
+/// (TLSH 4.12.1+).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QRatioInteger;
+impl private::Sealed for QRatioInteger {}
+impl QRatioComputation for QRatioInteger {
+    const PURE_INTEGER: bool = true;
+}
+
+/// The marker type to compute Q ratio values using [`f32`]
+/// (TLSH -4.12.0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QRatioFloat;
+impl private::Sealed for QRatioFloat {}
+impl QRatioComputation for QRatioFloat {
+    const PURE_INTEGER: bool = false;
+}
+
+/// Computes Q ratio values by pure integers (TLSH 4.12.1+).
+///
+/// `q3` must not be zero.
+#[inline]
+pub(crate) fn qratios_integer(q1: u32, q2: u32, q3: u32) -> (u8, u8) {
+    (
+        (((q1 as u64 * 100) / q3 as u64) % 16) as u8,
+        (((q2 as u64 * 100) / q3 as u64) % 16) as u8,
+    )
+}
+
+/// Computes Q ratio values using [`f32`] (TLSH -4.12.0).
+///
+/// Note that multiplication by `100` wraps around as in the official
+/// implementation.  `q3` must not be zero.
+#[inline]
+pub(crate) fn qratios_float(q1: u32, q2: u32, q3: u32) -> (u8, u8) {
+    (
+        (((q1.wrapping_mul(100) as f32) / q3 as f32) as u32 % 16) as u8,
+        (((q2.wrapping_mul(100) as f32) / q3 as f32) as u32 % 16) as u8,
+    )
+}
+
+/// (fast-tlsh specific)
+/// The diagnostic information of the Q ratio computation.
+///
+/// This exposes the values used to compute Q ratio values on finalization
+/// with both algorithms (see
+/// [`GeneratorOptions::pure_integer_qratio_computation()`]) to find where
+/// they diverge (e.g. when reconciling outputs of different TLSH versions).
+///
+/// This is created by [`BucketStats::qratio_diagnostics()`].
+///
+/// # Example
+///
+/// ```
+/// use tlsh::prelude::*;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+/// let mut generator = TlshGenerator::new();
+/// generator.update(data);
+/// let diag = generator.bucket_stats().qratio_diagnostics();
+/// let hash = generator.finalize().unwrap();
+/// assert_eq!(diag.integer, [hash.qratios().q1ratio(), hash.qratios().q2ratio()]);
+/// // Both algorithms agree on usual inputs.
+/// assert!(!diag.is_divergent());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QRatioDiagnostics {
+    /// The quartile values (Q1, Q2 and Q3) used to compute Q ratio values.
+    ///
+    /// If Q3 of the buckets is zero (only accepted with a TLSH-incompatible
+    /// option), all of them are substituted with `1` as on finalization.
+    pub quartiles: [u32; 3],
+    /// Whether the quartile values are substituted because Q3 is zero.
+    pub is_substituted: bool,
+    /// The numerators of the exact Q ratios (`100 * Q1` and `100 * Q2`)
+    /// before division and modulo by 16.
+    ///
+    /// The exact Q ratios are these values divided by Q3.
+    pub exact_numerators: [u64; 2],
+    /// Q1 and Q2 ratio values computed by pure integers (TLSH 4.12.1+).
+    pub integer: [u8; 2],
+    /// Q1 and Q2 ratio values computed using [`f32`] (TLSH -4.12.0).
+    pub float: [u8; 2],
+}
+
+impl QRatioDiagnostics {
+    /// Computes the diagnostic information from the bucket statistics.
+    pub fn new(stats: &BucketStats) -> Self {
+        let [q1, q2, q3] = stats.quartiles;
+        let is_substituted = q3 == 0;
+        let (q1, q2, q3) = if is_substituted {
+            (1, 1, 1)
+        } else {
+            (q1, q2, q3)
+        };
+        let integer = qratios_integer(q1, q2, q3);
+        let float = qratios_float(q1, q2, q3);
+        Self {
+            quartiles: [q1, q2, q3],
+            is_substituted,
+            exact_numerators: [q1 as u64 * 100, q2 as u64 * 100],
+            integer: [integer.0, integer.1],
+            float: [float.0, float.1],
+        }
+    }
+
+    /// Returns whether two Q ratio algorithms give different results.
+    #[inline]
+    pub fn is_divergent(&self) -> bool {
+        self.integer != self.float
+    }
+}
+
+/// The fuzzy hash generator with the Q ratio computation algorithm `Q`
+/// fixed at compile time.
+///
+/// This is the same as [`Generator`] except that
+/// [`GeneratorOptions::pure_integer_qratio_computation()`] is ignored and
+/// overridden by `Q` ([`QRatioInteger`] or [`QRatioFloat`]) on finalization.
+///
+/// This is useful to define a type alias to reproduce fuzzy hashes generated
+/// by a specific version of TLSH without forgetting to set the option.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::generate::{GeneratorWithQRatio, QRatioFloat};
+/// use tlsh::prelude::*;
+/// use tlsh::GeneratorOptions;
+///
+/// // Reproduces fuzzy hashes generated by TLSH 4.12.0 or earlier.
+/// type LegacyTlshGenerator = GeneratorWithQRatio<Tlsh, QRatioFloat>;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+/// let mut generator = LegacyTlshGenerator::new();
+/// generator.update(data);
+/// let mut expected = TlshGenerator::new();
+/// expected.update(data);
+/// let options = GeneratorOptions::new().with_pure_integer_qratio_computation(false);
+/// assert_eq!(
+///     generator.finalize(),
+///     expected.finalize_with_options(&options)
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct GeneratorWithQRatio<T: ConstrainedFuzzyHashType, Q: QRatioComputation> {
+    /// The inner generator.
+    inner: Generator<T>,
+
+    /// The marker for the Q ratio computation algorithm.
+    qratio: PhantomData<fn() -> Q>,
+}
+
+impl<T: ConstrainedFuzzyHashType, Q: QRatioComputation> GeneratorWithQRatio<T, Q> {
+    /// Creates the new generator.
+    pub fn new() -> Self {
+        Self {
+            inner: Generator::new(),
+            qratio: PhantomData,
+        }
+    }
+
+    /// Returns the options with the Q ratio computation algorithm
+    /// overridden by `Q`.
+    #[inline(always)]
+    fn fix_options(options: &GeneratorOptions) -> GeneratorOptions {
+        options
+            .clone()
+            .with_pure_integer_qratio_computation(Q::PURE_INTEGER)
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType, Q: QRatioComputation> Default for GeneratorWithQRatio<T, Q> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType, Q: QRatioComputation> GeneratorType
+    for GeneratorWithQRatio<T, Q>
+{
+    type Output = T;
+
+    const IS_CHECKSUM_EFFECTIVE: bool = Generator::<T>::IS_CHECKSUM_EFFECTIVE;
+    const MIN: u32 = Generator::<T>::MIN;
+    const MIN_CONSERVATIVE: u32 = Generator::<T>::MIN_CONSERVATIVE;
+    const MAX: u32 = Generator::<T>::MAX;
+
+    #[inline(always)]
+    fn processed_len(&self) -> Option<u32> {
+        self.inner.processed_len()
+    }
+
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    #[inline(always)]
+    fn merge(&mut self, other: &Self, overlap: &[u8]) -> MergedChecksum {
+        self.inner.merge(&other.inner, overlap)
+    }
+
+    #[inline]
+    fn finalize_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner
+            .finalize_with_options(&Self::fix_options(options))
+    }
+
+    #[inline]
+    fn is_currently_hashable(&self, options: &GeneratorOptions) -> bool {
+        self.inner
+            .is_currently_hashable(&Self::fix_options(options))
+    }
+
+    #[inline]
+    fn finalize_cached_with_options(
+        &mut self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner
+            .finalize_cached_with_options(&Self::fix_options(options))
+    }
+
+    #[inline(always)]
+    fn bucket_stats(&self) -> BucketStats {
+        self.inner.bucket_stats()
+    }
+
+    #[inline(always)]
+    fn histogram(&self) -> [u32; NUM_BUCKETS_LONG] {
+        self.inner.histogram()
+    }
+}
+
+mod tests;
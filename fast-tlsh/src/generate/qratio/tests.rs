@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::qratio`].
+
+#![cfg(test)]
+
+use core::fmt::Debug;
+
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::{
+    GeneratorWithQRatio, QRatioComputation, QRatioDiagnostics, QRatioFloat, QRatioInteger,
+};
+
+use crate::generate::{BucketStats, GeneratorOptions};
+use crate::hashes;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::{FuzzyHashType, GeneratorType, TlshGeneratorFor};
+
+#[test]
+fn fixed_qratio_computation() {
+    fn check<F: ConstrainedFuzzyHashType + Debug, Q: QRatioComputation>(data: &[u8]) {
+        let mut generator = GeneratorWithQRatio::<F, Q>::new();
+        let mut expected = TlshGeneratorFor::<F>::new();
+        generator.update(data);
+        expected.update(data);
+        assert_eq!(generator.processed_len(), expected.processed_len());
+        assert_eq!(generator.bucket_stats(), expected.bucket_stats());
+        let expected = expected.finalize_with_options(
+            &GeneratorOptions::new().with_pure_integer_qratio_computation(Q::PURE_INTEGER),
+        );
+        // The option is overridden by Q.
+        for value in [false, true] {
+            let options = GeneratorOptions::new().with_pure_integer_qratio_computation(value);
+            assert_eq!(generator.finalize_with_options(&options), expected);
+            assert_eq!(generator.finalize_cached_with_options(&options), expected);
+        }
+        assert_eq!(generator.finalize(), expected);
+    }
+    fn test<F: ConstrainedFuzzyHashType + Debug>() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x4f1a_d3b2_77c0_9e15);
+        let mut data = [0u8; 1024];
+        for _ in 0..20 {
+            rng.fill_bytes(&mut data);
+            check::<F, QRatioInteger>(&data);
+            check::<F, QRatioFloat>(&data);
+        }
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn markers() {
+    assert!(QRatioInteger::PURE_INTEGER);
+    assert!(!QRatioFloat::PURE_INTEGER);
+}
+
+#[test]
+fn diagnostics_consistency() {
+    let options_int = GeneratorOptions::new()
+        .with_allow_small_size_files(true)
+        .with_allow_statistically_weak_buckets_quarter(true);
+    let options_float = options_int
+        .clone()
+        .with_pure_integer_qratio_computation(false);
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x92f0_3b6d_c18a_e457);
+    let mut data = [0u8; 2048];
+    for len in [0, 1, 10, 50, 100, 500, 2048] {
+        for _ in 0..10 {
+            rng.fill_bytes(&mut data[..len]);
+            let mut generator = TlshGeneratorFor::<hashes::Normal>::new();
+            generator.update(&data[..len]);
+            let stats = generator.bucket_stats();
+            let diag = stats.qratio_diagnostics();
+            assert_eq!(diag, QRatioDiagnostics::new(&stats));
+            assert_eq!(diag.is_substituted, stats.quartiles[2] == 0);
+            let hash_int = generator.finalize_with_options(&options_int).unwrap();
+            let hash_float = generator.finalize_with_options(&options_float).unwrap();
+            assert_eq!(
+                diag.integer,
+                [hash_int.qratios().q1ratio(), hash_int.qratios().q2ratio()]
+            );
+            assert_eq!(
+                diag.float,
+                [
+                    hash_float.qratios().q1ratio(),
+                    hash_float.qratios().q2ratio()
+                ]
+            );
+            let q3 = diag.quartiles[2] as u64;
+            assert_eq!(diag.integer[0] as u64, diag.exact_numerators[0] / q3 % 16);
+            assert_eq!(diag.integer[1] as u64, diag.exact_numerators[1] / q3 % 16);
+        }
+    }
+}
+
+#[test]
+fn diagnostics_examples() {
+    // Zero Q3 causes substitution.
+    let diag = BucketStats {
+        nonzero: 0,
+        total: 128,
+        quartiles: [0, 0, 0],
+    }
+    .qratio_diagnostics();
+    assert!(diag.is_substituted);
+    assert_eq!(diag.quartiles, [1, 1, 1]);
+    assert_eq!(diag.exact_numerators, [100, 100]);
+    assert_eq!(diag.integer, [100 % 16, 100 % 16]);
+    assert!(!diag.is_divergent());
+    // Large quartiles: 100 * Q1 overflows on the f32-based algorithm.
+    let diag = BucketStats {
+        nonzero: 128,
+        total: 128,
+        quartiles: [50_000_000, 60_000_000, 70_000_000],
+    }
+    .qratio_diagnostics();
+    assert!(!diag.is_substituted);
+    assert_eq!(diag.exact_numerators, [5_000_000_000, 6_000_000_000]);
+    assert_eq!(diag.integer, [71 % 16, 85 % 16]);
+    // (5_000_000_000 mod 2^32) / 70_000_000 = 10.07...
+    // (6_000_000_000 mod 2^32) / 70_000_000 = 24.35...
+    assert_eq!(diag.float, [10, 24 % 16]);
+    assert!(diag.is_divergent());
+}
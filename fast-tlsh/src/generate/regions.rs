@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Fuzzy hashes of overlapping regions of a buffer.
+
+use core::iter::FusedIterator;
+
+use crate::errors::GeneratorError;
+use crate::generate::Generator;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::{GeneratorType, Tlsh};
+
+/// (fast-tlsh specific)
+/// The iterator over fuzzy hashes of (possibly overlapping) fixed-size
+/// windows of a buffer.
+///
+/// Each item is the result of hashing one window independently
+/// (as [`hash_buf_for()`](crate::hash_buf_for()) does).
+///
+/// This is created by [`windowed_hashes()`] or [`windowed_hashes_for()`].
+#[derive(Debug, Clone)]
+pub struct WindowedHashes<'a, T: ConstrainedFuzzyHashType> {
+    /// The generator reused for each window.
+    generator: Generator<T>,
+    /// The whole buffer.
+    data: &'a [u8],
+    /// The window size.
+    window: usize,
+    /// The step between the start offsets of adjacent windows.
+    step: usize,
+    /// The start offset of the next window.
+    offset: usize,
+}
+
+impl<'a, T: ConstrainedFuzzyHashType> WindowedHashes<'a, T> {
+    /// Creates an iterator over fuzzy hashes of windows.
+    fn new(data: &'a [u8], window: usize, step: usize) -> Self {
+        assert!(window != 0, "window size must be non-zero");
+        assert!(step != 0, "step must be non-zero");
+        Self {
+            generator: Generator::new(),
+            data,
+            window,
+            step,
+            offset: 0,
+        }
+    }
+
+    /// Returns the number of remaining windows.
+    fn remaining(&self) -> usize {
+        match self.data.len().checked_sub(self.window) {
+            Some(last) if self.offset <= last => (last - self.offset) / self.step + 1,
+            _ => 0,
+        }
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType> Iterator for WindowedHashes<'_, T> {
+    type Item = Result<T, GeneratorError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining() == 0 {
+            return None;
+        }
+        let start = self.offset;
+        self.offset = self.offset.saturating_add(self.step);
+        self.generator.reset();
+        self.generator
+            .update(&self.data[start..start + self.window]);
+        Some(self.generator.finalize())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType> ExactSizeIterator for WindowedHashes<'_, T> {}
+impl<T: ConstrainedFuzzyHashType> FusedIterator for WindowedHashes<'_, T> {}
+
+/// (fast-tlsh specific)
+/// Generates fuzzy hashes of fixed-size windows of a buffer
+/// (with specified output type).
+///
+/// Windows of `window` bytes start at offsets `0`, `step`, `2 * step`...
+/// and each window is hashed independently.  Windows overlap if `step` is
+/// less than `window`.  Trailing bytes which do not fill a whole window are
+/// not hashed (so nothing is generated if `data` is shorter than `window`).
+///
+/// Each item is a [`Result`] because a window may be too small or too
+/// monotonous to generate a fuzzy hash (e.g. a window smaller than the
+/// minimum input size yields [`GeneratorError::TooSmallInput`]) and
+/// such windows are still reported to keep the item index corresponding
+/// to the window offset (`index * step`).
+///
+/// This is useful to locate similar regions of large files.
+///
+/// # Panics
+///
+/// It panics if either `window` or `step` is zero.
+///
+/// # Example
+///
+/// ```
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// let data = [b"Hello, World!".as_slice(); 8].concat();
+/// let hashes: Vec<_> = tlsh::generate::windowed_hashes_for::<CustomTlsh>(&data, 52, 26)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(hashes.len(), 3);
+/// // The same content (shifted by a multiple of 13 bytes) is hashed equally.
+/// assert_eq!(hashes[0], hashes[1]);
+/// assert_eq!(hashes[1], hashes[2]);
+/// ```
+#[inline]
+pub fn windowed_hashes_for<T: ConstrainedFuzzyHashType>(
+    data: &[u8],
+    window: usize,
+    step: usize,
+) -> WindowedHashes<'_, T> {
+    WindowedHashes::new(data, window, step)
+}
+
+/// (fast-tlsh specific)
+/// Generates fuzzy hashes of fixed-size windows of a buffer.
+///
+/// See [`windowed_hashes_for()`] for details.
+///
+/// # Panics
+///
+/// It panics if either `window` or `step` is zero.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::GeneratorError;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+///
+/// // Windows smaller than the minimum input size cannot be hashed.
+/// let mut windows = tlsh::generate::windowed_hashes(data, 32, 16);
+/// assert_eq!(windows.len(), 6);
+/// assert!(windows.all(|hash| hash == Err(GeneratorError::TooSmallInput)));
+///
+/// // Nothing is generated if the data is shorter than the window.
+/// assert_eq!(tlsh::generate::windowed_hashes(data, 4096, 16).count(), 0);
+/// ```
+#[inline]
+pub fn windowed_hashes(data: &[u8], window: usize, step: usize) -> WindowedHashes<'_, Tlsh> {
+    windowed_hashes_for::<Tlsh>(data, window, step)
+}
+
+mod tests;
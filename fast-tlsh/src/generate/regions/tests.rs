@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::regions`].
+
+#![cfg(test)]
+
+use core::fmt::Debug;
+
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::{windowed_hashes, windowed_hashes_for};
+
+use crate::generate::tests::LOREM_IPSUM;
+use crate::generate::Generator;
+use crate::hashes;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::{GeneratorError, GeneratorType};
+
+#[test]
+fn windowed_hashes_equivalence() {
+    fn check<T: ConstrainedFuzzyHashType + Debug + PartialEq>(seed: u64) {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        let mut data = [0u8; 2000];
+        rng.fill_bytes(&mut data);
+        for (window, step) in [
+            (1, 1),
+            (50, 7),
+            (256, 64),
+            (256, 256),
+            (300, 500),
+            (1999, 1),
+            (2000, 1),
+            (2000, 2000),
+        ] {
+            let mut count = 0;
+            let mut iter = windowed_hashes_for::<T>(&data, window, step);
+            let expected_len = (data.len() - window) / step + 1;
+            assert_eq!(iter.len(), expected_len);
+            for (index, hash) in iter.by_ref().enumerate() {
+                let offset = index * step;
+                let mut generator = Generator::<T>::new();
+                generator.update(&data[offset..offset + window]);
+                assert_eq!(hash, generator.finalize());
+                count += 1;
+            }
+            assert_eq!(count, expected_len);
+            assert_eq!(iter.len(), 0);
+            assert_eq!(iter.next(), None);
+        }
+        // No windows if the data is shorter than the window.
+        assert_eq!(windowed_hashes_for::<T>(&data, 2001, 1).len(), 0);
+        assert_eq!(windowed_hashes_for::<T>(&[], 1, 1).len(), 0);
+    }
+    #[cfg(feature = "experiment-variants")]
+    check::<hashes::ExtraShort>(0x1c8e_47a2_d06b_f593);
+    check::<hashes::Short>(0x7b35_e09d_24c1_a8f6);
+    check::<hashes::Normal>(0xe4a0_6c5f_b817_3d92);
+    check::<hashes::NormalWithLongChecksum>(0x52d9_f3b0_8e4a_1c67);
+    check::<hashes::Long>(0xa16f_2d84_c95e_07b3);
+    check::<hashes::LongWithLongChecksum>(0x3f07_b6e1_5a29_d84c);
+}
+
+#[test]
+fn windowed_hashes_small_windows() {
+    // Windows smaller than the minimum length are reported as errors.
+    assert!(
+        windowed_hashes(LOREM_IPSUM, 49, 1).all(|hash| hash == Err(GeneratorError::TooSmallInput))
+    );
+    // The whole buffer as a single window.
+    let mut generator = Generator::new();
+    generator.update(LOREM_IPSUM);
+    let expected = generator.finalize();
+    assert!(expected.is_ok());
+    let mut hashes = windowed_hashes(LOREM_IPSUM, LOREM_IPSUM.len(), 1);
+    assert_eq!(hashes.next(), Some(expected));
+    assert_eq!(hashes.next(), None);
+}
+
+#[test]
+#[should_panic]
+fn windowed_hashes_zero_window() {
+    let _ = windowed_hashes(LOREM_IPSUM, 0, 1);
+}
+
+#[test]
+#[should_panic]
+fn windowed_hashes_zero_step() {
+    let _ = windowed_hashes(LOREM_IPSUM, 1, 0);
+}
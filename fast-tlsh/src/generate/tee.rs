@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The generator which also feeds data to another digest.
+
+#![cfg(feature = "digest")]
+
+use digest::Update;
+
+use crate::errors::GeneratorError;
+use crate::generate::GeneratorOptions;
+use crate::GeneratorType;
+
+/// (fast-tlsh specific)
+/// The fuzzy hash generator which feeds the same data to another digest.
+///
+/// This is useful to compute both a fuzzy hash and another digest (such as
+/// SHA-256) of the same stream in one pass, without reading large files
+/// twice.  The fuzzy hash is the same as the one generated by the inner
+/// generator alone.
+///
+/// The digest is any type implementing [`digest::Update`] (e.g. `Sha256`
+/// from the `sha2` crate).  This type is only available with the `digest`
+/// feature.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::generate::TeeGenerator;
+/// use tlsh::prelude::*;
+///
+/// /// A (non-cryptographic) byte counter as an example digest.
+/// #[derive(Default)]
+/// struct ByteCount(usize);
+/// impl digest::Update for ByteCount {
+///     fn update(&mut self, data: &[u8]) {
+///         self.0 += data.len();
+///     }
+/// }
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+/// let mut generator = TeeGenerator::new(TlshGenerator::new(), ByteCount::default());
+/// generator.update(data);
+/// let (hash, count) = generator.finalize_with_digest();
+/// assert_eq!(count.0, data.len());
+///
+/// let mut generator = TlshGenerator::new();
+/// generator.update(data);
+/// assert_eq!(hash, generator.finalize());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TeeGenerator<G: GeneratorType, D: Update> {
+    /// The fuzzy hash generator.
+    generator: G,
+
+    /// The digest updated with the same data.
+    digest: D,
+}
+
+impl<G: GeneratorType, D: Update> TeeGenerator<G, D> {
+    /// Creates the new generator from the fuzzy hash generator and
+    /// the digest.
+    pub fn new(generator: G, digest: D) -> Self {
+        Self { generator, digest }
+    }
+
+    /// Returns the reference to the fuzzy hash generator.
+    #[inline(always)]
+    pub fn generator(&self) -> &G {
+        &self.generator
+    }
+
+    /// Returns the reference to the digest.
+    #[inline(always)]
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Returns the data length processed by the fuzzy hash generator.
+    ///
+    /// See [`GeneratorType::processed_len()`] for details.
+    #[inline(always)]
+    pub fn processed_len(&self) -> Option<u32> {
+        self.generator.processed_len()
+    }
+
+    /// Update both the fuzzy hash generator and the digest by feeding data.
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        self.generator.update(data);
+        Update::update(&mut self.digest, data);
+    }
+
+    /// Finalize the fuzzy hash with specified options.
+    ///
+    /// The digest is not affected.
+    ///
+    /// See [`GeneratorType::finalize_with_options()`] for details.
+    #[inline]
+    pub fn finalize_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<G::Output, GeneratorError> {
+        self.generator.finalize_with_options(options)
+    }
+
+    /// Finalize the fuzzy hash with the default options.
+    ///
+    /// The digest is not affected.
+    #[inline]
+    pub fn finalize(&self) -> Result<G::Output, GeneratorError> {
+        self.generator.finalize()
+    }
+
+    /// Finalize the fuzzy hash with specified options and
+    /// return it with the digest (to be finalized by the caller).
+    pub fn finalize_with_digest_and_options(
+        self,
+        options: &GeneratorOptions,
+    ) -> (Result<G::Output, GeneratorError>, D) {
+        (self.generator.finalize_with_options(options), self.digest)
+    }
+
+    /// Finalize the fuzzy hash with the default options and
+    /// return it with the digest (to be finalized by the caller).
+    pub fn finalize_with_digest(self) -> (Result<G::Output, GeneratorError>, D) {
+        self.finalize_with_digest_and_options(&GeneratorOptions::default())
+    }
+
+    /// Decomposes this object into the fuzzy hash generator and the digest.
+    pub fn into_parts(self) -> (G, D) {
+        (self.generator, self.digest)
+    }
+}
+
+mod tests;
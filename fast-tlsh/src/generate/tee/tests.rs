@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::tee`].
+
+#![cfg(test)]
+
+use std::vec::Vec;
+
+use digest::Update;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::TeeGenerator;
+
+use crate::generate::tests::LOREM_IPSUM;
+use crate::generate::GeneratorOptions;
+use crate::hashes;
+use crate::{GeneratorType, TlshGeneratorFor};
+
+/// The 64-bit FNV-1a hash as an example digest.
+#[derive(Debug, Clone)]
+struct Fnv1a64(u64);
+
+impl Fnv1a64 {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Update for Fnv1a64 {
+    fn update(&mut self, data: &[u8]) {
+        for &b in data {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+/// The digest which collects all data.
+#[derive(Debug, Clone, Default)]
+struct Collect(Vec<u8>);
+
+impl Update for Collect {
+    fn update(&mut self, data: &[u8]) {
+        self.0.extend_from_slice(data);
+    }
+}
+
+#[test]
+fn fnv1a64_example() {
+    let mut digest = Fnv1a64::new();
+    Update::update(&mut digest, b"a");
+    assert_eq!(digest.0, 0xaf63_dc4c_8601_ec8c);
+}
+
+#[test]
+fn output_unchanged() {
+    let options = GeneratorOptions::new()
+        .with_allow_small_size_files(true)
+        .with_allow_statistically_weak_buckets_quarter(true);
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x6b1e_d043_a9f2_57c8);
+    let mut data = [0u8; 4096];
+    for _ in 0..30 {
+        rng.fill_bytes(&mut data);
+        let mut expected = TlshGeneratorFor::<hashes::Normal>::new();
+        expected.update(&data);
+        let mut expected_digest = Fnv1a64::new();
+        Update::update(&mut expected_digest, &data);
+        // Feed by random chunks.
+        let mut generator =
+            TeeGenerator::new(TlshGeneratorFor::<hashes::Normal>::new(), Fnv1a64::new());
+        let mut rest = &data[..];
+        while !rest.is_empty() {
+            let (chunk, next) = rest.split_at(rng.gen_range(0..=rest.len().min(500)));
+            generator.update(chunk);
+            rest = next;
+        }
+        assert_eq!(generator.processed_len(), expected.processed_len());
+        assert_eq!(generator.generator().histogram(), expected.histogram());
+        assert_eq!(generator.digest().0, expected_digest.0);
+        assert_eq!(
+            generator.finalize_with_options(&options),
+            expected.finalize_with_options(&options)
+        );
+        let (hash, digest) = generator.finalize_with_digest_and_options(&options);
+        assert_eq!(hash, expected.finalize_with_options(&options));
+        assert_eq!(digest.0, expected_digest.0);
+    }
+}
+
+#[test]
+fn into_parts() {
+    let mut generator =
+        TeeGenerator::new(TlshGeneratorFor::<hashes::Long>::new(), Collect::default());
+    for chunk in LOREM_IPSUM.chunks(7) {
+        generator.update(chunk);
+    }
+    let (inner, digest) = generator.into_parts();
+    assert_eq!(digest.0, LOREM_IPSUM);
+    let mut expected = TlshGeneratorFor::<hashes::Long>::new();
+    expected.update(LOREM_IPSUM);
+    assert_eq!(inner.finalize(), expected.finalize());
+}
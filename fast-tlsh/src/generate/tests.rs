@@ -5,7 +5,10 @@
 
 #![cfg(test)]
 
-use super::{ConstrainedFuzzyHashType, GeneratorOptions, GeneratorType, WINDOW_SIZE};
+use super::{
+    BucketStats, ConstrainedFuzzyHashType, GeneratorOptions, GeneratorType, MergedChecksum,
+    Quality, WINDOW_SIZE,
+};
 
 use core::fmt::Debug;
 use core::str::FromStr;
@@ -137,6 +140,9 @@ fn generator_options_compatibility() {
     let mut options = base_options.clone();
     let options = options.pure_integer_qratio_computation(false);
     assert!(options.is_tlsh_compatible());
+    let mut options = base_options.clone();
+    let options = options.emit_null_on_failure(false);
+    assert!(options.is_tlsh_compatible());
     // Incompatible with the official implementation:
     let mut options = base_options.clone();
     let options = options.allow_small_size_files(true);
@@ -147,12 +153,115 @@ fn generator_options_compatibility() {
     let mut options = base_options.clone();
     let options = options.allow_statistically_weak_buckets_quarter(true);
     assert!(!options.is_tlsh_compatible());
+    let mut options = base_options.clone();
+    let options = options.emit_null_on_failure(true);
+    assert!(!options.is_tlsh_compatible());
     // Compatible with the official implementation:
     let mut options = base_options.clone();
     let options = options.pure_integer_qratio_computation(true);
     assert!(options.is_tlsh_compatible());
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn generator_options_incompatibility_report() {
+    let options = GeneratorOptions::new()
+        .with_length_processing_mode(DataLengthProcessingMode::Optimistic)
+        .with_pure_integer_qratio_computation(true);
+    assert!(options.incompatibility_report().is_empty());
+    let options = GeneratorOptions::new().with_allow_small_size_files(true);
+    let report = options.incompatibility_report();
+    assert_eq!(report.len(), 1);
+    assert!(report[0].starts_with("small-size-files: "));
+    let options = GeneratorOptions::new().with_allow_statistically_weak_buckets_quarter(true);
+    let report = options.incompatibility_report();
+    assert_eq!(report.len(), 1);
+    assert!(report[0].starts_with("weak-buckets-quarter: "));
+    let options = GeneratorOptions::new()
+        .with_allow_small_size_files(true)
+        .with_allow_statistically_weak_buckets_half(true)
+        .with_allow_statistically_weak_buckets_quarter(true)
+        .with_emit_null_on_failure(true);
+    let report = options.incompatibility_report();
+    assert_eq!(
+        report
+            .iter()
+            .map(|x| x.split(':').next().unwrap())
+            .collect::<Vec<_>>(),
+        [
+            "small-size-files",
+            "weak-buckets-half",
+            "weak-buckets-quarter",
+            "emit-null-on-failure"
+        ]
+    );
+    assert_eq!(report.len(), 4);
+    assert!(!options.is_tlsh_compatible());
+}
+
+#[test]
+fn generator_options_owned_builder() {
+    let base_options = GeneratorOptions::new();
+    let mut expected = base_options.clone();
+    expected.length_processing_mode(DataLengthProcessingMode::Conservative);
+    assert_eq!(
+        base_options
+            .clone()
+            .with_length_processing_mode(DataLengthProcessingMode::Conservative),
+        expected
+    );
+    for value in [false, true] {
+        let mut expected = base_options.clone();
+        expected.pure_integer_qratio_computation(value);
+        assert_eq!(
+            base_options
+                .clone()
+                .with_pure_integer_qratio_computation(value),
+            expected
+        );
+        let mut expected = base_options.clone();
+        expected.allow_small_size_files(value);
+        assert_eq!(
+            base_options.clone().with_allow_small_size_files(value),
+            expected
+        );
+        let mut expected = base_options.clone();
+        expected.allow_statistically_weak_buckets_half(value);
+        assert_eq!(
+            base_options
+                .clone()
+                .with_allow_statistically_weak_buckets_half(value),
+            expected
+        );
+        let mut expected = base_options.clone();
+        expected.allow_statistically_weak_buckets_quarter(value);
+        assert_eq!(
+            base_options
+                .clone()
+                .with_allow_statistically_weak_buckets_quarter(value),
+            expected
+        );
+        let mut expected = base_options.clone();
+        expected.emit_null_on_failure(value);
+        assert_eq!(
+            base_options.clone().with_emit_null_on_failure(value),
+            expected
+        );
+    }
+    // Chaining
+    let mut expected = base_options.clone();
+    expected
+        .length_processing_mode(DataLengthProcessingMode::Optimistic)
+        .allow_small_size_files(true)
+        .allow_statistically_weak_buckets_quarter(true);
+    let options = base_options
+        .with_length_processing_mode(DataLengthProcessingMode::Optimistic)
+        .with_allow_small_size_files(true)
+        .with_allow_statistically_weak_buckets_quarter(true);
+    assert_eq!(options, expected);
+    assert!(!options.is_tlsh_compatible());
+}
+
 #[test]
 fn tlsh_timing_unittest_vector() {
     // Displayed in the official implementation's timing_unittest.
@@ -215,6 +324,643 @@ fn generator_update_strategies() {
     }
 }
 
+#[test]
+fn generator_random_streams() {
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    // Feeding data at once (which may be processed block by block) and
+    // feeding byte by byte (always processed by the scalar loop) must result
+    // in the same internal state.
+    macro_rules! test {
+        ($hash_type:ty, $seed:literal) => {
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut data = [0u8; 1024];
+            for _ in 0..100 {
+                let len = rng.gen_range(0..=data.len());
+                let data = &mut data[..len];
+                rng.fill_bytes(data);
+                let mut expected = TlshGeneratorFor::<$hash_type>::new();
+                data.iter().for_each(|&b| expected.update(&[b]));
+                let mut generator = TlshGeneratorFor::<$hash_type>::new();
+                generator.update(data);
+                assert_eq!(generator.inner, expected.inner, "failed on data={data:?}");
+                let mut generator = TlshGeneratorFor::<$hash_type>::new();
+                let (first, second) = data.split_at(rng.gen_range(0..=len));
+                generator.update(first);
+                generator.update(second);
+                assert_eq!(generator.inner, expected.inner, "failed on data={data:?}");
+            }
+        };
+    }
+    test!(hashes::Short, 0x7f31c0a5e2d84b96);
+    test!(hashes::Normal, 0x1ad5b8e0c4f26379);
+    test!(hashes::Long, 0xe8062d4b97a1f35c);
+}
+
+#[test]
+fn generator_reset() {
+    fn test<F: ConstrainedFuzzyHashType + Debug>() {
+        let mut fresh = TlshGeneratorFor::<F>::new();
+        fresh.update(LOREM_IPSUM);
+        let expected = fresh.finalize().unwrap();
+        let mut generator = TlshGeneratorFor::<F>::new();
+        generator.update(b"Hello, World!");
+        generator.update(&LOREM_IPSUM[..LOREM_IPSUM.len() / 2]);
+        generator.reset();
+        assert_eq!(generator.processed_len(), Some(0));
+        generator.update(LOREM_IPSUM);
+        assert_eq!(generator.finalize(), Ok(expected));
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+    // Internal state is identical to the new one.
+    let mut generator = TlshGenerator::new();
+    generator.update(LOREM_IPSUM);
+    generator.reset();
+    assert_eq!(generator.inner, TlshGenerator::new().inner);
+}
+
+#[test]
+fn generator_merge() {
+    fn test<F: ConstrainedFuzzyHashType + Debug>() {
+        let mut generator = TlshGeneratorFor::<F>::new();
+        generator.update(LOREM_IPSUM);
+        let mut expected = generator.finalize().unwrap();
+        let expected_with_checksum = expected.clone();
+        expected.clear_checksum();
+        for offset in 0..=LOREM_IPSUM.len() {
+            let (first, second) = LOREM_IPSUM.split_at(offset);
+            let overlap = &second[..second.len().min(WINDOW_SIZE - 1)];
+            let mut generator1 = TlshGeneratorFor::<F>::new();
+            let mut generator2 = TlshGeneratorFor::<F>::new();
+            generator1.update(first);
+            generator2.update(second);
+            let checksum = generator1.merge(&generator2, overlap);
+            assert_eq!(
+                generator1.processed_len(),
+                Some(LOREM_IPSUM.len() as u32),
+                "failed on offset={offset}"
+            );
+            let hash = generator1.finalize().unwrap();
+            if offset == 0 || offset == LOREM_IPSUM.len() {
+                // The checksum is preserved if either one is empty.
+                assert_eq!(checksum, MergedChecksum::Exact, "failed on offset={offset}");
+                assert_eq!(hash, expected_with_checksum, "failed on offset={offset}");
+            } else {
+                assert_eq!(
+                    checksum,
+                    MergedChecksum::Cleared,
+                    "failed on offset={offset}"
+                );
+                assert_eq!(hash, expected, "failed on offset={offset}");
+            }
+        }
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+#[should_panic]
+fn generator_merge_wrong_overlap() {
+    let mut generator1 = TlshGenerator::new();
+    let mut generator2 = TlshGenerator::new();
+    generator1.update(&LOREM_IPSUM[..100]);
+    generator2.update(&LOREM_IPSUM[100..]);
+    let _ = generator1.merge(&generator2, &LOREM_IPSUM[100..103]);
+}
+
+#[test]
+fn generator_update_iter() {
+    fn test<F: ConstrainedFuzzyHashType + Debug>() {
+        let mut data = Vec::new();
+        while data.len() < super::UPDATE_ITER_CHUNK_SIZE * 3 + 1 {
+            data.extend_from_slice(LOREM_IPSUM);
+        }
+        for len in [
+            0,
+            1,
+            WINDOW_SIZE,
+            super::UPDATE_ITER_CHUNK_SIZE - 1,
+            super::UPDATE_ITER_CHUNK_SIZE,
+            super::UPDATE_ITER_CHUNK_SIZE + 1,
+            super::UPDATE_ITER_CHUNK_SIZE * 3,
+            super::UPDATE_ITER_CHUNK_SIZE * 3 + 1,
+        ] {
+            let data = &data[..len];
+            let mut generator1 = TlshGeneratorFor::<F>::new();
+            let mut generator2 = TlshGeneratorFor::<F>::new();
+            generator1.update(&LOREM_IPSUM[..3]);
+            generator2.update(&LOREM_IPSUM[..3]);
+            generator1.update(data);
+            generator2.update_iter(data.iter().copied());
+            assert_eq!(generator1.processed_len(), generator2.processed_len());
+            assert_eq!(generator1.bucket_stats(), generator2.bucket_stats());
+            assert_eq!(
+                generator1.finalize(),
+                generator2.finalize(),
+                "failed on len={len}"
+            );
+        }
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn generator_emit_null_on_failure() {
+    fn test<F: ConstrainedFuzzyHashType + Debug>() {
+        let options = GeneratorOptions::new().with_emit_null_on_failure(true);
+        // Too small input
+        let mut generator = TlshGeneratorFor::<F>::new();
+        generator.update(b"Hello!");
+        assert_eq!(generator.finalize(), Err(GeneratorError::TooSmallInput));
+        assert!(generator.finalize_with_options(&options).unwrap().is_null());
+        assert!(generator
+            .finalize_cached_with_options(&options)
+            .unwrap()
+            .is_null());
+        // Statistically weak buckets
+        let mut generator = TlshGeneratorFor::<F>::new();
+        generator.update(&[b'A'; 4096]);
+        assert!(matches!(
+            generator.finalize(),
+            Err(GeneratorError::BucketsAreHalfEmpty | GeneratorError::BucketsAreThreeQuarterEmpty)
+        ));
+        assert!(generator.finalize_with_options(&options).unwrap().is_null());
+        // Valid input (not affected)
+        let mut generator = TlshGeneratorFor::<F>::new();
+        generator.update(LOREM_IPSUM);
+        let hash = generator.finalize_with_options(&options).unwrap();
+        assert!(!hash.is_null());
+        assert_eq!(Ok(hash), generator.finalize());
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn generator_bucket_stats_quartiles() {
+    macro_rules! test {
+        ($ty:ty) => {
+            let mut generator = TlshGeneratorFor::<$ty>::new();
+            generator.update(LOREM_IPSUM);
+            let stats = generator.bucket_stats();
+            let (q1, q2, q3) = crate::buckets::quartiles(generator.inner.buckets.data());
+            assert_eq!(stats.quartiles, [q1, q2, q3]);
+        };
+    }
+    test!(hashes::Short);
+    test!(hashes::Normal);
+    test!(hashes::Long);
+}
+
+#[test]
+fn generator_bytes_until_valid() {
+    fn test<T: ConstrainedFuzzyHashType>() {
+        let optimistic = DataLengthProcessingMode::Optimistic;
+        let conservative = DataLengthProcessingMode::Conservative;
+        let mut generator = TlshGeneratorFor::<T>::new();
+        assert_eq!(
+            generator.bytes_until_valid(optimistic),
+            Some(TlshGeneratorFor::<T>::MIN)
+        );
+        assert_eq!(
+            generator.bytes_until_valid(conservative),
+            Some(TlshGeneratorFor::<T>::MIN_CONSERVATIVE)
+        );
+        for len in 1..=TlshGeneratorFor::<T>::MIN_CONSERVATIVE {
+            generator.update(&[0]);
+            assert_eq!(generator.processed_len(), Some(len));
+            assert_eq!(
+                generator.bytes_until_valid(optimistic),
+                TlshGeneratorFor::<T>::MIN
+                    .checked_sub(len)
+                    .filter(|&x| x != 0)
+            );
+            assert_eq!(
+                generator.bytes_until_valid(conservative),
+                TlshGeneratorFor::<T>::MIN_CONSERVATIVE
+                    .checked_sub(len)
+                    .filter(|&x| x != 0)
+            );
+        }
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn generator_histogram() {
+    fn test<T: ConstrainedFuzzyHashType>() {
+        let mut generator = TlshGeneratorFor::<T>::new();
+        assert_eq!(generator.histogram(), [0; NUM_BUCKETS_LONG]);
+        generator.update(LOREM_IPSUM);
+        let histogram = generator.histogram();
+        let stats = generator.bucket_stats();
+        assert_eq!(
+            histogram[..T::NUMBER_OF_BUCKETS]
+                .iter()
+                .filter(|&&x| x != 0)
+                .count(),
+            stats.nonzero
+        );
+        assert!(histogram[T::NUMBER_OF_BUCKETS..].iter().all(|&x| x == 0));
+        // Each 5-byte window updates 6 buckets (some of them may be ignored).
+        let sum: u32 = histogram.iter().sum();
+        let max_sum = 6 * (LOREM_IPSUM.len() as u32 - (WINDOW_SIZE as u32 - 1));
+        if T::NUMBER_OF_BUCKETS == NUM_BUCKETS_LONG {
+            assert_eq!(sum, max_sum);
+        } else {
+            assert!(sum <= max_sum);
+        }
+        // Finalization does not change the histogram.
+        let _ = generator.finalize();
+        assert_eq!(generator.histogram(), histogram);
+        generator.reset();
+        assert_eq!(generator.histogram(), [0; NUM_BUCKETS_LONG]);
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn generator_is_currently_hashable() {
+    fn test<T: ConstrainedFuzzyHashType>() {
+        let mut options_list = Vec::new();
+        for bits in 0..(1u32 << 5) {
+            options_list.push(
+                GeneratorOptions::new()
+                    .with_length_processing_mode(if bits & 1 != 0 {
+                        DataLengthProcessingMode::Conservative
+                    } else {
+                        DataLengthProcessingMode::Optimistic
+                    })
+                    .with_allow_small_size_files(bits & 2 != 0)
+                    .with_allow_statistically_weak_buckets_half(bits & 4 != 0)
+                    .with_allow_statistically_weak_buckets_quarter(bits & 8 != 0)
+                    .with_emit_null_on_failure(bits & 16 != 0),
+            );
+        }
+        // Feed (initially repetitive) data byte by byte and check whether
+        // the result matches the actual finalization on every step.
+        let mut generator = TlshGeneratorFor::<T>::new();
+        let data = [b'A'; 64]
+            .iter()
+            .chain(LOREM_IPSUM.iter())
+            .chain([0u8; 64].iter());
+        for &b in data {
+            generator.update(&[b]);
+            // Without the cached statistics.
+            assert_eq!(
+                generator.is_currently_hashable(&options_list[0]),
+                generator.finalize_with_options(&options_list[0]).is_ok()
+            );
+            // With the cached statistics (computed on the first call).
+            for options in options_list.iter() {
+                let expected = generator.finalize_cached_with_options(options).is_ok();
+                assert_eq!(
+                    generator.is_currently_hashable(options),
+                    expected,
+                    "failed on len={:?}, options={options:?}",
+                    generator.processed_len()
+                );
+            }
+        }
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn generator_try_update() {
+    let mut generator = TlshGenerator::new();
+    assert_eq!(generator.try_update(b""), Ok(()));
+    assert_eq!(generator.try_update(b"ABC"), Ok(()));
+    assert_eq!(generator.processed_len(), Some(3));
+    // Pretend that the generator has processed 4GiB - 8 bytes.
+    generator.update(b"DEFG");
+    generator.inner.len = u32::MAX - 7 - 4;
+    assert_eq!(generator.processed_len(), Some(u32::MAX - 7));
+    let state = generator.clone();
+    // It fails without updating the generator if any bytes are ignored.
+    assert_eq!(
+        generator.try_update(b"012345678"),
+        Err(GeneratorError::TooLargeInput)
+    );
+    assert_eq!(generator.processed_len(), Some(u32::MAX - 7));
+    assert_eq!(generator.histogram(), state.histogram());
+    // Filling exactly 4GiB is fine.
+    assert_eq!(generator.try_update(b"0123"), Ok(()));
+    assert_eq!(generator.processed_len(), Some(u32::MAX - 3));
+    assert_eq!(generator.try_update(b"4567"), Ok(()));
+    assert_eq!(generator.processed_len(), None);
+    assert_eq!(generator.try_update(b""), Ok(()));
+    assert_eq!(
+        generator.try_update(b"8"),
+        Err(GeneratorError::TooLargeInput)
+    );
+    // On the other hand, update() silently ignores the rest.
+    let mut generator = state;
+    generator.update(b"012345678");
+    assert_eq!(generator.processed_len(), None);
+    assert_eq!(generator.finalize(), Err(GeneratorError::TooLargeInput));
+}
+
+#[test]
+fn generator_max_input_size() {
+    use crate::FuzzyHashType;
+
+    let len = LOREM_IPSUM.len() as u32;
+    let expected = Tlsh::from_str(LOREM_IPSUM_HASH_NORMAL).unwrap();
+    // It is a local policy, not a TLSH-incompatible option.
+    let options = GeneratorOptions::new().with_max_input_size(Some(len));
+    assert!(options.is_tlsh_compatible());
+    assert_ne!(options, GeneratorOptions::new());
+    assert_eq!(
+        GeneratorOptions::new().with_max_input_size(None),
+        GeneratorOptions::new()
+    );
+    assert_ne!(
+        GeneratorOptions::new().with_max_input_size(Some(u32::MAX)),
+        GeneratorOptions::new()
+    );
+    assert_eq!(
+        options.clone().with_max_input_size(None),
+        GeneratorOptions::new()
+    );
+    // Exactly at the limit.
+    let mut generator = TlshGenerator::with_options(options.clone());
+    assert_eq!(generator.try_update(LOREM_IPSUM), Ok(()));
+    assert_eq!(generator.finalize(), Ok(expected));
+    assert_eq!(generator.finalize_cached(), Ok(expected));
+    assert!(generator.is_currently_hashable(&options));
+    // try_update() fails without updating the generator.
+    let state = generator.clone();
+    assert_eq!(generator.try_update(b""), Ok(()));
+    assert_eq!(
+        generator.try_update(b"A"),
+        Err(GeneratorError::TooLargeInput)
+    );
+    assert_eq!(generator.processed_len(), Some(len));
+    assert_eq!(generator.histogram(), state.histogram());
+    // update() exceeds the limit and finalization fails.
+    generator.update(b"A");
+    assert_eq!(generator.finalize(), Err(GeneratorError::TooLargeInput));
+    assert_eq!(
+        generator.finalize_cached(),
+        Err(GeneratorError::TooLargeInput)
+    );
+    assert!(!generator.is_currently_hashable(&options));
+    // Even if the null hash is emitted on failure.
+    assert_eq!(
+        generator.finalize_with_options(&options.clone().with_emit_null_on_failure(true)),
+        Err(GeneratorError::TooLargeInput)
+    );
+    // Other options are not affected.
+    assert!(generator
+        .finalize_with_options(&GeneratorOptions::new())
+        .is_ok());
+    // The limit is kept after reset.
+    generator.reset();
+    assert_eq!(generator.try_update(&LOREM_IPSUM[..1]), Ok(()));
+    assert_eq!(
+        generator.try_update(LOREM_IPSUM),
+        Err(GeneratorError::TooLargeInput)
+    );
+    // Zero means that only the empty input is accepted.
+    let options = GeneratorOptions::new()
+        .with_max_input_size(Some(0))
+        .with_emit_null_on_failure(true);
+    let mut generator = TlshGenerator::with_options(options);
+    assert_eq!(generator.try_update(b""), Ok(()));
+    assert!(generator.finalize().unwrap().is_null());
+    assert_eq!(
+        generator.try_update(b"A"),
+        Err(GeneratorError::TooLargeInput)
+    );
+    // The default (None) is limited only by the generator (4GiB).
+    let mut generator = TlshGenerator::new();
+    generator.update(b"DEFG");
+    generator.inner.len = u32::MAX - 3 - 4;
+    assert_eq!(generator.try_update(b"0123"), Ok(()));
+    assert_eq!(generator.processed_len(), None);
+    assert_eq!(
+        generator.try_update(b"4"),
+        Err(GeneratorError::TooLargeInput)
+    );
+    // The largest explicit limit is one byte smaller than the default.
+    let mut generator =
+        TlshGenerator::with_options(GeneratorOptions::new().with_max_input_size(Some(u32::MAX)));
+    generator.update(b"DEFG");
+    generator.inner.len = u32::MAX - 3 - 4;
+    assert_eq!(
+        generator.try_update(b"0123"),
+        Err(GeneratorError::TooLargeInput)
+    );
+    assert_eq!(generator.try_update(b"012"), Ok(()));
+    assert_eq!(generator.processed_len(), Some(u32::MAX));
+    assert_eq!(
+        generator.try_update(b"3"),
+        Err(GeneratorError::TooLargeInput)
+    );
+    // The limit just below that.
+    let mut generator = TlshGenerator::with_options(
+        GeneratorOptions::new().with_max_input_size(Some(u32::MAX - 1)),
+    );
+    generator.update(b"DEFG");
+    generator.inner.len = u32::MAX - 3 - 4;
+    assert_eq!(generator.processed_len(), Some(u32::MAX - 3));
+    assert_eq!(generator.try_update(b"01"), Ok(()));
+    assert_eq!(generator.processed_len(), Some(u32::MAX - 1));
+    assert_eq!(
+        generator.try_update(b"2"),
+        Err(GeneratorError::TooLargeInput)
+    );
+}
+
+#[test]
+fn generator_update_vectored() {
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    let expected = Tlsh::from_str(LOREM_IPSUM_HASH_NORMAL).unwrap();
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x7d2e_91c4_08b3_f655);
+    // Empty input and empty buffers.
+    let mut generator = TlshGenerator::new();
+    generator.update_vectored(&[]);
+    generator.update_vectored(&[&[], &[]]);
+    assert_eq!(generator.processed_len(), Some(0));
+    for num_bufs in 1..=16 {
+        for _ in 0..20 {
+            // Split LOREM_IPSUM into num_bufs buffers (some may be empty).
+            let mut offsets: Vec<_> = (1..num_bufs)
+                .map(|_| rng.gen_range(0..=LOREM_IPSUM.len()))
+                .collect();
+            offsets.push(0);
+            offsets.push(LOREM_IPSUM.len());
+            offsets.sort_unstable();
+            let bufs: Vec<_> = offsets
+                .windows(2)
+                .map(|w| &LOREM_IPSUM[w[0]..w[1]])
+                .collect();
+            let mut generator = TlshGenerator::new();
+            generator.update_vectored(&bufs);
+            assert_eq!(
+                generator.finalize(),
+                Ok(expected),
+                "failed on offsets={offsets:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn generator_eq_ignores_cached_stats() {
+    let mut generator1 = TlshGenerator::new();
+    generator1.update(LOREM_IPSUM);
+    let mut generator2 = generator1.clone();
+    assert_eq!(generator1.inner, generator2.inner);
+    // Memoizing the statistics does not affect the equality.
+    assert!(generator1.finalize_cached().is_ok());
+    assert!(generator1.inner.cached_stats.is_some());
+    assert!(generator2.inner.cached_stats.is_none());
+    assert_eq!(generator1.inner, generator2.inner);
+    // But the actual state does.
+    generator2.update(b"A");
+    assert_ne!(generator1.inner, generator2.inner);
+}
+
+#[test]
+fn generator_finalize_cached() {
+    fn test<F: ConstrainedFuzzyHashType + Debug>() {
+        let options_default = GeneratorOptions::default();
+        let mut options_weak = GeneratorOptions::new();
+        options_weak
+            .allow_small_size_files(true)
+            .allow_statistically_weak_buckets_quarter(true);
+        let mut generator = TlshGeneratorFor::<F>::new();
+        for chunk in LOREM_IPSUM.chunks(13) {
+            generator.update(chunk);
+            for options in [&options_default, &options_weak] {
+                let expected = generator.finalize_with_options(options);
+                // First call (computes the statistics) and second call
+                // (uses the cached statistics).
+                assert_eq!(generator.finalize_cached_with_options(options), expected);
+                assert_eq!(generator.finalize_cached_with_options(options), expected);
+            }
+            // Empty update does not change the result.
+            generator.update(&[]);
+            assert_eq!(generator.finalize_cached(), generator.finalize());
+        }
+        // The cache is invalidated on reset.
+        generator.reset();
+        generator.update(&LOREM_IPSUM[..LOREM_IPSUM.len() / 2]);
+        assert_eq!(generator.finalize_cached(), generator.finalize());
+        // The cache is invalidated on merge.
+        let mut other = TlshGeneratorFor::<F>::new();
+        let second = &LOREM_IPSUM[LOREM_IPSUM.len() / 2..];
+        other.update(second);
+        assert_eq!(other.finalize_cached(), other.finalize());
+        let _ = generator.merge(&other, &second[..WINDOW_SIZE - 1]);
+        assert_eq!(generator.finalize_cached(), generator.finalize());
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn generator_bucket_stats() {
+    let mut generator = TlshGenerator::new();
+    let stats = generator.bucket_stats();
+    assert_eq!(
+        stats,
+        BucketStats {
+            nonzero: 0,
+            total: NUM_BUCKETS_NORMAL,
+            quartiles: [0, 0, 0]
+        }
+    );
+    generator.update(LOREM_IPSUM);
+    let state = generator.inner.clone();
+    let stats = generator.bucket_stats();
+    // The generator state is not changed.
+    assert_eq!(generator.inner, state);
+    assert_eq!(stats.total, NUM_BUCKETS_NORMAL);
+    assert_eq!(stats.nonzero, generator.bucket_stats().nonzero);
+    assert!(stats.nonzero > NUM_BUCKETS_NORMAL / 2);
+    let [q1, q2, q3] = stats.quartiles;
+    assert!(q1 <= q2 && q2 <= q3 && q3 != 0);
+    // Consistent with the bucket counts.
+    let buckets = &generator.inner.buckets.data()[..NUM_BUCKETS_NORMAL];
+    assert_eq!(buckets.iter().filter(|&&x| x != 0).count(), stats.nonzero);
+    assert!(buckets.iter().filter(|&&x| x <= q3).count() >= NUM_BUCKETS_NORMAL * 3 / 4);
+    assert!(buckets.iter().filter(|&&x| x >= q3).count() >= NUM_BUCKETS_NORMAL / 4);
+    // Also consistent after finalize_cached.
+    generator.finalize_cached().unwrap();
+    assert_eq!(generator.bucket_stats(), stats);
+    // Extremely unbalanced data (see the example of
+    // GeneratorOptions::allow_statistically_weak_buckets_quarter).
+    let mut generator = TlshGenerator::new();
+    generator.update(b"ABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDEABCDE");
+    let stats = generator.bucket_stats();
+    assert!(stats.nonzero < NUM_BUCKETS_NORMAL / 2);
+    assert_eq!(stats.quartiles[2], 0);
+    assert_eq!(
+        generator.finalize(),
+        Err(GeneratorError::BucketsAreThreeQuarterEmpty)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generator_as_io_write() {
+    use std::io::Write;
+    let expected = Tlsh::from_str(LOREM_IPSUM_HASH_NORMAL).unwrap();
+    // Through std::io::copy
+    {
+        let mut generator = TlshGenerator::new();
+        let mut reader = LOREM_IPSUM;
+        let copied = std::io::copy(&mut reader, &mut generator).unwrap();
+        assert_eq!(copied, LOREM_IPSUM.len() as u64);
+        assert_eq!(generator.finalize(), Ok(expected));
+    }
+    // Through partial writes
+    {
+        let mut generator = TlshGenerator::new();
+        for chunk in LOREM_IPSUM.chunks(7) {
+            assert_eq!(generator.write(chunk).unwrap(), chunk.len());
+        }
+        generator.flush().unwrap();
+        assert_eq!(generator.finalize(), Ok(expected));
+    }
+}
+
 #[test]
 fn generator_example_with_variants() {
     fn check_lorem_ipsum<F: ConstrainedFuzzyHashType + Debug>(expected: &str) {
@@ -399,6 +1145,109 @@ fn extreme_unbalanced_data_forced_to_finalize() {
     );
 }
 
+#[test]
+fn generator_with_stored_options() {
+    // Default
+    let generator = TlshGenerator::new();
+    assert_eq!(generator.options(), &GeneratorOptions::new());
+    // Conservative
+    let mut generator = TlshGenerator::new_conservative();
+    assert_eq!(
+        generator.options(),
+        &GeneratorOptions::new()
+            .with_length_processing_mode(DataLengthProcessingMode::Conservative)
+    );
+    // Too small on the conservative mode (requires at least 128 bytes).
+    generator.update(&LOREM_IPSUM[..100]);
+    let expected = generator.finalize_with_options(generator.options());
+    assert_eq!(expected, Err(GeneratorError::TooSmallInput));
+    assert_eq!(generator.finalize(), expected);
+    assert_eq!(generator.finalize_cached(), expected);
+    // Explicit options take precedence.
+    assert!(generator
+        .finalize_with_options(&GeneratorOptions::new())
+        .is_ok());
+    assert!(generator
+        .finalize_cached_with_options(&GeneratorOptions::new())
+        .is_ok());
+    // Options are kept after reset.
+    generator.reset();
+    assert_eq!(
+        generator.options().clone(),
+        GeneratorOptions::new().with_length_processing_mode(DataLengthProcessingMode::Conservative)
+    );
+    // Custom options
+    let options = GeneratorOptions::new().with_emit_null_on_failure(true);
+    let mut generator = TlshGenerator::with_options(options.clone());
+    assert_eq!(generator.options(), &options);
+    generator.update(b"Hello!");
+    assert_eq!(
+        generator.finalize(),
+        generator.finalize_with_options(&options)
+    );
+    assert!(generator.finalize().is_ok());
+    assert_eq!(generator.finalize(), generator.finalize_cached());
+}
+
+#[test]
+fn finalize_with_quality() {
+    use crate::FuzzyHashType;
+
+    let options_half = GeneratorOptions::new().with_allow_statistically_weak_buckets_half(true);
+    let options_quarter =
+        GeneratorOptions::new().with_allow_statistically_weak_buckets_quarter(true);
+    let options_null = GeneratorOptions::new().with_emit_null_on_failure(true);
+    let mut generator = TlshGenerator::new();
+    // Strong
+    generator.update(BUCKETS_FILLED_65_OF_128);
+    for options in [
+        &GeneratorOptions::new(),
+        &options_half,
+        &options_quarter,
+        &options_null,
+    ] {
+        let (hash, quality) = generator.finalize_with_quality(options).unwrap();
+        assert_eq!(hash, generator.finalize_with_options(options).unwrap());
+        assert_eq!(quality, Quality::Strong);
+        assert!(quality.is_strong());
+    }
+    // Weak (half)
+    generator.reset();
+    generator.update(BUCKETS_FILLED_64_OF_128);
+    assert_eq!(
+        generator.finalize_with_quality(&GeneratorOptions::new()),
+        Err(GeneratorError::BucketsAreHalfEmpty)
+    );
+    for options in [&options_half, &options_quarter] {
+        let (hash, quality) = generator.finalize_with_quality(options).unwrap();
+        assert_eq!(hash, generator.finalize_with_options(options).unwrap());
+        assert_eq!(quality, Quality::WeakHalf);
+        assert!(!quality.is_strong());
+    }
+    // Weak (quarter)
+    generator.reset();
+    generator.update(BUCKETS_FILLED_32_OF_128);
+    for options in [&GeneratorOptions::new(), &options_half] {
+        assert_eq!(
+            generator.finalize_with_quality(options),
+            Err(GeneratorError::BucketsAreThreeQuarterEmpty)
+        );
+    }
+    let (hash, quality) = generator.finalize_with_quality(&options_quarter).unwrap();
+    assert_eq!(
+        hash,
+        generator.finalize_with_options(&options_quarter).unwrap()
+    );
+    assert_eq!(quality, Quality::WeakQuarter);
+    // The null hash emitted on failure
+    let (hash, quality) = generator.finalize_with_quality(&options_null).unwrap();
+    assert!(hash.is_null());
+    assert_eq!(quality, Quality::WeakQuarter);
+    // Ordering: the strong one is the best.
+    assert!(Quality::Strong < Quality::WeakHalf);
+    assert!(Quality::WeakHalf < Quality::WeakQuarter);
+}
+
 #[test]
 fn min_nonzero_buckets_in_data() {
     fn check_state<F: ConstrainedFuzzyHashType>(data: &[u8], expected: usize) -> bool {
@@ -406,7 +1255,7 @@ fn min_nonzero_buckets_in_data() {
         let mut generator = TlshGeneratorFor::<F>::new();
         generator.update(data);
         let result = generator.finalize();
-        assert_eq!(generator.count_nonzero_buckets(), expected);
+        assert_eq!(generator.bucket_stats().nonzero, expected);
         if let Err(err) = result {
             assert_eq!(err.category(), GeneratorErrorCategory::DataDistribution);
             // More extreme distribution: BucketsAreThreeQuarterEmpty
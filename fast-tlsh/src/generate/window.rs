@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright 2013 Trend Micro Incorporated
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The generator with a configurable window size.
+
+use core::marker::PhantomData;
+
+use crate::buckets::NUM_BUCKETS_LONG;
+use crate::errors::GeneratorError;
+use crate::generate::inner::{WindowTail, WindowedGenerator};
+use crate::generate::{
+    BucketMapping, BucketStats, GeneratorOptions, MergedChecksum, TlshBucketMapping, WINDOW_SIZE,
+};
+use crate::params::{ConstrainedFuzzyHashParams, ConstrainedFuzzyHashType};
+use crate::GeneratorType;
+
+/// The minimum window size supported by [`GeneratorWithWindow`].
+pub(super) const MIN_WINDOW_SIZE: usize = 4;
+
+/// The maximum window size supported by [`GeneratorWithWindow`].
+pub(super) const MAX_WINDOW_SIZE: usize = 8;
+
+/// Triplets to compute bucket mappings on a sliding window.
+///
+/// Each entry consists of the salt and two offsets from the latest byte
+/// (the latest byte itself is always used).  An entry is used only if the
+/// window is large enough to contain both bytes.
+///
+/// The order and the salt values follow the original implementation (the
+/// first six entries are used on the default window size [`WINDOW_SIZE`]).
+pub(super) const WINDOW_TRIPLETS: [(u8, usize, usize); 21] = [
+    (2, 1, 2),
+    (3, 1, 3),
+    (5, 2, 3),
+    (7, 2, 4),
+    (11, 1, 4),
+    (13, 3, 4),
+    (17, 1, 5),
+    (19, 2, 5),
+    (23, 3, 5),
+    (29, 4, 5),
+    (31, 1, 6),
+    (37, 2, 6),
+    (41, 3, 6),
+    (43, 4, 6),
+    (47, 5, 6),
+    (53, 1, 7),
+    (59, 2, 7),
+    (61, 3, 7),
+    (67, 4, 7),
+    (71, 5, 7),
+    (73, 6, 7),
+];
+
+/// (fast-tlsh specific)
+/// The fuzzy hash generator with a configurable window size `W`
+/// (and optionally, a custom bucket mapping `M`).
+///
+/// Like the original implementation, the window size (`W`) can be any value
+/// between 4 and 8 (inclusive).  Specifying other window sizes results in
+/// a compilation error.
+///
+/// **Warning**: This is TLSH-incompatible unless `W` is equal to
+/// [`WINDOW_SIZE`] (in which case, the result is the same as
+/// [`Generator`](crate::generate::Generator) but slower).
+/// It is also TLSH-incompatible if `M` is not the default
+/// [`TlshBucketMapping`] (see [`BucketMapping`] for details).
+/// Use [`is_tlsh_compatible()`](Self::is_tlsh_compatible()) to query
+/// whether the result is compatible with the official TLSH implementation.
+///
+/// This is mainly for research purposes (e.g. comparing against
+/// the original implementation built with a non-default window size) and not
+/// optimized as [`Generator`](crate::generate::Generator) is.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::generate::GeneratorWithWindow;
+/// use tlsh::hashes;
+/// use tlsh::prelude::*;
+///
+/// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+/// let options = tlsh::GeneratorOptions::new();
+///
+/// // The default window size reproduces the regular generator.
+/// let mut generator = GeneratorWithWindow::<hashes::Normal, 5>::new();
+/// generator.update(data);
+/// assert!(generator.is_tlsh_compatible(&options));
+/// let mut expected = TlshGenerator::new();
+/// expected.update(data);
+/// assert_eq!(generator.finalize().unwrap(), expected.finalize().unwrap());
+///
+/// // Other window sizes generate TLSH-incompatible fuzzy hashes.
+/// let mut generator = GeneratorWithWindow::<hashes::Normal, 7>::new();
+/// generator.update(data);
+/// assert!(!generator.is_tlsh_compatible(&options));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GeneratorWithWindow<
+    T: ConstrainedFuzzyHashType,
+    const W: usize,
+    M: BucketMapping = TlshBucketMapping,
+> {
+    /// The inner generator to store the buckets, the checksum and
+    /// the data length.
+    ///
+    /// Its `tail_len` field is shared with this generator but its `tail`
+    /// is unused (use [`tail`](Self::tail) instead).
+    inner:
+        <<T as ConstrainedFuzzyHashType>::Params as ConstrainedFuzzyHashParams>::InnerGeneratorType,
+
+    /// Previous (last) bytes processed.
+    ///
+    /// Only the first `W - 1` bytes are used.  The effective length is
+    /// handled by the `tail_len` field of [`inner`](Self::inner).
+    tail: WindowTail,
+
+    /// The marker for the bucket mapping.
+    mapping: PhantomData<fn() -> M>,
+}
+
+impl<T: ConstrainedFuzzyHashType, const W: usize, M: BucketMapping> GeneratorWithWindow<T, W, M> {
+    /// The check whether the window size is supported.
+    const VALID_WINDOW_SIZE: () = assert!(
+        MIN_WINDOW_SIZE <= W && W <= MAX_WINDOW_SIZE,
+        "window size must be between 4 and 8 (inclusive)"
+    );
+
+    /// Creates the new generator.
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_WINDOW_SIZE;
+        Self {
+            inner: Default::default(),
+            tail: [0; MAX_WINDOW_SIZE - 1],
+            mapping: PhantomData,
+        }
+    }
+
+    /// Query whether the result of this generator with specified options is
+    /// compatible to the official implementation of TLSH.
+    ///
+    /// It returns [`true`] only if the window size is the default one
+    /// ([`WINDOW_SIZE`]), the bucket mapping is the default one
+    /// ([`TlshBucketMapping`]) and the options are TLSH-compatible
+    /// (see [`GeneratorOptions::is_tlsh_compatible()`]).
+    pub fn is_tlsh_compatible(&self, options: &GeneratorOptions) -> bool {
+        W == WINDOW_SIZE && M::IS_TLSH_COMPATIBLE && options.is_tlsh_compatible()
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType, const W: usize, M: BucketMapping> Default
+    for GeneratorWithWindow<T, W, M>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ConstrainedFuzzyHashType, const W: usize, M: BucketMapping> GeneratorType
+    for GeneratorWithWindow<T, W, M>
+{
+    type Output = T;
+
+    const IS_CHECKSUM_EFFECTIVE: bool = <inner_type!(T)>::IS_CHECKSUM_EFFECTIVE;
+    const MIN: u32 = <inner_type!(T)>::MIN;
+    const MIN_CONSERVATIVE: u32 = <inner_type!(T)>::MIN_CONSERVATIVE;
+    const MAX: u32 = <inner_type!(T)>::MAX;
+
+    #[inline(always)]
+    fn processed_len(&self) -> Option<u32> {
+        self.inner.processed_len()
+    }
+
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update_with_window::<W, M>(&mut self.tail, data);
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.tail = [0; MAX_WINDOW_SIZE - 1];
+    }
+
+    #[inline(always)]
+    fn merge(&mut self, other: &Self, overlap: &[u8]) -> MergedChecksum {
+        self.inner
+            .merge_with_window::<W, M>(&mut self.tail, &other.inner, &other.tail, overlap)
+    }
+
+    #[inline(always)]
+    fn finalize_with_options(
+        &self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner.finalize_with_options(options).map(T::new)
+    }
+
+    #[inline(always)]
+    fn is_currently_hashable(&self, options: &GeneratorOptions) -> bool {
+        self.inner.is_currently_hashable(options)
+    }
+
+    #[inline(always)]
+    fn finalize_cached_with_options(
+        &mut self,
+        options: &GeneratorOptions,
+    ) -> Result<Self::Output, GeneratorError> {
+        self.inner.finalize_cached_with_options(options).map(T::new)
+    }
+
+    #[inline(always)]
+    fn bucket_stats(&self) -> BucketStats {
+        self.inner.bucket_stats()
+    }
+
+    #[inline(always)]
+    fn histogram(&self) -> [u32; NUM_BUCKETS_LONG] {
+        self.inner.histogram()
+    }
+}
+
+mod tests;
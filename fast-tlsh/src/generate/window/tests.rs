@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate::window`].
+
+#![cfg(test)]
+
+use core::fmt::Debug;
+
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::GeneratorWithWindow;
+
+use crate::generate::tests::LOREM_IPSUM;
+use crate::generate::{GeneratorOptions, MergedChecksum};
+use crate::hashes;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::{FuzzyHashType, GeneratorType, TlshGeneratorFor};
+
+#[test]
+fn default_window_equivalence() {
+    fn check<F: ConstrainedFuzzyHashType + Debug>(chunks: &[&[u8]]) {
+        let mut generator = GeneratorWithWindow::<F, 5>::new();
+        let mut expected = TlshGeneratorFor::<F>::new();
+        for chunk in chunks {
+            generator.update(chunk);
+            expected.update(chunk);
+        }
+        assert_eq!(generator.processed_len(), expected.processed_len());
+        assert_eq!(generator.bucket_stats(), expected.bucket_stats());
+        let mut options = GeneratorOptions::new();
+        options
+            .allow_small_size_files(true)
+            .allow_statistically_weak_buckets_quarter(true);
+        assert_eq!(generator.finalize(), expected.finalize());
+        assert_eq!(
+            generator.finalize_with_options(&options),
+            expected.finalize_with_options(&options)
+        );
+    }
+    fn test<F: ConstrainedFuzzyHashType + Debug>() {
+        check::<F>(&[LOREM_IPSUM]);
+        check::<F>(&LOREM_IPSUM.chunks(1).collect::<Vec<_>>());
+        check::<F>(&LOREM_IPSUM.chunks(3).collect::<Vec<_>>());
+        for len in 0..=60 {
+            check::<F>(&[&LOREM_IPSUM[..len]]);
+        }
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x2c6b8e01f4d7a953);
+        let mut data = [0u8; 4096];
+        for _ in 0..10 {
+            rng.fill_bytes(&mut data);
+            let len = (rng.next_u32() as usize) % data.len();
+            let data = &data[..len];
+            let split = (rng.next_u32() as usize) % (len + 1);
+            check::<F>(&[&data[..split], &data[split..]]);
+        }
+    }
+    test::<hashes::Short>();
+    test::<hashes::Normal>();
+    test::<hashes::NormalWithLongChecksum>();
+    test::<hashes::Long>();
+    test::<hashes::LongWithLongChecksum>();
+}
+
+#[test]
+fn other_windows() {
+    fn test<const W: usize>() {
+        let mut generator = GeneratorWithWindow::<hashes::Normal, W>::new();
+        generator.update(LOREM_IPSUM);
+        assert_eq!(generator.processed_len(), Some(LOREM_IPSUM.len() as u32));
+        assert!(!generator.is_tlsh_compatible(&GeneratorOptions::new()));
+        let hash = generator.finalize().unwrap();
+        // The result does not depend on how the data is split.
+        for size in 1..=(W + 1) {
+            let mut generator = GeneratorWithWindow::<hashes::Normal, W>::new();
+            for chunk in LOREM_IPSUM.chunks(size) {
+                generator.update(chunk);
+            }
+            assert_eq!(
+                generator.finalize().unwrap(),
+                hash,
+                "failed on W={W}, size={size}"
+            );
+        }
+        // It differs from the result with the default window size.
+        let mut expected = TlshGeneratorFor::<hashes::Normal>::new();
+        expected.update(LOREM_IPSUM);
+        assert_ne!(hash, expected.finalize().unwrap(), "failed on W={W}");
+        // Merging segments.
+        for offset in 0..=LOREM_IPSUM.len() {
+            let (first, second) = LOREM_IPSUM.split_at(offset);
+            let overlap = &second[..second.len().min(W - 1)];
+            let mut generator1 = GeneratorWithWindow::<hashes::Normal, W>::new();
+            let mut generator2 = GeneratorWithWindow::<hashes::Normal, W>::new();
+            generator1.update(first);
+            generator2.update(second);
+            let checksum = generator1.merge(&generator2, overlap);
+            let merged = generator1.finalize().unwrap();
+            let mut hash = hash;
+            if checksum == MergedChecksum::Cleared {
+                hash.clear_checksum();
+            }
+            assert_eq!(
+                checksum.is_exact(),
+                offset == 0 || offset == LOREM_IPSUM.len(),
+                "failed on W={W}, offset={offset}"
+            );
+            assert_eq!(merged, hash, "failed on W={W}, offset={offset}");
+        }
+    }
+    test::<4>();
+    test::<6>();
+    test::<7>();
+    test::<8>();
+}
+
+#[test]
+fn compatibility() {
+    let mut options = GeneratorOptions::new();
+    let generator = GeneratorWithWindow::<hashes::Normal, 5>::new();
+    assert!(generator.is_tlsh_compatible(&options));
+    options.allow_small_size_files(true);
+    assert!(!generator.is_tlsh_compatible(&options));
+    let generator = GeneratorWithWindow::<hashes::Normal, 4>::new();
+    assert!(!generator.is_tlsh_compatible(&GeneratorOptions::new()));
+}
+
+#[test]
+fn reset() {
+    let mut generator = GeneratorWithWindow::<hashes::Normal, 8>::new();
+    generator.update(b"Hello, World!");
+    generator.reset();
+    assert_eq!(generator.processed_len(), Some(0));
+    generator.update(LOREM_IPSUM);
+    let mut expected = GeneratorWithWindow::<hashes::Normal, 8>::new();
+    expected.update(LOREM_IPSUM);
+    assert_eq!(generator.finalize(), expected.finalize());
+}
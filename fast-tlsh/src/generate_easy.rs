@@ -5,6 +5,9 @@
 
 #![cfg(feature = "easy-functions")]
 
+#[cfg(feature = "rayon")]
+use alloc::vec::Vec;
+
 use crate::errors::GeneratorError;
 use crate::generate::Generator;
 use crate::params::ConstrainedFuzzyHashType;
@@ -13,6 +16,15 @@ use crate::{GeneratorType, Tlsh};
 /// Generates a fuzzy hash from a given buffer
 /// (with specified output type).
 ///
+/// # Performance
+///
+/// This function does not allocate any memory on the heap (the generator
+/// state is placed on the stack) and works without the `alloc` feature.
+/// So, it is suitable to benchmark the algorithm itself.
+///
+/// For the feature flags affecting the speed, see
+/// [the feature guide](crate::_docs::features#benchmarking-the-generator).
+///
 /// # Example
 ///
 /// ```
@@ -25,6 +37,7 @@ use crate::{GeneratorType, Tlsh};
 /// // Note: short fuzzy hashes accept very short inputs.
 /// assert_eq!(hash.to_string(), "T1E16004017D3551777571D55C005CC5");
 /// ```
+#[inline]
 pub fn hash_buf_for<T: ConstrainedFuzzyHashType>(buffer: &[u8]) -> Result<T, GeneratorError> {
     let mut generator = Generator::<T>::new();
     generator.update(buffer);
@@ -33,6 +46,9 @@ pub fn hash_buf_for<T: ConstrainedFuzzyHashType>(buffer: &[u8]) -> Result<T, Gen
 
 /// Generates a fuzzy hash from a given buffer.
 ///
+/// Like [`hash_buf_for()`], this function does not allocate any memory on
+/// the heap.
+///
 /// # Example
 ///
 /// ```
@@ -54,8 +70,139 @@ pub fn hash_buf_for<T: ConstrainedFuzzyHashType>(buffer: &[u8]) -> Result<T, Gen
 ///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
 /// );
 /// ```
+#[inline]
 pub fn hash_buf(buffer: &[u8]) -> Result<Tlsh, GeneratorError> {
     hash_buf_for::<Tlsh>(buffer)
 }
 
+/// Generates a fuzzy hash from a given string
+/// (with specified output type).
+///
+/// See [`hash_str()`] for details.
+///
+/// # Example
+///
+/// ```
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// let hash = tlsh::hash_str_for::<CustomTlsh>("Hello, World!").unwrap();
+/// assert_eq!(hash, tlsh::hash_buf_for::<CustomTlsh>(b"Hello, World!").unwrap());
+/// ```
+#[inline]
+pub fn hash_str_for<T: ConstrainedFuzzyHashType>(s: &str) -> Result<T, GeneratorError> {
+    hash_buf_for::<T>(s.as_bytes())
+}
+
+/// Generates a fuzzy hash from a given string.
+///
+/// # Encoding
+///
+/// This function hashes the UTF-8 encoded bytes of `s` (exactly the same as
+/// [`hash_buf(s.as_bytes())`](hash_buf())), not the Unicode scalar values
+/// (or any other abstract representation of the text).
+///
+/// So, strings which look the same may have different fuzzy hashes if
+/// their byte sequences differ.  For instance, `"é"` may be represented
+/// either as a single precomposed character (U+00E9; Unicode normalization
+/// form NFC) or as `"e"` followed by a combining acute accent (U+0301;
+/// NFD).  Normalize strings (and, if necessary, line endings) before
+/// hashing if such differences should be ignored.
+///
+/// To hash a text in another encoding (e.g. UTF-16), encode it by
+/// yourself and use [`hash_buf()`].
+///
+/// # Example
+///
+/// ```
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// let nfc = "Caf\u{e9} au lait, cr\u{e8}me br\u{fb}l\u{e9}e";
+/// let nfd = "Cafe\u{301} au lait, cre\u{300}me bru\u{302}le\u{301}e";
+/// // Visually identical but different as byte sequences.
+/// assert_ne!(nfc.as_bytes(), nfd.as_bytes());
+/// let hash_nfc = tlsh::hash_str_for::<CustomTlsh>(nfc).unwrap();
+/// let hash_nfd = tlsh::hash_str_for::<CustomTlsh>(nfd).unwrap();
+/// assert_ne!(hash_nfc, hash_nfd);
+///
+/// // The same as hashing the UTF-8 bytes.
+/// let text = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
+///     eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad \
+///     minim veniam, quis nostrud exercitation ullamco laboris nisi ut \
+///     aliquip ex ea commodo consequat. Duis aute irure dolor in \
+///     reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla \
+///     pariatur. Excepteur sint occaecat cupidatat non proident, sunt in \
+///     culpa qui officia deserunt mollit anim id est laborum.";
+/// assert_eq!(tlsh::hash_str(text), tlsh::hash_buf(text.as_bytes()));
+/// ```
+#[inline]
+pub fn hash_str(s: &str) -> Result<Tlsh, GeneratorError> {
+    hash_str_for::<Tlsh>(s)
+}
+
+/// Generates fuzzy hashes from given buffers in parallel
+/// (with specified output type).
+///
+/// See [`hash_bufs_par()`] for details.
+///
+/// # Example
+///
+/// ```
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// let inputs: [&[u8]; 2] = [b"Hello, World!", b""];
+/// let hashes = tlsh::hash_bufs_par_for::<CustomTlsh>(&inputs);
+/// assert_eq!(hashes[0], tlsh::hash_buf_for::<CustomTlsh>(inputs[0]));
+/// assert!(hashes[1].is_err());
+/// ```
+#[cfg(feature = "rayon")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "rayon")))]
+pub fn hash_bufs_par_for<T: ConstrainedFuzzyHashType + Send>(
+    inputs: &[&[u8]],
+) -> Vec<Result<T, GeneratorError>> {
+    use rayon::prelude::*;
+    inputs
+        .par_iter()
+        .map(|buffer| hash_buf_for::<T>(buffer))
+        .collect()
+}
+
+/// Generates fuzzy hashes from given buffers in parallel.
+///
+/// This is a thin wrapper of [`hash_buf()`] using [`rayon`] and
+/// the `i`-th element of the result corresponds to `inputs[i]`.
+///
+/// Each input is processed by its own generator (no state is shared
+/// between inputs).  So, the throughput scales almost linearly with the
+/// number of worker threads as long as there are enough inputs.
+///
+/// This function is only available with the `rayon` feature.
+///
+/// # Example
+///
+/// ```
+/// let inputs: [&[u8]; 2] = [
+///     b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
+///     eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad \
+///     minim veniam, quis nostrud exercitation ullamco laboris nisi ut \
+///     aliquip ex ea commodo consequat. Duis aute irure dolor in \
+///     reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla \
+///     pariatur. Excepteur sint occaecat cupidatat non proident, sunt in \
+///     culpa qui officia deserunt mollit anim id est laborum.",
+///     b"Hello, World!",
+/// ];
+/// let hashes = tlsh::hash_bufs_par(&inputs);
+/// assert_eq!(
+///     hashes[0].as_ref().unwrap().to_string(),
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+/// );
+/// // Too small to generate a fuzzy hash of the default type.
+/// assert!(hashes[1].is_err());
+/// ```
+#[cfg(feature = "rayon")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "rayon")))]
+#[inline]
+pub fn hash_bufs_par(inputs: &[&[u8]]) -> Vec<Result<Tlsh, GeneratorError>> {
+    hash_bufs_par_for::<Tlsh>(inputs)
+}
+
 mod tests;
@@ -5,7 +5,7 @@
 
 #![cfg(test)]
 
-use super::{hash_buf, hash_buf_for};
+use super::{hash_buf, hash_buf_for, hash_str, hash_str_for};
 
 use crate::generate::tests::{LOREM_IPSUM, LOREM_IPSUM_HASH_NORMAL};
 use crate::hashes;
@@ -29,3 +29,60 @@ fn example_hash_buf() {
     let hash = hash_buf(LOREM_IPSUM).unwrap();
     assert_eq!(hash.to_string(), LOREM_IPSUM_HASH_NORMAL);
 }
+
+#[test]
+fn hash_str_utf8_bytes() {
+    let text = core::str::from_utf8(LOREM_IPSUM).unwrap();
+    assert_eq!(hash_str(text).unwrap().to_string(), LOREM_IPSUM_HASH_NORMAL);
+    // Non-ASCII characters are hashed as UTF-8 byte sequences.
+    let text = "\u{3042}\u{3044}\u{3046}\u{3048}\u{304a} Hello, World! \u{1f600}";
+    assert_eq!(
+        hash_str_for::<hashes::Short>(text),
+        hash_buf_for::<hashes::Short>(text.as_bytes())
+    );
+    // Different normalization forms generate different fuzzy hashes.
+    let nfc = "Caf\u{e9} au lait, cr\u{e8}me br\u{fb}l\u{e9}e";
+    let nfd = "Cafe\u{301} au lait, cre\u{300}me bru\u{302}le\u{301}e";
+    assert_ne!(
+        hash_str_for::<hashes::Short>(nfc).unwrap(),
+        hash_str_for::<hashes::Short>(nfd).unwrap()
+    );
+    // Too small inputs are rejected the same way as hash_buf().
+    assert_eq!(hash_str(""), hash_buf(b""));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn hash_bufs_par_order() {
+    use std::vec::Vec;
+
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use super::{hash_bufs_par, hash_bufs_par_for};
+
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x3c5a_91d2_0e47_b86f);
+    let mut buffers: Vec<Vec<u8>> = (0..200)
+        .map(|i| {
+            let mut buffer = std::vec![0u8; i * 7];
+            rng.fill_bytes(&mut buffer);
+            buffer
+        })
+        .collect();
+    buffers.push(LOREM_IPSUM.to_vec());
+    let inputs: Vec<&[u8]> = buffers.iter().map(|x| x.as_slice()).collect();
+    // The order is preserved and each result matches hash_buf().
+    let hashes = hash_bufs_par(&inputs);
+    assert_eq!(hashes.len(), inputs.len());
+    for (hash, input) in hashes.iter().zip(inputs.iter()) {
+        assert_eq!(*hash, hash_buf(input));
+    }
+    assert!(hashes.iter().any(|x| x.is_err()));
+    assert!(hashes.iter().any(|x| x.is_ok()));
+    let hashes = hash_bufs_par_for::<hashes::Short>(&inputs);
+    for (hash, input) in hashes.iter().zip(inputs.iter()) {
+        assert_eq!(*hash, hash_buf_for::<hashes::Short>(input));
+    }
+    // Empty input.
+    assert!(hash_bufs_par(&[]).is_empty());
+}
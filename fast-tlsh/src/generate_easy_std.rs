@@ -90,6 +90,62 @@ pub fn hash_stream<R: Read>(reader: &mut R) -> Result<Tlsh, GeneratorOrIOError>
     hash_stream_for::<Tlsh, _>(reader)
 }
 
+/// Generates fuzzy hashes from two given reader streams and compares them
+/// (with specified output type).
+///
+/// Each stream is hashed with its own generator and the resulting fuzzy
+/// hashes are compared with the default configuration
+/// (see [`FuzzyHashType::compare()`](crate::FuzzyHashType::compare())).
+///
+/// # Example
+///
+/// ```
+/// use std::fs::File;
+///
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// fn main() -> Result<(), tlsh::GeneratorOrIOError> {
+///     let mut stream1 = File::open("data/examples/smallexe.exe")?;
+///     let mut stream2 = File::open("data/examples/smallexe.exe")?;
+///     let distance = tlsh::compare_streams_for::<CustomTlsh, _, _>(&mut stream1, &mut stream2)?;
+///     assert_eq!(distance, 0);
+///     Ok(())
+/// }
+/// ```
+pub fn compare_streams_for<T: ConstrainedFuzzyHashType, R1: Read, R2: Read>(
+    lhs: &mut R1,
+    rhs: &mut R2,
+) -> Result<u32, GeneratorOrIOError> {
+    let lhs: T = hash_stream_for(lhs)?;
+    let rhs: T = hash_stream_for(rhs)?;
+    Ok(lhs.compare(&rhs))
+}
+
+/// Generates fuzzy hashes from two given reader streams and compares them.
+///
+/// This is useful to check whether two (possibly large) files are similar
+/// in one call.
+///
+/// # Example
+///
+/// ```
+/// use std::fs::File;
+///
+/// fn main() -> Result<(), tlsh::GeneratorOrIOError> {
+///     let mut stream1 = File::open("data/examples/smallexe.exe")?;
+///     let mut stream2 = File::open("data/examples/smallexe.exe")?;
+///     let distance = tlsh::compare_streams(&mut stream1, &mut stream2)?;
+///     assert_eq!(distance, 0);
+///     Ok(())
+/// }
+/// ```
+pub fn compare_streams<R1: Read, R2: Read>(
+    lhs: &mut R1,
+    rhs: &mut R2,
+) -> Result<u32, GeneratorOrIOError> {
+    compare_streams_for::<Tlsh, _, _>(lhs, rhs)
+}
+
 /// Generates a fuzzy hash from a given file
 /// (with specified output type).
 ///
@@ -129,4 +185,74 @@ pub fn hash_file<P: AsRef<Path>>(path: P) -> Result<Tlsh, GeneratorOrIOError> {
     hash_file_for::<Tlsh, _>(path)
 }
 
+/// Generates a fuzzy hash from a given file by mapping it to memory
+/// (with specified output type).
+///
+/// See [`hash_file_mmap()`] for details.
+///
+/// # Example
+///
+/// ```
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// fn main() -> Result<(), tlsh::GeneratorOrIOError> {
+///     let fuzzy_hash: CustomTlsh = tlsh::hash_file_mmap_for("data/examples/smallexe.exe")?;
+///     let fuzzy_hash_str = fuzzy_hash.to_string();
+///     assert_eq!(fuzzy_hash_str, "T140E0483A5DFC1B073D86A4A2C55A43");
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "mmap")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "mmap")))]
+pub fn hash_file_mmap_for<T: ConstrainedFuzzyHashType, P: AsRef<Path>>(
+    path: P,
+) -> Result<T, GeneratorOrIOError> {
+    let file = File::open(path)?;
+    let mut generator = Generator::<T>::new();
+    // Mapping an empty file may fail on some platforms.
+    // Just skip it (finalization fails with TooSmallInput).
+    if file.metadata()?.len() != 0 {
+        // SAFETY: the mapping is read-only and dropped before returning.
+        // Modification of the file by other processes while hashing is
+        // not prevented (see the documentation of hash_file_mmap()).
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        generator.update(&map);
+    }
+    Ok(generator.finalize()?)
+}
+
+/// Generates a fuzzy hash from a given file by mapping it to memory.
+///
+/// Unlike [`hash_file()`], this function maps the whole file to memory
+/// and feeds it to the generator at once (without an intermediate buffer).
+/// It is faster on large files on many platforms.
+///
+/// The result is the same as [`hash_file()`], including an empty file
+/// (which results in [`GeneratorError::TooSmallInput`](crate::GeneratorError::TooSmallInput)).
+///
+/// This function is only available with the `mmap` feature.
+///
+/// # Safety (Warning)
+///
+/// This function is not marked as `unsafe` but if the file is modified
+/// (especially truncated) by another process while hashing, the result
+/// is unspecified and the process may be killed by a signal (e.g. `SIGBUS`
+/// on Unix-like systems).  Use [`hash_file()`] if that is a concern.
+///
+/// # Example
+///
+/// ```
+/// fn main() -> Result<(), tlsh::GeneratorOrIOError> {
+///     let fuzzy_hash = tlsh::hash_file_mmap("data/examples/smallexe.exe")?;
+///     let fuzzy_hash_str = fuzzy_hash.to_string();
+///     assert_eq!(fuzzy_hash_str, "T1FFE04C037F895471D42E5530499E47473757E5E456D28B13ED1944654C8534C7CE9E01");
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "mmap")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "mmap")))]
+pub fn hash_file_mmap<P: AsRef<Path>>(path: P) -> Result<Tlsh, GeneratorOrIOError> {
+    hash_file_mmap_for::<Tlsh, _>(path)
+}
+
 mod tests;
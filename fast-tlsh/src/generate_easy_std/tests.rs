@@ -5,13 +5,16 @@
 
 #![cfg(test)]
 
-use super::{hash_file, hash_file_for, hash_stream, hash_stream_for};
+use super::{
+    compare_streams, compare_streams_for, hash_file, hash_file_for, hash_stream, hash_stream_for,
+};
 
 use std::fs::File;
 use std::io::Read;
 
 use crate::errors::{GeneratorError, GeneratorOrIOError};
 use crate::hashes;
+use crate::FuzzyHashType;
 
 const NONEXISTENT_PATH: &str = "data/examples/nonexistent_path";
 const EMPTY_PATH: &str = "data/examples/empty.bin";
@@ -97,3 +100,74 @@ fn example_hash_file_empty() {
         ))
     ));
 }
+
+#[test]
+fn example_compare_streams() {
+    use core::str::FromStr;
+
+    // Same file
+    let mut stream1 = File::open(SMALL_EXE_PATH).unwrap();
+    let mut stream2 = File::open(SMALL_EXE_PATH).unwrap();
+    assert_eq!(compare_streams(&mut stream1, &mut stream2).unwrap(), 0);
+    // Different inputs: matches comparison of two individually generated hashes.
+    let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+    let mut stream1 = File::open(SMALL_EXE_PATH).unwrap();
+    let distance = compare_streams(&mut stream1, &mut { data }).unwrap();
+    let hash1 = hashes::Normal::from_str(SMALL_EXE_TLSH_NORMAL).unwrap();
+    let hash2: hashes::Normal = hash_stream_for(&mut { data }).unwrap();
+    assert_eq!(distance, hash1.compare(&hash2));
+    // Custom type
+    let mut stream1 = File::open(SMALL_EXE_PATH).unwrap();
+    let distance = compare_streams_for::<hashes::Short, _, _>(&mut stream1, &mut { data }).unwrap();
+    let hash1 = hashes::Short::from_str(SMALL_EXE_TLSH_SHORT).unwrap();
+    let hash2: hashes::Short = hash_stream_for(&mut { data }).unwrap();
+    assert_eq!(distance, hash1.compare(&hash2));
+}
+
+#[test]
+fn example_compare_streams_errors() {
+    // Error on either side
+    let mut stream1 = File::open(SMALL_EXE_PATH).unwrap();
+    let mut stream2 = File::open(EMPTY_PATH).unwrap();
+    assert!(matches!(
+        compare_streams(&mut stream1, &mut stream2),
+        Err(GeneratorOrIOError::GeneratorError(
+            GeneratorError::TooSmallInput
+        ))
+    ));
+    let mut stream1 = File::open(EMPTY_PATH).unwrap();
+    let mut stream2 = File::open(SMALL_EXE_PATH).unwrap();
+    assert!(matches!(
+        compare_streams(&mut stream1, &mut stream2),
+        Err(GeneratorOrIOError::GeneratorError(
+            GeneratorError::TooSmallInput
+        ))
+    ));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn hash_file_mmap_examples() {
+    use super::{hash_file_mmap, hash_file_mmap_for};
+
+    // The same as hash_file.
+    let fuzzy_hash = hash_file_mmap(SMALL_EXE_PATH).unwrap();
+    assert_eq!(fuzzy_hash.to_string(), SMALL_EXE_TLSH_NORMAL);
+    let fuzzy_hash: hashes::Short = hash_file_mmap_for(SMALL_EXE_PATH).unwrap();
+    assert_eq!(fuzzy_hash.to_string(), SMALL_EXE_TLSH_SHORT);
+    // Nonexistent file
+    let result = hash_file_mmap(NONEXISTENT_PATH);
+    assert!(matches!(
+        result,
+        Err(GeneratorOrIOError::IOError(err)) if err.kind() == std::io::ErrorKind::NotFound
+    ));
+    // Empty file
+    let result = hash_file_mmap(EMPTY_PATH);
+    assert!(matches!(
+        result,
+        Err(GeneratorOrIOError::GeneratorError(
+            GeneratorError::TooSmallInput
+        ))
+    ));
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The easy wrapper for generator functionalities (for `tokio` users).
+
+#![cfg(all(feature = "tokio", feature = "easy-functions"))]
+
+use tokio::io::AsyncRead;
+
+use crate::errors::GeneratorOrIOError;
+use crate::generate::Generator;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::{GeneratorType, Tlsh};
+
+/// Generates a fuzzy hash from a given asynchronous reader
+/// (with specified output type).
+///
+/// The data is read asynchronously but each chunk is hashed synchronously
+/// by the generator (through its [`tokio::io::AsyncWrite`] implementation).
+///
+/// # Example
+///
+/// ```
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let mut reader: &[u8] = b"Hello, World!";
+/// let fuzzy_hash: CustomTlsh = runtime
+///     .block_on(tlsh::hash_async_reader_for(&mut reader))
+///     .unwrap();
+/// assert_eq!(fuzzy_hash.to_string(), "T1E16004017D3551777571D55C005CC5");
+/// ```
+pub async fn hash_async_reader_for<T: ConstrainedFuzzyHashType, R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<T, GeneratorOrIOError>
+where
+    Generator<T>: Unpin,
+{
+    let mut generator = Generator::<T>::new();
+    tokio::io::copy(reader, &mut generator).await?;
+    Ok(generator.finalize()?)
+}
+
+/// Generates a fuzzy hash from a given asynchronous reader.
+///
+/// This is the asynchronous counterpart of
+/// [`hash_stream()`](crate::hash_stream()).
+///
+/// # Example
+///
+/// ```
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let mut reader: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
+///     eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad \
+///     minim veniam, quis nostrud exercitation ullamco laboris nisi ut \
+///     aliquip ex ea commodo consequat. Duis aute irure dolor in \
+///     reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla \
+///     pariatur. Excepteur sint occaecat cupidatat non proident, sunt in \
+///     culpa qui officia deserunt mollit anim id est laborum.";
+/// let fuzzy_hash = runtime.block_on(tlsh::hash_async_reader(&mut reader)).unwrap();
+/// assert_eq!(
+///     fuzzy_hash.to_string(),
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+/// );
+/// ```
+pub async fn hash_async_reader<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Tlsh, GeneratorOrIOError> {
+    hash_async_reader_for::<Tlsh, _>(reader).await
+}
+
+mod tests;
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::generate_easy_tokio`].
+
+#![cfg(test)]
+
+use super::{hash_async_reader, hash_async_reader_for};
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWriteExt, ReadBuf};
+
+use crate::errors::{GeneratorError, GeneratorOrIOError};
+use crate::generate::tests::{LOREM_IPSUM, LOREM_IPSUM_HASH_NORMAL};
+use crate::generate_easy::hash_buf_for;
+use crate::generate_easy_std::hash_stream;
+use crate::hashes;
+use crate::{GeneratorType, TlshGenerator};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn matches_sync_digest() {
+    let mut reader = LOREM_IPSUM;
+    let expected = hash_stream(&mut reader).unwrap();
+    let mut reader = LOREM_IPSUM;
+    let fuzzy_hash = block_on(hash_async_reader(&mut reader)).unwrap();
+    assert_eq!(fuzzy_hash, expected);
+    assert_eq!(fuzzy_hash.to_string(), LOREM_IPSUM_HASH_NORMAL);
+    // With specified output type.
+    let mut reader = LOREM_IPSUM;
+    let fuzzy_hash: hashes::Short = block_on(hash_async_reader_for(&mut reader)).unwrap();
+    assert_eq!(
+        fuzzy_hash,
+        hash_buf_for::<hashes::Short>(LOREM_IPSUM).unwrap()
+    );
+}
+
+#[test]
+fn generator_as_async_write() {
+    let mut generator = TlshGenerator::new();
+    block_on(async {
+        for chunk in LOREM_IPSUM.chunks(7) {
+            generator.write_all(chunk).await.unwrap();
+        }
+        generator.flush().await.unwrap();
+        generator.shutdown().await.unwrap();
+    });
+    assert_eq!(
+        generator.finalize().unwrap().to_string(),
+        LOREM_IPSUM_HASH_NORMAL
+    );
+}
+
+#[test]
+fn errors() {
+    // Generator error (too small input)
+    let mut reader: &[u8] = b"";
+    assert!(matches!(
+        block_on(hash_async_reader(&mut reader)),
+        Err(GeneratorOrIOError::GeneratorError(
+            GeneratorError::TooSmallInput
+        ))
+    ));
+    // Custom AsyncRead implementation (which always fails)
+    struct IOFail;
+    impl AsyncRead for IOFail {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            _buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Err(std::io::Error::from(std::io::ErrorKind::Other)))
+        }
+    }
+    assert!(matches!(
+        block_on(hash_async_reader(&mut IOFail)),
+        Err(GeneratorOrIOError::IOError(_))
+    ));
+}
@@ -4,27 +4,33 @@
 
 //! The fuzzy hash and its parts (unless a part has its own module).
 
+use core::cmp::Ordering;
 use core::fmt::Display;
+use core::ops::RangeInclusive;
 use core::str::FromStr;
 
 #[cfg(feature = "serde")]
-use serde::de::Visitor;
+use serde::de::{IgnoredAny, SeqAccess, Visitor};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::compare::ComparisonConfiguration;
+use crate::compare::{ComparisonConfiguration, Distance, DistanceBreakdown, Similarity};
 use crate::errors::{OperationError, ParseError};
-use crate::hash::body::FuzzyHashBody;
+use crate::hash::body::{BodyDiff, FuzzyHashBody};
 use crate::hash::checksum::FuzzyHashChecksum;
 use crate::hash::qratios::FuzzyHashQRatios;
 use crate::length::FuzzyHashLengthEncoding;
 use crate::params::{ConstrainedFuzzyHashParams, FuzzyHashParams};
+use crate::parse::base64;
 use crate::FuzzyHashType;
 
 pub mod body;
+mod borrowed;
 pub mod checksum;
 pub mod qratios;
 
+pub use borrowed::BorrowedFuzzyHash;
+
 /// Denotes the prefix on the TLSH's hexadecimal representation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum HexStringPrefix {
@@ -46,11 +52,78 @@ pub enum HexStringPrefix {
     ///
     /// If this value is specified to a parser method, it expects that the
     /// TLSHv1 prefix (`"T1"`) exists at the beginning.
-    /// *This prefix is case-sensitive.*
+    /// The parser also accepts the lowercase prefix (`"t1"`).
     #[default]
     WithVersion,
 }
 
+/// (fast-tlsh specific)
+/// Denotes the case of hexadecimal digits on the TLSH's hexadecimal
+/// representation.
+///
+/// Parsers always accept both cases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HexCase {
+    /// Uppercase hexadecimal digits (default; compatible with the official
+    /// implementation).
+    #[default]
+    Upper,
+
+    /// Lowercase hexadecimal digits.
+    ///
+    /// Note that the TLSHv1 prefix (`"T1"`) is not affected.
+    Lower,
+}
+
+/// The iterator over fuzzy hashes within the specified distance.
+///
+/// Each item is a tuple of the index in the slice, the reference to the fuzzy
+/// hash and the distance from the query.
+///
+/// This is created by [`FuzzyHashType::find_within()`].
+#[derive(Debug, Clone)]
+pub struct FindWithin<'a, T: FuzzyHashType> {
+    /// The query.
+    query: &'a T,
+    /// The remaining fuzzy hashes to compare (with indices).
+    others: core::iter::Enumerate<core::slice::Iter<'a, T>>,
+    /// The maximum distance (inclusive).
+    max_distance: u32,
+}
+
+impl<'a, T: FuzzyHashType> FindWithin<'a, T> {
+    /// Creates an iterator over fuzzy hashes within the specified distance.
+    #[inline(always)]
+    pub(crate) fn new(query: &'a T, others: &'a [T], max_distance: u32) -> Self {
+        Self {
+            query,
+            others: others.iter().enumerate(),
+            max_distance,
+        }
+    }
+}
+
+impl<'a, T: FuzzyHashType> Iterator for FindWithin<'a, T> {
+    type Item = (usize, &'a T, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let query = self.query;
+        let max_distance = self.max_distance;
+        self.others.find_map(|(index, other)| {
+            query
+                .compare_within(other, max_distance)
+                .map(|distance| (index, other, distance))
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.others.size_hint().1)
+    }
+}
+
+impl<T: FuzzyHashType> core::iter::FusedIterator for FindWithin<'_, T> {}
+
 /// The public part for later `pub use` at crate root.
 pub(crate) mod public {
     use super::*;
@@ -138,6 +211,49 @@ pub(crate) mod public {
         /// `prefix` of [`HexStringPrefix::WithVersion`].
         const LEN_IN_STR: usize;
 
+        /// Length in the hexadecimal string representation
+        /// with the prefix `"T1"`.
+        ///
+        /// This is an alias of [`LEN_IN_STR`](Self::LEN_IN_STR) and
+        /// corresponds to [`HexStringPrefix::WithVersion`].
+        /// This is also the maximum length written by
+        /// [`store_into_str_bytes()`](Self::store_into_str_bytes())
+        /// (regardless of the prefix) and thus, the safe buffer size.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        /// use tlsh::HexStringPrefix;
+        ///
+        /// let hash = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2").unwrap();
+        /// let mut buffer = [0u8; Tlsh::LEN_IN_STR_WITH_PREFIX];
+        /// assert_eq!(
+        ///     hash.store_into_str_bytes(&mut buffer, HexStringPrefix::WithVersion),
+        ///     Ok(Tlsh::LEN_IN_STR_WITH_PREFIX)
+        /// );
+        /// assert_eq!(
+        ///     hash.store_into_str_bytes(&mut buffer, HexStringPrefix::Empty),
+        ///     Ok(Tlsh::LEN_IN_STR_NO_PREFIX)
+        /// );
+        /// ```
+        const LEN_IN_STR_WITH_PREFIX: usize = Self::LEN_IN_STR;
+
+        /// Length in the hexadecimal string representation
+        /// without the prefix `"T1"`.
+        ///
+        /// This is an alias of
+        /// [`LEN_IN_STR_EXCEPT_PREFIX`](Self::LEN_IN_STR_EXCEPT_PREFIX) and
+        /// corresponds to [`HexStringPrefix::Empty`].
+        const LEN_IN_STR_NO_PREFIX: usize = Self::LEN_IN_STR_EXCEPT_PREFIX;
+
+        /// Length in the URL-safe Base64 representation (without padding).
+        ///
+        /// This is the fixed size and required buffer size for the
+        /// [`to_base64_into()`](Self::to_base64_into()) method.
+        const LEN_IN_BASE64: usize;
+
         /// Returns the checksum part.
         fn checksum(&self) -> &Self::ChecksumType;
         /// Returns the length part.
@@ -147,6 +263,37 @@ pub(crate) mod public {
         /// Returns the body part.
         fn body(&self) -> &Self::BodyType;
 
+        /// Creates the fuzzy hash object from its parts.
+        ///
+        /// This is the opposite of the accessors
+        /// ([`checksum()`](Self::checksum()), [`length()`](Self::length()),
+        /// [`qratios()`](Self::qratios()) and [`body()`](Self::body())).
+        ///
+        /// Note that no validation is performed (even with the
+        /// `strict-parser` feature).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash1 = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2").unwrap();
+        /// let hash2 = Tlsh::from_str("T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008").unwrap();
+        /// // Replace the body of hash1 with hash2's.
+        /// let hash = Tlsh::from_parts(*hash1.checksum(), *hash1.length(), *hash1.qratios(), *hash2.body());
+        /// assert_eq!(
+        ///     hash.to_string(),
+        ///     "T1DCF0DC088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008"
+        /// );
+        /// ```
+        fn from_parts(
+            checksum: Self::ChecksumType,
+            length: FuzzyHashLengthEncoding,
+            qratios: FuzzyHashQRatios,
+            body: Self::BodyType,
+        ) -> Self;
+
         /// Try parsing the fuzzy hash object from the given TLSH's hexadecimal
         /// representation and the operation mode.
         ///
@@ -164,11 +311,181 @@ pub(crate) mod public {
         /// If the argument `prefix` is [`None`], the existence of the prefix
         /// will be auto-detected.  Otherwise, the existence of
         /// [the specified prefix](HexStringPrefix) is checked.
-        #[inline(always)]
+        ///
+        /// Unlike [`from_str_bytes()`](Self::from_str_bytes()), leading and
+        /// trailing ASCII whitespace characters (e.g. a newline) are ignored.
+        /// Whitespace characters inside the representation are still
+        /// rejected.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::ParseError;
+        ///
+        /// let hash = Tlsh::from_str_with(
+        ///     " t1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2\n",
+        ///     None
+        /// );
+        /// assert!(hash.is_ok());
+        /// let hash = Tlsh::from_str_with(
+        ///     "T1DCF0DC36520C1B007FD32079B226559FD99 A0200725E75AFCEAC99F5881184A4B1AA2",
+        ///     None
+        /// );
+        /// assert_eq!(hash, Err(ParseError::InvalidCharacter));
+        /// ```
+        #[inline]
         fn from_str_with(s: &str, prefix: Option<HexStringPrefix>) -> Result<Self, ParseError> {
+            let s = s.trim_matches(|c: char| c.is_ascii_whitespace());
             Self::from_str_bytes(s.as_bytes(), prefix)
         }
 
+        /// (fast-tlsh specific)
+        /// Try parsing the fuzzy hash object from the legacy TLSH's
+        /// hexadecimal representation (without the `"T1"` prefix).
+        ///
+        /// Older versions of the official implementation emitted fuzzy hashes
+        /// without the version prefix.  This is the same as
+        /// [`from_str_with()`](Self::from_str_with()) with
+        /// [`HexStringPrefix::Empty`] and rejects strings with the prefix.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash = Tlsh::from_legacy_str(
+        ///     "DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+        /// );
+        /// assert!(hash.is_ok());
+        /// let hash = Tlsh::from_legacy_str(
+        ///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+        /// );
+        /// assert!(hash.is_err());
+        /// ```
+        #[inline]
+        fn from_legacy_str(s: &str) -> Result<Self, ParseError> {
+            Self::from_str_with(s, Some(HexStringPrefix::Empty))
+        }
+
+        /// Try parsing the fuzzy hash object from the given TLSH's hexadecimal
+        /// representation, handling the null hash as [`None`].
+        ///
+        /// The official implementation emits `"TNULL"` instead of the fuzzy
+        /// hash if it cannot generate one from the input.  While other
+        /// parsers return [`ParseError::NullHash`] on this sentinel
+        /// (and `"NULL"`), this method returns [`Ok`]`(`[`None`]`)`.
+        /// Other strings are handled as in
+        /// [`from_str_with()`](Self::from_str_with()) with the prefix
+        /// auto-detection.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::ParseError;
+        ///
+        /// assert_eq!(Tlsh::from_str_opt("TNULL"), Ok(None));
+        /// let hash = Tlsh::from_str_opt(
+        ///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+        /// );
+        /// assert!(matches!(hash, Ok(Some(_))));
+        /// assert_eq!(Tlsh::from_str_opt("T1"), Err(ParseError::InvalidStringLength));
+        /// ```
+        fn from_str_opt(s: &str) -> Result<Option<Self>, ParseError> {
+            match Self::from_str_with(s, None) {
+                Ok(hash) => Ok(Some(hash)),
+                Err(ParseError::NullHash) => Ok(None),
+                Err(err) => Err(err),
+            }
+        }
+
+        /// (fast-tlsh specific)
+        /// Try parsing the fuzzy hash from the given TLSH's hexadecimal
+        /// representation into this object.
+        ///
+        /// This is useful to reuse existing objects (e.g. in an object pool)
+        /// when parsing many fuzzy hashes.
+        ///
+        /// On success, `self` is updated to the same value as
+        /// [`from_str()`](FromStr::from_str()) would return.
+        /// On failure, `self` is left unchanged.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let s1 = "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2";
+        /// let s2 = "T1DCF0DC088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let mut hash = Tlsh::from_str(s1).unwrap();
+        /// assert!(hash.parse_into(s2).is_ok());
+        /// assert_eq!(hash.to_string(), s2);
+        /// // On failure, the object is not modified.
+        /// assert!(hash.parse_into("TNULL").is_err());
+        /// assert_eq!(hash.to_string(), s2);
+        /// ```
+        #[inline]
+        fn parse_into(&mut self, s: &str) -> Result<(), ParseError> {
+            *self = Self::from_str(s)?;
+            Ok(())
+        }
+
+        /// (fast-tlsh specific)
+        /// Try parsing the fuzzy hash object from the given TLSH's
+        /// hexadecimal representation, reporting the position of
+        /// an invalid character.
+        ///
+        /// This is the same as [`from_str()`](FromStr::from_str()) except
+        /// that, on [`ParseError::InvalidCharacter`], the byte offset (in `s`)
+        /// of the first invalid character is also returned.  On other errors,
+        /// the offset is [`None`].
+        ///
+        /// This is useful to show user-facing error messages pointing
+        /// the offending character.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::ParseError;
+        ///
+        /// let s = "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2";
+        /// assert!(Tlsh::from_str_detailed(s).is_ok());
+        /// let s = "T1DCF0DC36520C1B007FD32079B226559FD998A020072XE75AFCEAC99F5881184A4B1AA2";
+        /// assert_eq!(
+        ///     Tlsh::from_str_detailed(s),
+        ///     Err((ParseError::InvalidCharacter, Some(45)))
+        /// );
+        /// assert_eq!(
+        ///     Tlsh::from_str_detailed("T1"),
+        ///     Err((ParseError::InvalidStringLength, None))
+        /// );
+        /// ```
+        fn from_str_detailed(s: &str) -> Result<Self, (ParseError, Option<usize>)> {
+            match Self::from_str(s) {
+                Ok(hash) => Ok(hash),
+                Err(ParseError::InvalidCharacter) => {
+                    // Locate the hexadecimal part as in from_str_with().
+                    let trimmed = s.trim_start_matches(|c: char| c.is_ascii_whitespace());
+                    let start = s.len() - trimmed.len();
+                    let trimmed = trimmed.trim_end_matches(|c: char| c.is_ascii_whitespace());
+                    let start = if trimmed.len() == Self::LEN_IN_STR_WITH_PREFIX {
+                        start + 2
+                    } else {
+                        start
+                    };
+                    let offset = s.as_bytes()[start..]
+                        .iter()
+                        .position(|ch| !ch.is_ascii_hexdigit())
+                        .map(|pos| start + pos);
+                    Err((ParseError::InvalidCharacter, offset))
+                }
+                Err(err) => Err((err, None)),
+            }
+        }
+
         /// Store the contents of this object to the specified slice
         /// (in a binary format).
         ///
@@ -223,79 +540,988 @@ pub(crate) mod public {
         /// +- No header and version
         /// ```
         ///
-        /// # The Specification
+        /// # The Specification
+        ///
+        /// This method concatenates:
+        ///
+        /// 1.  Checksum
+        /// 2.  Length encoding
+        /// 3.  Q ratio pair
+        /// 4.  Body
+        ///
+        /// in that order (without any explicit variable length encodings or
+        /// separators).  Each binary representation of the part can be
+        /// retrieved as either an [`u8`] value or a slice / array of [`u8`].
+        ///
+        /// See [struct documentation](Self#tlsh-internals) for details.
+        fn store_into_bytes(&self, out: &mut [u8]) -> Result<usize, OperationError>;
+
+        /// (fast-tlsh specific)
+        /// Store the contents of this object to the specified slice
+        /// (in the little-endian binary format).
+        ///
+        /// This is a variant of [`store_into_bytes()`](Self::store_into_bytes())
+        /// for storage formats expecting a little-endian bucket layout.
+        ///
+        /// # The Specification
+        ///
+        /// The header (checksum, length encoding and Q ratio pair) is
+        /// exactly the same as [the canonical binary format](Self::store_into_bytes()).
+        /// The only difference is the byte order of the body.
+        ///
+        /// *   In the canonical binary format (and the TLSH's hexadecimal
+        ///     representation), the body starts with the *last* buckets.
+        ///     For `N`-byte body, the byte `N - 1 - i` contains the buckets
+        ///     `4i` through `4i + 3`.
+        /// *   In the little-endian binary format, the body starts with the
+        ///     *first* buckets.  The byte `i` (of the body) contains the
+        ///     buckets `4i` through `4i + 3`.
+        ///
+        /// In both formats, the quartile value of the bucket `4i + k`
+        /// (`0 <= k < 4`) is stored in the bits `2k` (LSB) and `2k + 1` of the
+        /// corresponding byte.  That means, the body in the little-endian
+        /// binary format is a plain little-endian bit array of quartile values.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2").unwrap();
+        /// let mut canonical = [0u8; Tlsh::SIZE_IN_BYTES];
+        /// let mut le = [0u8; Tlsh::SIZE_IN_BYTES];
+        /// hash.store_into_bytes(&mut canonical).unwrap();
+        /// hash.store_into_bytes_le(&mut le).unwrap();
+        /// // The header is the same.
+        /// assert_eq!(canonical[..3], le[..3]);
+        /// // The body is reversed.
+        /// assert_eq!(canonical[3], le[Tlsh::SIZE_IN_BYTES - 1]);
+        /// assert_eq!(Tlsh::from_bytes_le(&le), Ok(hash));
+        /// ```
+        fn store_into_bytes_le(&self, out: &mut [u8]) -> Result<usize, OperationError>;
+
+        /// (fast-tlsh specific)
+        /// Try parsing the fuzzy hash object from
+        /// [the little-endian binary format](Self::store_into_bytes_le()).
+        ///
+        /// If the length of the slice is not
+        /// [`SIZE_IN_BYTES`](Self::SIZE_IN_BYTES), it returns
+        /// [`ParseError::InvalidStringLength`].  Other validation is the same
+        /// as parsing from [the canonical binary format](Self::store_into_bytes()).
+        fn from_bytes_le(bytes: &[u8]) -> Result<Self, ParseError>;
+
+        /// Store the contents of this object to the specified slice
+        /// (in the TLSH's hexadecimal representation).
+        ///
+        /// This method stores the contents as a TLSH's hexadecimal string
+        /// representation with [the specified prefix](HexStringPrefix).
+        fn store_into_str_bytes(
+            &self,
+            out: &mut [u8],
+            prefix: HexStringPrefix,
+        ) -> Result<usize, OperationError>;
+
+        /// (fast-tlsh specific)
+        /// Store the contents of this object to the specified slice
+        /// (in the TLSH's hexadecimal representation with the specified case).
+        ///
+        /// This is the same as
+        /// [`store_into_str_bytes()`](Self::store_into_str_bytes()) except
+        /// that hexadecimal digits are emitted in [the specified case](HexCase).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        /// use tlsh::{HexCase, HexStringPrefix};
+        ///
+        /// let hash_str = "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2";
+        /// let hash = Tlsh::from_str(hash_str).unwrap();
+        /// let mut buffer = [0u8; Tlsh::LEN_IN_STR_WITH_PREFIX];
+        /// let len = hash
+        ///     .store_into_str_bytes_with_case(&mut buffer, HexStringPrefix::WithVersion, HexCase::Lower)
+        ///     .unwrap();
+        /// assert_eq!(
+        ///     &buffer[..len],
+        ///     b"T1dcf0dc36520c1b007fd32079b226559fd998a0200725e75afceac99f5881184a4b1aa2"
+        /// );
+        /// ```
+        fn store_into_str_bytes_with_case(
+            &self,
+            out: &mut [u8],
+            prefix: HexStringPrefix,
+            case: HexCase,
+        ) -> Result<usize, OperationError> {
+            let len = self.store_into_str_bytes(out, prefix)?;
+            if case == HexCase::Lower {
+                let start = match prefix {
+                    HexStringPrefix::Empty => 0,
+                    HexStringPrefix::WithVersion => 2,
+                };
+                out[start..len].make_ascii_lowercase();
+            }
+            Ok(len)
+        }
+
+        /// (fast-tlsh specific)
+        /// Converts this object to the TLSH's hexadecimal representation
+        /// with lowercase hexadecimal digits.
+        ///
+        /// The TLSHv1 prefix (`"T1"`) is kept uppercase.
+        /// See [`store_into_str_bytes_with_case()`](Self::store_into_str_bytes_with_case())
+        /// for details.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash_str = "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2";
+        /// let hash = Tlsh::from_str(hash_str).unwrap();
+        /// let lower = hash.to_string_lower();
+        /// assert_eq!(
+        ///     lower,
+        ///     "T1dcf0dc36520c1b007fd32079b226559fd998a0200725e75afceac99f5881184a4b1aa2"
+        /// );
+        /// assert_eq!(Tlsh::from_str(&lower), Ok(hash));
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(feature = "unstable", doc(cfg(feature = "alloc")))]
+        fn to_string_lower(&self) -> alloc::string::String {
+            let mut buf = alloc::vec![0u8; Self::LEN_IN_STR_WITH_PREFIX];
+            self.store_into_str_bytes_with_case(
+                &mut buf,
+                HexStringPrefix::WithVersion,
+                HexCase::Lower,
+            )
+            .unwrap();
+            alloc::string::String::from_utf8(buf).unwrap()
+        }
+
+        /// (fast-tlsh specific)
+        /// Converts this object to the legacy TLSH's hexadecimal representation
+        /// (without the `"T1"` prefix).
+        ///
+        /// This is the same as
+        /// [`store_into_str_bytes()`](Self::store_into_str_bytes()) with
+        /// [`HexStringPrefix::Empty`].  The result can be parsed by
+        /// [`from_legacy_str()`](Self::from_legacy_str()).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash_str = "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2";
+        /// let hash = Tlsh::from_str(hash_str).unwrap();
+        /// let legacy = hash.to_legacy_string();
+        /// assert_eq!(legacy, hash_str[2..]);
+        /// assert_eq!(Tlsh::from_legacy_str(&legacy), Ok(hash));
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(feature = "unstable", doc(cfg(feature = "alloc")))]
+        fn to_legacy_string(&self) -> alloc::string::String {
+            let mut buf = alloc::vec![0u8; Self::LEN_IN_STR_NO_PREFIX];
+            self.store_into_str_bytes(&mut buf, HexStringPrefix::Empty)
+                .unwrap();
+            alloc::string::String::from_utf8(buf).unwrap()
+        }
+
+        /// Try parsing the fuzzy hash object from the URL-safe Base64
+        /// representation (without padding) of
+        /// [the binary format](Self::store_into_bytes()).
+        ///
+        /// If the length of the string is not
+        /// [`LEN_IN_BASE64`](Self::LEN_IN_BASE64), it returns
+        /// [`ParseError::InvalidStringLength`].
+        fn from_base64(s: &str) -> Result<Self, ParseError>;
+
+        /// Store the contents of this object to the specified slice
+        /// (in the URL-safe Base64 representation without padding).
+        ///
+        /// This method encodes [the binary format](Self::store_into_bytes())
+        /// as the "base64url" encoding defined in the RFC 4648 (without
+        /// trailing `=` characters), which is shorter than the TLSH's
+        /// hexadecimal representation and safe to be used in URLs.
+        fn to_base64_into(&self, out: &mut [u8]) -> Result<usize, OperationError>;
+
+        /// Converts this object to the URL-safe Base64 representation
+        /// (without padding).
+        ///
+        /// See [`to_base64_into()`](Self::to_base64_into()) for details.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash_str = "T14D9ADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888";
+        /// let hash = Tlsh::from_str(hash_str).unwrap();
+        /// let base64 = hash.to_base64();
+        /// assert_eq!(base64.len(), Tlsh::LEN_IN_BASE64);
+        /// assert_eq!(Tlsh::from_base64(&base64), Ok(hash));
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(feature = "unstable", doc(cfg(feature = "alloc")))]
+        fn to_base64(&self) -> alloc::string::String {
+            let mut buf = alloc::vec![0u8; Self::LEN_IN_BASE64];
+            self.to_base64_into(&mut buf).unwrap();
+            alloc::string::String::from_utf8(buf).unwrap()
+        }
+
+        /// Returns an iterator over buckets whose quartile values differ
+        /// between two fuzzy hashes.
+        ///
+        /// Each item is a tuple of `(bucket_index, self_quartile,
+        /// other_quartile)` where the bucket index is less than
+        /// [`NUMBER_OF_BUCKETS`](Self::NUMBER_OF_BUCKETS) and each quartile
+        /// value is in `0b00..=0b11`.  It helps to understand which part of
+        /// the body contributes to the distance.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash1 = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2").unwrap();
+        /// let hash2 = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA1").unwrap();
+        /// // The last hex digit represents buckets 0 and 1.
+        /// let diff: Vec<_> = hash1.body_diff(&hash2).collect();
+        /// assert_eq!(diff, [(0, 0b10, 0b01)]);
+        /// ```
+        #[inline]
+        fn body_diff<'a>(&'a self, other: &'a Self) -> BodyDiff<'a, Self::BodyType> {
+            BodyDiff::new(self.body(), other.body())
+        }
+
+        /// Compare only the checksum part with another instance and
+        /// return the (unweighted) distance between them.
+        ///
+        /// This is the same as the checksum part of the distance on
+        /// [the default configuration](ComparisonConfiguration::Default)
+        /// (see [`compare_parts()`](Self::compare_parts())).  It is `0` if
+        /// and only if the checksums match and does not exceed
+        /// [`ChecksumType::MAX_DISTANCE`](FuzzyHashChecksum::MAX_DISTANCE)
+        /// (the number of mismatching bytes on the long checksum).
+        ///
+        /// Because the checksum is computed from the whole data, a match
+        /// alone is not a strong evidence of the similarity but it is cheap to
+        /// compute.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+        /// let hash1 = Tlsh::from_str(hash1).unwrap();
+        /// let hash2 = Tlsh::from_str(hash2).unwrap();
+        /// assert_eq!(hash1.checksum_distance(&hash1), 0);
+        /// assert_eq!(hash1.checksum_distance(&hash2), 1);
+        /// ```
+        #[inline]
+        fn checksum_distance(&self, other: &Self) -> u32 {
+            self.checksum().compare(other.checksum())
+        }
+
+        /// Compute the max distance on [comparison](Self::compare()) with
+        /// the specified comparison configuration.
+        ///
+        /// If you need the maximum distance on the default configuration,
+        /// use the first argument of [`Default::default()`].
+        ///
+        /// The result is equal to the [total](DistanceBreakdown::total()) of
+        /// the breakdown returned by
+        /// [`max_distance_parts()`](Self::max_distance_parts()).
+        fn max_distance(config: ComparisonConfiguration) -> u32;
+
+        /// Compute the per-part breakdown of the max distance on
+        /// [comparison](Self::compare()) with the specified comparison
+        /// configuration.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::ComparisonConfiguration;
+        ///
+        /// let parts = Tlsh::max_distance_parts(ComparisonConfiguration::NoChecksum);
+        /// assert_eq!(parts.checksum, 0);
+        /// assert_eq!(parts.total(), Tlsh::max_distance(ComparisonConfiguration::NoChecksum));
+        /// ```
+        fn max_distance_parts(config: ComparisonConfiguration) -> DistanceBreakdown {
+            DistanceBreakdown {
+                checksum: config.weight_checksum_distance(Self::ChecksumType::MAX_DISTANCE),
+                length: config.weight_length_distance(FuzzyHashLengthEncoding::MAX_DISTANCE),
+                qratios: config.weight_qratios_distance(FuzzyHashQRatios::MAX_DISTANCE),
+                body: Self::BodyType::MAX_DISTANCE,
+            }
+        }
+
+        /// Compare with another instance (with a configuration) and
+        /// return the distance between them.
+        ///
+        /// The result is equal to the [total](DistanceBreakdown::total()) of
+        /// the breakdown returned by [`compare_parts()`](Self::compare_parts()).
+        ///
+        /// Normally, you will likely use the default configuration and use
+        /// [`compare()`](Self::compare()) instead.
+        fn compare_with_config(&self, other: &Self, config: ComparisonConfiguration) -> u32;
+
+        /// Compare with another instance (with a configuration) and
+        /// return the per-part breakdown of the distance between them.
+        ///
+        /// This is useful to examine which part dominates the distance.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        /// use tlsh::ComparisonConfiguration;
+        ///
+        /// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+        /// let hash1 = Tlsh::from_str(hash1).unwrap();
+        /// let hash2 = Tlsh::from_str(hash2).unwrap();
+        /// let parts = hash1.compare_parts(&hash2, ComparisonConfiguration::Default);
+        /// assert_eq!(parts.total(), hash1.compare(&hash2));
+        /// ```
+        fn compare_parts(&self, other: &Self, config: ComparisonConfiguration)
+            -> DistanceBreakdown;
+
+        /// Compare with another instance with [the default configuration](ComparisonConfiguration::Default)
+        /// and return the distance between them.
+        ///
+        /// The result is equal to the [total](DistanceBreakdown::total()) of
+        /// the breakdown returned by [`compare_parts()`](Self::compare_parts())
+        /// with the default configuration.
+        ///
+        /// If you need to use a non-default option, use
+        /// [`compare_with_config()`](Self::compare_with_config()) instead.
+        #[inline(always)]
+        fn compare(&self, other: &Self) -> u32 {
+            self.compare_with_config(other, ComparisonConfiguration::Default)
+        }
+
+        /// (fast-tlsh specific)
+        /// Compare with another instance (with the default configuration)
+        /// and return the type-safe [distance](Distance).
+        ///
+        /// This is the same as [`compare()`](Self::compare()) except that
+        /// the result is wrapped in [`Distance`] so that it is not confused
+        /// with the [similarity score](Similarity).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        /// use tlsh::{Distance, Similarity};
+        ///
+        /// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+        /// let hash1 = Tlsh::from_str(hash1).unwrap();
+        /// let hash2 = Tlsh::from_str(hash2).unwrap();
+        /// let distance = hash1.compare_typed(&hash2);
+        /// assert_eq!(distance, Distance(138));
+        /// assert!(distance.is_within(Distance(150)));
+        /// assert_eq!(Similarity::from_distance::<Tlsh>(distance).value(), 95);
+        /// ```
+        #[inline]
+        fn compare_typed(&self, other: &Self) -> Distance {
+            Distance(self.compare(other))
+        }
+
+        /// Compare the length part with the length encoding of another
+        /// fuzzy hash and return the distance between them.
+        ///
+        /// The length encoding is structurally the same between all variants.
+        /// So, this can be used to compare fuzzy hashes of different variants
+        /// (e.g. normal and long ones) in the length dimension even if
+        /// the bodies are not comparable.
+        ///
+        /// The result is the same as the raw distance of the length part
+        /// (before applying any configuration) as in
+        /// [`compare_parts()`](Self::compare_parts()).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        /// use tlsh::hashes;
+        ///
+        /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        /// let normal = hashes::Normal::from_iter(data.iter().copied()).unwrap();
+        /// let long = hashes::Long::from_iter(data.iter().copied()).unwrap();
+        /// // Same data length results in zero distance.
+        /// assert_eq!(normal.compare_length(long.length()), 0);
+        /// ```
+        #[inline]
+        fn compare_length(&self, other_len: &FuzzyHashLengthEncoding) -> u32 {
+            self.length().compare(other_len)
+        }
+
+        /// (fast-tlsh specific)
+        /// Compare only the body part with another instance with
+        /// a custom outlier value and return the distance between them.
+        ///
+        /// On TLSH, the distance between two quartile values is the
+        /// absolute difference, except that the maximum difference (`0b00`
+        /// and `0b11`) is penalized as the outlier value `6` instead of `3`.
+        /// This method replaces this outlier value with `outlier` to
+        /// experiment with different penalties.
+        ///
+        /// This is implemented by a simple scalar loop and is much slower
+        /// than [`compare_with_config()`](Self::compare_with_config()) with
+        /// [`BodyOnly`](ComparisonConfiguration::BodyOnly).  The result is
+        /// TLSH-compatible only if `outlier` is `6`.  If the distance
+        /// overflows, it saturates at [`u32::MAX`].
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        /// use tlsh::ComparisonConfiguration;
+        ///
+        /// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+        /// let hash1 = Tlsh::from_str(hash1).unwrap();
+        /// let hash2 = Tlsh::from_str(hash2).unwrap();
+        /// assert_eq!(
+        ///     hash1.compare_body_with_outlier(&hash2, 6),
+        ///     hash1.compare_with_config(&hash2, ComparisonConfiguration::BodyOnly)
+        /// );
+        /// // Without the outlier penalty.
+        /// assert!(hash1.compare_body_with_outlier(&hash2, 3) < hash1.compare_body_with_outlier(&hash2, 6));
+        /// ```
+        fn compare_body_with_outlier(&self, other: &Self, outlier: u32) -> u32 {
+            let (body1, body2) = (self.body(), other.body());
+            (0..Self::NUMBER_OF_BUCKETS).fold(0u32, |distance, index| {
+                let diff = u8::abs_diff(body1.quartile(index), body2.quartile(index));
+                distance.saturating_add(if diff == 0b11 { outlier } else { diff as u32 })
+            })
+        }
+
+        /// (fast-tlsh specific)
+        /// Computes the minimum and maximum distance which the length part
+        /// of this pair can add to the total distance.
+        ///
+        /// The length distance is a deterministic function of two length
+        /// encodings.  So, the only thing which changes its contribution to
+        /// the total distance is the [configuration](ComparisonConfiguration):
+        ///
+        /// *   The minimum (always `0`) is the contribution on configurations
+        ///     which ignore the length ([`NoLength`](ComparisonConfiguration::NoLength)
+        ///     and [`BodyOnly`](ComparisonConfiguration::BodyOnly)).
+        /// *   The maximum is the contribution on configurations which compare
+        ///     the length as is ([`Default`](ComparisonConfiguration::Default)
+        ///     and [`NoChecksum`](ComparisonConfiguration::NoChecksum)).
+        ///     It is the same as [`compare_length()`](Self::compare_length()).
+        ///
+        /// On [`Weighted`](ComparisonConfiguration::Weighted), the
+        /// contribution is the maximum multiplied by `length_multiplier`
+        /// (saturating at [`u32::MAX`]).
+        ///
+        /// In other words, the total distance on the default configuration
+        /// is the one on [`NoLength`](ComparisonConfiguration::NoLength)
+        /// plus the maximum.  It is useful to tell how much of the total
+        /// distance is attributable to the size difference (not the content).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::ComparisonConfiguration;
+        ///
+        /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        /// let hash1 = Tlsh::from_iter(data.iter().copied()).unwrap();
+        /// let hash2 = Tlsh::from_iter(data.iter().chain(data).copied()).unwrap();
+        /// let (min, max) = hash1.length_distance_bounds(&hash2);
+        /// assert_eq!(min, 0);
+        /// assert_eq!(max, hash1.compare_length(hash2.length()));
+        /// assert_eq!(
+        ///     hash1.compare_with_config(&hash2, ComparisonConfiguration::Default),
+        ///     hash1.compare_with_config(&hash2, ComparisonConfiguration::NoLength) + max
+        /// );
+        /// // The same lengths: the length part adds nothing.
+        /// assert_eq!(hash1.length_distance_bounds(&hash1), (0, 0));
+        /// ```
+        #[inline]
+        fn length_distance_bounds(&self, other: &Self) -> (u32, u32) {
+            (0, self.compare_length(other.length()))
+        }
+
+        /// Compare all parts except the checksum with another fuzzy hash
+        /// with the same body type and return the distance between them.
+        ///
+        /// Fuzzy hash types with the same number of buckets but with
+        /// different checksum sizes (e.g. [`Normal`](crate::hashes::Normal)
+        /// and [`NormalWithLongChecksum`](crate::hashes::NormalWithLongChecksum))
+        /// share the same body type.  Their length, Q ratio pair and body
+        /// are directly comparable but the checksum is not.
+        ///
+        /// This method compares such fuzzy hashes, ignoring the checksum part
+        /// regardless of `config`.  If both are the same type, the result is
+        /// the same as [`compare_with_config()`](Self::compare_with_config())
+        /// except that the checksum distance is always treated as zero.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::hashes;
+        /// use tlsh::ComparisonConfiguration;
+        ///
+        /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        /// let normal = hashes::Normal::from_iter(data.iter().copied()).unwrap();
+        /// let long_cksum = hashes::NormalWithLongChecksum::from_iter(data.iter().copied()).unwrap();
+        /// let config = ComparisonConfiguration::Default;
+        /// assert_eq!(normal.compare_body_and_length(&long_cksum, config), 0);
+        /// assert_eq!(long_cksum.compare_body_and_length(&normal, config), 0);
+        /// ```
+        #[inline]
+        fn compare_body_and_length<U>(&self, other: &U, config: ComparisonConfiguration) -> u32
+        where
+            U: FuzzyHashType<BodyType = Self::BodyType>,
+        {
+            let distance_body = self.body().compare(other.body());
+            if config == ComparisonConfiguration::BodyOnly {
+                return distance_body;
+            }
+            (distance_body + self.qratios().compare(other.qratios())).saturating_add(
+                config.weight_length_distance(self.length().compare(other.length())),
+            )
+        }
+
+        /// Compare with another instance (with a configuration) and
+        /// return the distance between them only if it does not exceed
+        /// `max_distance`.
+        ///
+        /// If the distance is within `max_distance`, the result is
+        /// equal to [`compare_with_config()`](Self::compare_with_config()).
+        /// Otherwise, [`None`] is returned.
+        ///
+        /// This is faster than comparing the result of
+        /// [`compare_with_config()`](Self::compare_with_config()) because
+        /// the body comparison stops early once the running distance exceeds
+        /// `max_distance`.  Note that SIMD implementations of the body
+        /// comparison check the running distance only per chunk
+        /// (32 bytes).
+        fn compare_within_with_config(
+            &self,
+            other: &Self,
+            config: ComparisonConfiguration,
+            max_distance: u32,
+        ) -> Option<u32>;
+
+        /// Compare with another instance with [the default configuration](ComparisonConfiguration::Default)
+        /// and return the distance between them only if it does not exceed
+        /// `max_distance`.
+        ///
+        /// See [`compare_within_with_config()`](Self::compare_within_with_config())
+        /// for details.
+        #[inline(always)]
+        fn compare_within(&self, other: &Self, max_distance: u32) -> Option<u32> {
+            self.compare_within_with_config(other, ComparisonConfiguration::Default, max_distance)
+        }
+
+        /// (fast-tlsh specific)
+        /// Validates the binary representation of a fuzzy hash without
+        /// constructing an object.
+        ///
+        /// It succeeds if and only if [`TryFrom<&[u8]>`](TryFrom) on `raw`
+        /// succeeds (otherwise, it returns the same error).  So, the length
+        /// of `raw` must be [`SIZE_IN_BYTES`](Self::SIZE_IN_BYTES).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use tlsh::prelude::*;
+        /// use tlsh::ParseError;
+        ///
+        /// let raw = [0u8; Tlsh::SIZE_IN_BYTES];
+        /// assert_eq!(Tlsh::validate_bytes(&raw), Tlsh::try_from(&raw[..]).map(|_| ()));
+        /// assert_eq!(
+        ///     Tlsh::validate_bytes(&raw[1..]),
+        ///     Err(ParseError::InvalidStringLength)
+        /// );
+        /// ```
+        fn validate_bytes(raw: &[u8]) -> Result<(), ParseError>;
+
+        /// Compare with the binary representation of another fuzzy hash
+        /// (with a configuration) and return the distance between them.
+        ///
+        /// `raw` is interpreted as the binary representation (as stored by
+        /// [`store_into_bytes()`](Self::store_into_bytes())) and validated
+        /// the same way as [`TryFrom<&[u8]>`](TryFrom).  So, its length must
+        /// be [`SIZE_IN_BYTES`](Self::SIZE_IN_BYTES).
+        ///
+        /// This is useful to scan a packed binary store of fuzzy hashes
+        /// because each part is read directly from `raw` (no fuzzy hash
+        /// object is constructed).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        /// use tlsh::ComparisonConfiguration;
+        ///
+        /// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+        /// let hash1 = Tlsh::from_str(hash1).unwrap();
+        /// let hash2 = Tlsh::from_str(hash2).unwrap();
+        /// let raw: [u8; Tlsh::SIZE_IN_BYTES] = hash2.to_bytes();
+        /// let config = ComparisonConfiguration::Default;
+        /// assert_eq!(
+        ///     hash1.compare_to_bytes(&raw, config),
+        ///     Ok(hash1.compare_with_config(&hash2, config))
+        /// );
+        /// ```
+        fn compare_to_bytes(
+            &self,
+            raw: &[u8],
+            config: ComparisonConfiguration,
+        ) -> Result<u32, ParseError>;
+
+        /// Finds fuzzy hashes in a slice whose distance from this one
+        /// (with [the default configuration](ComparisonConfiguration::Default))
+        /// does not exceed `max_distance`.
+        ///
+        /// The resulting iterator yields a tuple of the index, the reference
+        /// to the matched fuzzy hash and the distance (in the order of
+        /// the slice).  It uses [`compare_within()`](Self::compare_within())
+        /// so that the comparison against distant fuzzy hashes stops early.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+        /// let hash1 = Tlsh::from_str(hash1).unwrap();
+        /// let hash2 = Tlsh::from_str(hash2).unwrap();
+        /// let corpus = [hash2, hash1];
+        /// let found: Vec<_> = hash1.find_within(&corpus, 100).collect();
+        /// assert_eq!(found, [(1, &hash1, 0)]);
+        /// let found: Vec<_> = hash1.find_within(&corpus, 200).collect();
+        /// assert_eq!(found, [(0, &hash2, 138), (1, &hash1, 0)]);
+        /// ```
+        #[inline]
+        fn find_within<'a>(
+            &'a self,
+            others: &'a [Self],
+            max_distance: u32,
+        ) -> FindWithin<'a, Self> {
+            FindWithin::new(self, others, max_distance)
+        }
+
+        /// Compare with another instance and return the normalized
+        /// similarity score (in the range of `0..=100`).
+        ///
+        /// The score is computed from [the distance](Self::compare()) and
+        /// [the maximum distance](Self::max_distance()) (both on
+        /// [the default configuration](ComparisonConfiguration::Default)).
+        /// `100` means that two fuzzy hashes are identical and `0` means that
+        /// they are maximally different.
+        ///
+        /// Note that this is a simple linear mapping and typical distances
+        /// between unrelated data are much smaller than the maximum distance.
+        /// So, the score tends to be high even if two inputs are unrelated.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+        /// let hash1 = Tlsh::from_str(hash1).unwrap();
+        /// let hash2 = Tlsh::from_str(hash2).unwrap();
+        /// assert_eq!(hash1.similarity(&hash1), 100);
+        /// assert_eq!(hash1.similarity(&hash2), 95);
+        /// ```
+        #[inline]
+        fn similarity(&self, other: &Self) -> u8 {
+            Similarity::from_distance::<Self>(self.compare_typed(other)).value()
+        }
+
+        /// Compare with another instance and return the normalized
+        /// similarity (in the range of `0.0..=1.0`) as [`f64`].
+        ///
+        /// The similarity is computed as follows (both on
+        /// [the default configuration](ComparisonConfiguration::Default)):
+        ///
+        /// ```text
+        /// 1.0 - compare(other) / max_distance(Default)
+        /// ```
+        ///
+        /// and is clamped to `0.0..=1.0`.  `1.0` means that two fuzzy hashes
+        /// are identical and `0.0` means that they are maximally different.
+        ///
+        /// Note that [the maximum distance](Self::max_distance()) differs
+        /// depending on the variant (the number of buckets and the checksum
+        /// length).  So, the similarity is only comparable between the same
+        /// fuzzy hash type.  Like [`similarity()`](Self::similarity()),
+        /// the similarity tends to be high even if two inputs are unrelated.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        /// use tlsh::ComparisonConfiguration;
+        ///
+        /// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+        /// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+        /// let hash1 = Tlsh::from_str(hash1).unwrap();
+        /// let hash2 = Tlsh::from_str(hash2).unwrap();
+        /// assert_eq!(hash1.similarity_f64(&hash1), 1.0);
+        /// let max_distance = Tlsh::max_distance(ComparisonConfiguration::Default);
+        /// assert_eq!(
+        ///     hash1.similarity_f64(&hash2),
+        ///     1.0 - hash1.compare(&hash2) as f64 / max_distance as f64
+        /// );
+        /// ```
+        #[inline]
+        fn similarity_f64(&self, other: &Self) -> f64 {
+            let max_distance = Self::max_distance(ComparisonConfiguration::Default) as f64;
+            let distance = self.compare(other) as f64;
+            (1.0 - distance / max_distance).clamp(0.0, 1.0)
+        }
+
+        /// (fast-tlsh specific)
+        /// Computes a 64-bit locality-sensitive key for coarse bucketing.
+        ///
+        /// Similar fuzzy hashes tend to share the same key (or, at least,
+        /// its prefix) so that it can be used to build coarse hash tables
+        /// and to shrink the candidate set before exact comparison
+        /// (e.g. by [`compare()`](Self::compare())).
+        ///
+        /// Note that this is just a heuristic for candidate generation and
+        /// *not* a distance.  Similar fuzzy hashes may have completely
+        /// different keys and the same key does not mean that the fuzzy
+        /// hashes are similar.
+        ///
+        /// # Layout
+        ///
+        /// The layout is stable (does not depend on the build configuration):
+        ///
+        /// Bits      | Meaning
+        /// --------- | ---------------------------------------------------------
+        /// `63..=56` | The length band (the [encoded length](FuzzyHashLengthEncoding::value()) divided by 16)
+        /// `55..=0`  | The most significant bit of the quartile values of buckets `0..56` (bucket 0 is bit 55)
+        ///
+        /// The most significant bit of a quartile value represents whether
+        /// the bucket exceeds the median.  If the number of buckets is less
+        /// than 56, remaining (least significant) bits are zero.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash1 = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2").unwrap();
+        /// let hash2 = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA3").unwrap();
+        /// // Only the bucket 0 differs (0b10 and 0b11).
+        /// assert_eq!(hash1.compare(&hash2), 1);
+        /// assert_eq!(hash1.lsh_key(), hash2.lsh_key());
+        /// // The length band is placed in the most significant bits.
+        /// assert_eq!(hash1.lsh_key() >> 56, (hash1.length().value() / 16) as u64);
+        /// ```
+        fn lsh_key(&self) -> u64 {
+            const NUM_KEY_BUCKETS: usize = 56;
+            let body = self.body();
+            let mut key = (self.length().value() / 16) as u64;
+            for index in 0..NUM_KEY_BUCKETS {
+                let bit = if index < Self::NUMBER_OF_BUCKETS {
+                    body.quartile(index) >> 1
+                } else {
+                    0
+                };
+                key = (key << 1) | bit as u64;
+            }
+            key
+        }
+
+        /// Clear the checksum for comparison with another fuzzy hash without checksum.
+        fn clear_checksum(&mut self);
+
+        /// (fast-tlsh specific)
+        /// Clear the quartile values (set to `0b00`) of specified buckets.
+        ///
+        /// This is useful to exclude buckets known to be noisy before
+        /// comparison.  Indices are the same as
+        /// [`body_diff()`](Self::body_diff()) and
+        /// [`FuzzyHashBody::quartile()`].
+        ///
+        /// Note that a masked bucket is *not* ignored on comparison but
+        /// compared as `0b00`.  So, comparing a masked fuzzy hash with
+        /// an unmasked one still counts the difference on masked buckets.
+        /// To exclude buckets from the comparison, mask the same buckets on
+        /// both sides.
+        ///
+        /// # Panics
+        ///
+        /// It panics if any of the indices is not less than
+        /// [`NUMBER_OF_BUCKETS`](Self::NUMBER_OF_BUCKETS).
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash1 = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2").unwrap();
+        /// let hash2 = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA1").unwrap();
+        /// // Only the bucket 0 differs (0b10 and 0b01).
+        /// assert_eq!(hash1.compare(&hash2), 1);
+        /// let (mut masked1, mut masked2) = (hash1, hash2);
+        /// masked1.mask_buckets(&[0, 1]);
+        /// masked2.mask_buckets(&[0, 1]);
+        /// assert_eq!(masked1.compare(&masked2), 0);
+        /// // Masking only one side does not exclude the buckets.
+        /// assert_ne!(masked1.compare(&hash2), 0);
+        /// ```
+        fn mask_buckets(&mut self, indices: &[usize]);
+
+        /// Returns whether all parts of this fuzzy hash are zero.
+        ///
+        /// Such fuzzy hash is not generated from a real input in practice.
+        /// It is useful as a placeholder (e.g. in a fixed-size record) in place of
+        /// the null hash (`"TNULL"`) of the official implementation.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
+        ///
+        /// let hash = Tlsh::from_str(
+        ///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+        /// ).unwrap();
+        /// assert!(!hash.is_null());
+        /// let hash = Tlsh::try_from([0u8; Tlsh::SIZE_IN_BYTES].as_slice()).unwrap();
+        /// assert!(hash.is_null());
+        /// ```
+        fn is_null(&self) -> bool;
+
+        /// (fast-tlsh specific)
+        /// Checks internal invariants of this fuzzy hash.
+        ///
+        /// It returns [`false`] if this fuzzy hash could not have been
+        /// generated from a real input.  It checks:
+        ///
+        /// *   the validity of [the checksum](FuzzyHashChecksum::is_valid())
+        ///     (on the variant with 48 buckets, the 1-byte checksum must not
+        ///     exceed the number of buckets) and
+        /// *   the validity of
+        ///     [the length encoding](FuzzyHashLengthEncoding::is_valid())
+        ///     (it must correspond to a 32-bit size).
+        ///
+        /// Those are the same checks as the `strict-parser` feature performs
+        /// on parsing but this method can be used on already constructed
+        /// objects (e.g. parsed without the `strict-parser` feature or
+        /// deserialized from a storage) as a sanity check.
         ///
-        /// This method concatenates:
+        /// Note that the null (all-zero) fuzzy hash is considered consistent.
         ///
-        /// 1.  Checksum
-        /// 2.  Length encoding
-        /// 3.  Q ratio pair
-        /// 4.  Body
+        /// # Example
         ///
-        /// in that order (without any explicit variable length encodings or
-        /// separators).  Each binary representation of the part can be
-        /// retrieved as either an [`u8`] value or a slice / array of [`u8`].
+        /// ```
+        /// use core::str::FromStr;
+        /// use tlsh::prelude::*;
         ///
-        /// See [struct documentation](Self#tlsh-internals) for details.
-        fn store_into_bytes(&self, out: &mut [u8]) -> Result<usize, OperationError>;
+        /// let hash = Tlsh::from_str(
+        ///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+        /// ).unwrap();
+        /// assert!(hash.self_consistency());
+        /// // Length encoding 0xaa does not correspond to any 32-bit size.
+        /// let hash = Tlsh::from_str(
+        ///     "T14DAADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888"
+        /// );
+        /// if let Ok(hash) = hash {
+        ///     // Only without the `strict-parser` feature.
+        ///     assert!(!hash.self_consistency());
+        /// }
+        /// ```
+        #[inline]
+        fn self_consistency(&self) -> bool {
+            self.checksum().is_valid() && self.length().is_valid()
+        }
 
-        /// Store the contents of this object to the specified slice
-        /// (in the TLSH's hexadecimal representation).
+        /// (fast-tlsh specific)
+        /// Returns the approximate size of the input data (as an inclusive
+        /// range) which produced this fuzzy hash.
         ///
-        /// This method stores the contents as a TLSH's hexadecimal string
-        /// representation with [the specified prefix](HexStringPrefix).
-        fn store_into_str_bytes(
-            &self,
-            out: &mut [u8],
-            prefix: HexStringPrefix,
-        ) -> Result<usize, OperationError>;
-
-        /// Compute the max distance on [comparison](Self::compare()) with
-        /// the specified comparison configuration.
+        /// This is a shorthand of
+        /// [`length()`](Self::length())[`.range()`](FuzzyHashLengthEncoding::range())
+        /// and returns [`None`] if the length encoding is too large to
+        /// correspond to any 32-bit size.
         ///
-        /// If you need the maximum distance on the default configuration,
-        /// use the first argument of [`Default::default()`].
-        fn max_distance(config: ComparisonConfiguration) -> u32;
-
-        /// Compare with another instance (with a configuration) and
-        /// return the distance between them.
+        /// # Example
         ///
-        /// Normally, you will likely use the default configuration and use
-        /// [`compare()`](Self::compare()) instead.
-        fn compare_with_config(&self, other: &Self, config: ComparisonConfiguration) -> u32;
-
-        /// Compare with another instance with [the default configuration](ComparisonConfiguration::Default)
-        /// and return the distance between them.
+        /// ```
+        /// use tlsh::prelude::*;
         ///
-        /// If you need to use a non-default option, use
-        /// [`compare_with_config()`](Self::compare_with_config()) instead.
-        #[inline(always)]
-        fn compare(&self, other: &Self) -> u32 {
-            self.compare_with_config(other, ComparisonConfiguration::Default)
+        /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+        ///     sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        /// let hash = Tlsh::from_iter(data.iter().copied()).unwrap();
+        /// let range = hash.approximate_size().unwrap();
+        /// assert!(range.contains(&(data.len() as u32)));
+        /// ```
+        #[inline]
+        fn approximate_size(&self) -> Option<RangeInclusive<u32>> {
+            self.length().range()
         }
-
-        /// Clear the checksum for comparison with another fuzzy hash without checksum.
-        fn clear_checksum(&mut self);
     }
 }
 
+/// Checks whether the given string is the null hash
+/// (`"TNULL"` or `"NULL"`, case-insensitive).
+#[inline]
+pub(crate) fn is_null_hash_str(bytes: &[u8]) -> bool {
+    bytes.eq_ignore_ascii_case(b"TNULL") || bytes.eq_ignore_ascii_case(b"NULL")
+}
+
 /// The inner representation and its implementation.
 pub(crate) mod inner {
     use super::*;
 
     use crate::buckets::constrained::{FuzzyHashBucketMapper, FuzzyHashBucketsInfo};
+    use crate::hash::body::inner::InnerBody;
     use crate::hash::body::FuzzyHashBodyData;
     use crate::hash::checksum::FuzzyHashChecksumData;
     use crate::macros::{invariant, optionally_unsafe};
     use crate::params::{ConstrainedVerboseFuzzyHashParams, VerboseFuzzyHashParams};
     #[cfg(not(feature = "opt-simd-convert-hex"))]
     use crate::parse::hex_str::encode_array;
-    use crate::parse::hex_str::{encode_rev_1, encode_rev_array};
+    use crate::parse::hex_str::{
+        decode_1_const, decode_rev_1_const, encode_rev_1, encode_rev_array,
+    };
 
     /// The struct representing a fuzzy hash.
     ///
     /// This type is used as an inner representation of [`super::FuzzyHash`].
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct FuzzyHash<
         const SIZE_CKSUM: usize,
         const SIZE_BODY: usize,
@@ -345,7 +1571,7 @@ pub(crate) mod inner {
         >: ConstrainedVerboseFuzzyHashParams,
     {
         /// Creates an object from its raw parts.
-        pub(crate) fn from_raw(
+        pub(crate) const fn from_raw(
             body: FuzzyHashBodyData<SIZE_BODY>,
             checksum: FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>,
             lvalue: FuzzyHashLengthEncoding,
@@ -358,6 +1584,116 @@ pub(crate) mod inner {
                 qratios,
             }
         }
+
+        /// Validates the binary representation without constructing
+        /// an object.
+        ///
+        /// This performs the same checks as
+        /// [`TryFrom<&[u8; SIZE_IN_BYTES]>`](TryFrom) (which only fails with
+        /// the `strict-parser` feature).
+        #[inline(always)]
+        fn validate_raw(value: &[u8; SIZE_IN_BYTES]) -> Result<(), ParseError> {
+            #[cfg(feature = "strict-parser")]
+            {
+                let checksum = FuzzyHashChecksumData::<SIZE_CKSUM, SIZE_BUCKETS>::from_raw(
+                    value[0..SIZE_CKSUM].try_into().unwrap(),
+                );
+                if !checksum.is_valid() {
+                    return Err(ParseError::InvalidChecksum);
+                }
+                if !FuzzyHashLengthEncoding::from_raw(value[SIZE_CKSUM]).is_valid() {
+                    return Err(ParseError::LengthIsTooLarge);
+                }
+            }
+            #[cfg(not(feature = "strict-parser"))]
+            let _ = value;
+            Ok(())
+        }
+
+        /// Compare with the (validated) binary representation of another
+        /// fuzzy hash and return the distance between them.
+        ///
+        /// Each part is read directly from `raw` and the result is the same
+        /// as [`FuzzyHashType::compare_with_config()`].
+        #[inline]
+        fn compare_to_raw(
+            &self,
+            raw: &[u8; SIZE_IN_BYTES],
+            config: ComparisonConfiguration,
+        ) -> u32 {
+            let distance_body = self.body.compare_raw(&raw[SIZE_CKSUM + 2..]);
+            if config == ComparisonConfiguration::BodyOnly {
+                return distance_body;
+            }
+            let checksum = FuzzyHashChecksumData::<SIZE_CKSUM, SIZE_BUCKETS>::from_raw(
+                raw[0..SIZE_CKSUM].try_into().unwrap(),
+            );
+            let lvalue = FuzzyHashLengthEncoding::from_raw(raw[SIZE_CKSUM]);
+            let qratios = FuzzyHashQRatios::from_raw(raw[SIZE_CKSUM + 1]);
+            (distance_body
+                + config.weight_checksum_distance(self.checksum.compare(&checksum))
+                + self.qratios.compare(&qratios))
+            .saturating_add(config.weight_length_distance(self.lvalue.compare(&lvalue)))
+        }
+
+        /// Parses the TLSH's hexadecimal representation (in a `const` context).
+        ///
+        /// The existence of the prefix is auto-detected.  It performs the
+        /// same checks as [`FuzzyHashType::from_str_bytes()`] *without*
+        /// the `strict-parser` feature.
+        ///
+        /// # Panics
+        ///
+        /// It panics if the given string is not a valid fuzzy hash.
+        /// On constant evaluation, it causes a compile error instead.
+        pub(crate) const fn from_str_bytes_const(bytes: &[u8]) -> Self {
+            let mut offset = if bytes.len() == SIZE_IN_STR_BYTES {
+                if (bytes[0] != b'T' && bytes[0] != b't') || bytes[1] != b'1' {
+                    panic!("invalid prefix");
+                }
+                2
+            } else if bytes.len() == SIZE_IN_STR_BYTES - 2 {
+                0
+            } else {
+                panic!("invalid string length");
+            };
+            let mut checksum = [0u8; SIZE_CKSUM];
+            let mut i = 0;
+            while i < SIZE_CKSUM {
+                checksum[i] = match decode_rev_1_const(bytes, offset) {
+                    Some(value) => value,
+                    None => panic!("invalid character"),
+                };
+                offset += 2;
+                i += 1;
+            }
+            let lvalue = match decode_rev_1_const(bytes, offset) {
+                Some(value) => value,
+                None => panic!("invalid character"),
+            };
+            offset += 2;
+            let qratios = match decode_rev_1_const(bytes, offset) {
+                Some(value) => value,
+                None => panic!("invalid character"),
+            };
+            offset += 2;
+            let mut body = [0u8; SIZE_BODY];
+            let mut i = 0;
+            while i < SIZE_BODY {
+                body[i] = match decode_1_const(bytes, offset) {
+                    Some(value) => value,
+                    None => panic!("invalid character"),
+                };
+                offset += 2;
+                i += 1;
+            }
+            Self::from_raw(
+                FuzzyHashBodyData::from_raw(body),
+                FuzzyHashChecksumData::from_raw(&checksum),
+                FuzzyHashLengthEncoding::from_raw(lvalue),
+                FuzzyHashQRatios::from_raw(qratios),
+            )
+        }
     }
 
     impl<
@@ -387,12 +1723,16 @@ pub(crate) mod inner {
         const SIZE_IN_BYTES: usize = SIZE_IN_BYTES;
         const LEN_IN_STR_EXCEPT_PREFIX: usize = SIZE_IN_STR_BYTES - 2;
         const LEN_IN_STR: usize = SIZE_IN_STR_BYTES;
+        const LEN_IN_BASE64: usize = base64::encoded_len(SIZE_IN_BYTES);
 
         #[inline]
         fn from_str_bytes(
             bytes: &[u8],
             prefix: Option<HexStringPrefix>,
         ) -> Result<Self, ParseError> {
+            if is_null_hash_str(bytes) {
+                return Err(ParseError::NullHash);
+            }
             let mut bytes = bytes;
             let prefix = match prefix {
                 None => {
@@ -416,7 +1756,7 @@ pub(crate) mod inner {
                     if bytes.len() != Self::LEN_IN_STR {
                         return Err(ParseError::InvalidStringLength);
                     }
-                    if &bytes[0..2] != b"T1" {
+                    if !bytes[0..2].eq_ignore_ascii_case(b"T1") {
                         return Err(ParseError::InvalidPrefix);
                     }
                     bytes = &bytes[2..];
@@ -461,6 +1801,15 @@ pub(crate) mod inner {
         fn body(&self) -> &Self::BodyType {
             &self.body
         }
+        #[inline(always)]
+        fn from_parts(
+            checksum: Self::ChecksumType,
+            length: FuzzyHashLengthEncoding,
+            qratios: FuzzyHashQRatios,
+            body: Self::BodyType,
+        ) -> Self {
+            Self::from_raw(body, checksum, length, qratios)
+        }
 
         #[inline]
         fn store_into_bytes(&self, out: &mut [u8]) -> Result<usize, crate::errors::OperationError> {
@@ -474,6 +1823,24 @@ pub(crate) mod inner {
             Ok(Self::SIZE_IN_BYTES)
         }
 
+        #[inline]
+        fn store_into_bytes_le(&self, out: &mut [u8]) -> Result<usize, OperationError> {
+            let len = self.store_into_bytes(out)?;
+            out[SIZE_CKSUM + 2..SIZE_IN_BYTES].reverse();
+            Ok(len)
+        }
+
+        #[inline]
+        fn from_bytes_le(bytes: &[u8]) -> Result<Self, ParseError> {
+            if bytes.len() != SIZE_IN_BYTES {
+                return Err(ParseError::InvalidStringLength);
+            }
+            let mut buf = [0u8; SIZE_IN_BYTES];
+            buf.copy_from_slice(bytes);
+            buf[SIZE_CKSUM + 2..].reverse();
+            Self::try_from(&buf)
+        }
+
         #[inline]
         fn store_into_str_bytes(
             &self,
@@ -510,31 +1877,123 @@ pub(crate) mod inner {
             Ok(len)
         }
 
+        fn from_base64(s: &str) -> Result<Self, ParseError> {
+            if s.len() != Self::LEN_IN_BASE64 {
+                return Err(ParseError::InvalidStringLength);
+            }
+            let mut buf = [0u8; SIZE_IN_BYTES];
+            base64::decode(&mut buf, s.as_bytes()).ok_or(ParseError::InvalidCharacter)?;
+            Self::try_from(&buf)
+        }
+
+        #[inline]
+        fn to_base64_into(&self, out: &mut [u8]) -> Result<usize, OperationError> {
+            if out.len() < Self::LEN_IN_BASE64 {
+                return Err(OperationError::BufferIsTooSmall);
+            }
+            let mut buf = [0u8; SIZE_IN_BYTES];
+            self.store_into_bytes(&mut buf).unwrap();
+            base64::encode(&mut out[..Self::LEN_IN_BASE64], &buf);
+            Ok(Self::LEN_IN_BASE64)
+        }
+
         #[inline]
         fn max_distance(config: ComparisonConfiguration) -> u32 {
-            FuzzyHashBodyData::<SIZE_BODY>::MAX_DISTANCE
-                + FuzzyHashChecksumData::<SIZE_CKSUM, SIZE_BUCKETS>::MAX_DISTANCE
-                + FuzzyHashQRatios::MAX_DISTANCE
-                + (match config {
-                    ComparisonConfiguration::Default => FuzzyHashLengthEncoding::MAX_DISTANCE,
-                    ComparisonConfiguration::NoLength => 0,
-                })
+            Self::max_distance_parts(config).total()
         }
 
         #[inline]
         fn compare_with_config(&self, other: &Self, config: ComparisonConfiguration) -> u32 {
-            self.body.compare(&other.body)
-                + self.checksum.compare(&other.checksum)
-                + self.qratios.compare(&other.qratios)
-                + (match config {
-                    ComparisonConfiguration::Default => self.lvalue.compare(&other.lvalue),
-                    ComparisonConfiguration::NoLength => 0,
-                })
+            // Fast path: identical fuzzy hashes (common on duplicate-heavy
+            // data sets) always have the distance zero.
+            if self == other {
+                return 0;
+            }
+            if config == ComparisonConfiguration::BodyOnly {
+                return self.body.compare(&other.body);
+            }
+            (self.body.compare(&other.body)
+                + config.weight_checksum_distance(self.checksum.compare(&other.checksum))
+                + self.qratios.compare(&other.qratios))
+            .saturating_add(config.weight_length_distance(self.lvalue.compare(&other.lvalue)))
+        }
+
+        #[inline]
+        fn compare_within_with_config(
+            &self,
+            other: &Self,
+            config: ComparisonConfiguration,
+            max_distance: u32,
+        ) -> Option<u32> {
+            if config == ComparisonConfiguration::BodyOnly {
+                return self.body.compare_within(&other.body, max_distance);
+            }
+            // Compare cheap parts first.
+            let distance = (config
+                .weight_checksum_distance(self.checksum.compare(&other.checksum))
+                + self.qratios.compare(&other.qratios))
+            .saturating_add(config.weight_length_distance(self.lvalue.compare(&other.lvalue)));
+            if distance > max_distance {
+                return None;
+            }
+            let distance_body = self
+                .body
+                .compare_within(&other.body, max_distance - distance)?;
+            Some(distance + distance_body)
+        }
+
+        #[inline]
+        fn compare_parts(
+            &self,
+            other: &Self,
+            config: ComparisonConfiguration,
+        ) -> DistanceBreakdown {
+            DistanceBreakdown {
+                checksum: config.weight_checksum_distance(self.checksum.compare(&other.checksum)),
+                length: config.weight_length_distance(self.lvalue.compare(&other.lvalue)),
+                qratios: config.weight_qratios_distance(self.qratios.compare(&other.qratios)),
+                body: self.body.compare(&other.body),
+            }
+        }
+
+        #[inline]
+        fn validate_bytes(raw: &[u8]) -> Result<(), ParseError> {
+            let raw: &[u8; SIZE_IN_BYTES] = raw
+                .try_into()
+                .map_err(|_| ParseError::InvalidStringLength)?;
+            Self::validate_raw(raw)
+        }
+
+        #[inline]
+        fn compare_to_bytes(
+            &self,
+            raw: &[u8],
+            config: ComparisonConfiguration,
+        ) -> Result<u32, ParseError> {
+            let raw: &[u8; SIZE_IN_BYTES] = raw
+                .try_into()
+                .map_err(|_| ParseError::InvalidStringLength)?;
+            Self::validate_raw(raw)?;
+            Ok(self.compare_to_raw(raw, config))
         }
 
         fn clear_checksum(&mut self) {
             self.checksum.clear();
         }
+
+        fn mask_buckets(&mut self, indices: &[usize]) {
+            for &index in indices {
+                assert!(index < SIZE_BUCKETS);
+                self.body.clear_quartile(index);
+            }
+        }
+
+        fn is_null(&self) -> bool {
+            self.checksum.data().iter().all(|&x| x == 0)
+                && self.lvalue.value() == 0
+                && self.qratios.value() == 0
+                && self.body.data().iter().all(|&x| x == 0)
+        }
     }
 
     impl<
@@ -561,18 +2020,11 @@ pub(crate) mod inner {
 
         #[inline]
         fn try_from(value: &[u8; SIZE_IN_BYTES]) -> Result<Self, Self::Error> {
+            Self::validate_raw(value)?;
             let checksum = FuzzyHashChecksumData::<SIZE_CKSUM, SIZE_BUCKETS>::from_raw(
                 value[0..SIZE_CKSUM].try_into().unwrap(),
             );
-            #[cfg(feature = "strict-parser")]
-            if !checksum.is_valid() {
-                return Err(ParseError::InvalidChecksum);
-            }
             let lvalue = FuzzyHashLengthEncoding::from_raw(value[SIZE_CKSUM]);
-            #[cfg(feature = "strict-parser")]
-            if !lvalue.is_valid() {
-                return Err(ParseError::LengthIsTooLarge);
-            }
             let qratios = FuzzyHashQRatios::from_raw(value[SIZE_CKSUM + 1]);
             let value = &value[SIZE_CKSUM + 2..];
             optionally_unsafe! {
@@ -682,6 +2134,62 @@ pub(crate) mod inner {
         }
     }
 
+    impl<
+            const SIZE_CKSUM: usize,
+            const SIZE_BODY: usize,
+            const SIZE_BUCKETS: usize,
+            const SIZE_IN_BYTES: usize,
+            const SIZE_IN_STR_BYTES: usize,
+        > Ord for FuzzyHash<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+    where
+        FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper,
+        FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>: FuzzyHashChecksum,
+        VerboseFuzzyHashParams<
+            SIZE_CKSUM,
+            SIZE_BODY,
+            SIZE_BUCKETS,
+            SIZE_IN_BYTES,
+            SIZE_IN_STR_BYTES,
+        >: ConstrainedVerboseFuzzyHashParams,
+    {
+        fn cmp(&self, other: &Self) -> Ordering {
+            // Lexicographic order of the binary format (store_into_bytes).
+            self.checksum
+                .data()
+                .cmp(other.checksum.data())
+                .then_with(|| self.lvalue.value().cmp(&other.lvalue.value()))
+                .then_with(|| self.qratios.value().cmp(&other.qratios.value()))
+                .then_with(|| self.body.data().cmp(other.body.data()))
+        }
+    }
+
+    impl<
+            const SIZE_CKSUM: usize,
+            const SIZE_BODY: usize,
+            const SIZE_BUCKETS: usize,
+            const SIZE_IN_BYTES: usize,
+            const SIZE_IN_STR_BYTES: usize,
+        > PartialOrd
+        for FuzzyHash<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>
+    where
+        FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper,
+        FuzzyHashChecksumData<SIZE_CKSUM, SIZE_BUCKETS>: FuzzyHashChecksum,
+        VerboseFuzzyHashParams<
+            SIZE_CKSUM,
+            SIZE_BODY,
+            SIZE_BUCKETS,
+            SIZE_IN_BYTES,
+            SIZE_IN_STR_BYTES,
+        >: ConstrainedVerboseFuzzyHashParams,
+    {
+        #[inline(always)]
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
     #[cfg(feature = "serde")]
     impl<
             const SIZE_CKSUM: usize,
@@ -727,11 +2235,38 @@ pub(crate) mod inner {
         }
     }
 
+    /// Reads exactly `N` bytes from a sequence (such as a JSON array of
+    /// integers) on deserialization.
+    ///
+    /// `visitor` is only used to construct an error.
+    #[cfg(feature = "serde")]
+    fn deserialize_byte_seq<'de, A, V, const N: usize>(
+        mut seq: A,
+        visitor: &V,
+    ) -> Result<[u8; N], A::Error>
+    where
+        A: SeqAccess<'de>,
+        V: Visitor<'de>,
+    {
+        let mut buffer = [0u8; N];
+        for (i, b) in buffer.iter_mut().enumerate() {
+            *b = seq
+                .next_element()?
+                .ok_or_else(|| serde::de::Error::invalid_length(i, visitor))?;
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(serde::de::Error::invalid_length(N + 1, visitor));
+        }
+        Ok(buffer)
+    }
+
     /// The visitor struct to handle [fuzzy hash](FuzzyHash) deserialization
     /// as either a string or a sequence of bytes.
     ///
     /// The corresponding visitor implementation handles a fuzzy hash as
     /// either a string or a sequence of bytes, both representing the string
+    /// representation of that fuzzy hash.  It also accepts a sequence of
+    /// integers (such as a JSON array), representing the binary
     /// representation of that fuzzy hash.
     ///
     /// This visitor is used on human-readable formats (such as JSON).
@@ -757,12 +2292,13 @@ pub(crate) mod inner {
 
     #[cfg(feature = "serde")]
     impl<
+            'de,
             const SIZE_CKSUM: usize,
             const SIZE_BODY: usize,
             const SIZE_BUCKETS: usize,
             const SIZE_IN_BYTES: usize,
             const SIZE_IN_STR_BYTES: usize,
-        > Visitor<'_>
+        > Visitor<'de>
         for FuzzyHashStringVisitor<
             SIZE_CKSUM,
             SIZE_BODY,
@@ -786,7 +2322,7 @@ pub(crate) mod inner {
             FuzzyHash<SIZE_CKSUM, SIZE_BODY, SIZE_BUCKETS, SIZE_IN_BYTES, SIZE_IN_STR_BYTES>;
 
         fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
-            formatter.write_str("a fuzzy hash string")
+            formatter.write_str("a fuzzy hash string or a sequence of bytes")
         }
 
         #[inline]
@@ -794,7 +2330,8 @@ pub(crate) mod inner {
         where
             E: serde::de::Error,
         {
-            self.visit_bytes(v.as_bytes())
+            // Same as FromStr (including surrounding whitespace trimming).
+            Self::Value::from_str(v).map_err(serde::de::Error::custom::<ParseError>)
         }
 
         fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
@@ -803,13 +2340,23 @@ pub(crate) mod inner {
         {
             Self::Value::from_str_bytes(v, None).map_err(serde::de::Error::custom::<ParseError>)
         }
+
+        fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let buffer = deserialize_byte_seq::<A, Self, SIZE_IN_BYTES>(seq, &self)?;
+            // It may fail if the `strict-parser` feature is enabled.
+            Self::Value::try_from(&buffer).map_err(serde::de::Error::custom::<ParseError>)
+        }
     }
 
     /// The visitor struct to handle [fuzzy hash](FuzzyHash) deserialization
     /// as a byte sequence.
     ///
     /// The corresponding visitor implementation handles a fuzzy hash as
-    /// a plain sequence of bytes.
+    /// a plain sequence of bytes (either a byte string or a sequence of
+    /// integers).
     ///
     /// This visitor is used on machine-friendly formats (such as Postcard).
     #[cfg(feature = "serde")]
@@ -834,12 +2381,13 @@ pub(crate) mod inner {
 
     #[cfg(feature = "serde")]
     impl<
+            'de,
             const SIZE_CKSUM: usize,
             const SIZE_BODY: usize,
             const SIZE_BUCKETS: usize,
             const SIZE_IN_BYTES: usize,
             const SIZE_IN_STR_BYTES: usize,
-        > Visitor<'_>
+        > Visitor<'de>
         for FuzzyHashBytesVisitor<
             SIZE_CKSUM,
             SIZE_BODY,
@@ -873,7 +2421,17 @@ pub(crate) mod inner {
             if v.len() != SIZE_IN_BYTES {
                 return Err(serde::de::Error::invalid_length(v.len(), &self));
             }
-            Ok(Self::Value::try_from(v).unwrap())
+            // It may fail if the `strict-parser` feature is enabled.
+            Self::Value::try_from(v).map_err(serde::de::Error::custom::<ParseError>)
+        }
+
+        fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let buffer = deserialize_byte_seq::<A, Self, SIZE_IN_BYTES>(seq, &self)?;
+            // It may fail if the `strict-parser` feature is enabled.
+            Self::Value::try_from(&buffer).map_err(serde::de::Error::custom::<ParseError>)
         }
     }
 
@@ -904,30 +2462,17 @@ pub(crate) mod inner {
             D: serde::Deserializer<'de>,
         {
             if deserializer.is_human_readable() {
-                #[cfg(feature = "serde-buffered")]
-                {
-                    deserializer.deserialize_string(
-                        FuzzyHashStringVisitor::<
-                            SIZE_CKSUM,
-                            SIZE_BODY,
-                            SIZE_BUCKETS,
-                            SIZE_IN_BYTES,
-                            SIZE_IN_STR_BYTES,
-                        >,
-                    )
-                }
-                #[cfg(not(feature = "serde-buffered"))]
-                {
-                    deserializer.deserialize_str(
-                        FuzzyHashStringVisitor::<
-                            SIZE_CKSUM,
-                            SIZE_BODY,
-                            SIZE_BUCKETS,
-                            SIZE_IN_BYTES,
-                            SIZE_IN_STR_BYTES,
-                        >,
-                    )
-                }
+                // Accept both a string (the string representation) and
+                // a sequence (the binary representation) transparently.
+                deserializer.deserialize_any(
+                    FuzzyHashStringVisitor::<
+                        SIZE_CKSUM,
+                        SIZE_BODY,
+                        SIZE_BUCKETS,
+                        SIZE_IN_BYTES,
+                        SIZE_IN_STR_BYTES,
+                    >,
+                )
             } else {
                 #[cfg(feature = "serde-buffered")]
                 {
@@ -992,7 +2537,17 @@ macro_rules! inner_type {
 ///     with the TLSH hexadecimal representation
 ///     using either [`FuzzyHashType::store_into_str_bytes()`] or
 ///     through the [`Display`]-based formatting (including [`ToString`]).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// # Ordering
+///
+/// This struct implements [`Ord`] for use in ordered containers (such as
+/// [`BTreeMap`](std::collections::BTreeMap)) and for deterministic output.
+///
+/// The order is the lexicographic order of the binary representation
+/// (as emitted by [`FuzzyHashType::store_into_bytes()`]).  Note that this
+/// order has *no* meaning related to the similarity.  For instance, two
+/// adjacent fuzzy hashes in this order are not necessarily similar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FuzzyHash<const SIZE_CKSUM: usize, const SIZE_BUCKETS: usize>
 where
     FuzzyHashParams<SIZE_CKSUM, SIZE_BUCKETS>: ConstrainedFuzzyHashParams,
@@ -1007,7 +2562,7 @@ where
 {
     /// Creates an object from the inner object.
     #[inline(always)]
-    pub(crate) fn new(inner: inner_type!(SIZE_CKSUM, SIZE_BUCKETS)) -> Self {
+    pub(crate) const fn new(inner: inner_type!(SIZE_CKSUM, SIZE_BUCKETS)) -> Self {
         Self { inner }
     }
 }
@@ -1025,6 +2580,7 @@ where
     const LEN_IN_STR_EXCEPT_PREFIX: usize =
         <inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::LEN_IN_STR_EXCEPT_PREFIX;
     const LEN_IN_STR: usize = <inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::LEN_IN_STR;
+    const LEN_IN_BASE64: usize = <inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::LEN_IN_BASE64;
     #[inline(always)]
     fn from_str_bytes(
         bytes: &[u8],
@@ -1050,10 +2606,29 @@ where
         self.inner.body()
     }
     #[inline(always)]
+    fn from_parts(
+        checksum: Self::ChecksumType,
+        length: FuzzyHashLengthEncoding,
+        qratios: FuzzyHashQRatios,
+        body: Self::BodyType,
+    ) -> Self {
+        Self::new(<inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::from_parts(
+            checksum, length, qratios, body,
+        ))
+    }
+    #[inline(always)]
     fn store_into_bytes(&self, out: &mut [u8]) -> Result<usize, crate::errors::OperationError> {
         self.inner.store_into_bytes(out)
     }
     #[inline(always)]
+    fn store_into_bytes_le(&self, out: &mut [u8]) -> Result<usize, OperationError> {
+        self.inner.store_into_bytes_le(out)
+    }
+    #[inline(always)]
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ParseError> {
+        <inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::from_bytes_le(bytes).map(Self::new)
+    }
+    #[inline(always)]
     fn store_into_str_bytes(
         &self,
         out: &mut [u8],
@@ -1062,6 +2637,14 @@ where
         self.inner.store_into_str_bytes(out, prefix)
     }
     #[inline(always)]
+    fn from_base64(s: &str) -> Result<Self, ParseError> {
+        <inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::from_base64(s).map(Self::new)
+    }
+    #[inline(always)]
+    fn to_base64_into(&self, out: &mut [u8]) -> Result<usize, OperationError> {
+        self.inner.to_base64_into(out)
+    }
+    #[inline(always)]
     fn max_distance(config: ComparisonConfiguration) -> u32 {
         <inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::max_distance(config)
     }
@@ -1070,9 +2653,43 @@ where
         self.inner.compare_with_config(&other.inner, config)
     }
     #[inline(always)]
+    fn compare_within_with_config(
+        &self,
+        other: &Self,
+        config: ComparisonConfiguration,
+        max_distance: u32,
+    ) -> Option<u32> {
+        self.inner
+            .compare_within_with_config(&other.inner, config, max_distance)
+    }
+    #[inline(always)]
+    fn compare_parts(&self, other: &Self, config: ComparisonConfiguration) -> DistanceBreakdown {
+        self.inner.compare_parts(&other.inner, config)
+    }
+    #[inline(always)]
+    fn validate_bytes(raw: &[u8]) -> Result<(), ParseError> {
+        <inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::validate_bytes(raw)
+    }
+    #[inline(always)]
+    fn compare_to_bytes(
+        &self,
+        raw: &[u8],
+        config: ComparisonConfiguration,
+    ) -> Result<u32, ParseError> {
+        self.inner.compare_to_bytes(raw, config)
+    }
+    #[inline(always)]
     fn clear_checksum(&mut self) {
         self.inner.clear_checksum()
     }
+    #[inline(always)]
+    fn mask_buckets(&mut self, indices: &[usize]) {
+        self.inner.mask_buckets(indices)
+    }
+    #[inline(always)]
+    fn is_null(&self) -> bool {
+        self.inner.is_null()
+    }
 }
 impl<const SIZE_CKSUM: usize, const SIZE_BUCKETS: usize> Display
     for FuzzyHash<SIZE_CKSUM, SIZE_BUCKETS>
@@ -1096,6 +2713,18 @@ where
         <inner_type!(SIZE_CKSUM, SIZE_BUCKETS)>::from_str(s).map(Self::new)
     }
 }
+impl<'a, const SIZE_CKSUM: usize, const SIZE_BUCKETS: usize> TryFrom<&'a str>
+    for FuzzyHash<SIZE_CKSUM, SIZE_BUCKETS>
+where
+    FuzzyHashParams<SIZE_CKSUM, SIZE_BUCKETS>: ConstrainedFuzzyHashParams,
+    inner_type!(SIZE_CKSUM, SIZE_BUCKETS): FromStr<Err = ParseError>,
+{
+    type Error = ParseError;
+    #[inline(always)]
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::from_str(value)
+    }
+}
 impl<'a, const SIZE_CKSUM: usize, const SIZE_BUCKETS: usize, const SIZE_IN_BYTES: usize>
     TryFrom<&'a [u8; SIZE_IN_BYTES]> for FuzzyHash<SIZE_CKSUM, SIZE_BUCKETS>
 where
@@ -1154,4 +2783,4 @@ where
     }
 }
 
-mod tests;
+pub(crate) mod tests;
@@ -3,16 +3,37 @@
 
 //! The body part of the fuzzy hash.
 
+#[cfg(feature = "experiment-variants")]
+use crate::buckets::NUM_BUCKETS_EXTRA_SHORT;
 use crate::buckets::{NUM_BUCKETS_LONG, NUM_BUCKETS_NORMAL, NUM_BUCKETS_SHORT};
 use crate::compare::dist_body::{
-    distance_12, distance_32, distance_64, MAX_DISTANCE_LONG, MAX_DISTANCE_NORMAL,
-    MAX_DISTANCE_SHORT,
+    distance_12, distance_32, distance_64, distance_within_12, distance_within_32,
+    distance_within_64, MAX_DISTANCE_LONG, MAX_DISTANCE_NORMAL, MAX_DISTANCE_SHORT,
 };
+#[cfg(feature = "experiment-variants")]
+use crate::compare::dist_body::{distance_6, distance_within_6, MAX_DISTANCE_EXTRA_SHORT};
 use crate::errors::ParseError;
 
-#[cfg(not(feature = "opt-simd-parse-hex"))]
+#[cfg(any(
+    not(feature = "opt-simd-parse-hex"),
+    all(
+        feature = "simd-per-arch",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        any(feature = "detect-features", target_feature = "ssse3")
+    )
+))]
 use crate::parse::hex_str::decode_array;
 
+/// (fast-tlsh specific)
+/// The body size of the experimental extra short variant
+/// (with 24 effective buckets).
+///
+/// Because we need 2-bits body for each bucket, this is the quarter of
+/// [the number of effective buckets](crate::buckets::NUM_BUCKETS_EXTRA_SHORT).
+#[cfg(feature = "experiment-variants")]
+#[cfg_attr(feature = "unstable", doc(cfg(feature = "experiment-variants")))]
+pub const BODY_SIZE_EXTRA_SHORT: usize = NUM_BUCKETS_EXTRA_SHORT / 4;
+
 /// The body size of the short variant (with 48 effective buckets).
 ///
 /// Because we need 2-bits body for each bucket, this is the quarter of
@@ -37,8 +58,20 @@ mod private {
     pub trait Sealed {}
 }
 
+/// The inner part.
+pub(crate) mod inner {
+    /// The trait representing crate-internal operations of the body.
+    pub trait InnerBody: super::private::Sealed {
+        /// Compare against the raw body data (as stored in the binary
+        /// representation) and return the distance between them.
+        ///
+        /// The length of `other` must be the same as the body size.
+        fn compare_raw(&self, other: &[u8]) -> u32;
+    }
+}
+
 /// The trait representing the body part of the fuzzy hash.
-pub trait FuzzyHashBody: private::Sealed {
+pub trait FuzzyHashBody: inner::InnerBody {
     /// The number of buckets in the body.
     const NUM_BUCKETS: usize;
     /// The size of the body in bytes.
@@ -54,13 +87,53 @@ pub trait FuzzyHashBody: private::Sealed {
     fn quartile(&self, index: usize) -> u8;
     /// Compare against another body and return the distance between them.
     fn compare(&self, other: &Self) -> u32;
+    /// Compare against another body and return the distance between them
+    /// only if it does not exceed `max_distance`.
+    ///
+    /// The computation may stop early once the running distance exceeds
+    /// `max_distance` (the granularity depends on the implementation).
+    fn compare_within(&self, other: &Self, max_distance: u32) -> Option<u32>;
+    /// Returns the packed quartile values (dibits) of all buckets.
+    ///
+    /// The length of the slice is [`SIZE`](Self::SIZE) and the layout is
+    /// stable (same as the body part of the binary and the hexadecimal
+    /// representations):
+    ///
+    /// *   Each byte contains quartile values (`0b00..=0b11`) of
+    ///     four buckets.
+    /// *   Buckets are stored in the *reverse* order of bytes.
+    ///     The last byte contains buckets `0..4` and the first byte contains
+    ///     the last four buckets.
+    /// *   In each byte, the lower bits contain the lower bucket.
+    ///
+    /// In other words, the quartile value of the bucket `i` is
+    /// `(dibits[SIZE - 1 - i / 4] >> (2 * (i % 4))) & 0b11`
+    /// (the same as [`quartile()`](Self::quartile())).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::str::FromStr;
+    /// use tlsh::prelude::*;
+    /// use tlsh::hash::body::FuzzyHashBody;
+    ///
+    /// let hash = Tlsh::from_str("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2").unwrap();
+    /// let dibits = hash.body().as_packed_dibits();
+    /// // The body part is the last 64 hexadecimal digits.
+    /// assert_eq!(dibits[0], 0x36);
+    /// assert_eq!(dibits[dibits.len() - 1], 0xa2);
+    /// // Bucket 0 is stored in the lowest bits of the last byte.
+    /// assert_eq!(hash.body().quartile(0), 0b10);
+    /// assert_eq!(hash.body().quartile(3), 0b10);
+    /// ```
+    fn as_packed_dibits(&self) -> &[u8];
 }
 
 /// The body part data of the fuzzy hash.
 ///
 /// For the main functionalities, see [`FuzzyHashBody`] documentation.
 #[repr(align(16))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct FuzzyHashBodyData<const SIZE_BODY: usize> {
     /// The raw body data.
     data: [u8; SIZE_BODY],
@@ -68,7 +141,7 @@ pub struct FuzzyHashBodyData<const SIZE_BODY: usize> {
 
 impl<const SIZE_BODY: usize> FuzzyHashBodyData<SIZE_BODY> {
     /// Creates an object from the existing body.
-    pub(crate) fn from_raw(data: [u8; SIZE_BODY]) -> Self {
+    pub(crate) const fn from_raw(data: [u8; SIZE_BODY]) -> Self {
         Self { data }
     }
 
@@ -81,11 +154,19 @@ impl<const SIZE_BODY: usize> FuzzyHashBodyData<SIZE_BODY> {
         }
         let mut data = [0u8; SIZE_BODY];
         cfg_if::cfg_if! {
-            if #[cfg(feature = "opt-simd-parse-hex")] {
+            if #[cfg(any(
+                not(feature = "opt-simd-parse-hex"),
+                all(
+                    feature = "simd-per-arch",
+                    any(target_arch = "x86", target_arch = "x86_64"),
+                    any(feature = "detect-features", target_feature = "ssse3")
+                )
+            ))] {
+                // Use the SIMD (if available) or scalar implementation.
+                let result = decode_array(&mut data, bytes);
+            } else {
                 let result =
                     hex_simd::decode(bytes, hex_simd::Out::from_slice(data.as_mut_slice())).is_ok();
-            } else {
-                let result = decode_array(&mut data, bytes);
             }
         }
         if result {
@@ -100,10 +181,58 @@ impl<const SIZE_BODY: usize> FuzzyHashBodyData<SIZE_BODY> {
     pub fn data(&self) -> &[u8; SIZE_BODY] {
         &self.data
     }
+
+    /// Clears the quartile value (set to `0b00`) for specified bucket.
+    ///
+    /// It panics if `index` is out of range.
+    #[inline]
+    pub(crate) fn clear_quartile(&mut self, index: usize) {
+        self.data[SIZE_BODY - 1 - index / 4] &= !(0b11 << (2 * (index % 4)));
+    }
+}
+
+// Extra short (24 bucket; experimental) body implementation
+#[cfg(feature = "experiment-variants")]
+impl private::Sealed for FuzzyHashBodyData<BODY_SIZE_EXTRA_SHORT> {}
+#[cfg(feature = "experiment-variants")]
+impl inner::InnerBody for FuzzyHashBodyData<BODY_SIZE_EXTRA_SHORT> {
+    #[inline(always)]
+    fn compare_raw(&self, other: &[u8]) -> u32 {
+        distance_6(&self.data, other.try_into().unwrap())
+    }
+}
+#[cfg(feature = "experiment-variants")]
+impl FuzzyHashBody for FuzzyHashBodyData<BODY_SIZE_EXTRA_SHORT> {
+    const NUM_BUCKETS: usize = NUM_BUCKETS_EXTRA_SHORT;
+    const SIZE: usize = BODY_SIZE_EXTRA_SHORT;
+    const MAX_DISTANCE: u32 = MAX_DISTANCE_EXTRA_SHORT;
+    #[inline(always)]
+    fn quartile(&self, index: usize) -> u8 {
+        assert!(index < Self::NUM_BUCKETS);
+        (self.data[self.data.len() - 1 - index / 4] >> (2 * (index % 4))) & 0b11
+    }
+    #[inline(always)]
+    fn compare(&self, other: &Self) -> u32 {
+        distance_6(&self.data, &other.data)
+    }
+    #[inline(always)]
+    fn compare_within(&self, other: &Self, max_distance: u32) -> Option<u32> {
+        distance_within_6(&self.data, &other.data, max_distance)
+    }
+    #[inline(always)]
+    fn as_packed_dibits(&self) -> &[u8] {
+        &self.data
+    }
 }
 
 // Short (48 bucket) body implementation
 impl private::Sealed for FuzzyHashBodyData<BODY_SIZE_SHORT> {}
+impl inner::InnerBody for FuzzyHashBodyData<BODY_SIZE_SHORT> {
+    #[inline(always)]
+    fn compare_raw(&self, other: &[u8]) -> u32 {
+        distance_12(&self.data, other.try_into().unwrap())
+    }
+}
 impl FuzzyHashBody for FuzzyHashBodyData<BODY_SIZE_SHORT> {
     const NUM_BUCKETS: usize = NUM_BUCKETS_SHORT;
     const SIZE: usize = BODY_SIZE_SHORT;
@@ -117,10 +246,24 @@ impl FuzzyHashBody for FuzzyHashBodyData<BODY_SIZE_SHORT> {
     fn compare(&self, other: &Self) -> u32 {
         distance_12(&self.data, &other.data)
     }
+    #[inline(always)]
+    fn compare_within(&self, other: &Self, max_distance: u32) -> Option<u32> {
+        distance_within_12(&self.data, &other.data, max_distance)
+    }
+    #[inline(always)]
+    fn as_packed_dibits(&self) -> &[u8] {
+        &self.data
+    }
 }
 
 // Normal (128 bucket) body implementation
 impl private::Sealed for FuzzyHashBodyData<BODY_SIZE_NORMAL> {}
+impl inner::InnerBody for FuzzyHashBodyData<BODY_SIZE_NORMAL> {
+    #[inline(always)]
+    fn compare_raw(&self, other: &[u8]) -> u32 {
+        distance_32(&self.data, other.try_into().unwrap())
+    }
+}
 impl FuzzyHashBody for FuzzyHashBodyData<BODY_SIZE_NORMAL> {
     const NUM_BUCKETS: usize = NUM_BUCKETS_NORMAL;
     const SIZE: usize = BODY_SIZE_NORMAL;
@@ -134,10 +277,24 @@ impl FuzzyHashBody for FuzzyHashBodyData<BODY_SIZE_NORMAL> {
     fn compare(&self, other: &Self) -> u32 {
         distance_32(&self.data, &other.data)
     }
+    #[inline(always)]
+    fn compare_within(&self, other: &Self, max_distance: u32) -> Option<u32> {
+        distance_within_32(&self.data, &other.data, max_distance)
+    }
+    #[inline(always)]
+    fn as_packed_dibits(&self) -> &[u8] {
+        &self.data
+    }
 }
 
 // Long (256 bucket) body implementation
 impl private::Sealed for FuzzyHashBodyData<BODY_SIZE_LONG> {}
+impl inner::InnerBody for FuzzyHashBodyData<BODY_SIZE_LONG> {
+    #[inline(always)]
+    fn compare_raw(&self, other: &[u8]) -> u32 {
+        distance_64(&self.data, other.try_into().unwrap())
+    }
+}
 impl FuzzyHashBody for FuzzyHashBodyData<BODY_SIZE_LONG> {
     const NUM_BUCKETS: usize = NUM_BUCKETS_LONG;
     const SIZE: usize = BODY_SIZE_LONG;
@@ -151,6 +308,63 @@ impl FuzzyHashBody for FuzzyHashBodyData<BODY_SIZE_LONG> {
     fn compare(&self, other: &Self) -> u32 {
         distance_64(&self.data, &other.data)
     }
+    #[inline(always)]
+    fn compare_within(&self, other: &Self, max_distance: u32) -> Option<u32> {
+        distance_within_64(&self.data, &other.data, max_distance)
+    }
+    #[inline(always)]
+    fn as_packed_dibits(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// The iterator over differing buckets between two bodies.
+///
+/// Each item is a tuple of the bucket index, the quartile value (`0b00..=0b11`)
+/// of the first body and the quartile value of the second body, yielded only
+/// if two quartile values differ (in the ascending order of the bucket index).
+///
+/// This is created by [`FuzzyHashType::body_diff()`](crate::FuzzyHashType::body_diff()).
+#[derive(Debug, Clone)]
+pub struct BodyDiff<'a, B: FuzzyHashBody> {
+    /// The first body.
+    lhs: &'a B,
+    /// The second body.
+    rhs: &'a B,
+    /// The next bucket index to check.
+    index: usize,
+}
+
+impl<'a, B: FuzzyHashBody> BodyDiff<'a, B> {
+    /// Creates an iterator over differing buckets between two bodies.
+    #[inline(always)]
+    pub(crate) fn new(lhs: &'a B, rhs: &'a B) -> Self {
+        Self { lhs, rhs, index: 0 }
+    }
 }
 
+impl<B: FuzzyHashBody> Iterator for BodyDiff<'_, B> {
+    type Item = (usize, u8, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < B::NUM_BUCKETS {
+            let index = self.index;
+            self.index += 1;
+            let q1 = self.lhs.quartile(index);
+            let q2 = self.rhs.quartile(index);
+            if q1 != q2 {
+                return Some((index, q1, q2));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(B::NUM_BUCKETS - self.index))
+    }
+}
+
+impl<B: FuzzyHashBody> core::iter::FusedIterator for BodyDiff<'_, B> {}
+
 mod tests;
@@ -219,3 +219,29 @@ fn compare_dibits_all() {
     test::<BODY_SIZE_NORMAL>();
     test::<BODY_SIZE_LONG>();
 }
+
+#[test]
+fn as_packed_dibits_layout() {
+    fn test<const SIZE_BODY: usize>()
+    where
+        FuzzyHashBodyData<SIZE_BODY>: FuzzyHashBody,
+    {
+        let mut data = [0u8; SIZE_BODY];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(0x3b).wrapping_add(0x17);
+        }
+        let body = FuzzyHashBodyData::<SIZE_BODY>::from_raw(data);
+        let dibits = body.as_packed_dibits();
+        assert_eq!(dibits.len(), FuzzyHashBodyData::<SIZE_BODY>::SIZE);
+        assert_eq!(dibits, body.data());
+        for index in 0..FuzzyHashBodyData::<SIZE_BODY>::NUM_BUCKETS {
+            assert_eq!(
+                (dibits[SIZE_BODY - 1 - index / 4] >> (2 * (index % 4))) & 0b11,
+                body.quartile(index)
+            );
+        }
+    }
+    test::<BODY_SIZE_SHORT>();
+    test::<BODY_SIZE_NORMAL>();
+    test::<BODY_SIZE_LONG>();
+}
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The borrowed binary representation of a fuzzy hash.
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+use crate::compare::ComparisonConfiguration;
+use crate::errors::ParseError;
+use crate::FuzzyHashType;
+
+/// (fast-tlsh specific)
+/// The borrowed binary representation of a fuzzy hash.
+///
+/// This is a validated reference to the bytes (as stored by
+/// [`FuzzyHashType::store_into_bytes()`]) of a fuzzy hash of type `T`,
+/// typically in a memory-mapped digest store or a deserializer's input.
+/// Creating this object only validates the bytes (by
+/// [`FuzzyHashType::validate_bytes()`]) and comparison against an owned
+/// fuzzy hash of type `T` reads each part directly from the borrowed bytes
+/// (by [`FuzzyHashType::compare_to_bytes()`]).  In both cases, no owned
+/// fuzzy hash of type `T` is constructed.
+///
+/// With the `serde` feature, this type can be deserialized (borrowing
+/// the input) from formats which support borrowed byte strings.
+///
+/// # Example
+///
+/// ```
+/// use core::str::FromStr;
+/// use tlsh::prelude::*;
+/// use tlsh::BorrowedTlsh;
+///
+/// let hash1 = "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008";
+/// let hash2 = "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C";
+/// let hash1 = Tlsh::from_str(hash1).unwrap();
+/// let hash2 = Tlsh::from_str(hash2).unwrap();
+/// let raw: [u8; Tlsh::SIZE_IN_BYTES] = hash2.to_bytes();
+///
+/// let borrowed = BorrowedTlsh::new(&raw).unwrap();
+/// assert_eq!(borrowed.compare(&hash1), hash1.compare(&hash2));
+/// assert_eq!(borrowed.compare(&hash2), 0);
+/// assert_eq!(borrowed.to_owned_hash(), hash2);
+///
+/// // The length must match.
+/// assert!(BorrowedTlsh::new(&raw[1..]).is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct BorrowedFuzzyHash<'a, T: FuzzyHashType> {
+    /// The validated binary representation.
+    bytes: &'a [u8],
+    /// The marker for the fuzzy hash type.
+    _marker: PhantomData<T>,
+}
+
+impl<T: FuzzyHashType> Clone for BorrowedFuzzyHash<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: FuzzyHashType> Copy for BorrowedFuzzyHash<'_, T> {}
+
+impl<'a, T> BorrowedFuzzyHash<'a, T>
+where
+    T: FuzzyHashType + for<'b> TryFrom<&'b [u8], Error = ParseError>,
+{
+    /// Creates an object from the binary representation of a fuzzy hash.
+    ///
+    /// `bytes` is validated the same way as [`TryFrom<&[u8]>`](TryFrom)
+    /// on `T` (so, its length must be
+    /// [`SIZE_IN_BYTES`](FuzzyHashType::SIZE_IN_BYTES)).
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ParseError> {
+        T::validate_bytes(bytes)?;
+        Ok(Self {
+            bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the borrowed binary representation.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Converts this object to an owned fuzzy hash.
+    #[inline]
+    pub fn to_owned_hash(&self) -> T {
+        T::try_from(self.bytes).unwrap()
+    }
+
+    /// Compare with an owned fuzzy hash (with a configuration) and
+    /// return the distance between them.
+    ///
+    /// The result is the same as
+    /// [`FuzzyHashType::compare_with_config()`] on the owned versions.
+    #[inline]
+    pub fn compare_with_config(&self, other: &T, config: ComparisonConfiguration) -> u32 {
+        // Validated on construction.
+        other.compare_to_bytes(self.bytes, config).unwrap()
+    }
+
+    /// Compare with an owned fuzzy hash
+    /// (with [the default configuration](ComparisonConfiguration::Default))
+    /// and return the distance between them.
+    #[inline(always)]
+    pub fn compare(&self, other: &T) -> u32 {
+        self.compare_with_config(other, ComparisonConfiguration::Default)
+    }
+}
+
+impl<'a, T> TryFrom<&'a [u8]> for BorrowedFuzzyHash<'a, T>
+where
+    T: FuzzyHashType + for<'b> TryFrom<&'b [u8], Error = ParseError>,
+{
+    type Error = ParseError;
+
+    #[inline(always)]
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for BorrowedFuzzyHash<'de, T>
+where
+    T: FuzzyHashType + for<'b> TryFrom<&'b [u8], Error = ParseError>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = <&'de [u8]>::deserialize(deserializer)?;
+        Self::new(bytes).map_err(serde::de::Error::custom::<ParseError>)
+    }
+}
+
+mod tests;
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::hash::borrowed`].
+
+#![cfg(test)]
+
+use super::BorrowedFuzzyHash;
+
+use crate::errors::ParseError;
+use crate::hash::tests::{random_valid_hashes, ALL_CONFIGS};
+use crate::hashes;
+use crate::FuzzyHashType;
+
+#[test]
+fn compare_equivalence() {
+    macro_rules! test {
+        ($ty: ty, $seed: literal) => {{
+            for pair in random_valid_hashes::<$ty>($seed, 2000).chunks_exact(2) {
+                let (hash1, hash2) = (pair[0], pair[1]);
+                let raw2 = hash2.to_bytes();
+                let borrowed = BorrowedFuzzyHash::<$ty>::new(&raw2).unwrap();
+                assert_eq!(borrowed.as_bytes(), raw2.as_slice());
+                assert_eq!(borrowed.to_owned_hash(), hash2);
+                assert_eq!(borrowed.compare(&hash1), hash1.compare(&hash2));
+                assert_eq!(borrowed.compare(&hash2), 0);
+                for config in ALL_CONFIGS {
+                    assert_eq!(
+                        borrowed.compare_with_config(&hash1, config),
+                        hash1.compare_with_config(&hash2, config)
+                    );
+                }
+            }
+        }};
+    }
+    test!(hashes::Short, 0x6c2e_91d7_4a0f_b358);
+    test!(hashes::Normal, 0xd49b_07a3_e1f6_2c85);
+    test!(hashes::NormalWithLongChecksum, 0x1f85_c3e0_9b7d_4a26);
+    test!(hashes::Long, 0x87a0_5d2f_36c4_e91b);
+    test!(hashes::LongWithLongChecksum, 0x3e6d_b814_f5a2_07c9);
+}
+
+#[test]
+fn invalid_length() {
+    let raw = [0u8; hashes::Normal::SIZE_IN_BYTES + 1];
+    for len in [
+        0,
+        hashes::Normal::SIZE_IN_BYTES - 1,
+        hashes::Normal::SIZE_IN_BYTES + 1,
+    ] {
+        assert_eq!(
+            BorrowedFuzzyHash::<hashes::Normal>::new(&raw[..len]),
+            Err(ParseError::InvalidStringLength)
+        );
+        assert_eq!(
+            BorrowedFuzzyHash::<hashes::Normal>::try_from(&raw[..len]),
+            Err(ParseError::InvalidStringLength)
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_borrowed() {
+    use serde::de::value::{BorrowedBytesDeserializer, Error};
+    use serde::Deserialize;
+
+    let hash = crate::generate::tests::LOREM_IPSUM_HASH_NORMAL;
+    let hash: hashes::Normal = core::str::FromStr::from_str(hash).unwrap();
+    let raw: [u8; hashes::Normal::SIZE_IN_BYTES] = hash.to_bytes();
+    let borrowed = BorrowedFuzzyHash::<hashes::Normal>::deserialize(BorrowedBytesDeserializer::<
+        Error,
+    >::new(&raw))
+    .unwrap();
+    assert_eq!(borrowed.as_bytes().as_ptr(), raw.as_ptr());
+    assert_eq!(borrowed.to_owned_hash(), hash);
+    // Invalid length.
+    assert!(BorrowedFuzzyHash::<hashes::Normal>::deserialize(
+        BorrowedBytesDeserializer::<Error>::new(&raw[1..]),
+    )
+    .is_err());
+}
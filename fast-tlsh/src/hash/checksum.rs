@@ -6,11 +6,13 @@
 use crate::buckets::constrained::{
     FuzzyHashBucketMapper, FuzzyHashBucketsInfo, LongFuzzyHashBucketMapper,
 };
+#[cfg(feature = "experiment-variants")]
+use crate::buckets::NUM_BUCKETS_EXTRA_SHORT;
 use crate::buckets::{NUM_BUCKETS_LONG, NUM_BUCKETS_NORMAL, NUM_BUCKETS_SHORT};
 use crate::compare::dist_checksum::{distance_1, distance_3};
 use crate::errors::ParseError;
 use crate::parse::hex_str::decode_rev_array;
-use crate::pearson::tlsh_b_mapping_256;
+use crate::pearson::{final_256, final_256_init, init, update_double};
 
 /// The length of the normal (1-byte) checksum.
 pub const CHECKSUM_SIZE_NORMAL: usize = 1;
@@ -52,6 +54,18 @@ struct OneByteChecksumChecker<const SIZE_BUCKETS: usize>
 where
     FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper;
 
+#[cfg(feature = "experiment-variants")]
+impl private::Sealed for OneByteChecksumChecker<NUM_BUCKETS_EXTRA_SHORT> {}
+#[cfg(feature = "experiment-variants")]
+impl inner::OneByteChecksumChecker for OneByteChecksumChecker<NUM_BUCKETS_EXTRA_SHORT> {
+    /// Returns whether the given checksum value is valid.
+    ///
+    /// In the 24 bucket variant, it checks whether the value is
+    /// **equal to or less than** [the number of buckets](NUM_BUCKETS_EXTRA_SHORT).
+    fn is_valid(checksum: u8) -> bool {
+        checksum <= NUM_BUCKETS_EXTRA_SHORT as u8
+    }
+}
 impl private::Sealed for OneByteChecksumChecker<NUM_BUCKETS_SHORT> {}
 impl inner::OneByteChecksumChecker for OneByteChecksumChecker<NUM_BUCKETS_SHORT> {
     /// Returns whether the given checksum value is valid.
@@ -90,12 +104,13 @@ pub trait FuzzyHashChecksum: inner::InnerChecksum {
 ///
 /// This type supports following configurations:
 ///
-/// *   1-byte checksum (on 48, 128, 256 bucket variants)
+/// *   1-byte checksum (on 48, 128, 256 bucket variants and
+///     the experimental 24 bucket variant)
 /// *   3-byte checksum (on 128, 256 bucket variants)
 ///
 /// For the main functionalities except [`data()`](Self::data()),
 /// see [`FuzzyHashChecksum`] documentation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct FuzzyHashChecksumData<const SIZE_CKSUM: usize, const SIZE_BUCKETS: usize>
 where
@@ -118,7 +133,7 @@ where
     }
 
     /// Creates the checksum object from the raw data.
-    pub(crate) fn from_raw(data: &[u8; SIZE_CKSUM]) -> Self {
+    pub(crate) const fn from_raw(data: &[u8; SIZE_CKSUM]) -> Self {
         Self { data: *data }
     }
 
@@ -199,9 +214,22 @@ where
 {
     #[inline(always)]
     fn update(&mut self, curr: u8, prev: u8) {
-        self.data[0] = FuzzyHashBucketsInfo::<SIZE_BUCKETS>::b_mapping(0, curr, prev, self.data[0]);
-        self.data[1] = tlsh_b_mapping_256(self.data[0], curr, prev, self.data[1]);
-        self.data[2] = tlsh_b_mapping_256(self.data[1], curr, prev, self.data[2]);
+        // This is equivalent to following (because the long checksum is
+        // only available on 128 and 256 bucket variants):
+        //
+        // data[0] = tlsh_b_mapping_256(0, curr, prev, data[0]);
+        // data[1] = tlsh_b_mapping_256(data[0], curr, prev, data[1]);
+        // data[2] = tlsh_b_mapping_256(data[1], curr, prev, data[2]);
+        //
+        // but `init(data[i])` for the next byte is computed in parallel
+        // with `data[i]` to shorten the chain of dependent table lookups.
+        let state = update_double(init(0), curr, prev);
+        let next = update_double(final_256_init(state, self.data[0]), curr, prev);
+        self.data[0] = final_256(state, self.data[0]);
+        let state = next;
+        let next = update_double(final_256_init(state, self.data[1]), curr, prev);
+        self.data[1] = final_256(state, self.data[1]);
+        self.data[2] = final_256(next, self.data[2]);
     }
 }
 impl<const SIZE_BUCKETS: usize> FuzzyHashChecksum
@@ -250,3 +250,34 @@ fn checksum_update_256_example() {
     }
     assert_eq!(state.data(), &[0xe4]);
 }
+
+#[test]
+fn checksum_update_long_equivalence() {
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use crate::pearson::tlsh_b_mapping_256;
+
+    fn test<const SIZE_BUCKETS: usize>()
+    where
+        FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper,
+        FuzzyHashChecksumData<CHECKSUM_SIZE_LONG, SIZE_BUCKETS>: FuzzyHashChecksum,
+    {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(0x58c3_0e9a_b7f1_2d64);
+        let mut data = [0u8; 4096];
+        rng.fill_bytes(&mut data);
+        let mut state = FuzzyHashChecksumData::<CHECKSUM_SIZE_LONG, SIZE_BUCKETS>::new();
+        let mut expected = [0u8; CHECKSUM_SIZE_LONG];
+        for window in data.windows(2) {
+            let (curr, prev) = (window[1], window[0]);
+            state.update(curr, prev);
+            // Naïve implementation
+            expected[0] = tlsh_b_mapping_256(0, curr, prev, expected[0]);
+            expected[1] = tlsh_b_mapping_256(expected[0], curr, prev, expected[1]);
+            expected[2] = tlsh_b_mapping_256(expected[1], curr, prev, expected[2]);
+            assert_eq!(state.data(), &expected);
+        }
+    }
+    test::<NUM_BUCKETS_NORMAL>();
+    test::<NUM_BUCKETS_LONG>();
+}
@@ -9,7 +9,7 @@ use crate::parse::hex_str::decode_rev_1;
 
 /// Inner Q ratio pair struct.
 #[bitfield_struct::bitfield(u8, order = Lsb)]
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Hash)]
 struct InnerQRatios {
     /// The "Q1 ratio" value.
     #[bits(4)]
@@ -20,7 +20,7 @@ struct InnerQRatios {
 }
 
 /// Q ratio pair encoded in a fuzzy hash.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct FuzzyHashQRatios {
     /// Inner Q ratio pair encoding.
@@ -46,9 +46,9 @@ impl FuzzyHashQRatios {
 
     /// Creates the object from the raw encoding.
     #[inline(always)]
-    pub(crate) fn from_raw(qratios: u8) -> Self {
+    pub(crate) const fn from_raw(qratios: u8) -> Self {
         Self {
-            qratios: InnerQRatios::from(qratios),
+            qratios: InnerQRatios::from_bits(qratios),
         }
     }
 
@@ -5,7 +5,7 @@
 
 #![cfg(test)]
 
-use super::{ComparisonConfiguration, HexStringPrefix};
+use super::{ComparisonConfiguration, HexCase, HexStringPrefix};
 
 use core::str::FromStr;
 
@@ -13,8 +13,44 @@ use crate::buckets::NUM_BUCKETS_SHORT;
 use crate::errors::{OperationError, ParseError};
 use crate::hashes;
 use crate::length::ENCODED_VALUE_SIZE;
+use crate::parse::base64;
 use crate::FuzzyHashType;
 
+/// All comparison configurations exercised by the tests.
+pub(crate) const ALL_CONFIGS: [ComparisonConfiguration; 5] = [
+    ComparisonConfiguration::Default,
+    ComparisonConfiguration::NoLength,
+    ComparisonConfiguration::NoChecksum,
+    ComparisonConfiguration::Weighted {
+        length_multiplier: 3,
+    },
+    ComparisonConfiguration::BodyOnly,
+];
+
+/// Generates fuzzy hashes from `count` random byte sequences.
+///
+/// Sequences rejected by the parser are skipped (this only happens with
+/// the `strict-parser` feature), so the result may be shorter than `count`.
+pub(crate) fn random_valid_hashes<T>(seed: u64, count: usize) -> Vec<T>
+where
+    T: FuzzyHashType + for<'a> TryFrom<&'a [u8], Error = ParseError>,
+{
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let mut bytes = vec![0u8; T::SIZE_IN_BYTES];
+    let mut hashes = Vec::with_capacity(count);
+    for _ in 0..count {
+        rng.fill_bytes(&mut bytes);
+        match T::try_from(bytes.as_slice()) {
+            Ok(hash) => hashes.push(hash),
+            Err(_) => assert!(cfg!(feature = "strict-parser")),
+        }
+    }
+    hashes
+}
+
 #[test]
 fn hex_string_prefix_default() {
     // Check its default value.
@@ -24,6 +60,12 @@ fn hex_string_prefix_default() {
     );
 }
 
+#[test]
+fn hex_case_default() {
+    // Check its default value.
+    assert_eq!(<HexCase as Default>::default(), HexCase::Upper);
+}
+
 #[test]
 fn comparison_configuration_default() {
     // Check its default value.
@@ -65,18 +107,132 @@ fn from_str_prefix() {
 }
 
 #[test]
-fn from_str_other_errors() {
+fn null_hash() {
+    macro_rules! test {
+        ($ty:ty) => {
+            for s in ["TNULL", "tnull", "NULL", " TNULL\n"] {
+                assert_eq!(
+                    <$ty>::from_str(s),
+                    Err(ParseError::NullHash),
+                    "failed on {s:?}"
+                );
+                assert_eq!(<$ty>::from_str_opt(s), Ok(None), "failed on {s:?}");
+            }
+            for prefix in [HexStringPrefix::Empty, HexStringPrefix::WithVersion] {
+                assert_eq!(
+                    <$ty>::from_str_with("TNULL", Some(prefix)),
+                    Err(ParseError::NullHash)
+                );
+            }
+            // Other errors are preserved.
+            assert_eq!(
+                <$ty>::from_str_opt("TNUL"),
+                Err(ParseError::InvalidStringLength)
+            );
+            assert_eq!(
+                <$ty>::from_str_opt("TNULLL"),
+                Err(ParseError::InvalidStringLength)
+            );
+            // All-zero fuzzy hash
+            let hash = <$ty>::try_from([0u8; <$ty>::SIZE_IN_BYTES].as_slice()).unwrap();
+            assert!(hash.is_null());
+            assert_eq!(<$ty>::from_str_opt(&hash.to_string()), Ok(Some(hash)));
+            for i in 0..<$ty>::SIZE_IN_BYTES {
+                let mut bytes = [0u8; <$ty>::SIZE_IN_BYTES];
+                bytes[i] = 1;
+                let hash = <$ty>::try_from(bytes.as_slice()).unwrap();
+                assert!(!hash.is_null(), "failed on i={i}");
+            }
+        };
+    }
+    test!(hashes::Short);
+    test!(hashes::Normal);
+    test!(hashes::NormalWithLongChecksum);
+    test!(hashes::Long);
+    test!(hashes::LongWithLongChecksum);
+}
+
+#[test]
+fn from_str_whitespace_and_case() {
+    const HASH_STR: &str = "T1E16004017D3551777571D55C005CC5";
     type CustomTlsh = hashes::Short;
-    // Empty string (does not match to valid string length)
+    let expected = CustomTlsh::from_str(HASH_STR).unwrap();
+    // Surrounding ASCII whitespace characters
+    for s in [
+        " T1E16004017D3551777571D55C005CC5\n",
+        "T1E16004017D3551777571D55C005CC5\r\n",
+        "\t E16004017D3551777571D55C005CC5 ",
+    ] {
+        assert_eq!(CustomTlsh::from_str(s), Ok(expected));
+        assert_eq!(CustomTlsh::from_str_with(s, None), Ok(expected));
+    }
     assert_eq!(
-        CustomTlsh::from_str(""),
+        CustomTlsh::from_str_with(
+            " T1E16004017D3551777571D55C005CC5\n",
+            Some(HexStringPrefix::WithVersion)
+        ),
+        Ok(expected)
+    );
+    assert_eq!(
+        CustomTlsh::from_str_with(
+            " E16004017D3551777571D55C005CC5\n",
+            Some(HexStringPrefix::Empty)
+        ),
+        Ok(expected)
+    );
+    // Lowercase prefix
+    assert_eq!(
+        CustomTlsh::from_str("t1E16004017D3551777571D55C005CC5"),
+        Ok(expected)
+    );
+    assert_eq!(
+        CustomTlsh::from_str("t1e16004017d3551777571d55c005cc5"),
+        Ok(expected)
+    );
+    assert_eq!(
+        CustomTlsh::from_str_const("t1E16004017D3551777571D55C005CC5"),
+        expected
+    );
+    // Other prefixes are still invalid.
+    assert_eq!(
+        CustomTlsh::from_str("T2E16004017D3551777571D55C005CC5"),
+        Err(ParseError::InvalidPrefix)
+    );
+    assert_eq!(
+        CustomTlsh::from_str("t2E16004017D3551777571D55C005CC5"),
+        Err(ParseError::InvalidPrefix)
+    );
+    // Embedded whitespace characters are still invalid.
+    assert_eq!(
+        CustomTlsh::from_str("T1E16004017D3551777 71D55C005CC5"),
+        Err(ParseError::InvalidCharacter)
+    );
+    assert_eq!(
+        CustomTlsh::from_str("T1 E16004017D3551777571D55C005CC5"),
+        Err(ParseError::InvalidStringLength)
+    );
+    // The byte-based parser does not ignore whitespace characters.
+    assert_eq!(
+        CustomTlsh::from_str_bytes(b"T1E16004017D3551777571D55C005CC5\n", None),
+        Err(ParseError::InvalidStringLength)
+    );
+    // Whitespace-only string
+    assert_eq!(
+        CustomTlsh::from_str(" \n"),
         Err(ParseError::InvalidStringLength)
     );
-    // TNULL (does not match to valid string length)
+}
+
+#[test]
+fn from_str_other_errors() {
+    type CustomTlsh = hashes::Short;
+    // Empty string (does not match to valid string length)
     assert_eq!(
-        CustomTlsh::from_str("TNULL"),
+        CustomTlsh::from_str(""),
         Err(ParseError::InvalidStringLength)
     );
+    // TNULL (the null hash)
+    assert_eq!(CustomTlsh::from_str("TNULL"), Err(ParseError::NullHash));
     // Invalid prefix
     assert_eq!(
         CustomTlsh::from_str("T2E16004017D3551777571D55C005CC5"),
@@ -178,6 +334,9 @@ fn strict_parser_bytes_length() {
     assert!(result.is_ok());
     // Length encoding: 0xaa (invalid encoding)
     let result = hashes::Normal::try_from(BYTES2);
+    // Validation without construction agrees.
+    assert_eq!(hashes::Normal::validate_bytes(BYTES1), Ok(()));
+    assert_eq!(hashes::Normal::validate_bytes(BYTES2), result.map(|_| ()));
     cfg_if::cfg_if! {
         if #[cfg(feature = "strict-parser")] {
             assert_eq!(result, Err(ParseError::LengthIsTooLarge));
@@ -200,6 +359,13 @@ fn strict_parser_bytes_checksum() {
     assert!(result.is_ok());
     // Checksum: 0x31 (invalid checksum)
     let result = hashes::Short::try_from(BYTES2);
+    // Validation without construction agrees.
+    assert_eq!(hashes::Short::validate_bytes(BYTES1), Ok(()));
+    assert_eq!(hashes::Short::validate_bytes(BYTES2), result.map(|_| ()));
+    assert_eq!(
+        hashes::Short::validate_bytes(&BYTES2[1..]),
+        Err(ParseError::InvalidStringLength)
+    );
     cfg_if::cfg_if! {
         if #[cfg(feature = "strict-parser")] {
             assert_eq!(result, Err(ParseError::InvalidChecksum));
@@ -243,6 +409,54 @@ fn from_and_to_str_prefix() {
     assert_eq!(&buffer[..size], HASH_STR_0.as_bytes());
 }
 
+#[test]
+fn str_len_with_and_without_prefix() {
+    use crate::generate::tests::LOREM_IPSUM;
+    macro_rules! test {
+        ($ty: ty) => {{
+            type T = $ty;
+            assert_eq!(T::LEN_IN_STR_WITH_PREFIX, T::LEN_IN_STR);
+            assert_eq!(T::LEN_IN_STR_NO_PREFIX, T::LEN_IN_STR_EXCEPT_PREFIX);
+            assert_eq!(T::LEN_IN_STR_NO_PREFIX + 2, T::LEN_IN_STR_WITH_PREFIX);
+            let hash = T::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+            let mut buffer = [0u8; 256];
+            assert_eq!(
+                hash.store_into_str_bytes(&mut buffer, HexStringPrefix::WithVersion),
+                Ok(T::LEN_IN_STR_WITH_PREFIX)
+            );
+            assert_eq!(
+                hash.store_into_str_bytes(&mut buffer, HexStringPrefix::Empty),
+                Ok(T::LEN_IN_STR_NO_PREFIX)
+            );
+            // LEN_IN_STR_WITH_PREFIX is enough for both prefixes.
+            for prefix in [HexStringPrefix::Empty, HexStringPrefix::WithVersion] {
+                assert!(hash
+                    .store_into_str_bytes(&mut buffer[..T::LEN_IN_STR_WITH_PREFIX], prefix)
+                    .is_ok());
+            }
+            assert_eq!(
+                hash.store_into_str_bytes(
+                    &mut buffer[..T::LEN_IN_STR_WITH_PREFIX - 1],
+                    HexStringPrefix::WithVersion
+                ),
+                Err(OperationError::BufferIsTooSmall)
+            );
+            assert_eq!(
+                hash.store_into_str_bytes(
+                    &mut buffer[..T::LEN_IN_STR_NO_PREFIX - 1],
+                    HexStringPrefix::Empty
+                ),
+                Err(OperationError::BufferIsTooSmall)
+            );
+        }};
+    }
+    test!(hashes::Short);
+    test!(hashes::Normal);
+    test!(hashes::NormalWithLongChecksum);
+    test!(hashes::Long);
+    test!(hashes::LongWithLongChecksum);
+}
+
 #[test]
 fn store_into_bytes_example() {
     type CustomTlsh = hashes::NormalWithLongChecksum;
@@ -276,6 +490,162 @@ fn store_into_bytes_example() {
     assert_eq!(hash, hash3);
 }
 
+#[test]
+fn from_parts_consistency() {
+    macro_rules! test {
+        ($ty:ty, $hash_str:literal) => {
+            let hash = <$ty>::from_str($hash_str).unwrap();
+            let hash2 = <$ty>::from_parts(
+                *hash.checksum(),
+                *hash.length(),
+                *hash.qratios(),
+                *hash.body(),
+            );
+            assert_eq!(hash2, hash);
+            assert_eq!(hash2.to_string(), $hash_str);
+        };
+    }
+    test!(hashes::Short, "T1E1F029B2FCAA4D5FE04846105FA5E2");
+    test!(
+        hashes::Normal,
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        hashes::NormalWithLongChecksum,
+        "T1DC33D4F0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        hashes::Long,
+        "T1DCF0DCA405C02AF1D4860CA5894A05301D60E9915198060A7044C608A1E89A11BD2B2836520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        hashes::LongWithLongChecksum,
+        "T1DC33D4F0DCA405C02AF1D4860CA5894A05301D60E9915198060A7044C608A1E89A11BD2B2836520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+}
+
+#[test]
+fn bytes_le_layout_and_roundtrip() {
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use crate::hash::body::FuzzyHashBody;
+    use crate::hash::checksum::FuzzyHashChecksum;
+
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            let header_size = <$ty as FuzzyHashType>::ChecksumType::SIZE + 2;
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut bytes = [0u8; <$ty>::SIZE_IN_BYTES];
+            for _ in 0..1000 {
+                rng.fill_bytes(&mut bytes);
+                let hash = match <$ty>::try_from(bytes.as_slice()) {
+                    Ok(hash) => hash,
+                    Err(_) => continue, // only on the strict parser
+                };
+                let mut canonical = [0u8; <$ty>::SIZE_IN_BYTES];
+                let mut le = [0u8; <$ty>::SIZE_IN_BYTES];
+                assert_eq!(
+                    hash.store_into_bytes(&mut canonical),
+                    Ok(<$ty>::SIZE_IN_BYTES)
+                );
+                assert_eq!(hash.store_into_bytes_le(&mut le), Ok(<$ty>::SIZE_IN_BYTES));
+                // The header is the same.
+                assert_eq!(canonical[..header_size], le[..header_size]);
+                // The body is reversed.
+                let mut body = canonical;
+                body[header_size..].reverse();
+                assert_eq!(body, le);
+                // The body is a little-endian bit array of quartiles.
+                for index in 0..<$ty>::NUMBER_OF_BUCKETS {
+                    let byte = le[header_size + index / 4];
+                    assert_eq!(
+                        (byte >> (2 * (index % 4))) & 0b11,
+                        hash.body().quartile(index)
+                    );
+                }
+                // Round trip.
+                assert_eq!(<$ty>::from_bytes_le(&le), Ok(hash));
+                // Invalid length
+                assert_eq!(
+                    <$ty>::from_bytes_le(&le[1..]),
+                    Err(ParseError::InvalidStringLength)
+                );
+                // Insufficient buffer
+                assert_eq!(
+                    hash.store_into_bytes_le(&mut le[1..]),
+                    Err(OperationError::BufferIsTooSmall)
+                );
+            }
+        };
+    }
+    test!(hashes::Short, 0x0b5e_f2c8_7a19_d346);
+    test!(hashes::Normal, 0xd470_1c9b_e365_8a2f);
+    test!(hashes::NormalWithLongChecksum, 0x3f29_a6d0_5b8e_c174);
+    test!(hashes::Long, 0x86c1_3e7f_49a2_0db5);
+    test!(hashes::LongWithLongChecksum, 0x5ad8_904e_c62b_f713);
+}
+
+#[test]
+fn to_bytes_consistency() {
+    macro_rules! test {
+        ($ty:ty, $hash_str:literal) => {
+            let hash = <$ty>::from_str($hash_str).unwrap();
+            let mut expected = [0u8; <$ty>::SIZE_IN_BYTES];
+            assert_eq!(
+                hash.store_into_bytes(&mut expected),
+                Ok(<$ty>::SIZE_IN_BYTES)
+            );
+            let bytes: [u8; <$ty>::SIZE_IN_BYTES] = hash.to_bytes();
+            assert_eq!(bytes, expected);
+            let bytes: [u8; <$ty>::SIZE_IN_BYTES] = hash.into();
+            assert_eq!(bytes, expected);
+            assert_eq!(<$ty>::try_from(bytes.as_slice()), Ok(hash));
+        };
+    }
+    test!(hashes::Short, "T1E1F029B2FCAA4D5FE04846105FA5E2");
+    test!(
+        hashes::Normal,
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        hashes::NormalWithLongChecksum,
+        "T1DC33D4F0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        hashes::Long,
+        "T1DCF0DCA405C02AF1D4860CA5894A05301D60E9915198060A7044C608A1E89A11BD2B2836520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+    test!(
+        hashes::LongWithLongChecksum,
+        "T1DC33D4F0DCA405C02AF1D4860CA5894A05301D60E9915198060A7044C608A1E89A11BD2B2836520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+    );
+}
+
+#[test]
+fn roundtrip_bytes_str_random() {
+    // from_bytes -> to_string -> from_str -> to_bytes must be the identity
+    // (including non-canonical values accepted by the non-strict parser).
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            for hash in random_valid_hashes::<$ty>($seed, 10000) {
+                let bytes = hash.to_bytes();
+                assert_eq!(<$ty>::try_from(bytes.as_slice()), Ok(hash));
+                let s = hash.to_string();
+                let hash2 = <$ty>::from_str(&s).unwrap();
+                assert_eq!(hash2, hash, "failed on {s}");
+                assert_eq!(hash2.to_bytes(), bytes, "failed on {s}");
+                assert_eq!(hash2.to_string(), s);
+            }
+        };
+    }
+    test!(hashes::Short, 0x1e5c_7a03_b8d4_2f96);
+    test!(hashes::Normal, 0x93d0_4b6e_1f27_c85a);
+    test!(hashes::NormalWithLongChecksum, 0x6a81_f3c2_05de_947b);
+    test!(hashes::Long, 0xc427_9e15_a0b3_6d08);
+    test!(hashes::LongWithLongChecksum, 0x2fb6_d819_74ea_035c);
+}
+
 #[test]
 fn store_into_bytes_insufficient_buffer() {
     let hash = hashes::Normal::from_str(
@@ -306,6 +676,258 @@ fn store_into_str_bytes_insufficient_buffer() {
     );
 }
 
+#[test]
+fn base64_example() {
+    type CustomTlsh = hashes::NormalWithLongChecksum;
+    // In the example: 073FC70FCD36520C1B007FD320B9B266559FD998A0200725E75AFCEAC99F5881184A4B1AA2
+    const BASE64_REPRESENTATION: &str = "Bz_HD802UgwbAH_TILmyZlWf2ZigIAcl51r86smfWIEYSksaog";
+    let hash = CustomTlsh::from_str(
+        "T170F37CF0DC36520C1B007FD320B9B266559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    )
+    .unwrap();
+    assert_eq!(CustomTlsh::LEN_IN_BASE64, BASE64_REPRESENTATION.len());
+    let mut buffer = [0; 50];
+    assert_eq!(
+        hash.to_base64_into(&mut buffer),
+        Ok(CustomTlsh::LEN_IN_BASE64)
+    );
+    assert_eq!(&buffer, BASE64_REPRESENTATION.as_bytes());
+    assert_eq!(CustomTlsh::from_base64(BASE64_REPRESENTATION), Ok(hash));
+    #[cfg(feature = "alloc")]
+    assert_eq!(hash.to_base64(), BASE64_REPRESENTATION);
+}
+
+#[test]
+fn base64_roundtrip_with_bytes() {
+    fn test<T: FuzzyHashType + FromStr<Err = ParseError> + core::fmt::Debug + PartialEq>(
+        hash_str: &str,
+    ) {
+        let hash = T::from_str(hash_str).unwrap();
+        let mut bytes = [0u8; 69];
+        let bytes = &mut bytes[..T::SIZE_IN_BYTES];
+        hash.store_into_bytes(bytes).unwrap();
+        let mut encoded = [0u8; 92];
+        let encoded = &mut encoded[..T::LEN_IN_BASE64];
+        assert_eq!(hash.to_base64_into(encoded), Ok(T::LEN_IN_BASE64));
+        let encoded = core::str::from_utf8(encoded).unwrap();
+        assert_eq!(T::from_base64(encoded), Ok(hash));
+        // Decoded Base64 is the binary format.
+        let mut decoded = [0u8; 69];
+        let decoded = &mut decoded[..T::SIZE_IN_BYTES];
+        assert_eq!(base64::decode(decoded, encoded.as_bytes()), Some(()));
+        assert_eq!(decoded, bytes);
+    }
+    test::<hashes::Short>("T1E16004017D3551777571D55C005CC5");
+    test::<hashes::Normal>(
+        "T14D9ADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888",
+    );
+    test::<hashes::NormalWithLongChecksum>(
+        "T170F37CF0DC36520C1B007FD320B9B266559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    );
+}
+
+#[test]
+fn base64_errors() {
+    type CustomTlsh = hashes::Normal;
+    let hash = CustomTlsh::from_str(
+        "T14D9ADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888",
+    )
+    .unwrap();
+    let mut buffer = [0u8; 47];
+    assert_eq!(CustomTlsh::LEN_IN_BASE64, buffer.len());
+    assert_eq!(
+        hash.to_base64_into(&mut buffer[..46]),
+        Err(OperationError::BufferIsTooSmall)
+    );
+    assert_eq!(hash.to_base64_into(&mut buffer), Ok(47));
+    let encoded = core::str::from_utf8(&buffer).unwrap();
+    // Wrong lengths
+    assert_eq!(
+        CustomTlsh::from_base64(&encoded[..46]),
+        Err(ParseError::InvalidStringLength)
+    );
+    assert_eq!(
+        CustomTlsh::from_base64(""),
+        Err(ParseError::InvalidStringLength)
+    );
+    assert_eq!(
+        hashes::Long::from_base64(encoded),
+        Err(ParseError::InvalidStringLength)
+    );
+    // Invalid characters
+    let mut invalid = buffer;
+    invalid[10] = b'+';
+    assert_eq!(
+        CustomTlsh::from_base64(core::str::from_utf8(&invalid).unwrap()),
+        Err(ParseError::InvalidCharacter)
+    );
+}
+
+#[test]
+fn body_diff_consistency() {
+    use crate::compare::dist_body::naive::distance_dibits;
+    use crate::hash::body::FuzzyHashBody;
+
+    fn test<T: FuzzyHashType>(hash_str1: &str, hash_str2: &str) {
+        let hash1 = T::from_str(hash_str1).unwrap();
+        let hash2 = T::from_str(hash_str2).unwrap();
+        // Same hash: no differences.
+        assert_eq!(hash1.body_diff(&hash1).next(), None);
+        let mut prev = None;
+        let mut distance = 0;
+        let mut count = 0;
+        for (index, q1, q2) in hash1.body_diff(&hash2) {
+            assert!(index < T::NUMBER_OF_BUCKETS);
+            assert!(prev.map_or(true, |prev| prev < index));
+            assert_eq!(q1, hash1.body().quartile(index));
+            assert_eq!(q2, hash2.body().quartile(index));
+            assert_ne!(q1, q2);
+            distance += distance_dibits(q1, q2);
+            count += 1;
+            prev = Some(index);
+        }
+        // All differing buckets are yielded.
+        let all = (0..T::NUMBER_OF_BUCKETS)
+            .filter(|&i| hash1.body().quartile(i) != hash2.body().quartile(i))
+            .count();
+        assert_eq!(count, all);
+        assert_eq!(distance, hash1.body().compare(hash2.body()));
+    }
+    test::<hashes::Short>(
+        "T1E16004017D3551777571D55C005CC5",
+        "T1E16004017D3551777571D55C115CC6",
+    );
+    test::<hashes::Normal>(
+        "T14D9ADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888",
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    );
+    test::<hashes::Long>(
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2\
+        36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+        "T1DCF0DC0725E75AFCEAC99F5881184A4B1AA236520C1B007FD32079B226559FD998A020\
+        36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA1",
+    );
+}
+
+#[test]
+fn checksum_distance_consistency() {
+    use rand::{RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use crate::hash::checksum::FuzzyHashChecksum;
+
+    macro_rules! test {
+        ($ty: ty, $seed: literal) => {{
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut raw1 = [0u8; <$ty>::SIZE_IN_BYTES];
+            let mut raw2 = [0u8; <$ty>::SIZE_IN_BYTES];
+            for _ in 0..1000 {
+                rng.fill_bytes(&mut raw1);
+                rng.fill_bytes(&mut raw2);
+                // Make the checksums equal in some cases.
+                if rng.next_u32() % 2 == 0 {
+                    let size = <<$ty as FuzzyHashType>::ChecksumType as FuzzyHashChecksum>::SIZE;
+                    raw2[..size].copy_from_slice(&raw1[..size]);
+                }
+                let (hash1, hash2) = match (<$ty>::try_from(&raw1), <$ty>::try_from(&raw2)) {
+                    (Ok(hash1), Ok(hash2)) => (hash1, hash2),
+                    _ => continue, // may happen with the strict parser.
+                };
+                let distance = hash1.checksum_distance(&hash2);
+                assert_eq!(distance, hash2.checksum_distance(&hash1));
+                assert_eq!(hash1.checksum_distance(&hash1), 0);
+                assert_eq!(distance, hash1.checksum().compare(hash2.checksum()));
+                assert_eq!(
+                    distance,
+                    hash1
+                        .compare_parts(&hash2, ComparisonConfiguration::Default)
+                        .checksum
+                );
+                assert_eq!(
+                    distance == 0,
+                    hash1.checksum().data() == hash2.checksum().data()
+                );
+                assert!(
+                    distance
+                        <= <<$ty as FuzzyHashType>::ChecksumType as FuzzyHashChecksum>::MAX_DISTANCE
+                );
+            }
+        }};
+    }
+    test!(hashes::Short, 0x6d1f0a93c2e85b47);
+    test!(hashes::Normal, 0xa0c47e2b95f1d368);
+    test!(hashes::NormalWithLongChecksum, 0x2e9b5d70f4a16c83);
+    test!(hashes::Long, 0xf38a1c064d7e92b5);
+    test!(hashes::LongWithLongChecksum, 0x57c2e8b1036fad94);
+}
+
+#[test]
+fn mask_buckets_consistency() {
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    use crate::compare::dist_body::naive::distance_dibits;
+    use crate::hash::body::FuzzyHashBody;
+
+    macro_rules! test {
+        ($ty: ty, $seed: literal) => {{
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64($seed);
+            let mut raw1 = [0u8; <$ty>::SIZE_IN_BYTES];
+            let mut raw2 = [0u8; <$ty>::SIZE_IN_BYTES];
+            for _ in 0..100 {
+                rng.fill_bytes(&mut raw1);
+                rng.fill_bytes(&mut raw2);
+                let (hash1, hash2) = match (<$ty>::try_from(&raw1), <$ty>::try_from(&raw2)) {
+                    (Ok(hash1), Ok(hash2)) => (hash1, hash2),
+                    _ => continue, // may happen with the strict parser.
+                };
+                let indices: Vec<usize> = (0..rng.gen_range(0..=8))
+                    .map(|_| rng.gen_range(0..<$ty>::NUMBER_OF_BUCKETS))
+                    .collect();
+                let mut masked1 = hash1;
+                let mut masked2 = hash2;
+                masked1.mask_buckets(&indices);
+                masked2.mask_buckets(&indices);
+                // Masking is idempotent.
+                let mut masked1_twice = masked1;
+                masked1_twice.mask_buckets(&indices);
+                assert_eq!(masked1, masked1_twice);
+                // Only the body is changed.
+                assert_eq!(masked1.checksum(), hash1.checksum());
+                assert_eq!(masked1.length(), hash1.length());
+                assert_eq!(masked1.qratios(), hash1.qratios());
+                let mut expected = 0;
+                for index in 0..<$ty>::NUMBER_OF_BUCKETS {
+                    if indices.contains(&index) {
+                        assert_eq!(masked1.body().quartile(index), 0);
+                        assert_eq!(masked2.body().quartile(index), 0);
+                    } else {
+                        let q1 = hash1.body().quartile(index);
+                        let q2 = hash2.body().quartile(index);
+                        assert_eq!(masked1.body().quartile(index), q1);
+                        assert_eq!(masked2.body().quartile(index), q2);
+                        expected += distance_dibits(q1, q2);
+                    }
+                }
+                // Masked buckets are excluded if both sides are masked.
+                assert_eq!(masked1.body().compare(masked2.body()), expected);
+            }
+        }};
+    }
+    test!(hashes::Short, 0x34b7_e1c9_0a58_f26d);
+    test!(hashes::Normal, 0xd06a_3f85_c1e2_974b);
+    test!(hashes::NormalWithLongChecksum, 0x8e1c_52a7_f9d3_046b);
+    test!(hashes::Long, 0x61f4_9d0b_273e_c8a5);
+    test!(hashes::LongWithLongChecksum, 0xa5c8_7062_e41f_b39d);
+}
+
+#[test]
+#[should_panic]
+fn mask_buckets_out_of_range() {
+    let mut hash = hashes::Short::from_str("T1E16004017D3551777571D55C005CC5").unwrap();
+    hash.mask_buckets(&[0, hashes::Short::NUMBER_OF_BUCKETS]);
+}
+
 #[test]
 fn test_compare_with_config() {
     let hash1 = hashes::Normal::from_str(
@@ -333,6 +955,143 @@ fn test_compare_with_config() {
     );
 }
 
+#[test]
+fn compare_within_consistency() {
+    const HASHES: &[&str] = &[
+        "T11632623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+        "T11642623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+        "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008",
+        "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C",
+        "T14D9ADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888",
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    ];
+    let hashes: Vec<_> = HASHES
+        .iter()
+        .map(|s| hashes::Normal::from_str(s).unwrap())
+        .collect();
+    for config in [
+        ComparisonConfiguration::Default,
+        ComparisonConfiguration::NoLength,
+    ] {
+        for hash1 in hashes.iter() {
+            for hash2 in hashes.iter() {
+                let distance = hash1.compare_with_config(hash2, config);
+                for max_distance in (0..=distance + 2).chain([u32::MAX]) {
+                    let expected = (distance <= max_distance).then_some(distance);
+                    assert_eq!(
+                        hash1.compare_within_with_config(hash2, config, max_distance),
+                        expected
+                    );
+                    if config == ComparisonConfiguration::Default {
+                        assert_eq!(hash1.compare_within(hash2, max_distance), expected);
+                    }
+                }
+            }
+        }
+    }
+    // find_within yields the same result as filtering compare results.
+    for hash1 in hashes.iter() {
+        for max_distance in [0, 50, 100, 200, 300, u32::MAX] {
+            let expected: Vec<_> = hashes
+                .iter()
+                .enumerate()
+                .map(|(index, hash2)| (index, hash2, hash1.compare(hash2)))
+                .filter(|&(_, _, distance)| distance <= max_distance)
+                .collect();
+            let actual: Vec<_> = hash1.find_within(&hashes, max_distance).collect();
+            assert_eq!(actual, expected);
+        }
+    }
+}
+
+#[test]
+fn compare_length_cross_variant() {
+    use crate::generate::tests::LOREM_IPSUM;
+    use crate::length::FuzzyHashLengthEncoding;
+    let short = hashes::Short::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+    let normal = hashes::Normal::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+    let long = hashes::Long::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+    assert_eq!(short.compare_length(normal.length()), 0);
+    assert_eq!(normal.compare_length(long.length()), 0);
+    assert_eq!(long.compare_length(short.length()), 0);
+    let other = hashes::Normal::from_iter(LOREM_IPSUM[..100].iter().copied()).unwrap();
+    let distance = long.compare_length(other.length());
+    assert_ne!(distance, 0);
+    assert_eq!(distance, normal.compare_length(other.length()));
+    // Consistent with the breakdown of the distance.
+    assert_eq!(
+        distance,
+        normal
+            .compare_parts(&other, ComparisonConfiguration::Default)
+            .length
+    );
+    // Consistent with the length part.
+    for lvalue in [0, 1, 0x55, 0xa9] {
+        let lvalue = FuzzyHashLengthEncoding::from_raw(lvalue);
+        assert_eq!(
+            normal.compare_length(&lvalue),
+            normal.length().compare(&lvalue)
+        );
+    }
+}
+
+#[test]
+fn compare_to_bytes_consistency() {
+    const HASHES: &[&str] = &[
+        "T11632623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+        "T11642623FBA48037706C20162BB9764CBF21E903F3B552568354CC1681F6BA6543FB6EA",
+        "T1A12500088C838B0A0F0EC3C0ACAB82F3B8228B0308CFA302338C0F0AE2C24F28000008",
+        "T129251210F4C18D0A5F0661C4F64D905B585253A3024F022323E5074CC5601904886D1C",
+        "T14D9ADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888",
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    ];
+    let hashes: Vec<_> = HASHES
+        .iter()
+        .map(|s| hashes::Normal::from_str(s).unwrap())
+        .collect();
+    for config in [
+        ComparisonConfiguration::Default,
+        ComparisonConfiguration::NoLength,
+        ComparisonConfiguration::NoChecksum,
+        ComparisonConfiguration::BodyOnly,
+        ComparisonConfiguration::Weighted {
+            length_multiplier: 3,
+        },
+    ] {
+        for hash1 in hashes.iter() {
+            for hash2 in hashes.iter() {
+                let raw = hash2.to_bytes();
+                assert_eq!(
+                    hash1.compare_to_bytes(&raw, config),
+                    Ok(hash1.compare_with_config(hash2, config))
+                );
+                // Invalid length
+                assert_eq!(
+                    hash1.compare_to_bytes(&raw[1..], config),
+                    Err(ParseError::InvalidStringLength)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn compare_to_bytes_validation() {
+    // Corresponds: strict_parser_bytes_checksum
+    let hash = hashes::Short::from_str("T1E16004017D3551777571D55C005CC5").unwrap();
+    const BYTES: &[u8] = b"\x31\x0D\xAB\
+        \x38\x36\x18\x25\xF4\xFA\x6D\x0B\x57\x5C\x1C\xB5";
+    let result = hash.compare_to_bytes(BYTES, ComparisonConfiguration::Default);
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "strict-parser")] {
+            assert_eq!(result, Err(ParseError::InvalidChecksum));
+        } else {
+            let other = hashes::Short::try_from(BYTES).unwrap();
+            assert_eq!(result, Ok(hash.compare(&other)));
+        }
+    }
+}
+
 #[test]
 fn clear_checksum_modification() {
     const HASH_STR_1: &str = "T1E16004017D3551777571D55C005CC5";
@@ -349,6 +1108,164 @@ fn clear_checksum_modification() {
     assert_eq!(hash_1, hash_2);
 }
 
+#[test]
+fn from_iter_consistency() {
+    use crate::generate::tests::LOREM_IPSUM;
+    use crate::{GeneratorType, TlshGeneratorFor};
+    macro_rules! test {
+        ($ty:ty) => {
+            let mut generator = TlshGeneratorFor::<$ty>::new();
+            generator.update(LOREM_IPSUM);
+            assert_eq!(
+                <$ty>::from_iter(LOREM_IPSUM.iter().copied()),
+                generator.finalize()
+            );
+            // Errors are also propagated.
+            let mut generator = TlshGeneratorFor::<$ty>::new();
+            generator.update(&LOREM_IPSUM[..10]);
+            assert_eq!(
+                <$ty>::from_iter(LOREM_IPSUM[..10].iter().copied()),
+                generator.finalize()
+            );
+        };
+    }
+    test!(hashes::Short);
+    test!(hashes::Normal);
+    test!(hashes::NormalWithLongChecksum);
+    test!(hashes::Long);
+    test!(hashes::LongWithLongChecksum);
+}
+
+#[test]
+fn from_str_const() {
+    fn test<T: FuzzyHashType + FromStr + PartialEq + core::fmt::Debug>(
+        parse_const: fn(&str) -> T,
+        hash_str: &str,
+    ) where
+        <T as FromStr>::Err: core::fmt::Debug,
+    {
+        let expected = T::from_str(hash_str).unwrap();
+        assert_eq!(parse_const(hash_str), expected);
+        // Without prefix
+        assert_eq!(parse_const(&hash_str[2..]), expected);
+        // Lowercase
+        assert_eq!(
+            parse_const(&hash_str.to_ascii_lowercase().replacen('t', "T", 1)),
+            expected
+        );
+    }
+    test(
+        hashes::Short::from_str_const,
+        "T1E1F029B2FCAA4D5FE04846105FA5E2",
+    );
+    test(
+        hashes::Normal::from_str_const,
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    );
+    test(
+        hashes::NormalWithLongChecksum::from_str_const,
+        "T1DC33D4F0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    );
+    test(
+        hashes::Long::from_str_const,
+        "T1DCF0DCA405C02AF1D4860CA5894A05301D60E9915198060A7044C608A1E89A11BD2B2836520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    );
+    test(
+        hashes::LongWithLongChecksum::from_str_const,
+        "T1DC33D4F0DCA405C02AF1D4860CA5894A05301D60E9915198060A7044C608A1E89A11BD2B2836520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+    );
+    // Constant evaluation
+    const HASH: hashes::Short = hashes::Short::from_str_const("T1E1F029B2FCAA4D5FE04846105FA5E2");
+    assert_eq!(
+        HASH,
+        hashes::Short::from_str("T1E1F029B2FCAA4D5FE04846105FA5E2").unwrap()
+    );
+}
+
+#[test]
+#[should_panic]
+fn from_str_const_invalid_prefix() {
+    let _ = hashes::Short::from_str_const("T2E1F029B2FCAA4D5FE04846105FA5E2");
+}
+
+#[test]
+#[should_panic]
+fn from_str_const_invalid_length() {
+    let _ = hashes::Short::from_str_const("T1E1F029B2FCAA4D5FE04846105FA5E");
+}
+
+#[test]
+#[should_panic]
+fn from_str_const_invalid_character() {
+    let _ = hashes::Short::from_str_const("T1E1F029B2FCAA4D5FE04846105FA5EG");
+}
+
+#[test]
+fn hash_consistent_with_eq() {
+    use std::collections::HashSet;
+    const HASH_STR_1: &str = "T1E16004017D3551777571D55C005CC5";
+    const HASH_STR_2: &str = "T1006004017D3551777571D55C005CC5";
+    type CustomTlsh = hashes::Short;
+    let hash_1 = CustomTlsh::from_str(HASH_STR_1).unwrap();
+    let hash_2 = CustomTlsh::from_str(HASH_STR_2).unwrap();
+    let mut set = HashSet::new();
+    assert!(set.insert(hash_1));
+    assert!(set.insert(hash_2));
+    // Equal hashes are deduplicated.
+    assert!(!set.insert(CustomTlsh::from_str(HASH_STR_1).unwrap()));
+    assert_eq!(set.len(), 2);
+    // After clearing the checksum, hash_1 is equal to hash_2.
+    let mut hash_1 = hash_1;
+    hash_1.clear_checksum();
+    assert!(set.contains(&hash_1));
+    assert!(!set.insert(hash_1));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn ordering_by_bytes() {
+    type CustomTlsh = hashes::Short;
+    const HASH_STRS: [&str; 4] = [
+        "T1E16004017D3551777571D55C005CC5",
+        "T1006004017D3551777571D55C005CC5",
+        "T1E16004017D3551777571D55C005CC4",
+        "T1E16104017D3551777571D55C005CC5",
+    ];
+    let mut hashes: Vec<_> = HASH_STRS
+        .iter()
+        .map(|s| CustomTlsh::from_str(s).unwrap())
+        .collect();
+    hashes.sort();
+    // Compare with the order of the binary representation.
+    let mut expected: Vec<_> = HASH_STRS
+        .iter()
+        .map(|s| {
+            let hash = CustomTlsh::from_str(s).unwrap();
+            let mut bytes = [0u8; CustomTlsh::SIZE_IN_BYTES];
+            hash.store_into_bytes(&mut bytes).unwrap();
+            (bytes, hash)
+        })
+        .collect();
+    expected.sort_by_key(|x| x.0);
+    let expected: Vec<_> = expected.into_iter().map(|(_, hash)| hash).collect();
+    assert_eq!(hashes, expected);
+    // Explicit order
+    assert_eq!(
+        hashes,
+        [1, 2, 0, 3]
+            .iter()
+            .map(|&i| CustomTlsh::from_str(HASH_STRS[i]).unwrap())
+            .collect::<Vec<_>>()
+    );
+    // Consistency with equality
+    for a in &hashes {
+        for b in &hashes {
+            assert_eq!(a.cmp(b).is_eq(), a == b);
+            assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+        }
+    }
+}
+
 #[test]
 fn max_distances() {
     // Compare with pre-computed values.
@@ -393,3 +1310,490 @@ fn max_distances() {
         1707 + 1536
     );
 }
+
+#[test]
+fn lsh_key_layout() {
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            for mut hash in random_valid_hashes::<$ty>($seed, 1000) {
+                let data = hash.body().data();
+                let mut expected = (hash.length().value() >> 4) as u64;
+                for index in 0..56 {
+                    let bit = if index < <$ty>::NUMBER_OF_BUCKETS {
+                        (data[data.len() - 1 - index / 4] >> (2 * (index % 4) + 1)) & 1
+                    } else {
+                        0
+                    };
+                    expected = (expected << 1) | bit as u64;
+                }
+                let key = hash.lsh_key();
+                assert_eq!(key, expected);
+                // The checksum does not affect the key.
+                hash.clear_checksum();
+                assert_eq!(hash.lsh_key(), key);
+            }
+        };
+    }
+    test!(hashes::Short, 0x0c5e_92d7_4a1b_36f8);
+    test!(hashes::Normal, 0xb7e3_1d58_60af_c924);
+    test!(hashes::NormalWithLongChecksum, 0x4f29_a6c0_d38e_715b);
+    test!(hashes::Long, 0xe81a_5b36_c7f0_2d94);
+    test!(hashes::LongWithLongChecksum, 0x3690_f4eb_1c27_a85d);
+}
+
+#[test]
+fn compare_body_and_length_across_checksums() {
+    macro_rules! test {
+        ($ty1:ty, $ty2:ty, $seed1:literal, $seed2:literal) => {
+            for (hash1, hash2) in random_valid_hashes::<$ty1>($seed1, 1000)
+                .into_iter()
+                .zip(random_valid_hashes::<$ty2>($seed2, 1000))
+            {
+                // Reconstruct the second one with the type of the first one
+                // (with a cleared checksum).
+                let mut other = <$ty1>::from_parts(
+                    *hash1.checksum(),
+                    *hash2.length(),
+                    *hash2.qratios(),
+                    *hash2.body(),
+                );
+                other.clear_checksum();
+                let mut cleared = hash1;
+                cleared.clear_checksum();
+                for config in ALL_CONFIGS {
+                    let expected = cleared.compare_with_config(&other, config);
+                    assert_eq!(hash1.compare_body_and_length(&hash2, config), expected);
+                    assert_eq!(hash2.compare_body_and_length(&hash1, config), expected);
+                    assert_eq!(hash1.compare_body_and_length(&other, config), expected);
+                }
+            }
+        };
+    }
+    test!(
+        hashes::Normal,
+        hashes::NormalWithLongChecksum,
+        0x58d1_0e6a_f3b7_92c4,
+        0x2b96_e3d0_7c15_a84f
+    );
+    test!(
+        hashes::Long,
+        hashes::LongWithLongChecksum,
+        0xa2c7_4f19_e05b_d836,
+        0x6e04_b8a3_d1f7_593c
+    );
+}
+
+#[test]
+fn store_into_str_bytes_with_case() {
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            let mut upper = [0u8; <$ty>::LEN_IN_STR_WITH_PREFIX];
+            let mut lower = [0u8; <$ty>::LEN_IN_STR_WITH_PREFIX];
+            for hash in random_valid_hashes::<$ty>($seed, 1000) {
+                for prefix in [HexStringPrefix::Empty, HexStringPrefix::WithVersion] {
+                    let len = hash.store_into_str_bytes(&mut upper, prefix).unwrap();
+                    assert_eq!(
+                        hash.store_into_str_bytes_with_case(&mut lower, prefix, HexCase::Upper),
+                        Ok(len)
+                    );
+                    assert_eq!(lower[..len], upper[..len]);
+                    assert_eq!(
+                        hash.store_into_str_bytes_with_case(&mut lower, prefix, HexCase::Lower),
+                        Ok(len)
+                    );
+                    let s = core::str::from_utf8(&lower[..len]).unwrap();
+                    let digits = match prefix {
+                        HexStringPrefix::Empty => s,
+                        HexStringPrefix::WithVersion => s.strip_prefix("T1").unwrap(),
+                    };
+                    assert!(digits.bytes().all(|ch| matches!(ch, b'0'..=b'9' | b'a'..=b'f')));
+                    assert!(upper[..len].eq_ignore_ascii_case(&lower[..len]));
+                    assert_eq!(<$ty>::from_str(s), Ok(hash));
+                }
+                #[cfg(feature = "alloc")]
+                assert_eq!(
+                    hash.to_string_lower(),
+                    core::str::from_utf8(&lower).unwrap()
+                );
+                // Insufficient buffer.
+                assert_eq!(
+                    hash.store_into_str_bytes_with_case(
+                        &mut lower[..<$ty>::LEN_IN_STR_NO_PREFIX - 1],
+                        HexStringPrefix::Empty,
+                        HexCase::Lower
+                    ),
+                    Err(OperationError::BufferIsTooSmall)
+                );
+            }
+        };
+    }
+    test!(hashes::Short, 0x7d02_e4b9_15ac_68f3);
+    test!(hashes::Normal, 0x2e95_c1d7_8b40_f36a);
+    test!(hashes::NormalWithLongChecksum, 0xc0b8_3f6e_a752_1d49);
+    test!(hashes::Long, 0x41a7_d9e2_6c3b_0f85);
+    test!(hashes::LongWithLongChecksum, 0x9f3c_52a0_e18d_b764);
+}
+
+#[test]
+fn length_distance_bounds() {
+    use crate::compare::dist_length::MAX_DISTANCE;
+
+    fn make(lvalue: u8) -> hashes::Normal {
+        let mut bytes = [0u8; hashes::Normal::SIZE_IN_BYTES];
+        bytes[1] = lvalue;
+        hashes::Normal::try_from(bytes.as_slice()).unwrap()
+    }
+    // Examples (the multiplier 12 applies when the difference exceeds 1).
+    for (lvalue1, lvalue2, expected) in [
+        (0x10, 0x10, (0, 0)),
+        (0x10, 0x11, (0, 1)),
+        (0x11, 0x10, (0, 1)),
+        (0x10, 0x12, (0, 24)),
+        (0x00, 0x80, (0, MAX_DISTANCE)),
+    ] {
+        assert_eq!(
+            make(lvalue1).length_distance_bounds(&make(lvalue2)),
+            expected
+        );
+    }
+    // Both endpoints are the actual contributions to the total distance.
+    fn check<T: FuzzyHashType + core::fmt::Debug>(hash1: &T, hash2: &T) {
+        let (min, max) = hash1.length_distance_bounds(hash2);
+        assert_eq!(hash2.length_distance_bounds(hash1), (min, max));
+        assert!(max <= MAX_DISTANCE);
+        let base = hash1.compare_with_config(hash2, ComparisonConfiguration::NoLength);
+        for (config, contribution) in [
+            (ComparisonConfiguration::NoLength, min),
+            (
+                ComparisonConfiguration::Weighted {
+                    length_multiplier: 0,
+                },
+                min,
+            ),
+            (ComparisonConfiguration::Default, max),
+            (
+                ComparisonConfiguration::Weighted {
+                    length_multiplier: 1,
+                },
+                max,
+            ),
+        ] {
+            assert_eq!(
+                hash1.compare_with_config(hash2, config),
+                base + contribution,
+                "failed on {hash1:?}, {hash2:?}, {config:?}"
+            );
+        }
+        // Also on the configurations without the checksum.
+        assert_eq!(
+            hash1.compare_with_config(hash2, ComparisonConfiguration::NoChecksum),
+            base - hash1.checksum_distance(hash2) + max
+        );
+    }
+    for lvalue1 in 0..ENCODED_VALUE_SIZE as u8 {
+        for lvalue2 in 0..ENCODED_VALUE_SIZE as u8 {
+            check(&make(lvalue1), &make(lvalue2));
+        }
+    }
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            for pair in random_valid_hashes::<$ty>($seed, 1000).chunks_exact(2) {
+                check(&pair[0], &pair[1]);
+            }
+        };
+    }
+    test!(hashes::Short, 0x5a1f_c7e2_30b9_d864);
+    test!(hashes::Normal, 0xb37d_0e95_4c2a_f18e);
+    test!(hashes::NormalWithLongChecksum, 0x2c86_f4a1_9d5e_073b);
+    test!(hashes::Long, 0xe4d0_6b38_a17f_c259);
+    test!(hashes::LongWithLongChecksum, 0x7f92_1c5d_e80a_364b);
+}
+
+#[test]
+fn legacy_str_roundtrip() {
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            let mut buf_s = [0u8; <$ty>::LEN_IN_STR];
+            let mut buf_legacy = [0u8; <$ty>::LEN_IN_STR];
+            for hash in random_valid_hashes::<$ty>($seed, 1000) {
+                let len = hash
+                    .store_into_str_bytes(&mut buf_s, HexStringPrefix::WithVersion)
+                    .unwrap();
+                let s = core::str::from_utf8(&buf_s[..len]).unwrap();
+                let len = hash
+                    .store_into_str_bytes(&mut buf_legacy, HexStringPrefix::Empty)
+                    .unwrap();
+                let legacy = core::str::from_utf8(&buf_legacy[..len]).unwrap();
+                assert_eq!(legacy.len(), <$ty>::LEN_IN_STR_NO_PREFIX);
+                assert_eq!(s.strip_prefix("T1"), Some(legacy));
+                #[cfg(feature = "alloc")]
+                {
+                    assert_eq!(hash.to_string(), s);
+                    assert_eq!(hash.to_legacy_string(), legacy);
+                }
+                assert_eq!(<$ty>::from_legacy_str(legacy), Ok(hash));
+                // Prefixed strings are rejected.
+                assert!(<$ty>::from_legacy_str(s).is_err());
+            }
+        };
+    }
+    test!(hashes::Short, 0xd5a0_3e17_c84b_926f);
+    test!(hashes::Normal, 0x18f6_b2c9_03ea_547d);
+    test!(hashes::NormalWithLongChecksum, 0x8e3d_41a7_f290_6cb5);
+    test!(hashes::Long, 0x5b72_e80c_9d16_a3f4);
+    test!(hashes::LongWithLongChecksum, 0xa069_cd5b_7e83_14e2);
+}
+
+#[test]
+fn parse_into() {
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            let mut target = <$ty>::from_str_bytes(
+                &[b'0'; <$ty>::LEN_IN_STR_NO_PREFIX],
+                Some(HexStringPrefix::Empty),
+            )
+            .unwrap();
+            for hash in random_valid_hashes::<$ty>($seed, 1000) {
+                let s = hash.to_string();
+                assert_eq!(target.parse_into(&s), Ok(()));
+                assert_eq!(target, hash);
+                // Also accepts what from_str accepts.
+                let lower = s.to_ascii_lowercase();
+                assert_eq!(target.parse_into(&lower), Ok(()));
+                assert_eq!(Ok(target), <$ty>::from_str(&lower));
+                // On failure, the target is unchanged and the error matches.
+                for invalid in ["", "TNULL", &s[1..], &s.replace('T', "X")] {
+                    assert_eq!(
+                        target.parse_into(invalid),
+                        <$ty>::from_str(invalid).map(|_| ())
+                    );
+                    assert_eq!(target, hash);
+                }
+            }
+        };
+    }
+    test!(hashes::Short, 0x47c1_9e2b_d05a_6f83);
+    test!(hashes::Normal, 0xb3e8_5d06_1a9c_f274);
+    test!(hashes::NormalWithLongChecksum, 0x2f94_a7c3_e815_0d6b);
+    test!(hashes::Long, 0xc65d_0b48_93f1_7ae2);
+    test!(hashes::LongWithLongChecksum, 0x7a03_f6e9_2cb8_451d);
+}
+
+#[test]
+fn self_consistency() {
+    use crate::hash::checksum::{FuzzyHashChecksum, FuzzyHashChecksumData};
+    use crate::hash::inner;
+    use crate::length::FuzzyHashLengthEncoding;
+
+    // Examples from the strict parser tests
+    const STR_VALID_LENGTH: &str =
+        "T14D9ADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888";
+    const STR_INVALID_LENGTH: &str =
+        "T14DAADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888";
+    const STR_VALID_CHECKSUM: &str = "T103D0BA38361825F4FA6D0B575C1CB5";
+    const STR_INVALID_CHECKSUM: &str = "T113D0BA38361825F4FA6D0B575C1CB5";
+    assert!(hashes::Normal::from_str(STR_VALID_LENGTH)
+        .unwrap()
+        .self_consistency());
+    assert!(hashes::Short::from_str(STR_VALID_CHECKSUM)
+        .unwrap()
+        .self_consistency());
+    cfg_if::cfg_if! {
+        if #[cfg(not(feature = "strict-parser"))] {
+            assert!(!hashes::Normal::from_str(STR_INVALID_LENGTH)
+                .unwrap()
+                .self_consistency());
+            assert!(!hashes::Short::from_str(STR_INVALID_CHECKSUM)
+                .unwrap()
+                .self_consistency());
+        } else {
+            assert!(hashes::Normal::from_str(STR_INVALID_LENGTH).is_err());
+            assert!(hashes::Short::from_str(STR_INVALID_CHECKSUM).is_err());
+        }
+    }
+
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            // The null hash is consistent.
+            assert!(<$ty>::try_from([0u8; <$ty>::SIZE_IN_BYTES].as_slice())
+                .unwrap()
+                .self_consistency());
+            for hash in random_valid_hashes::<$ty>($seed, 1000) {
+                let expected = hash.checksum().is_valid() && hash.length().is_valid();
+                assert_eq!(hash.self_consistency(), expected);
+                // The strict parser only accepts consistent ones.
+                if cfg!(feature = "strict-parser") {
+                    assert!(hash.self_consistency());
+                }
+                // Construct inconsistent ones (bypassing the parser).
+                for lvalue in [ENCODED_VALUE_SIZE as u8, u8::MAX] {
+                    let inconsistent = <$ty>::new(inner::FuzzyHash::from_raw(
+                        *hash.body(),
+                        *hash.checksum(),
+                        FuzzyHashLengthEncoding::from_raw(lvalue),
+                        *hash.qratios(),
+                    ));
+                    assert!(!inconsistent.self_consistency());
+                }
+            }
+        };
+    }
+    // Checksum out of range (only on the short variant).
+    for hash in random_valid_hashes::<hashes::Short>(0x3c97_5e0a_d1f4_862b, 100) {
+        for checksum in [NUM_BUCKETS_SHORT as u8 + 1, u8::MAX] {
+            let inconsistent = hashes::Short::new(inner::FuzzyHash::from_raw(
+                *hash.body(),
+                FuzzyHashChecksumData::from_raw(&[checksum]),
+                *hash.length(),
+                *hash.qratios(),
+            ));
+            assert!(!inconsistent.self_consistency());
+        }
+    }
+    test!(hashes::Short, 0x6b1e_d842_0fa3_c957);
+    test!(hashes::Normal, 0xe2d7_0593_b8c4_1a6f);
+    test!(hashes::NormalWithLongChecksum, 0x19a4_c6f0_7e35_b28d);
+    test!(hashes::Long, 0x80fc_3b5e_a297_d461);
+    test!(hashes::LongWithLongChecksum, 0x5d38_9e14_c06b_f7a2);
+}
+
+#[test]
+fn compare_identical_fast_path() {
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            for hash in random_valid_hashes::<$ty>($seed, 1000) {
+                let copy = hash;
+                for config in ALL_CONFIGS {
+                    // The fast path must match the full computation.
+                    assert_eq!(hash.compare_with_config(&copy, config), 0);
+                    assert_eq!(hash.compare_parts(&copy, config).total(), 0);
+                }
+            }
+        };
+    }
+    test!(hashes::Short, 0x3fa1_86d2_c04e_b795);
+    test!(hashes::Normal, 0x91c5_2e7b_d83a_064f);
+    test!(hashes::NormalWithLongChecksum, 0x6e08_b4f3_1d97_a2c5);
+    test!(hashes::Long, 0xd27b_5c19_e6a0_38f4);
+    test!(hashes::LongWithLongChecksum, 0x0b9e_f7a4_62d1_c583);
+}
+
+#[test]
+fn approximate_size() {
+    use crate::length::{decode_length_range, encode_length};
+
+    // The maximum valid encoding (0xa9) and the invalid one (0xaa)
+    const STR_VALID_LENGTH: &str =
+        "T14D9ADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888";
+    const STR_INVALID_LENGTH: &str =
+        "T14DAADDD869983B33E27B4F308C459ED4F77FE24A4BC42C52CF1C9F046D5945AEA69888";
+    let hash = hashes::Normal::from_str(STR_VALID_LENGTH).unwrap();
+    assert_eq!(hash.approximate_size(), decode_length_range(0xa9));
+    assert!(hash.approximate_size().is_some());
+    if let Ok(hash) = hashes::Normal::from_str(STR_INVALID_LENGTH) {
+        assert!(!cfg!(feature = "strict-parser"));
+        assert_eq!(hash.approximate_size(), None);
+    }
+    // Generated fuzzy hashes
+    for len in [50u32, 51, 256, 1000, 4096, 65536] {
+        let data: Vec<u8> = (0..len).map(|i| (i * 7 + i / 13) as u8).collect();
+        let Ok(hash) = crate::Tlsh::from_iter(data.iter().copied()) else {
+            continue;
+        };
+        let range = hash.approximate_size().unwrap();
+        assert!(range.contains(&len), "failed on len={len}");
+        assert_eq!(encode_length(*range.start()), Some(hash.length().value()));
+        assert_eq!(encode_length(*range.end()), Some(hash.length().value()));
+    }
+}
+
+#[test]
+fn from_str_detailed() {
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            // Positions to corrupt are chosen from a separate generator.
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64(!$seed);
+            for hash in random_valid_hashes::<$ty>($seed, 1000) {
+                let s = hash.to_string();
+                assert_eq!(<$ty>::from_str_detailed(&s), Ok(hash));
+                let legacy = &s[2..];
+                for (prefix, body, suffix) in [("", s.as_str(), ""), (" \t", legacy, "\n")] {
+                    // Replace one hexadecimal digit with an invalid character.
+                    let pos = rng.gen_range(0..body.len());
+                    if body.len() == <$ty>::LEN_IN_STR && pos < 2 {
+                        continue;
+                    }
+                    let mut corrupted = String::new();
+                    corrupted.push_str(prefix);
+                    corrupted.push_str(&body[..pos]);
+                    corrupted.push('G');
+                    corrupted.push_str(&body[pos + 1..]);
+                    corrupted.push_str(suffix);
+                    assert_eq!(
+                        <$ty>::from_str_detailed(&corrupted),
+                        Err((ParseError::InvalidCharacter, Some(prefix.len() + pos))),
+                        "failed on {corrupted:?}"
+                    );
+                    assert_eq!(
+                        <$ty>::from_str(&corrupted),
+                        Err(ParseError::InvalidCharacter)
+                    );
+                }
+            }
+            // Other errors do not have offsets.
+            assert_eq!(
+                <$ty>::from_str_detailed("TNULL"),
+                Err((ParseError::NullHash, None))
+            );
+            assert_eq!(
+                <$ty>::from_str_detailed(""),
+                Err((ParseError::InvalidStringLength, None))
+            );
+        };
+    }
+    test!(hashes::Short, 0x74e2_b0c9_1f58_a63d);
+    test!(hashes::Normal, 0xc3a9_5f17_0e8d_b264);
+    test!(hashes::NormalWithLongChecksum, 0x1b6f_e843_d92c_0a75);
+    test!(hashes::Long, 0x8d05_3a6e_f1b7_c492);
+    test!(hashes::LongWithLongChecksum, 0x5fc8_27d1_64a3_e90b);
+}
+
+#[test]
+fn compare_body_with_outlier() {
+    macro_rules! test {
+        ($ty:ty, $seed:literal) => {
+            for pair in random_valid_hashes::<$ty>($seed, 2000).chunks_exact(2) {
+                let (hash1, hash2) = (pair[0], pair[1]);
+                // The default outlier value is TLSH-compatible.
+                let expected = hash1.compare_with_config(&hash2, ComparisonConfiguration::BodyOnly);
+                assert_eq!(hash1.compare_body_with_outlier(&hash2, 6), expected);
+                // Count outliers from the body difference.
+                let num_outliers = hash1
+                    .body_diff(&hash2)
+                    .filter(|&(_, x, y)| x.abs_diff(y) == 0b11)
+                    .count() as u32;
+                for outlier in [0, 1, 3, 12] {
+                    assert_eq!(
+                        hash1.compare_body_with_outlier(&hash2, outlier),
+                        expected - num_outliers * 6 + num_outliers * outlier
+                    );
+                }
+                assert_eq!(
+                    hash1.compare_body_with_outlier(&hash2, u32::MAX),
+                    if num_outliers == 0 {
+                        expected
+                    } else {
+                        u32::MAX
+                    }
+                );
+                assert_eq!(hash1.compare_body_with_outlier(&hash1, u32::MAX), 0);
+            }
+        };
+    }
+    test!(hashes::Short, 0x47c2_a9e0_1b5f_d836);
+    test!(hashes::Normal, 0xe3b8_0d51_7a6c_f249);
+    test!(hashes::Long, 0x0a9f_6e24_c3d7_851b);
+}
@@ -44,5 +44,302 @@
 //!      Long (full hash): `256` | [`Long`]    | [`LongWithLongChecksum`]
 //!
 //! Note that not all parameter combinations are valid.
+//!
+//! # Experimental Variants
+//!
+//! With the `experiment-variants` feature, this module also provides
+//! `ExtraShort`, a fast-tlsh specific variant with 24 buckets (6-byte body)
+//! and 1-byte checksum.  Its bucket mapping folds the 48-bucket mapping of
+//! [`Short`] into 24 buckets.
+//!
+//! **Warning**: This variant has no official counterpart and is
+//! TLSH-incompatible.  It is not covered by [`TlshVariant`] either.
 
 pub use crate::params::exported_hashes::*;
+
+use core::fmt::Display;
+use core::str::FromStr;
+
+use crate::compare::ComparisonConfiguration;
+use crate::errors::ParseError;
+use crate::hash::{checksum, is_null_hash_str};
+use crate::FuzzyHashType;
+
+/// The variant (parameters) of a fuzzy hash.
+///
+/// Each variant corresponds to the fuzzy hash type with the same name
+/// in this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TlshVariant {
+    /// [`Short`] (48 buckets and 1-byte checksum).
+    Short,
+    /// [`Normal`] (128 buckets and 1-byte checksum).
+    Normal,
+    /// [`NormalWithLongChecksum`] (128 buckets and 3-byte checksum).
+    NormalWithLongChecksum,
+    /// [`Long`] (256 buckets and 1-byte checksum).
+    Long,
+    /// [`LongWithLongChecksum`] (256 buckets and 3-byte checksum).
+    LongWithLongChecksum,
+}
+
+impl TlshVariant {
+    /// All variants (in the order of the string length).
+    pub const ALL: [TlshVariant; 5] = [
+        TlshVariant::Short,
+        TlshVariant::Normal,
+        TlshVariant::NormalWithLongChecksum,
+        TlshVariant::Long,
+        TlshVariant::LongWithLongChecksum,
+    ];
+
+    /// Returns the number of effective buckets.
+    pub const fn number_of_buckets(&self) -> usize {
+        match self {
+            TlshVariant::Short => Short::NUMBER_OF_BUCKETS,
+            TlshVariant::Normal => Normal::NUMBER_OF_BUCKETS,
+            TlshVariant::NormalWithLongChecksum => NormalWithLongChecksum::NUMBER_OF_BUCKETS,
+            TlshVariant::Long => Long::NUMBER_OF_BUCKETS,
+            TlshVariant::LongWithLongChecksum => LongWithLongChecksum::NUMBER_OF_BUCKETS,
+        }
+    }
+
+    /// Returns the size of the checksum in bytes.
+    pub const fn checksum_size(&self) -> usize {
+        match self {
+            TlshVariant::Short | TlshVariant::Normal | TlshVariant::Long => {
+                checksum::CHECKSUM_SIZE_NORMAL
+            }
+            TlshVariant::NormalWithLongChecksum | TlshVariant::LongWithLongChecksum => {
+                checksum::CHECKSUM_SIZE_LONG
+            }
+        }
+    }
+
+    /// Returns the length of the string representation
+    /// without the prefix.
+    pub const fn len_in_str_no_prefix(&self) -> usize {
+        match self {
+            TlshVariant::Short => Short::LEN_IN_STR_NO_PREFIX,
+            TlshVariant::Normal => Normal::LEN_IN_STR_NO_PREFIX,
+            TlshVariant::NormalWithLongChecksum => NormalWithLongChecksum::LEN_IN_STR_NO_PREFIX,
+            TlshVariant::Long => Long::LEN_IN_STR_NO_PREFIX,
+            TlshVariant::LongWithLongChecksum => LongWithLongChecksum::LEN_IN_STR_NO_PREFIX,
+        }
+    }
+}
+
+/// Detects the variant of a fuzzy hash string.
+///
+/// The variant is inferred only from the length of the string (after
+/// removing leading and trailing ASCII whitespace characters and the `T1`
+/// prefix, if any).  As in [`FuzzyHashType::from_str_with()`], the
+/// lowercase prefix (`t1`) is also accepted.  For all variants provided by this
+/// crate, the lengths of the string representations are unique and the
+/// prefix (if present) never looks like a part of the hexadecimal
+/// representation.  So, if this function returns [`Some`], it is the only
+/// variant which may accept the string.
+///
+/// Note that:
+///
+/// *   The fuzzy hash string itself has no marker of the variant.
+///     If new variants are added in the future, their lengths might
+///     coincide with existing ones and this function would report
+///     only one of them.
+/// *   This function does not validate the contents.  To make sure that
+///     the string is valid, parse it with the corresponding type.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::hashes::TlshVariant;
+///
+/// assert_eq!(
+///     tlsh::detect_variant("T140D5F17F44F8AB007AE2AC46E515DC"),
+///     Some(TlshVariant::Short)
+/// );
+/// assert_eq!(
+///     tlsh::detect_variant(
+///         "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+///     ),
+///     Some(TlshVariant::Normal)
+/// );
+/// // Without the prefix.
+/// assert_eq!(
+///     tlsh::detect_variant(
+///         "DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+///     ),
+///     Some(TlshVariant::Normal)
+/// );
+/// // Surrounding whitespace and the lowercase prefix are accepted.
+/// assert_eq!(
+///     tlsh::detect_variant(
+///         " t1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2\n"
+///     ),
+///     Some(TlshVariant::Normal)
+/// );
+/// assert_eq!(tlsh::detect_variant("T1DCF0"), None);
+/// ```
+pub fn detect_variant(s: &str) -> Option<TlshVariant> {
+    let s = s.trim_matches(|c: char| c.is_ascii_whitespace()).as_bytes();
+    let s = match s {
+        [b'T' | b't', b'1', rest @ ..] => rest,
+        _ => s,
+    };
+    TlshVariant::ALL
+        .into_iter()
+        .find(|variant| variant.len_in_str_no_prefix() == s.len())
+}
+
+/// The fuzzy hash of any variant provided by this crate.
+///
+/// This is the dynamic counterpart of the fuzzy hash types in this module
+/// and useful to store fuzzy hashes from unknown sources (with possibly
+/// different variants) in one collection.
+///
+/// On parsing, the variant is [detected](detect_variant()) from the length
+/// of the string.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::hashes::{AnyTlsh, TlshVariant};
+///
+/// let hashes: Vec<AnyTlsh> = [
+///     "T140D5F17F44F8AB007AE2AC46E515DC",
+///     "T140D5F17F44FCAB007AE2A846E515DC",
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+/// ]
+/// .into_iter()
+/// .map(|s| s.parse().unwrap())
+/// .collect();
+/// assert_eq!(hashes[0].variant(), TlshVariant::Short);
+/// assert_eq!(hashes[2].variant(), TlshVariant::Normal);
+/// assert_eq!(
+///     hashes[2].to_string(),
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+/// );
+///
+/// // Only fuzzy hashes of the same variant are comparable.
+/// assert_eq!(hashes[0].compare(&hashes[1]), Some(2));
+/// assert_eq!(hashes[0].compare(&hashes[2]), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnyTlsh {
+    /// [`Short`] fuzzy hash.
+    Short(Short),
+    /// [`Normal`] fuzzy hash.
+    Normal(Normal),
+    /// [`NormalWithLongChecksum`] fuzzy hash.
+    NormalWithLongChecksum(NormalWithLongChecksum),
+    /// [`Long`] fuzzy hash.
+    Long(Long),
+    /// [`LongWithLongChecksum`] fuzzy hash.
+    LongWithLongChecksum(LongWithLongChecksum),
+}
+
+impl AnyTlsh {
+    /// Returns the variant of this fuzzy hash.
+    pub const fn variant(&self) -> TlshVariant {
+        match self {
+            AnyTlsh::Short(_) => TlshVariant::Short,
+            AnyTlsh::Normal(_) => TlshVariant::Normal,
+            AnyTlsh::NormalWithLongChecksum(_) => TlshVariant::NormalWithLongChecksum,
+            AnyTlsh::Long(_) => TlshVariant::Long,
+            AnyTlsh::LongWithLongChecksum(_) => TlshVariant::LongWithLongChecksum,
+        }
+    }
+
+    /// Compare with another fuzzy hash with the specified configuration.
+    ///
+    /// It returns [`None`] if the variants mismatch.  Otherwise, it returns
+    /// [`Some`] containing the distance as in
+    /// [`FuzzyHashType::compare_with_config()`].
+    pub fn compare_with_config(
+        &self,
+        other: &AnyTlsh,
+        config: ComparisonConfiguration,
+    ) -> Option<u32> {
+        match (self, other) {
+            (AnyTlsh::Short(a), AnyTlsh::Short(b)) => Some(a.compare_with_config(b, config)),
+            (AnyTlsh::Normal(a), AnyTlsh::Normal(b)) => Some(a.compare_with_config(b, config)),
+            (AnyTlsh::NormalWithLongChecksum(a), AnyTlsh::NormalWithLongChecksum(b)) => {
+                Some(a.compare_with_config(b, config))
+            }
+            (AnyTlsh::Long(a), AnyTlsh::Long(b)) => Some(a.compare_with_config(b, config)),
+            (AnyTlsh::LongWithLongChecksum(a), AnyTlsh::LongWithLongChecksum(b)) => {
+                Some(a.compare_with_config(b, config))
+            }
+            _ => None,
+        }
+    }
+
+    /// Compare with another fuzzy hash with
+    /// [the default configuration](ComparisonConfiguration::Default).
+    ///
+    /// It returns [`None`] if the variants mismatch.  Otherwise, it returns
+    /// [`Some`] containing the distance as in [`FuzzyHashType::compare()`].
+    #[inline]
+    pub fn compare(&self, other: &AnyTlsh) -> Option<u32> {
+        self.compare_with_config(other, ComparisonConfiguration::Default)
+    }
+}
+
+/// Implements conversions from concrete fuzzy hash types to [`AnyTlsh`].
+macro_rules! any_tlsh_from {
+    ($($ty:ident),*) => {
+        $(
+            impl From<$ty> for AnyTlsh {
+                #[inline(always)]
+                fn from(value: $ty) -> Self {
+                    AnyTlsh::$ty(value)
+                }
+            }
+        )*
+    };
+}
+any_tlsh_from!(
+    Short,
+    Normal,
+    NormalWithLongChecksum,
+    Long,
+    LongWithLongChecksum
+);
+
+impl Display for AnyTlsh {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AnyTlsh::Short(hash) => hash.fmt(f),
+            AnyTlsh::Normal(hash) => hash.fmt(f),
+            AnyTlsh::NormalWithLongChecksum(hash) => hash.fmt(f),
+            AnyTlsh::Long(hash) => hash.fmt(f),
+            AnyTlsh::LongWithLongChecksum(hash) => hash.fmt(f),
+        }
+    }
+}
+
+impl FromStr for AnyTlsh {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match detect_variant(s) {
+            Some(TlshVariant::Short) => Short::from_str(s).map(AnyTlsh::Short),
+            Some(TlshVariant::Normal) => Normal::from_str(s).map(AnyTlsh::Normal),
+            Some(TlshVariant::NormalWithLongChecksum) => {
+                NormalWithLongChecksum::from_str(s).map(AnyTlsh::NormalWithLongChecksum)
+            }
+            Some(TlshVariant::Long) => Long::from_str(s).map(AnyTlsh::Long),
+            Some(TlshVariant::LongWithLongChecksum) => {
+                LongWithLongChecksum::from_str(s).map(AnyTlsh::LongWithLongChecksum)
+            }
+            None if is_null_hash_str(
+                s.trim_matches(|c: char| c.is_ascii_whitespace()).as_bytes(),
+            ) =>
+            {
+                Err(ParseError::NullHash)
+            }
+            None => Err(ParseError::InvalidStringLength),
+        }
+    }
+}
+
+mod tests;
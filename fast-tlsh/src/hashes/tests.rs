@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::hashes`].
+
+#![cfg(test)]
+
+use super::{detect_variant, AnyTlsh, TlshVariant};
+
+use crate::errors::ParseError;
+use crate::generate::tests::LOREM_IPSUM;
+use crate::hash::checksum::FuzzyHashChecksum;
+use crate::hashes;
+use crate::{ComparisonConfiguration, FuzzyHashType, HexStringPrefix};
+
+#[test]
+fn variant_parameters() {
+    macro_rules! test {
+        ($ty: ident) => {{
+            let variant = TlshVariant::$ty;
+            assert_eq!(variant.number_of_buckets(), hashes::$ty::NUMBER_OF_BUCKETS);
+            assert_eq!(
+                variant.checksum_size(),
+                <hashes::$ty as FuzzyHashType>::ChecksumType::SIZE
+            );
+            assert_eq!(
+                variant.len_in_str_no_prefix(),
+                hashes::$ty::LEN_IN_STR_NO_PREFIX
+            );
+        }};
+    }
+    test!(Short);
+    test!(Normal);
+    test!(NormalWithLongChecksum);
+    test!(Long);
+    test!(LongWithLongChecksum);
+}
+
+#[test]
+fn detect_variant_unambiguous() {
+    // All string lengths (with or without the prefix) must be unique.
+    for (i, a) in TlshVariant::ALL.iter().enumerate() {
+        for b in TlshVariant::ALL[i + 1..].iter() {
+            let a_len = a.len_in_str_no_prefix();
+            let b_len = b.len_in_str_no_prefix();
+            assert_ne!(a_len, b_len);
+            assert_ne!(a_len + 2, b_len);
+            assert_ne!(a_len, b_len + 2);
+        }
+    }
+}
+
+#[test]
+fn detect_variant_examples() {
+    macro_rules! test {
+        ($ty: ident) => {{
+            let hash = hashes::$ty::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+            let mut buffer = [0u8; hashes::$ty::LEN_IN_STR_WITH_PREFIX];
+            for prefix in [HexStringPrefix::WithVersion, HexStringPrefix::Empty] {
+                let len = hash.store_into_str_bytes(&mut buffer, prefix).unwrap();
+                let s = core::str::from_utf8(&buffer[..len]).unwrap();
+                assert_eq!(detect_variant(s), Some(TlshVariant::$ty));
+                // Surrounding whitespace is ignored (as in the parser).
+                let padded = std::format!(" {s}\n");
+                assert_eq!(detect_variant(&padded), Some(TlshVariant::$ty));
+                assert_eq!(detect_variant(&padded[..len]), None);
+                // Any truncation or extension makes it unrecognizable.
+                assert_eq!(detect_variant(&s[..len - 1]), None);
+                let mut extended = std::string::String::from(s);
+                extended.push('0');
+                assert_eq!(detect_variant(&extended), None);
+            }
+        }};
+    }
+    test!(Short);
+    test!(Normal);
+    test!(NormalWithLongChecksum);
+    test!(Long);
+    test!(LongWithLongChecksum);
+    assert_eq!(detect_variant(""), None);
+    assert_eq!(detect_variant("T1"), None);
+    assert_eq!(detect_variant(" \t\n"), None);
+}
+
+#[test]
+fn detect_variant_lowercase_prefix() {
+    macro_rules! test {
+        ($ty: ident) => {{
+            let hash = hashes::$ty::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+            let mut buffer = [0u8; hashes::$ty::LEN_IN_STR_WITH_PREFIX];
+            let len = hash
+                .store_into_str_bytes(&mut buffer, HexStringPrefix::WithVersion)
+                .unwrap();
+            buffer[0] = b't';
+            let s = core::str::from_utf8(&buffer[..len]).unwrap();
+            assert!(s.parse::<hashes::$ty>().is_ok());
+            assert_eq!(detect_variant(s), Some(TlshVariant::$ty));
+        }};
+    }
+    test!(Short);
+    test!(Normal);
+    test!(NormalWithLongChecksum);
+    test!(Long);
+    test!(LongWithLongChecksum);
+}
+
+#[cfg(feature = "experiment-variants")]
+#[test]
+fn extra_short_variant() {
+    use crate::generate::GeneratorWithWindow;
+    use crate::{GeneratorType, TlshGeneratorFor};
+
+    assert_eq!(hashes::ExtraShort::NUMBER_OF_BUCKETS, 24);
+    assert_eq!(hashes::ExtraShort::LEN_IN_STR_NO_PREFIX, 6 * 2 + 6);
+    assert_eq!(TlshGeneratorFor::<hashes::ExtraShort>::MIN, 8);
+    assert_eq!(TlshGeneratorFor::<hashes::ExtraShort>::MIN_CONSERVATIVE, 8);
+    // The experimental variant must not be detected.
+    let hash = hashes::ExtraShort::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+    let mut buffer = [0u8; hashes::ExtraShort::LEN_IN_STR_WITH_PREFIX];
+    let len = hash
+        .store_into_str_bytes(&mut buffer, HexStringPrefix::WithVersion)
+        .unwrap();
+    let s = core::str::from_utf8(&buffer[..len]).unwrap();
+    assert_eq!(detect_variant(s), None);
+    // Round-trip and the distance to itself.
+    let parsed: hashes::ExtraShort = s.parse().unwrap();
+    assert_eq!(parsed, hash);
+    assert_eq!(hash.compare(&parsed), 0);
+    // The windowed generator (with the default window size) is consistent.
+    let mut generator = GeneratorWithWindow::<hashes::ExtraShort, 5>::new();
+    generator.update(LOREM_IPSUM);
+    assert_eq!(generator.finalize().unwrap(), hash);
+    // Folded buckets: each is the sum of two buckets of the short variant.
+    let mut generator = TlshGeneratorFor::<hashes::Short>::new();
+    generator.update(LOREM_IPSUM);
+    let short = generator.histogram();
+    let mut generator = TlshGeneratorFor::<hashes::ExtraShort>::new();
+    generator.update(LOREM_IPSUM);
+    let extra_short = generator.histogram();
+    for i in 0..24 {
+        assert_eq!(extra_short[i], short[i] + short[i + 24]);
+    }
+}
+
+#[test]
+fn any_tlsh_roundtrip_and_compare() {
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    macro_rules! make {
+        ($ty: ident, $data: expr) => {
+            AnyTlsh::from(hashes::$ty::from_iter($data.iter().copied()).unwrap())
+        };
+    }
+    let (first, second) = LOREM_IPSUM.split_at(LOREM_IPSUM.len() - 40);
+    let mut modified = Vec::from(first);
+    modified.extend(second.iter().rev());
+    let pairs = [
+        (make!(Short, LOREM_IPSUM), make!(Short, modified)),
+        (make!(Normal, LOREM_IPSUM), make!(Normal, modified)),
+        (
+            make!(NormalWithLongChecksum, LOREM_IPSUM),
+            make!(NormalWithLongChecksum, modified),
+        ),
+        (make!(Long, LOREM_IPSUM), make!(Long, modified)),
+        (
+            make!(LongWithLongChecksum, LOREM_IPSUM),
+            make!(LongWithLongChecksum, modified),
+        ),
+    ];
+    for (i, (hash1, hash2)) in pairs.iter().enumerate() {
+        assert_eq!(hash1.variant(), TlshVariant::ALL[i]);
+        assert_eq!(hash2.variant(), TlshVariant::ALL[i]);
+        // Round-trip through the string representation.
+        for hash in [hash1, hash2] {
+            let s = hash.to_string();
+            assert_eq!(s.parse::<AnyTlsh>(), Ok(*hash));
+            assert_eq!(s[2..].parse::<AnyTlsh>(), Ok(*hash));
+        }
+        // Same as comparing concrete types.
+        macro_rules! expected {
+            ($config: expr) => {
+                match (hash1, hash2) {
+                    (AnyTlsh::Short(a), AnyTlsh::Short(b)) => a.compare_with_config(b, $config),
+                    (AnyTlsh::Normal(a), AnyTlsh::Normal(b)) => a.compare_with_config(b, $config),
+                    (AnyTlsh::NormalWithLongChecksum(a), AnyTlsh::NormalWithLongChecksum(b)) => {
+                        a.compare_with_config(b, $config)
+                    }
+                    (AnyTlsh::Long(a), AnyTlsh::Long(b)) => a.compare_with_config(b, $config),
+                    (AnyTlsh::LongWithLongChecksum(a), AnyTlsh::LongWithLongChecksum(b)) => {
+                        a.compare_with_config(b, $config)
+                    }
+                    _ => unreachable!(),
+                }
+            };
+        }
+        assert_eq!(
+            hash1.compare(hash2),
+            Some(expected!(ComparisonConfiguration::Default))
+        );
+        assert_eq!(
+            hash1.compare_with_config(hash2, ComparisonConfiguration::NoLength),
+            Some(expected!(ComparisonConfiguration::NoLength))
+        );
+        assert_eq!(hash1.compare(hash1), Some(0));
+        // Different variants are not comparable.
+        for (j, (other, _)) in pairs.iter().enumerate() {
+            assert_eq!(hash1.compare(other).is_some(), i == j);
+        }
+    }
+}
+
+#[test]
+fn any_tlsh_parse_agrees_with_concrete_types() {
+    use std::string::String;
+
+    macro_rules! test {
+        ($ty: ident) => {{
+            let hash = hashes::$ty::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+            let mut buffer = [0u8; hashes::$ty::LEN_IN_STR_WITH_PREFIX];
+            for prefix in [HexStringPrefix::WithVersion, HexStringPrefix::Empty] {
+                let len = hash.store_into_str_bytes(&mut buffer, prefix).unwrap();
+                let s = core::str::from_utf8(&buffer[..len]).unwrap();
+                let mut candidates = std::vec![
+                    String::from(s),
+                    s.to_ascii_lowercase(),
+                    std::format!(" {s}\n"),
+                    std::format!("\t{}\r\n", s.to_ascii_lowercase()),
+                ];
+                if prefix == HexStringPrefix::WithVersion {
+                    candidates.push(std::format!("t1{}", &s[2..]));
+                }
+                for s in candidates {
+                    // Every string accepted by the concrete type is accepted.
+                    assert_eq!(s.parse::<hashes::$ty>(), Ok(hash), "failed on s={s:?}");
+                    assert_eq!(
+                        s.parse::<AnyTlsh>(),
+                        Ok(AnyTlsh::from(hash)),
+                        "failed on s={s:?}"
+                    );
+                }
+            }
+        }};
+    }
+    test!(Short);
+    test!(Normal);
+    test!(NormalWithLongChecksum);
+    test!(Long);
+    test!(LongWithLongChecksum);
+}
+
+#[test]
+fn any_tlsh_parse_errors() {
+    assert_eq!("".parse::<AnyTlsh>(), Err(ParseError::InvalidStringLength));
+    assert_eq!(
+        "T1DCF0".parse::<AnyTlsh>(),
+        Err(ParseError::InvalidStringLength)
+    );
+    assert_eq!("TNULL".parse::<AnyTlsh>(), Err(ParseError::NullHash));
+    assert_eq!("null".parse::<AnyTlsh>(), Err(ParseError::NullHash));
+    assert_eq!(" TNULL\n".parse::<AnyTlsh>(), Err(ParseError::NullHash));
+    // The variant is detected but the content is invalid.
+    assert_eq!(
+        "T140D5F17F44F8AB007AE2AC46E515DX".parse::<AnyTlsh>(),
+        Err(ParseError::InvalidCharacter)
+    );
+}
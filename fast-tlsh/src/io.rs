@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! I/O helpers for packed binary fuzzy hashes.
+//!
+//! A packed binary store is a plain concatenation of fuzzy hashes, each of
+//! which is written by [`FuzzyHashType::store_into_bytes()`](crate::FuzzyHashType::store_into_bytes()) (so that each
+//! entry has exactly [`SIZE_IN_BYTES`](crate::FuzzyHashType::SIZE_IN_BYTES) bytes).
+
+#![cfg(feature = "std")]
+#![cfg_attr(feature = "unstable", doc(cfg(feature = "std")))]
+
+use std::io::{ErrorKind, Read};
+
+use crate::errors::ParseError;
+use crate::params::ConstrainedFuzzyHashType;
+use crate::Tlsh;
+
+/// The iterator to read fuzzy hashes from a packed binary store.
+///
+/// This is returned by [`read_hashes()`] and [`read_hashes_for()`].
+///
+/// Each item is the result of parsing one fixed-size chunk with
+/// [`TryFrom<&[u8]>`](TryFrom).  If the last chunk is incomplete,
+/// the last item is [`ParseError::InvalidStringLength`].
+///
+/// If the reader returns an I/O error (other than
+/// [`ErrorKind::Interrupted`], which is retried), the iteration stops and
+/// the error is kept to be retrieved by [`io_error()`](Self::io_error()).
+#[derive(Debug)]
+pub struct ReadHashes<T: ConstrainedFuzzyHashType, R: Read> {
+    /// The reader.
+    reader: R,
+    /// The buffer for one chunk.
+    buffer: Vec<u8>,
+    /// The I/O error which stopped the iteration (if any).
+    error: Option<std::io::Error>,
+    /// Whether the iteration is finished.
+    finished: bool,
+    /// The marker for the fuzzy hash type.
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T: ConstrainedFuzzyHashType, R: Read> ReadHashes<T, R> {
+    /// Creates a new iterator from the reader.
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: vec![0u8; T::SIZE_IN_BYTES],
+            error: None,
+            finished: false,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the I/O error which stopped the iteration (if any).
+    ///
+    /// If this is [`None`] after the iteration, the whole stream is
+    /// successfully read to the end.
+    pub fn io_error(&self) -> Option<&std::io::Error> {
+        self.error.as_ref()
+    }
+}
+
+impl<T, R: Read> Iterator for ReadHashes<T, R>
+where
+    T: ConstrainedFuzzyHashType + for<'a> TryFrom<&'a [u8], Error = ParseError>,
+{
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let mut filled = 0;
+        while filled < self.buffer.len() {
+            match self.reader.read(&mut self.buffer[filled..]) {
+                Ok(0) => {
+                    self.finished = true;
+                    if filled == 0 {
+                        return None;
+                    }
+                    return Some(Err(ParseError::InvalidStringLength));
+                }
+                Ok(len) => filled += len,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.finished = true;
+                    self.error = Some(err);
+                    return None;
+                }
+            }
+        }
+        Some(T::try_from(&self.buffer[..]))
+    }
+}
+
+impl<T, R: Read> core::iter::FusedIterator for ReadHashes<T, R> where
+    T: ConstrainedFuzzyHashType + for<'a> TryFrom<&'a [u8], Error = ParseError>
+{
+}
+
+/// Reads fuzzy hashes from a packed binary store
+/// (with specified fuzzy hash type).
+///
+/// See [`ReadHashes`] for details.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::prelude::*;
+///
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// let hash: CustomTlsh = str::parse("T140D5F17F44F8AB007AE2AC46E515DC").unwrap();
+/// let mut packed = [0u8; CustomTlsh::SIZE_IN_BYTES * 2];
+/// hash.store_into_bytes(&mut packed[..CustomTlsh::SIZE_IN_BYTES]).unwrap();
+/// hash.store_into_bytes(&mut packed[CustomTlsh::SIZE_IN_BYTES..]).unwrap();
+///
+/// let hashes: Vec<_> = tlsh::io::read_hashes_for::<CustomTlsh, _>(&packed[..]).collect();
+/// assert_eq!(hashes, [Ok(hash), Ok(hash)]);
+/// ```
+pub fn read_hashes_for<T, R: Read>(reader: R) -> ReadHashes<T, R>
+where
+    T: ConstrainedFuzzyHashType + for<'a> TryFrom<&'a [u8], Error = ParseError>,
+{
+    ReadHashes::new(reader)
+}
+
+/// Reads fuzzy hashes from a packed binary store.
+///
+/// See [`ReadHashes`] for details.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::prelude::*;
+///
+/// let hash: Tlsh = str::parse(
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+/// ).unwrap();
+/// let mut packed = Vec::new();
+/// let mut buffer = [0u8; Tlsh::SIZE_IN_BYTES];
+/// hash.store_into_bytes(&mut buffer).unwrap();
+/// packed.extend_from_slice(&buffer);
+/// // An incomplete chunk at the end.
+/// packed.extend_from_slice(&buffer[..10]);
+///
+/// let mut iter = tlsh::io::read_hashes(&packed[..]);
+/// assert_eq!(iter.next(), Some(Ok(hash)));
+/// assert_eq!(iter.next(), Some(Err(tlsh::ParseError::InvalidStringLength)));
+/// assert_eq!(iter.next(), None);
+/// assert!(iter.io_error().is_none());
+/// ```
+pub fn read_hashes<R: Read>(reader: R) -> ReadHashes<Tlsh, R> {
+    read_hashes_for(reader)
+}
+
+mod tests;
@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::io`].
+
+#![cfg(test)]
+
+use super::{read_hashes, read_hashes_for};
+
+use std::io::{Error, ErrorKind, Read};
+use std::vec::Vec;
+
+use crate::errors::ParseError;
+use crate::generate::tests::{LOREM_IPSUM, LOREM_IPSUM_HASH_NORMAL};
+use crate::hashes;
+use crate::{FuzzyHashType, Tlsh};
+
+/// A reader which returns at most 3 bytes at once and
+/// interrupts every other call.
+struct ChoppyReader<'a> {
+    data: &'a [u8],
+    interrupt: bool,
+}
+
+impl Read for ChoppyReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(Error::from(ErrorKind::Interrupted));
+        }
+        let len = buf.len().min(self.data.len()).min(3);
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Ok(len)
+    }
+}
+
+/// A reader which fails after reading the inner data.
+struct FailingReader<'a> {
+    data: &'a [u8],
+}
+
+impl Read for FailingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.data.is_empty() {
+            return Err(Error::from(ErrorKind::BrokenPipe));
+        }
+        self.data.read(buf)
+    }
+}
+
+#[test]
+fn read_hashes_examples() {
+    let hash: Tlsh = str::parse(LOREM_IPSUM_HASH_NORMAL).unwrap();
+    let mut buffer = [0u8; Tlsh::SIZE_IN_BYTES];
+    hash.store_into_bytes(&mut buffer).unwrap();
+    let mut packed = Vec::new();
+    for _ in 0..3 {
+        packed.extend_from_slice(&buffer);
+    }
+    // Empty
+    let mut iter = read_hashes(&[][..]);
+    assert_eq!(iter.next(), None);
+    assert!(iter.io_error().is_none());
+    // Complete chunks
+    let mut iter = read_hashes(&packed[..]);
+    assert_eq!(iter.by_ref().collect::<Vec<_>>(), [Ok(hash); 3]);
+    assert!(iter.io_error().is_none());
+    // Partial final chunk (for each partial length)
+    for len in 1..Tlsh::SIZE_IN_BYTES {
+        let data = &packed[..Tlsh::SIZE_IN_BYTES + len];
+        let mut iter = read_hashes(data);
+        assert_eq!(iter.next(), Some(Ok(hash)));
+        assert_eq!(iter.next(), Some(Err(ParseError::InvalidStringLength)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert!(iter.io_error().is_none());
+    }
+    // Short reads and interruptions
+    let reader = ChoppyReader {
+        data: &packed,
+        interrupt: false,
+    };
+    let mut iter = read_hashes(reader);
+    assert_eq!(iter.by_ref().collect::<Vec<_>>(), [Ok(hash); 3]);
+    assert!(iter.io_error().is_none());
+    // I/O error
+    let reader = FailingReader {
+        data: &packed[..Tlsh::SIZE_IN_BYTES * 2 + 1],
+    };
+    let mut iter = read_hashes(reader);
+    assert_eq!(iter.by_ref().collect::<Vec<_>>(), [Ok(hash); 2]);
+    assert_eq!(iter.io_error().unwrap().kind(), ErrorKind::BrokenPipe);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn read_hashes_variants() {
+    macro_rules! test {
+        ($ty: ty) => {{
+            let hash = <$ty>::from_iter(LOREM_IPSUM.iter().copied()).unwrap();
+            let mut buffer = [0u8; <$ty>::SIZE_IN_BYTES];
+            hash.store_into_bytes(&mut buffer).unwrap();
+            let mut packed = Vec::new();
+            packed.extend_from_slice(&buffer);
+            packed.extend_from_slice(&buffer);
+            let hashes: Vec<_> = read_hashes_for::<$ty, _>(&packed[..]).collect();
+            assert_eq!(hashes, [Ok(hash), Ok(hash)]);
+        }};
+    }
+    test!(hashes::Short);
+    test!(hashes::Normal);
+    test!(hashes::NormalWithLongChecksum);
+    test!(hashes::Long);
+    test!(hashes::LongWithLongChecksum);
+}
@@ -7,6 +7,8 @@
 use core::ops::RangeInclusive;
 
 use crate::buckets::constrained::{FuzzyHashBucketMapper, FuzzyHashBucketsInfo};
+#[cfg(feature = "experiment-variants")]
+use crate::buckets::NUM_BUCKETS_EXTRA_SHORT;
 use crate::buckets::{NUM_BUCKETS_LONG, NUM_BUCKETS_NORMAL, NUM_BUCKETS_SHORT};
 use crate::compare::dist_length::{distance, MAX_DISTANCE};
 use crate::errors::ParseError;
@@ -232,6 +234,23 @@ pub trait ConstrainedLengthProcessingInfo: private::Sealed {
 pub struct LengthProcessingInfo<const SIZE_BUCKETS: usize>
 where
     FuzzyHashBucketsInfo<SIZE_BUCKETS>: FuzzyHashBucketMapper;
+// Extra short (24 bucket; experimental) information
+#[cfg(feature = "experiment-variants")]
+impl private::Sealed for LengthProcessingInfo<NUM_BUCKETS_EXTRA_SHORT> where
+    FuzzyHashBucketsInfo<NUM_BUCKETS_EXTRA_SHORT>: FuzzyHashBucketMapper
+{
+}
+#[cfg(feature = "experiment-variants")]
+impl ConstrainedLengthProcessingInfo for LengthProcessingInfo<NUM_BUCKETS_EXTRA_SHORT>
+where
+    FuzzyHashBucketsInfo<NUM_BUCKETS_EXTRA_SHORT>: FuzzyHashBucketMapper,
+{
+    // The smallest length to make at least 24 bucket increments
+    // ((8 - 4) windows * 6 mappings).
+    const MIN: u32 = 8;
+    // Like the short variant, there is no separate conservative minimum.
+    const MIN_CONSERVATIVE: u32 = 8;
+}
 // Short (48 bucket) information
 impl private::Sealed for LengthProcessingInfo<NUM_BUCKETS_SHORT> where
     FuzzyHashBucketsInfo<NUM_BUCKETS_SHORT>: FuzzyHashBucketMapper
@@ -396,7 +415,7 @@ impl DataLengthValidity {
 ///
 /// This struct only handles the validness of its encoding.
 /// So, the case 2 above is considered "valid" in this type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct FuzzyHashLengthEncoding {
     /// The raw (approximated) length encoding.
@@ -408,7 +427,7 @@ impl FuzzyHashLengthEncoding {
 
     /// Creates the object from the raw encoding.
     #[inline(always)]
-    pub(crate) fn from_raw(lvalue: u8) -> Self {
+    pub(crate) const fn from_raw(lvalue: u8) -> Self {
         Self { lvalue }
     }
 
@@ -525,12 +544,73 @@ impl TryFrom<u32> for FuzzyHashLengthEncoding {
 }
 
 /// Encode the 32-bit data length as rough 8-bit representation.
-#[cfg(any(doc, test))]
-#[cfg_attr(feature = "unstable", doc(cfg(all())))]
-fn encode(len: u32) -> Option<u8> {
+///
+/// This is a shorthand of [`FuzzyHashLengthEncoding::new()`] returning
+/// the raw encoding.  It will return [`None`] if the length is too large.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::length::{decode_length_range, encode_length};
+///
+/// let encoding = encode_length(1_000_000).unwrap();
+/// assert_eq!(encoding, 0x52);
+/// assert!(decode_length_range(encoding).unwrap().contains(&1_000_000));
+/// ```
+#[inline]
+pub fn encode_length(len: u32) -> Option<u8> {
     FuzzyHashLengthEncoding::new(len).map(|x| x.lvalue)
 }
 
+/// Decode the encoded 8-bit length approximation as an inclusive 32-bit
+/// input size range that will produce the given encoding.
+///
+/// This is a shorthand of [`FuzzyHashLengthEncoding::range()`] taking
+/// the raw encoding.  It will return [`None`] if there's no valid 32-bit size
+/// for given encoding.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::length::decode_length_range;
+///
+/// // The digest with this encoding is generated from a file with
+/// // the size between 70124149 and 77136564 bytes (inclusive).
+/// assert_eq!(decode_length_range(0x7f), Some(70_124_149..=77_136_564));
+/// assert_eq!(decode_length_range(0xff), None);
+/// ```
+#[inline]
+pub fn decode_length_range(encoding: u8) -> Option<RangeInclusive<u32>> {
+    FuzzyHashLengthEncoding::from_raw(encoding).range()
+}
+
+/// Returns the boundaries of the data length encoding.
+///
+/// The returned slice has [`ENCODED_VALUE_SIZE`] elements and is strictly
+/// increasing.  The element at the index `i` is the *maximum* data length
+/// (inclusive) for the encoding `i` (and the minimum data length for the
+/// encoding `i + 1` is that plus one).
+///
+/// This is useful to enumerate boundary file sizes (e.g. to generate test
+/// vectors) without copying the internal table.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::length::{decode_length_range, encode_length, length_boundaries};
+///
+/// let boundaries = length_boundaries();
+/// assert_eq!(boundaries.len(), tlsh::length::ENCODED_VALUE_SIZE);
+/// for (i, &top) in boundaries.iter().enumerate() {
+///     assert_eq!(encode_length(top), Some(i as u8));
+///     assert_eq!(*decode_length_range(i as u8).unwrap().end(), top);
+/// }
+/// ```
+#[inline(always)]
+pub fn length_boundaries() -> &'static [u32] {
+    &TOP_VALUE_BY_ENCODING
+}
+
 /// The naïve implementation.
 #[cfg(any(doc, test))]
 #[cfg_attr(feature = "unstable", doc(cfg(all())))]
@@ -6,9 +6,9 @@
 #![cfg(test)]
 
 use super::{
-    encode, naive, ConstrainedLengthProcessingInfo, DataLengthProcessingMode, DataLengthValidity,
-    FuzzyHashLengthEncoding, LengthProcessingInfo, ENCODED_INDICES_BY_LEADING_ZEROS,
-    ENCODED_VALUE_SIZE, TOP_VALUE_BY_ENCODING,
+    decode_length_range, encode_length, length_boundaries, naive, ConstrainedLengthProcessingInfo,
+    DataLengthProcessingMode, DataLengthValidity, FuzzyHashLengthEncoding, LengthProcessingInfo,
+    ENCODED_INDICES_BY_LEADING_ZEROS, ENCODED_VALUE_SIZE, TOP_VALUE_BY_ENCODING,
 };
 
 use crate::buckets::constrained::{FuzzyHashBucketMapper, FuzzyHashBucketsInfo};
@@ -238,17 +238,45 @@ fn test_encode() {
         )
         .chain((u32::MAX - 5)..=u32::MAX)
     {
-        assert_eq!(super::encode(len), naive::encode(len));
+        assert_eq!(encode_length(len), naive::encode(len));
     }
 }
 
+#[test]
+fn decode_length_range_consistency() {
+    for encoding in 0..=u8::MAX {
+        let range = decode_length_range(encoding);
+        assert_eq!(
+            range,
+            FuzzyHashLengthEncoding::from_raw(encoding).range(),
+            "failed on encoding={encoding}"
+        );
+        if let Some(range) = range {
+            assert_eq!(encode_length(*range.start()), Some(encoding));
+            assert_eq!(encode_length(*range.end()), Some(encoding));
+        } else {
+            assert!((encoding as usize) >= ENCODED_VALUE_SIZE);
+        }
+    }
+}
+
+#[test]
+fn length_boundaries_table() {
+    let boundaries = length_boundaries();
+    assert_eq!(boundaries, TOP_VALUE_BY_ENCODING.as_slice());
+    assert_eq!(boundaries.len(), ENCODED_VALUE_SIZE);
+    assert!(boundaries.windows(2).all(|w| w[0] < w[1]));
+    // Both calls refer to the same static table.
+    assert!(core::ptr::eq(boundaries, length_boundaries()));
+}
+
 #[test]
 fn encode_top_and_above() {
     for (i, &top) in TOP_VALUE_BY_ENCODING.as_slice().iter().enumerate() {
         let encoding = i as u8;
-        assert_eq!(encode(top), Some(encoding));
+        assert_eq!(encode_length(top), Some(encoding));
         assert_ne!(top, u32::MAX);
-        let above_top = encode(top + 1);
+        let above_top = encode_length(top + 1);
         assert!(above_top.is_none() || above_top == Some(encoding + 1));
     }
 }
@@ -63,39 +63,62 @@ extern crate alloc;
 
 pub mod _docs;
 pub mod buckets;
+pub mod cluster;
 mod compare;
 mod compare_easy;
 mod errors;
+pub mod ffi;
 pub mod generate;
 mod generate_easy;
 mod generate_easy_std;
+mod generate_easy_tokio;
 pub mod hash;
 pub mod hashes;
 mod intrinsics;
+pub mod io;
 pub mod length;
 mod macros;
 mod params;
 mod parse;
+mod parse_easy;
 mod pearson;
+pub mod simd;
+pub mod wasm;
 
 // Easy function re-exports
 #[cfg(feature = "easy-functions")]
-pub use compare_easy::{compare, compare_with};
+pub use compare_easy::{compare, compare_any, compare_any_with, compare_with};
 #[cfg(feature = "easy-functions")]
-pub use generate_easy::{hash_buf, hash_buf_for};
+pub use generate_easy::{hash_buf, hash_buf_for, hash_str, hash_str_for};
+#[cfg(all(feature = "easy-functions", feature = "rayon"))]
+pub use generate_easy::{hash_bufs_par, hash_bufs_par_for};
 #[cfg(all(feature = "easy-functions", feature = "std"))]
-pub use generate_easy_std::{hash_file, hash_file_for, hash_stream, hash_stream_for};
+pub use generate_easy_std::{
+    compare_streams, compare_streams_for, hash_file, hash_file_for, hash_stream, hash_stream_for,
+};
+#[cfg(all(feature = "easy-functions", feature = "mmap"))]
+pub use generate_easy_std::{hash_file_mmap, hash_file_mmap_for};
+#[cfg(all(feature = "easy-functions", feature = "tokio"))]
+pub use generate_easy_tokio::{hash_async_reader, hash_async_reader_for};
+#[cfg(feature = "easy-functions")]
+pub use parse_easy::{is_valid_hash_str, is_valid_hash_str_for};
 
 // Trait re-exports
 pub use generate::public::GeneratorType;
 pub use hash::public::FuzzyHashType;
 
 // Type re-exports
-pub use compare::ComparisonConfiguration;
+pub use compare::{
+    min_distance_packed, min_distance_to_bytes, ComparisonConfiguration, Distance,
+    DistanceBreakdown, PreparedQuery, Similarity,
+};
+#[cfg(feature = "alloc")]
+pub use compare::{nearest_n, pack_bodies};
 pub use errors::{GeneratorError, GeneratorErrorCategory};
 pub use errors::{OperationError, ParseError};
-pub use generate::GeneratorOptions;
-pub use hash::HexStringPrefix;
+pub use generate::{generator_for_variant, BucketStats, GeneratorOptions};
+pub use hash::{HexCase, HexStringPrefix};
+pub use hashes::{detect_variant, AnyTlsh};
 pub use length::DataLengthProcessingMode;
 
 #[cfg(all(feature = "easy-functions", feature = "std"))]
@@ -106,6 +129,12 @@ pub use errors::{ParseErrorEither, ParseErrorSide};
 /// The default fuzzy hash type.
 pub type Tlsh = hashes::Normal;
 
+/// (fast-tlsh specific)
+/// The borrowed binary representation of the default fuzzy hash type.
+///
+/// See [`hash::BorrowedFuzzyHash`] for details.
+pub type BorrowedTlsh<'a> = hash::BorrowedFuzzyHash<'a, Tlsh>;
+
 /// The fuzzy hash generator with the default parameter.
 pub type TlshGenerator = generate::Generator<Tlsh>;
 
@@ -3,6 +3,8 @@
 
 //! The TLSH parameters.
 
+#[cfg(feature = "experiment-variants")]
+use crate::buckets::NUM_BUCKETS_EXTRA_SHORT;
 use crate::buckets::{NUM_BUCKETS_LONG, NUM_BUCKETS_NORMAL, NUM_BUCKETS_SHORT};
 use crate::hash::checksum::{CHECKSUM_SIZE_LONG, CHECKSUM_SIZE_NORMAL};
 use crate::{FuzzyHashType, GeneratorType};
@@ -32,12 +34,17 @@ pub trait ConstrainedFuzzyHashParams: private::SealedParam {
         + Clone
         + Copy
         + PartialEq
-        + Eq;
+        + Eq
+        + PartialOrd
+        + Ord
+        + core::hash::Hash;
     /// The inner generator type used by the public implementation.
     ///
     /// This is an instantiation of
     /// [`Generator`](crate::generate::inner::Generator).
     type InnerGeneratorType: GeneratorType<Output = Self::InnerFuzzyHashType>
+        + crate::generate::inner::WindowedGenerator
+        + crate::generate::inner::ChecksumlessGenerator
         + core::fmt::Debug
         + Default
         + Clone;
@@ -74,6 +81,9 @@ impl<T> ConstrainedVerboseFuzzyHashParams for T where T: private::SealedVerboseP
 
 /// The macro to convert symbolic buckets constant name to string.
 macro_rules! param_buckets_desc {
+    (NUM_BUCKETS_EXTRA_SHORT) => {
+        "Extra short (experimental)"
+    };
     (NUM_BUCKETS_SHORT) => {
         "Short"
     };
@@ -87,6 +97,9 @@ macro_rules! param_buckets_desc {
 
 /// The macro to convert symbolic buckets constant name to the official name.
 macro_rules! param_buckets_desc_alt {
+    (NUM_BUCKETS_EXTRA_SHORT) => {
+        "no official counterpart"
+    };
     (NUM_BUCKETS_SHORT) => {
         "min hash"
     };
@@ -136,12 +149,14 @@ macro_rules! inner_generator_type {
 
 /// The fuzzy hash parameter template generator.
 macro_rules! params {
-    {$($name:ident = ($size_checksum:tt, $size_buckets:tt);)*} => {
+    {$($(#[$meta:meta])* $name:ident = ($size_checksum:tt, $size_buckets:tt);)*} => {
         $(
+            $(#[$meta])*
             impl private::SealedParam
                 for FuzzyHashParams<{$size_checksum}, {$size_buckets}>
             {
             }
+            $(#[$meta])*
             impl private::SealedVerboseParam
                 for VerboseFuzzyHashParams<
                     {$size_checksum},
@@ -152,14 +167,147 @@ macro_rules! params {
                 >
             {
             }
+            $(#[$meta])*
             impl ConstrainedFuzzyHashParams for FuzzyHashParams<{$size_checksum}, {$size_buckets}> {
                 type InnerFuzzyHashType = inner_fuzzy_hash_type!($size_checksum, $size_buckets);
                 type InnerGeneratorType = inner_generator_type!($size_checksum, $size_buckets);
             }
+            $(#[$meta])*
             impl private::SealedFuzzyHashes
                 for crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}>
             {
             }
+            // The string representation never exceeds the length with the prefix.
+            $(#[$meta])*
+            static_assertions::const_assert!(
+                <crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}> as crate::FuzzyHashType>::LEN_IN_STR_NO_PREFIX
+                    <= <crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}> as crate::FuzzyHashType>::LEN_IN_STR_WITH_PREFIX
+            );
+            $(#[$meta])*
+            static_assertions::const_assert_eq!(
+                <crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}> as crate::FuzzyHashType>::LEN_IN_STR_WITH_PREFIX,
+                <crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}> as crate::FuzzyHashType>::SIZE_IN_BYTES * 2 + 2
+            );
+            $(#[$meta])*
+            impl crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}> {
+                /// Parses the TLSH's hexadecimal representation
+                /// in a `const` context.
+                ///
+                /// The existence of the prefix is auto-detected.
+                /// It performs the same checks as
+                /// [`FuzzyHashType::from_str_bytes()`](crate::FuzzyHashType::from_str_bytes())
+                /// *without* the `strict-parser` feature.
+                ///
+                /// This is useful to embed known fuzzy hashes as constants.
+                /// If the string is malformed, it causes a compile error
+                /// when evaluated in a `const` context.
+                ///
+                /// # Panics
+                ///
+                /// It panics if the given string is not a valid fuzzy hash
+                /// (only when evaluated at runtime).
+                ///
+                /// # Example
+                ///
+                /// ```
+                /// use core::str::FromStr;
+                /// use tlsh::Tlsh;
+                ///
+                /// const HASH: Tlsh = Tlsh::from_str_const(
+                ///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+                /// );
+                /// assert_eq!(
+                ///     HASH,
+                ///     Tlsh::from_str(
+                ///         "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+                ///     ).unwrap()
+                /// );
+                /// ```
+                ///
+                /// Malformed strings cause a compile error.
+                ///
+                /// ```compile_fail
+                /// use tlsh::Tlsh;
+                ///
+                /// const HASH: Tlsh = Tlsh::from_str_const(
+                ///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AAX"
+                /// );
+                /// ```
+                pub const fn from_str_const(s: &str) -> Self {
+                    Self::new(
+                        <inner_fuzzy_hash_type!($size_checksum, $size_buckets)>::from_str_bytes_const(
+                            s.as_bytes(),
+                        ),
+                    )
+                }
+
+                /// Returns the contents of this object as an owned array
+                /// (in the binary format).
+                ///
+                /// The result is the same as the one stored by
+                /// [`FuzzyHashType::store_into_bytes()`](crate::FuzzyHashType::store_into_bytes())
+                /// but no buffer is required.  The length of the array is
+                /// [`SIZE_IN_BYTES`](crate::FuzzyHashType::SIZE_IN_BYTES).
+                ///
+                /// The same conversion is also available through [`From`].
+                ///
+                /// # Example
+                ///
+                /// ```
+                /// use core::str::FromStr;
+                /// use tlsh::prelude::*;
+                ///
+                /// let hash = Tlsh::from_str(
+                ///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+                /// ).unwrap();
+                /// let bytes: [u8; Tlsh::SIZE_IN_BYTES] = hash.to_bytes();
+                /// assert_eq!(bytes[..3], [0xcd, 0x0f, 0xcd]);
+                /// assert_eq!(Tlsh::try_from(bytes.as_slice()), Ok(hash));
+                /// ```
+                pub fn to_bytes(&self) -> [u8; $size_buckets / 4 + 2 + $size_checksum] {
+                    let mut out = [0u8; $size_buckets / 4 + 2 + $size_checksum];
+                    crate::FuzzyHashType::store_into_bytes(self, &mut out).unwrap();
+                    out
+                }
+
+                /// Generates the fuzzy hash from bytes provided by an iterator
+                /// (with the default generator options).
+                ///
+                /// This is a shorthand to feed all bytes to a new generator
+                /// using [`GeneratorType::update_iter()`](crate::GeneratorType::update_iter())
+                /// and to finalize it.
+                ///
+                /// # Example
+                ///
+                /// ```
+                /// use core::str::FromStr;
+                /// use tlsh::prelude::*;
+                ///
+                /// let data: &[u8] = b"Lovak won the squad prize cup for sixty big jumps.";
+                /// let hash = Tlsh::from_iter(data.iter().copied()).unwrap();
+                /// let expected = "T14A90024954691E114404124180D942C1450F8423775ADE1510211420456593621A8173";
+                /// assert_eq!(hash, Tlsh::from_str(expected).unwrap());
+                /// ```
+                #[allow(clippy::should_implement_trait)]
+                pub fn from_iter<I: IntoIterator<Item = u8>>(
+                    iter: I,
+                ) -> Result<Self, crate::errors::GeneratorError> {
+                    use crate::GeneratorType;
+                    let mut generator = crate::generate::Generator::<Self>::new();
+                    generator.update_iter(iter);
+                    generator.finalize()
+                }
+            }
+            $(#[$meta])*
+            impl From<crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}>>
+                for [u8; $size_buckets / 4 + 2 + $size_checksum]
+            {
+                #[inline(always)]
+                fn from(value: crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}>) -> Self {
+                    value.to_bytes()
+                }
+            }
+            $(#[$meta])*
             impl ConstrainedFuzzyHashType for crate::hash::FuzzyHash<{$size_checksum}, {$size_buckets}> {
                 type Params = FuzzyHashParams<{$size_checksum}, {$size_buckets}>;
                 fn new(inner: <Self::Params as ConstrainedFuzzyHashParams>::InnerFuzzyHashType) -> Self {
@@ -171,6 +319,7 @@ macro_rules! params {
         pub(crate) mod exported_hashes {
             use super::*;
             $(
+                $(#[$meta])*
                 #[doc = concat!(
                     param_buckets_desc!($size_buckets),
                     " fuzzy hash type (",
@@ -192,6 +341,8 @@ macro_rules! params {
     };
 }
 params! {
+    #[cfg(feature = "experiment-variants")]
+    ExtraShort             = (CHECKSUM_SIZE_NORMAL, NUM_BUCKETS_EXTRA_SHORT);
     Short                  = (CHECKSUM_SIZE_NORMAL, NUM_BUCKETS_SHORT);
     Normal                 = (CHECKSUM_SIZE_NORMAL, NUM_BUCKETS_NORMAL);
     NormalWithLongChecksum = (CHECKSUM_SIZE_LONG,   NUM_BUCKETS_NORMAL);
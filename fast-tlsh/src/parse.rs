@@ -3,5 +3,6 @@
 
 //! Small utilities for the parser.
 
+pub(crate) mod base64;
 pub(crate) mod bits;
 pub(crate) mod hex_str;
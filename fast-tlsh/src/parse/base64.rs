@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! URL-safe Base64 utilities (without padding).
+//!
+//! This module implements the "base64url" encoding defined in the
+//! RFC 4648 (section 5) without trailing padding characters (`=`).
+
+/// The URL-safe Base64 alphabet.
+const BASE64_URL_TABLE: [u8; 64] =
+    *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Invalid Base64 character (in [`BASE64_URL_REV_TABLE`]).
+const BASE64_INVALID: u8 = 0xff;
+
+/// The URL-safe Base64 character-to-value table.
+const BASE64_URL_REV_TABLE: [u8; 256] = {
+    let mut array = [BASE64_INVALID; 256];
+    let mut i = 0;
+    while i < BASE64_URL_TABLE.len() {
+        array[BASE64_URL_TABLE[i] as usize] = i as u8;
+        i += 1;
+    }
+    array
+};
+
+/// Computes the encoded length of the binary data with given length.
+#[inline(always)]
+pub(crate) const fn encoded_len(len: usize) -> usize {
+    (len * 4 + 2) / 3
+}
+
+/// Encodes the binary data to URL-safe Base64 without padding.
+///
+/// `dst` must have exactly [`encoded_len(src.len())`](encoded_len()) bytes.
+pub(crate) fn encode(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), encoded_len(src.len()));
+    let mut chunks_dst = dst.chunks_exact_mut(4);
+    let mut chunks_src = src.chunks_exact(3);
+    for (dst, src) in (&mut chunks_dst).zip(&mut chunks_src) {
+        let v = (src[0] as u32) << 16 | (src[1] as u32) << 8 | (src[2] as u32);
+        dst[0] = BASE64_URL_TABLE[(v >> 18) as usize & 0x3f];
+        dst[1] = BASE64_URL_TABLE[(v >> 12) as usize & 0x3f];
+        dst[2] = BASE64_URL_TABLE[(v >> 6) as usize & 0x3f];
+        dst[3] = BASE64_URL_TABLE[v as usize & 0x3f];
+    }
+    let dst = chunks_dst.into_remainder();
+    match *chunks_src.remainder() {
+        [] => {}
+        [b0] => {
+            dst[0] = BASE64_URL_TABLE[(b0 >> 2) as usize];
+            dst[1] = BASE64_URL_TABLE[((b0 & 0x03) << 4) as usize];
+        }
+        [b0, b1] => {
+            let v = (b0 as u32) << 8 | (b1 as u32);
+            dst[0] = BASE64_URL_TABLE[(v >> 10) as usize & 0x3f];
+            dst[1] = BASE64_URL_TABLE[(v >> 4) as usize & 0x3f];
+            dst[2] = BASE64_URL_TABLE[((v & 0x0f) << 2) as usize];
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Decodes URL-safe Base64 data without padding.
+///
+/// `src` must have exactly [`encoded_len(dst.len())`](encoded_len()) bytes.
+///
+/// It returns [`None`] if there's an invalid character or
+/// unused trailing bits are not zero (non-canonical encoding).
+pub(crate) fn decode(dst: &mut [u8], src: &[u8]) -> Option<()> {
+    assert_eq!(src.len(), encoded_len(dst.len()));
+    let mut invalid = false;
+    let mut value = |ch: u8| {
+        let v = BASE64_URL_REV_TABLE[ch as usize];
+        invalid |= v == BASE64_INVALID;
+        v as u32
+    };
+    let mut chunks_dst = dst.chunks_exact_mut(3);
+    let mut chunks_src = src.chunks_exact(4);
+    for (dst, src) in (&mut chunks_dst).zip(&mut chunks_src) {
+        let v = value(src[0]) << 18 | value(src[1]) << 12 | value(src[2]) << 6 | value(src[3]);
+        dst[0] = (v >> 16) as u8;
+        dst[1] = (v >> 8) as u8;
+        dst[2] = v as u8;
+    }
+    let dst = chunks_dst.into_remainder();
+    let mut trailing = 0;
+    match *chunks_src.remainder() {
+        [] => {}
+        [c0, c1] => {
+            let v = value(c0) << 6 | value(c1);
+            dst[0] = (v >> 4) as u8;
+            trailing = v & 0x0f;
+        }
+        [c0, c1, c2] => {
+            let v = value(c0) << 12 | value(c1) << 6 | value(c2);
+            dst[0] = (v >> 10) as u8;
+            dst[1] = (v >> 2) as u8;
+            trailing = v & 0x03;
+        }
+        _ => unreachable!(),
+    }
+    (!invalid && trailing == 0).then_some(())
+}
+
+mod tests;
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::parse::base64`].
+
+#![cfg(test)]
+
+use super::{decode, encode, encoded_len};
+
+#[test]
+fn encoded_len_examples() {
+    assert_eq!(encoded_len(0), 0);
+    assert_eq!(encoded_len(1), 2);
+    assert_eq!(encoded_len(2), 3);
+    assert_eq!(encoded_len(3), 4);
+    assert_eq!(encoded_len(4), 6);
+    assert_eq!(encoded_len(35), 47);
+    assert_eq!(encoded_len(69), 92);
+}
+
+#[test]
+fn rfc4648_examples() {
+    // RFC 4648 section 10 (without padding).
+    const EXAMPLES: &[(&[u8], &[u8])] = &[
+        (b"", b""),
+        (b"f", b"Zg"),
+        (b"fo", b"Zm8"),
+        (b"foo", b"Zm9v"),
+        (b"foob", b"Zm9vYg"),
+        (b"fooba", b"Zm9vYmE"),
+        (b"foobar", b"Zm9vYmFy"),
+    ];
+    for &(raw, encoded) in EXAMPLES {
+        let mut buf = [0u8; 8];
+        let buf = &mut buf[..encoded.len()];
+        encode(buf, raw);
+        assert_eq!(buf, encoded);
+        let mut buf = [0u8; 6];
+        let buf = &mut buf[..raw.len()];
+        assert_eq!(decode(buf, encoded), Some(()));
+        assert_eq!(buf, raw);
+    }
+}
+
+#[test]
+fn url_safe_alphabet() {
+    let mut buf = [0u8; 4];
+    encode(&mut buf, &[0xfb, 0xff, 0xbf]);
+    assert_eq!(&buf, b"-_-_");
+    let mut raw = [0u8; 3];
+    assert_eq!(decode(&mut raw, b"-_-_"), Some(()));
+    assert_eq!(raw, [0xfb, 0xff, 0xbf]);
+    // Standard Base64 characters are invalid.
+    assert_eq!(decode(&mut raw, b"+/+/"), None);
+}
+
+#[test]
+fn decode_errors() {
+    let mut raw = [0u8; 4];
+    // Invalid characters (including padding) at every position
+    for pos in 0..6 {
+        let mut encoded = *b"Zm9vYg";
+        encoded[pos] = b'=';
+        assert_eq!(decode(&mut raw, &encoded), None);
+    }
+    // Non-canonical trailing bits
+    assert_eq!(decode(&mut raw, b"Zm9vYh"), None);
+    assert_eq!(decode(&mut raw[..2], b"Zm8"), Some(()));
+    assert_eq!(decode(&mut raw[..2], b"Zm9"), None);
+}
+
+#[test]
+fn roundtrip_all_lengths() {
+    let data: [u8; 70] = core::array::from_fn(|i| (i as u8).wrapping_mul(0x9d) ^ 0x5a);
+    for len in 0..=data.len() {
+        let mut encoded = [0u8; 94];
+        let encoded = &mut encoded[..encoded_len(len)];
+        encode(encoded, &data[..len]);
+        assert!(encoded
+            .iter()
+            .all(|ch| ch.is_ascii_alphanumeric() || *ch == b'-' || *ch == b'_'));
+        let mut decoded = [0u8; 70];
+        assert_eq!(decode(&mut decoded[..len], encoded), Some(()));
+        assert_eq!(&decoded[..len], &data[..len]);
+    }
+}
@@ -142,10 +142,8 @@ const HEX_REV_TABLE_LO: [HexDecodeTableType; 256] = [
 ///
 /// Note that this kind of implementation is notoriously bad
 /// for branch prediction.
-#[cfg(any(doc, test, feature = "opt-low-memory-hex-str-decode-min-table"))]
-#[cfg_attr(feature = "unstable", doc(cfg(all())))]
 #[inline]
-pub(super) const fn decode_digit(digit: u8) -> u8 {
+pub(crate) const fn decode_digit(digit: u8) -> u8 {
     match digit {
         b'0'..=b'9' => digit - b'0',
         b'A'..=b'F' => digit - b'A' + 10,
@@ -157,12 +155,40 @@ pub(super) const fn decode_digit(digit: u8) -> u8 {
 #[cfg(feature = "opt-low-memory-hex-str-decode-quarter-table")]
 static_assertions::const_assert_eq!(HEX_INVALID, 0xff);
 
+/// Converts length 2 hexadecimal string (with normal nibble endianness)
+/// at the specified offset to an [`u8`] value (in a `const` context).
+///
+/// If the conversion fails, it returns [`None`].
+#[inline]
+pub(crate) const fn decode_1_const(src: &[u8], offset: usize) -> Option<u8> {
+    let value_hi = decode_digit(src[offset]);
+    let value_lo = decode_digit(src[offset + 1]);
+    if value_hi == 0xff || value_lo == 0xff {
+        None
+    } else {
+        Some(value_hi << 4 | value_lo)
+    }
+}
+
+/// Converts length 2 hexadecimal string (with "reverse" nibble endianness)
+/// at the specified offset to an [`u8`] value (in a `const` context).
+///
+/// If the conversion fails, it returns [`None`].
+#[inline]
+pub(crate) const fn decode_rev_1_const(src: &[u8], offset: usize) -> Option<u8> {
+    let value_lo = decode_digit(src[offset]);
+    let value_hi = decode_digit(src[offset + 1]);
+    if value_hi == 0xff || value_lo == 0xff {
+        None
+    } else {
+        Some(value_hi << 4 | value_lo)
+    }
+}
+
 /// Converts length 2 hexadecimal string (with normal nibble endianness)
 /// to an [`u8`] value.
 ///
 /// If the conversion fails, it returns [`None`].
-#[cfg(any(test, doc, not(feature = "opt-simd-parse-hex")))]
-#[cfg_attr(feature = "unstable", doc(cfg(all())))]
 #[inline(always)]
 pub fn decode_1(src: &[u8]) -> Option<u8> {
     if src.len() != 2 {
@@ -210,22 +236,20 @@ pub fn decode_1(src: &[u8]) -> Option<u8> {
 ///
 /// It returns whether this function has succeeded.
 /// If not, `dst` may be partially written (or may be not).
-#[cfg(any(test, doc, not(feature = "opt-simd-parse-hex")))]
+#[cfg(any(
+    test,
+    doc,
+    not(feature = "opt-simd-parse-hex"),
+    all(
+        feature = "simd-per-arch",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        any(feature = "detect-features", target_feature = "ssse3")
+    )
+))]
 #[cfg_attr(feature = "unstable", doc(cfg(all())))]
 #[inline]
 pub fn decode_array<const N: usize>(dst: &mut [u8; N], src: &[u8]) -> bool {
-    if src.len() != N * 2 {
-        return false;
-    }
-    for (dst, src) in dst.iter_mut().zip(src.chunks_exact(2)) {
-        let value = decode_1(src);
-        if let Some(value) = value {
-            *dst = value;
-        } else {
-            return false;
-        }
-    }
-    true
+    decode_array_impl::<N, false>(dst, src)
 }
 
 /// Converts length 2 hexadecimal string (with "reverse" nibble endianness)
@@ -281,11 +305,72 @@ pub fn decode_rev_1(src: &[u8]) -> Option<u8> {
 /// If not, `dst` may be partially written (or may be not).
 #[inline]
 pub fn decode_rev_array<const N: usize>(dst: &mut [u8; N], src: &[u8]) -> bool {
+    decode_array_impl::<N, true>(dst, src)
+}
+
+/// Whether the SIMD hexadecimal decoder can be used on this environment.
+#[cfg(all(
+    feature = "simd-per-arch",
+    feature = "opt-simd-parse-hex",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "detect-features", target_feature = "ssse3")
+))]
+#[inline(always)]
+fn is_simd_decoder_available() -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "detect-features")] {
+            // The detection result is cached by the standard library.
+            crate::simd::allows(crate::simd::Backend::Ssse3)
+                && std::arch::is_x86_feature_detected!("ssse3")
+        } else {
+            true
+        }
+    }
+}
+
+/// Converts a hexadecimal string to an array of [`u8`].
+///
+/// If `REV` is [`true`], the nibble endianness is "reverse".
+///
+/// If available, 16 hexadecimal digits are decoded at once using SIMD
+/// and the rest is decoded using the scalar implementation.
+#[inline(always)]
+fn decode_array_impl<const N: usize, const REV: bool>(dst: &mut [u8; N], src: &[u8]) -> bool {
     if src.len() != N * 2 {
         return false;
     }
+    #[allow(unused_mut)]
+    let mut dst = dst.as_mut_slice();
+    #[allow(unused_mut)]
+    let mut src = src;
+    #[cfg(all(
+        feature = "simd-per-arch",
+        feature = "opt-simd-parse-hex",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        any(feature = "detect-features", target_feature = "ssse3")
+    ))]
+    if is_simd_decoder_available() {
+        let mut dst_chunks = dst.chunks_exact_mut(8);
+        let mut src_chunks = src.chunks_exact(16);
+        for (dst, src) in (&mut dst_chunks).zip(&mut src_chunks) {
+            // Safety: SSSE3 availability is checked above.
+            #[allow(unsafe_code)]
+            let result = unsafe {
+                x86_ssse3::decode_16::<REV>(dst.try_into().unwrap(), src.try_into().unwrap())
+            };
+            if !result {
+                return false;
+            }
+        }
+        dst = dst_chunks.into_remainder();
+        src = src_chunks.remainder();
+    }
     for (dst, src) in dst.iter_mut().zip(src.chunks_exact(2)) {
-        let value = decode_rev_1(src);
+        let value = if REV {
+            decode_rev_1(src)
+        } else {
+            decode_1(src)
+        };
         if let Some(value) = value {
             *dst = value;
         } else {
@@ -344,4 +429,6 @@ pub fn encode_rev_array<const N: usize>(dst: &mut [u8], src: &[u8; N]) {
     }
 }
 
+mod x86_ssse3;
+
 mod tests;
@@ -292,6 +292,36 @@ fn decode_rev_array_fail_data() {
     assert!(!decode_rev_array(&mut array, b"0123456@89abcdef"));
 }
 
+#[test]
+fn decode_array_consistency_with_1() {
+    // 18 bytes: two 16-digit blocks (possibly decoded by SIMD) and the rest.
+    const N: usize = 18;
+    let valid: Vec<u8> = b"0123456789abcdefABCDEF"
+        .iter()
+        .cycle()
+        .take(N * 2)
+        .copied()
+        .collect();
+    for pos in 0..N * 2 {
+        for ch in u8::MIN..=u8::MAX {
+            let mut src = valid.clone();
+            src[pos] = ch;
+            let expected_1: Option<Vec<u8>> = src.chunks_exact(2).map(decode_1).collect();
+            let expected_rev_1: Option<Vec<u8>> = src.chunks_exact(2).map(decode_rev_1).collect();
+            let mut array = [0u8; N];
+            assert_eq!(decode_array(&mut array, &src), expected_1.is_some());
+            if let Some(expected) = expected_1 {
+                assert_eq!(array.as_slice(), expected.as_slice());
+            }
+            let mut array = [0u8; N];
+            assert_eq!(decode_rev_array(&mut array, &src), expected_rev_1.is_some());
+            if let Some(expected) = expected_rev_1 {
+                assert_eq!(array.as_slice(), expected.as_slice());
+            }
+        }
+    }
+}
+
 #[test]
 fn encode_rev_1_example() {
     let mut dst = [0u8; 2];
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! SSSE3 implementation (x86) of hexadecimal string decoding.
+//!
+//! This implementation handles 16 hexadecimal digits (8 bytes) at once.
+
+#![cfg(all(
+    feature = "simd-per-arch",
+    feature = "opt-simd-parse-hex",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "detect-features", target_feature = "ssse3")
+))]
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+/// Decodes 16 hexadecimal digits into 8 bytes.
+///
+/// If `REV` is [`true`], the first digit of each pair is treated as
+/// the low nibble ("reverse" nibble endianness).  Otherwise, the first digit
+/// is the high nibble (normal nibble endianness).
+///
+/// It returns whether all digits are valid.  If not, `dst` is not modified.
+#[allow(unsafe_code)]
+#[cfg_attr(not(feature = "detect-features"), inline(always))]
+#[cfg_attr(feature = "detect-features", target_feature(enable = "ssse3"), inline)]
+pub(super) unsafe fn decode_16<const REV: bool>(dst: &mut [u8; 8], src: &[u8; 16]) -> bool {
+    let data = _mm_loadu_si128(src.as_ptr() as *const __m128i);
+    // Bytes 0x80-0xff are negative (signed) and rejected by both ranges.
+    // Because of that, ORing 0x20 maps only 'A'-'F' and 'a'-'f' into 'a'-'f'.
+    let lower = _mm_or_si128(data, _mm_set1_epi8(0x20));
+    let is_digit = _mm_and_si128(
+        _mm_cmpgt_epi8(data, _mm_set1_epi8((b'0' - 1) as i8)),
+        _mm_cmpgt_epi8(_mm_set1_epi8((b'9' + 1) as i8), data),
+    );
+    let is_alpha = _mm_and_si128(
+        _mm_cmpgt_epi8(lower, _mm_set1_epi8((b'a' - 1) as i8)),
+        _mm_cmpgt_epi8(_mm_set1_epi8((b'f' + 1) as i8), lower),
+    );
+    if _mm_movemask_epi8(_mm_or_si128(is_digit, is_alpha)) != 0xffff {
+        return false;
+    }
+    let nibbles = _mm_or_si128(
+        _mm_and_si128(is_digit, _mm_sub_epi8(data, _mm_set1_epi8(b'0' as i8))),
+        _mm_and_si128(
+            is_alpha,
+            _mm_sub_epi8(lower, _mm_set1_epi8((b'a' - 10) as i8)),
+        ),
+    );
+    // Combine each nibble pair into a 16-bit lane (the multiplier of the
+    // high nibble is 0x10 and the low nibble is 0x01).
+    let weights = if REV {
+        _mm_set1_epi16(0x1001)
+    } else {
+        _mm_set1_epi16(0x0110)
+    };
+    let words = _mm_maddubs_epi16(nibbles, weights);
+    // Gather the low byte of each 16-bit lane into the low 64 bits.
+    let packed = _mm_shuffle_epi8(
+        words,
+        _mm_set_epi8(
+            -128, -128, -128, -128, -128, -128, -128, -128, 14, 12, 10, 8, 6, 4, 2, 0,
+        ),
+    );
+    _mm_storel_epi64(dst.as_mut_ptr() as *mut __m128i, packed);
+    true
+}
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Easy validation for TLSH strings.
+
+#![cfg(feature = "easy-functions")]
+
+use crate::params::ConstrainedFuzzyHashType;
+use crate::Tlsh;
+
+/// Checks whether the given string is a valid fuzzy hash
+/// (with specified fuzzy hash type).
+///
+/// This is equivalent to checking the result of [`str::parse()`]
+/// (or [`FromStr::from_str()`](core::str::FromStr::from_str())): the
+/// prefix is auto-detected and leading and trailing ASCII whitespace
+/// characters are ignored.  If the `strict-parser` feature is enabled,
+/// validity of the checksum and the length fields is also checked.
+/// The decoded value is discarded and nothing is allocated.
+///
+/// # Examples
+///
+/// ```
+/// type CustomTlsh = tlsh::hashes::Short;
+///
+/// assert!(tlsh::is_valid_hash_str_for::<CustomTlsh>("T140D5F17F44F8AB007AE2AC46E515DC"));
+/// assert!(!tlsh::is_valid_hash_str_for::<CustomTlsh>("T140D5F17F44F8AB007AE2AC46E515DX"));
+/// assert!(!tlsh::is_valid_hash_str_for::<CustomTlsh>("TNULL"));
+/// // Surrounding whitespace is ignored (as in `str::parse`).
+/// assert!(tlsh::is_valid_hash_str_for::<CustomTlsh>(" T140D5F17F44F8AB007AE2AC46E515DC\n"));
+/// ```
+pub fn is_valid_hash_str_for<T: ConstrainedFuzzyHashType>(s: &str) -> bool {
+    T::from_str(s).is_ok()
+}
+
+/// Checks whether the given string is a valid fuzzy hash.
+///
+/// See [`is_valid_hash_str_for()`] for details.
+///
+/// # Examples
+///
+/// ```
+/// assert!(tlsh::is_valid_hash_str(
+///     "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+/// ));
+/// // Without the prefix.
+/// assert!(tlsh::is_valid_hash_str(
+///     "DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2"
+/// ));
+/// // Garbage
+/// assert!(!tlsh::is_valid_hash_str("<script>alert(1)</script>"));
+/// ```
+#[inline(always)]
+pub fn is_valid_hash_str(s: &str) -> bool {
+    is_valid_hash_str_for::<Tlsh>(s)
+}
+
+mod tests;
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::parse_easy`].
+
+#![cfg(test)]
+
+use super::{is_valid_hash_str, is_valid_hash_str_for};
+
+use core::str::FromStr;
+
+use crate::generate::tests::LOREM_IPSUM_HASH_NORMAL;
+use crate::hash::HexStringPrefix;
+use crate::hashes;
+use crate::FuzzyHashType;
+
+#[test]
+fn consistency_with_parser() {
+    const STRS: &[&str] = &[
+        LOREM_IPSUM_HASH_NORMAL,
+        "DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+        "t1dcf0dc36520c1b007fd32079b226559fd998a0200725e75afceac99f5881184a4b1aa2",
+        // Invalid prefix
+        "T2DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+        // Invalid character
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AAG",
+        // Invalid length
+        "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA",
+        // Surrounding whitespace
+        " T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2\n",
+        // Large length (rejected only by the strict parser)
+        "T1DCFFDC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2",
+        // Null hashes
+        "TNULL",
+        "NULL",
+        "",
+        "T140D5F17F44F8AB007AE2AC46E515DC",
+    ];
+    for &s in STRS {
+        assert_eq!(
+            is_valid_hash_str(s),
+            crate::Tlsh::from_str(s).is_ok(),
+            "failed on {s:?}"
+        );
+        assert_eq!(
+            is_valid_hash_str_for::<hashes::Short>(s),
+            hashes::Short::from_str(s).is_ok(),
+            "failed on {s:?}"
+        );
+    }
+    assert!(is_valid_hash_str(LOREM_IPSUM_HASH_NORMAL));
+    // Surrounding whitespace is ignored.
+    assert!(is_valid_hash_str(STRS[6]));
+    assert!(is_valid_hash_str_for::<hashes::Short>(
+        "\tT140D5F17F44F8AB007AE2AC46E515DC \r\n"
+    ));
+    assert!(!is_valid_hash_str(" \n"));
+    assert!(!is_valid_hash_str("TNULL"));
+    assert!(is_valid_hash_str_for::<hashes::Short>(
+        "T140D5F17F44F8AB007AE2AC46E515DC"
+    ));
+    assert!(!is_valid_hash_str_for::<hashes::Long>(
+        LOREM_IPSUM_HASH_NORMAL
+    ));
+    // The large length field
+    assert_eq!(is_valid_hash_str(STRS[7]), !cfg!(feature = "strict-parser"));
+    assert_eq!(
+        crate::Tlsh::from_str_bytes(STRS[7].as_bytes(), Some(HexStringPrefix::WithVersion)).is_ok(),
+        !cfg!(feature = "strict-parser")
+    );
+}
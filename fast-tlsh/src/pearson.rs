@@ -102,7 +102,7 @@ static SUBST_TABLE_DOUBLE: [[u8; 256]; 256] = {
 /// Instead, it increases the bias on the checksum (because values other than
 /// `48` will get intermediate frequency of `5/256` but `48` gets `16/256`;
 /// `256 / 48 == 5`, `256 - 256 / 48 * 48 == 16`).
-const SUBST_TABLE_48: [u8; 256] = {
+pub(crate) const SUBST_TABLE_48: [u8; 256] = {
     let mut array = SUBST_TABLE;
     let mut i = 0;
     while i < 256 {
@@ -116,6 +116,19 @@ const SUBST_TABLE_48: [u8; 256] = {
     array
 };
 
+/// The substitution table applied twice (`SUBST_TABLE[SUBST_TABLE[x]]`).
+///
+/// This is used by [`final_256_init()`].
+static SUBST_TABLE_SQUARED: [u8; 256] = {
+    let mut array = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        array[i] = SUBST_TABLE[SUBST_TABLE[i] as usize];
+        i += 1;
+    }
+    array
+};
+
 /// Process one byte (as a initialization) using Pearson hashing.
 #[inline(always)]
 pub const fn init(value: u8) -> u8 {
@@ -155,6 +168,18 @@ pub const fn final_256(state: u8, value: u8) -> u8 {
     update(state, value)
 }
 
+/// Process one byte using Pearson hashing for 256-bucket finalization and
+/// use the result to initialize another Pearson hashing state.
+///
+/// This is equivalent to `init(final_256(state, value))` but requires only
+/// one table lookup (not two dependent lookups).  When both
+/// [`final_256()`] and this function are called with the same arguments,
+/// two lookups can be performed in parallel.
+#[inline(always)]
+pub fn final_256_init(state: u8, value: u8) -> u8 {
+    SUBST_TABLE_SQUARED[(state ^ value) as usize]
+}
+
 /// Process one byte using Pearson hashing for 48-bucket finalization.
 ///
 /// Assuming that the return value of [`final_256()`] is `x`,
@@ -210,4 +235,36 @@ pub fn tlsh_b_mapping_48(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
     final_48(update_double(init(b0), b1, b2), b3)
 }
 
+/// Folds the result of the 48-bucket B mapping into the 24-bucket one.
+///
+/// Because `240` is also a multiple of `24`, it maps `x % 48` to `x % 24`
+/// and `48` (the outlier) to `24`.
+#[cfg(feature = "experiment-variants")]
+#[inline(always)]
+pub const fn fold_48_to_24(value: u8) -> u8 {
+    if value < 48 {
+        value % 24
+    } else {
+        24
+    }
+}
+
+/// (fast-tlsh specific)
+/// TLSH's B (bucket) mapping on the experimental 24-bucket variant.
+///
+/// Assuming that the return value of [`tlsh_b_mapping_256()`] is `x`,
+/// the return value of this function is as follows:
+///
+/// *   `x % 24` (when `x < 240`)
+/// *   `24`     (otherwise)
+///
+/// Like [`tlsh_b_mapping_48()`], it only uses first
+/// `256 / 24 * 24 == 240` values for bucket counting
+/// (which happens to be the same threshold as the 48-bucket variant).
+#[cfg(feature = "experiment-variants")]
+#[inline(always)]
+pub fn tlsh_b_mapping_24(b0: u8, b1: u8, b2: u8, b3: u8) -> u8 {
+    fold_48_to_24(tlsh_b_mapping_48(b0, b1, b2, b3))
+}
+
 mod tests;
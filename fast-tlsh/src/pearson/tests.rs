@@ -6,8 +6,8 @@
 #![cfg(test)]
 
 use super::{
-    final_256, final_48, init, tlsh_b_mapping_256, tlsh_b_mapping_48, update, update_double,
-    INITIAL_STATE, SUBST_TABLE,
+    final_256, final_256_init, final_48, init, tlsh_b_mapping_256, tlsh_b_mapping_48, update,
+    update_double, INITIAL_STATE, SUBST_TABLE,
 };
 
 #[test]
@@ -49,6 +49,15 @@ fn final_256_example() {
     assert_eq!(state, 0x4b);
 }
 
+#[test]
+fn final_256_init_equivalence() {
+    for state in u8::MIN..=u8::MAX {
+        for value in u8::MIN..=u8::MAX {
+            assert_eq!(final_256_init(state, value), init(final_256(state, value)));
+        }
+    }
+}
+
 #[test]
 fn final_48_example() {
     let state = init(0x02);
@@ -74,6 +83,35 @@ fn final_48_and_256() {
     }
 }
 
+#[cfg(feature = "experiment-variants")]
+#[test]
+fn b_mapping_24_and_256() {
+    use super::{fold_48_to_24, tlsh_b_mapping_24};
+    for value in u8::MIN..=48 {
+        assert_eq!(
+            fold_48_to_24(value),
+            if value == 48 { 24 } else { value % 24 }
+        );
+    }
+    for b0 in [0x00, 0x02, 0x03, 0x05, 0x07, 0x0b, 0x0d] {
+        for b1 in u8::MIN..=u8::MAX {
+            for b2 in u8::MIN..=u8::MAX {
+                for b3 in [0x00, 0x17, 0x18, 0x80, 0xff] {
+                    let expected = {
+                        let v = tlsh_b_mapping_256(b0, b1, b2, b3);
+                        if v >= 240 {
+                            24
+                        } else {
+                            v % 24
+                        }
+                    };
+                    assert_eq!(tlsh_b_mapping_24(b0, b1, b2, b3), expected);
+                }
+            }
+        }
+    }
+}
+
 #[test]
 fn tlsh_b_mapping_examples() {
     // See also: final_256_example
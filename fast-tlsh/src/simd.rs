@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Runtime override of the SIMD backend.
+//!
+//! If the `detect-features` feature is enabled, some internal functions
+//! (e.g. the body comparison and the bucket aggregation) are dynamically
+//! dispatched depending on the CPU features detected at the first use.
+//!
+//! This module provides a way to limit the instruction set used by those
+//! functions, which is useful to benchmark each implementation fairly or to
+//! reproduce issues reported on other machines (e.g. forcing SSE2 on the
+//! machine with AVX2).
+//!
+//! The backend is selected once per process, either by calling
+//! [`force_backend()`] or by the environment variable
+//! [`FAST_TLSH_SIMD_BACKEND`](ENV_BACKEND) (read once on the first use).
+//! So, [`force_backend()`] must be called before the first fuzzy hash
+//! generation, comparison or parsing.
+//!
+//! This does not affect implementations in external crates
+//! (e.g. the hexadecimal encoder using the `hex-simd` crate).
+//!
+//! Note that the backend only *limits* the implementation.  Even if
+//! a backend is forced, an implementation requiring unsupported CPU features
+//! is never selected (the next best implementation is used instead).
+
+#![cfg(feature = "detect-features")]
+#![cfg_attr(feature = "unstable", doc(cfg(feature = "detect-features")))]
+
+use std::sync::OnceLock;
+
+/// The name of the environment variable to select the SIMD backend.
+///
+/// It is read only once (on the first use) unless [`force_backend()`] is
+/// called before.  Valid values are the names returned by
+/// [`Backend::name()`] (unknown values are treated as [`Backend::Auto`]).
+pub const ENV_BACKEND: &str = "FAST_TLSH_SIMD_BACKEND";
+
+/// The SIMD backend (the most capable instruction set to use).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Use the best implementation detected at runtime (the default).
+    #[default]
+    Auto,
+    /// Use non-SIMD (scalar or pseudo-SIMD) implementations only.
+    Scalar,
+    /// Use x86 SSE2 implementations at most.
+    Sse2,
+    /// Use x86 SSSE3 implementations at most.
+    Ssse3,
+    /// Use x86 SSE4.1 implementations at most.
+    Sse4_1,
+    /// Use x86 AVX2 implementations at most.
+    Avx2,
+    /// Use x86 AVX-512 (BW) implementations at most.
+    Avx512,
+    /// Use Arm NEON implementations
+    /// (only dynamically dispatched on 32-bit Arm).
+    Neon,
+}
+
+impl Backend {
+    /// All backends.
+    pub const ALL: [Backend; 8] = [
+        Backend::Auto,
+        Backend::Scalar,
+        Backend::Sse2,
+        Backend::Ssse3,
+        Backend::Sse4_1,
+        Backend::Avx2,
+        Backend::Avx512,
+        Backend::Neon,
+    ];
+
+    /// Returns the name of the backend
+    /// (used by the [environment variable](ENV_BACKEND)).
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Backend::Auto => "auto",
+            Backend::Scalar => "scalar",
+            Backend::Sse2 => "sse2",
+            Backend::Ssse3 => "ssse3",
+            Backend::Sse4_1 => "sse4.1",
+            Backend::Avx2 => "avx2",
+            Backend::Avx512 => "avx512",
+            Backend::Neon => "neon",
+        }
+    }
+
+    /// Returns the backend with the specified name (if any).
+    pub fn from_name(name: &str) -> Option<Backend> {
+        Self::ALL.into_iter().find(|x| x.name() == name)
+    }
+
+    /// The rank of the x86 instruction set (if this is an x86 backend).
+    const fn x86_rank(&self) -> Option<u8> {
+        match self {
+            Backend::Sse2 => Some(1),
+            Backend::Ssse3 => Some(2),
+            Backend::Sse4_1 => Some(3),
+            Backend::Avx2 => Some(4),
+            Backend::Avx512 => Some(5),
+            _ => None,
+        }
+    }
+
+    /// Checks whether an implementation using `required` is allowed
+    /// on this backend.
+    pub(crate) const fn allows(&self, required: Backend) -> bool {
+        match (self, self.x86_rank(), required.x86_rank()) {
+            (Backend::Auto, _, _) => true,
+            (_, Some(rank), Some(required_rank)) => required_rank <= rank,
+            (Backend::Neon, _, _) => matches!(required, Backend::Neon),
+            _ => false,
+        }
+    }
+}
+
+/// The selected backend.
+static BACKEND: OnceLock<Backend> = OnceLock::new();
+
+/// Forces the SIMD backend.
+///
+/// This function must be called before the first use of any dynamically
+/// dispatched functions (i.e. before generating, comparing or parsing
+/// fuzzy hashes).  Otherwise, the backend is already fixed (either by
+/// [the environment variable](ENV_BACKEND) or automatically) and this
+/// function returns [`Err`] containing `backend`.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::simd::{force_backend, Backend};
+///
+/// // Call this before any other uses of this crate.
+/// if force_backend(Backend::Sse2).is_ok() {
+///     assert_eq!(tlsh::simd::backend(), Backend::Sse2);
+/// }
+/// ```
+pub fn force_backend(backend: Backend) -> Result<(), Backend> {
+    BACKEND.set(backend)
+}
+
+/// Returns the SIMD backend in effect.
+///
+/// Note that calling this function fixes the backend (then, calling
+/// [`force_backend()`] after this will fail).
+pub fn backend() -> Backend {
+    *BACKEND.get_or_init(|| {
+        std::env::var(ENV_BACKEND)
+            .ok()
+            .and_then(|name| Backend::from_name(&name))
+            .unwrap_or_default()
+    })
+}
+
+/// Checks whether an implementation using `required` is allowed
+/// on the current backend.
+#[inline]
+pub(crate) fn allows(required: Backend) -> bool {
+    backend().allows(required)
+}
+
+mod tests;
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::simd`].
+
+#![cfg(test)]
+
+use super::Backend;
+
+#[test]
+fn backend_default() {
+    assert_eq!(<Backend as Default>::default(), Backend::Auto);
+}
+
+#[test]
+fn backend_names() {
+    for backend in Backend::ALL {
+        assert_eq!(Backend::from_name(backend.name()), Some(backend));
+    }
+    assert_eq!(Backend::from_name(""), None);
+    assert_eq!(Backend::from_name("AVX2"), None);
+    assert_eq!(Backend::from_name("sse4_1"), None);
+}
+
+#[test]
+fn backend_allows() {
+    const X86: [Backend; 5] = [
+        Backend::Sse2,
+        Backend::Ssse3,
+        Backend::Sse4_1,
+        Backend::Avx2,
+        Backend::Avx512,
+    ];
+    for required in Backend::ALL {
+        // Auto allows everything and Scalar allows nothing.
+        assert!(Backend::Auto.allows(required));
+        assert!(!Backend::Scalar.allows(required));
+        // Neon allows only NEON.
+        assert_eq!(Backend::Neon.allows(required), required == Backend::Neon);
+    }
+    // x86 backends allow less or equally capable instruction sets.
+    for (i, backend) in X86.iter().enumerate() {
+        for (j, required) in X86.iter().enumerate() {
+            assert_eq!(
+                backend.allows(*required),
+                j <= i,
+                "{backend:?}, {required:?}"
+            );
+        }
+        assert!(!backend.allows(Backend::Neon));
+    }
+}
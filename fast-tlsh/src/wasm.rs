@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! The WebAssembly interface for JavaScript (through `wasm-bindgen`).
+//!
+//! This module exposes a small subset of the easy functions with
+//! JavaScript-compatible types.  All functions operate on the default
+//! fuzzy hash type ([`Tlsh`](crate::Tlsh)).
+//!
+//! To use them from JavaScript, build this crate with the `wasm-bindgen`
+//! feature for the `wasm32-unknown-unknown` target (as a `cdylib`) and
+//! process the output with `wasm-bindgen` (or `wasm-pack`).
+//!
+//! ```js
+//! import { hash_bytes, compare_str } from "./tlsh.js";
+//!
+//! const hash1 = hash_bytes(new TextEncoder().encode("..."));
+//! const hash2 = hash_bytes(new TextEncoder().encode("..."));
+//! if (hash1 !== undefined && hash2 !== undefined) {
+//!     console.log(compare_str(hash1, hash2));
+//! }
+//! ```
+//!
+//! On other targets, those are ordinary Rust functions.
+
+#![cfg(feature = "wasm-bindgen")]
+
+use std::string::{String, ToString};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Generates a fuzzy hash from a given buffer and returns its string
+/// representation (with the `T1` prefix).
+///
+/// It returns [`None`] (`undefined` in JavaScript) if the generator fails
+/// (e.g. the input is too small).
+///
+/// See [`hash_buf()`](crate::hash_buf()) for details.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::wasm::hash_bytes;
+///
+/// assert_eq!(
+///     hash_bytes(
+///         b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
+///         eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad \
+///         minim veniam, quis nostrud exercitation ullamco laboris nisi ut \
+///         aliquip ex ea commodo consequat. Duis aute irure dolor in \
+///         reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla \
+///         pariatur. Excepteur sint occaecat cupidatat non proident, sunt in \
+///         culpa qui officia deserunt mollit anim id est laborum."
+///     )
+///     .as_deref(),
+///     Some("T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2")
+/// );
+/// assert_eq!(hash_bytes(b""), None);
+/// ```
+#[wasm_bindgen]
+pub fn hash_bytes(data: &[u8]) -> Option<String> {
+    crate::hash_buf(data).ok().map(|hash| hash.to_string())
+}
+
+/// Compares two fuzzy hashes in their string representations.
+///
+/// It returns the distance between two fuzzy hashes (as in
+/// [`compare()`](crate::compare())) on success.  If either of the strings
+/// cannot be parsed as a fuzzy hash of the default type ([`Tlsh`](crate::Tlsh)),
+/// it returns `-1`.
+///
+/// # Example
+///
+/// ```
+/// use tlsh::wasm::compare_str;
+///
+/// let hash1 = "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA2";
+/// let hash2 = "T1DCF0DC36520C1B007FD32079B226559FD998A0200725E75AFCEAC99F5881184A4B1AA3";
+/// assert_eq!(compare_str(hash1, hash1), 0);
+/// assert!(compare_str(hash1, hash2) > 0);
+/// assert_eq!(compare_str(hash1, "T1"), -1);
+/// ```
+#[wasm_bindgen]
+pub fn compare_str(a: &str, b: &str) -> i32 {
+    match crate::compare(a, b) {
+        // The distance is always small enough to fit in i32.
+        Ok(distance) => distance as i32,
+        Err(_) => -1,
+    }
+}
+
+mod tests;
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// SPDX-FileCopyrightText: Copyright (C) 2024 Tsukasa OI <floss_ssdeep@irq.a4lg.com>.
+
+//! Tests: [`crate::wasm`].
+
+#![cfg(test)]
+
+use super::{compare_str, hash_bytes};
+
+use crate::generate::tests::{LOREM_IPSUM, LOREM_IPSUM_HASH_NORMAL};
+
+#[test]
+fn generate_and_compare() {
+    // Not enough data
+    assert_eq!(hash_bytes(b"Hello"), None);
+    let hash1 = hash_bytes(LOREM_IPSUM).unwrap();
+    assert_eq!(hash1, LOREM_IPSUM_HASH_NORMAL);
+    let hash2 = hash_bytes(&LOREM_IPSUM[..LOREM_IPSUM.len() - 40]).unwrap();
+    assert_eq!(compare_str(&hash1, &hash1), 0);
+    assert_eq!(
+        compare_str(&hash1, &hash2),
+        crate::compare(&hash1, &hash2).unwrap() as i32
+    );
+    assert!(compare_str(&hash1, &hash2) > 0);
+    // Parse errors on either side
+    assert_eq!(compare_str(&hash1, "T1"), -1);
+    assert_eq!(compare_str("T1", &hash2), -1);
+    assert_eq!(compare_str("TNULL", "TNULL"), -1);
+}